@@ -0,0 +1,24 @@
+//! Semantic actions of the `calc-actions.rs.g` grammar.
+//!
+//! Regular Rust functions, which are called from the grammar actions,
+//! the types of the parser are available from the parent module.
+
+use super::{Node, Token};
+
+/**
+ * Binary expression node, the operator is the token value.
+ */
+pub fn binary(left: Node, op: Token, right: Node) -> Node {
+    Node::Binary {
+        op: op.value.to_string(),
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+/**
+ * Number literal node.
+ */
+pub fn literal(number: Token) -> Node {
+    Node::Literal(number.value.parse::<i32>().unwrap())
+}
@@ -0,0 +1,68 @@
+/**
+ * Generated parser in Rust, with semantic actions in a separate module.
+ *
+ * The actions call functions from the `actions` module, which is
+ * declared by the generator with the `--actions-module` option. The
+ * `actions.rs` file is placed next to the generated parser:
+ *
+ * ./bin/syntax -g examples/calc-actions.rs.g -m lalr1 -o src/lib.rs --actions-module actions
+ * cp examples/actions.rs src/actions.rs
+ *
+ * use syntax::Parser;
+ *
+ * let mut parser = Parser::new();
+ *
+ * let ast = parser.parse("2 + 2 * 2").unwrap();
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+\d+         return "NUMBER";
+
+"+"         return "+";
+"*"         return "*";
+
+"("         return "(";
+")"         return ")";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+/**
+ * AST node, built by the functions of the `actions` module.
+ */
+#[derive(Debug)]
+pub enum Node {
+
+    Literal(i32),
+
+    Binary {
+        op: String,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+}
+
+/**
+ * Final result type returned from `parse` method call.
+ */
+pub type TResult = Node;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: Node; $2: Token; $3: Node| -> Node; actions::binary($1, $2, $3) }
+    | Expr * Expr   { |$1: Node; $2: Token; $3: Node| -> Node; actions::binary($1, $2, $3) }
+    | ( Expr )      { $$ = $2 }
+    | NUMBER        { |$1: Token| -> Node; actions::literal($1) }
+    ;
@@ -5,9 +5,9 @@
  *
  * use syntax::Parser;
  *
- * let parser = Parser::new();
+ * let mut parser = Parser::new();
  *
- * let ast = parser.parse("2 + 2 * 2");
+ * let ast = parser.parse("2 + 2 * 2").unwrap();
  */
 
 %lex
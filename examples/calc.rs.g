@@ -5,10 +5,15 @@
  *
  * use syntax::Parser;
  *
- * let parser = Parser::new();
+ * let mut parser = Parser::new();
  *
- * println!("{:?}", parser.parse("2 + 2 * 2"));   // 6
- * println!("{:?}", parser.parse("(2 + 2) * 2")); // 8
+ * println!("{:?}", parser.parse("2 + 2 * 2"));   // Ok(6)
+ * println!("{:?}", parser.parse("(2 + 2) * 2")); // Ok(8)
+ *
+ * match parser.parse("2 + * 2") {
+ *   Ok(result) => println!("{}", result),
+ *   Err(error) => eprintln!("{}", error), // Unexpected token: "*" at 1:4; expected one of: NUMBER, (.
+ * }
  */
 
 {
@@ -0,0 +1,58 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import ParseTree from '../parse-tree';
+import Grammar from '../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../grammar/grammar-mode';
+import LRParser from '../lr/lr-parser';
+
+const grammar = Grammar.fromGrammarFile(
+  __dirname + '/../grammar/__tests__/calc.g',
+  {mode: GRAMMAR_MODE.LALR1}
+);
+
+function parseTree(string) {
+  return new LRParser({grammar, parseTree: true}).parse(string).tree;
+}
+
+describe('parse tree', () => {
+  it('tree of the productions', () => {
+    const tree = parseTree('2 + 2 * 2');
+
+    expect(tree.symbol).toBe('E');
+    expect(tree.children.map(child => child.symbol)).toEqual(['E', '+', 'E']);
+    expect(tree.children[0].children).toEqual([{symbol: 'NUMBER', value: '2'}]);
+  });
+
+  it('pretty', () => {
+    expect(ParseTree.pretty(parseTree('2 + 2 * 2'))).toBe(
+      [
+        'E',
+        '  E',
+        '    NUMBER "2"',
+        '  +',
+        '  E',
+        '    E',
+        '      NUMBER "2"',
+        '    *',
+        '    E',
+        '      NUMBER "2"',
+      ].join('\n')
+    );
+  });
+
+  it('indent of the root', () => {
+    expect(ParseTree.pretty(parseTree('(7)'), 1)).toBe(
+      ['  E', '    (', '    E', '      NUMBER "7"', '    )'].join('\n')
+    );
+  });
+
+  it('no tree by default', () => {
+    const parsed = new LRParser({grammar}).parse('2 + 2');
+
+    expect(parsed.value).toBe(4);
+    expect(parsed.tree).toBeUndefined();
+  });
+});
@@ -1,12 +1,24 @@
 extern crate calc_syntax;
 
 use calc_syntax::Parser;
+use std::env;
+use std::process;
 
 fn main() {
+    let source = env::args().nth(1);
+
     let mut parser = Parser::new();
 
-    let parse_string = String::from("2 + 2 * 2");
-    let result = parser.parse(&parse_string);
+    let result = match &source {
+        Some(source) => parser.parse(source),
+        None => parser.parse("2 + 2 * 2"),
+    };
 
-    println!("parse result: {}", result);
+    match result {
+        Ok(result) => println!("parse result: {}", result),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,31 @@
+/**
+ * Evaluates a calc expression at compile time:
+ *
+ *   use calc_macro::calc;
+ *
+ *   assert_eq!(calc!("2 + 2 * 2"), 6);
+ *
+ * A syntax error is a compile error at the unexpected token of the
+ * string literal (on the stable Rust at the whole literal):
+ *
+ *   calc!("2 + * 2");
+ *            ^
+ */
+
+extern crate proc_macro;
+
+use calc_macro_syntax::Parser;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+#[proc_macro]
+pub fn calc(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+
+    match Parser::new().parse(&literal.value()) {
+        Ok(result) => quote!(#result).into(),
+        Err(error) => syn::Error::new(error.span_in(&literal.token()), &error.message)
+            .to_compile_error()
+            .into(),
+    }
+}
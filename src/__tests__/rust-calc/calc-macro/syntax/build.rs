@@ -0,0 +1,7 @@
+use std::process::Command;
+
+fn main() {
+    Command::new("make")
+        .status()
+        .unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+/**
+ * Calls the calc parser compiled to WebAssembly:
+ *
+ *   wasm-pack build --target nodejs
+ *   node index.js
+ */
+
+const {parse} = require('./pkg/calc_wasm');
+
+console.info(parse('2 + 2 * 2')); // 6
+
+try {
+  parse('2 + * 2');
+} catch (error) {
+  console.error(error); // Unexpected token: "*" at 1:4; expected one of: NUMBER, (.
+}
@@ -0,0 +1,67 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import packTable from '../plugins/rust/rust-compact-table';
+import Grammar from '../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../grammar/grammar-mode';
+
+const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+  .default;
+
+function tableRows(grammarFile) {
+  // Handlers of other languages are not compiled as JS.
+  global.globalOptions = {output: 'lib.rs'};
+
+  const generator = new LRParserGeneratorRust({
+    grammar: Grammar.fromGrammarFile(grammarFile, {mode: GRAMMAR_MODE.LALR1}),
+    outputFile: null,
+    options: {},
+  });
+
+  return generator._buildTable(generator.generateParseTableData());
+}
+
+function lookup({base, check, entries}, state, symbol) {
+  const index = base[state] + Number(symbol);
+  return check[index] === state ? entries[index] : null;
+}
+
+describe('rust compact table', () => {
+  ['calc.g', 'typed-tokens.g', 'source-text.g'].forEach(grammarFile => {
+    it(`same entries as the rows (${grammarFile})`, () => {
+      const rows = tableRows(__dirname + '/rust-fixtures/' + grammarFile);
+      const table = packTable(rows);
+
+      const symbols = new Set();
+      rows.forEach(row => Object.keys(row).forEach(s => symbols.add(s)));
+
+      rows.forEach((row, state) => {
+        symbols.forEach(symbol => {
+          expect(lookup(table, state, symbol)).toBe(
+            row.hasOwnProperty(symbol) ? row[symbol] : null
+          );
+        });
+      });
+
+      expect(table.entries.length).toBeLessThan(rows.length * symbols.size);
+    });
+  });
+
+  it('free slots', () => {
+    const table = packTable([{0: 'a', 2: 'b'}, {}, {0: 'c'}]);
+
+    expect(table).toEqual({
+      base: [0, 0, 1],
+      check: [0, 2, 0],
+      entries: ['a', 'c', 'b'],
+    });
+
+    expect(packTable([{1: 'a'}, {1: 'b'}])).toEqual({
+      base: [0, 1],
+      check: [-1, 0, 1],
+      entries: [null, 'a', 'b'],
+    });
+  });
+});
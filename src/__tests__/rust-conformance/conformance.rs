@@ -0,0 +1,29 @@
+use super::*;
+
+use std::fs;
+
+/**
+ * Parses each input of `input.cases` (separated by `---` lines, the
+ * trailing new line of the file is not a part of the last input), and
+ * writes the results to `output.txt`, one line per input.
+ */
+#[test]
+fn conformance() {
+    let mut cases = fs::read_to_string("input.cases").unwrap();
+    if cases.ends_with('\n') {
+        cases.pop();
+    }
+
+    let mut output = String::new();
+
+    for input in cases.split("\n---\n") {
+        let result = match Parser::new().parse(input) {
+            Ok(value) => format!("Ok({:?})", value),
+            Err(error) => format!("Err({:?})", error.message),
+        };
+        output.push_str(&result);
+        output.push('\n');
+    }
+
+    fs::write("output.txt", output).unwrap();
+}
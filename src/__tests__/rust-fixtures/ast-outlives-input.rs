@@ -0,0 +1,12 @@
+use super::*;
+
+// Must not compile: the parsed value can't outlive the parsing string.
+fn parse_input() -> Ast<'static> {
+    let input = String::from("foo");
+    Parser::new().parse(&input).unwrap()
+}
+
+#[test]
+fn ast_outlives_input() {
+    assert_eq!(parse_input()[0].value, "foo");
+}
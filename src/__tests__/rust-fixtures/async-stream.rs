@@ -0,0 +1,72 @@
+use super::*;
+use futures::executor::block_on;
+use futures::stream;
+
+/**
+ * Tokens of a string, as a stream of them would deliver.
+ */
+fn tokens(string: &'static str) -> Vec<Token<'static>> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        tokens.push(token);
+
+        if token.is_eof() {
+            return tokens;
+        }
+    }
+}
+
+#[test]
+fn parses_a_stream_of_tokens() {
+    let mut parser = Parser::new();
+
+    let result = block_on(parser.parse_stream(stream::iter(tokens("2 + 2 * 2"))));
+    assert_eq!(result.unwrap(), 6);
+
+    let result = block_on(parser.parse_stream(stream::iter(tokens("(2 + 2) * 2"))));
+    assert_eq!(result.unwrap(), 8);
+}
+
+#[test]
+fn end_of_stream_is_end_of_input() {
+    let mut without_eof = tokens("2 + 3");
+    without_eof.pop();
+
+    let result = block_on(Parser::new().parse_stream(stream::iter(without_eof)));
+    assert_eq!(result.unwrap(), 5);
+
+    let mut incomplete = tokens("2 +");
+    incomplete.pop();
+
+    let error = block_on(Parser::new().parse_stream(stream::iter(incomplete))).unwrap_err();
+
+    // The end of input is at the end of the last token.
+    assert!(error.message.starts_with("Unexpected end of input"), "{}", error.message);
+    assert_eq!((error.loc.start_offset, error.loc.start_column), (3, 3));
+}
+
+#[test]
+fn unexpected_token_of_a_stream() {
+    let plus = token_kind("+").unwrap();
+
+    let tokens = vec![
+        Token { kind: plus, value: "+", end_offset: 1, end_column: 1, ..Token::default() },
+    ];
+
+    let error = block_on(Parser::new().parse_stream(stream::iter(tokens))).unwrap_err();
+    assert_eq!(error.expected, vec!["NUMBER", "("]);
+}
+
+#[test]
+fn tokens_after_eof_are_not_read() {
+    let mut tokens = tokens("1 * 7");
+    tokens.extend(self::tokens("+ 1"));
+
+    let result = block_on(Parser::new().parse_stream(stream::iter(tokens)));
+    assert_eq!(result.unwrap(), 7);
+}
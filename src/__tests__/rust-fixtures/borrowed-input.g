@@ -0,0 +1,26 @@
+/**
+ * Identifiers, the result is a vector of tokens borrowing
+ * from the parsing string.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\w+     return "ID";
+
+/lex
+
+%{
+
+type TResult<'t> = Vec<Token<'t>>;
+
+%}
+
+%%
+
+Items
+    : Items ID  { |$1: Vec<Token>, $2: Token| -> Vec<Token>; $1.push($2); $$ = $1 }
+    | ID        { |$1: Token| -> Vec<Token>; $$ = vec![$1] }
+    ;
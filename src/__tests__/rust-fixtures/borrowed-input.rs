@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn result_borrows_from_input() {
+    let input = String::from("foo\nbar");
+    let mut parser = Parser::new();
+
+    let tokens = parser.parse(&input).unwrap();
+    let values: Vec<&str> = tokens.iter().map(|token| token.value).collect();
+
+    assert_eq!(values, vec!["foo", "bar"]);
+    assert_eq!(tokens[1].start_line, 2);
+}
+
+#[test]
+fn shifted_tokens_borrow_from_input() {
+    let input = String::from("foo bar");
+    let mut parser = Parser::new();
+
+    let values: Vec<&str> = parser
+        .parse_events(&input)
+        .filter_map(|event| match event {
+            Ok(ParseEvent::Shift(token)) if !token.is_eof() => Some(token.value),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(values, vec!["foo", "bar"]);
+}
+
+#[test]
+fn parse_str_like_input() {
+    let owned = String::from("foo bar");
+    let boxed: Box<str> = "foo bar baz".into();
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("foo").unwrap().len(), 1);
+    assert_eq!(parser.parse(&owned).unwrap().len(), 2);
+    assert_eq!(parser.parse(&boxed).unwrap().len(), 3);
+    assert!(matches!(
+        parser.parse_events(&owned).last(),
+        Some(Ok(ParseEvent::Accept))
+    ));
+}
+
+fn identifiers(source: &str) -> Result<Ast<'_>, SyntaxError> {
+    Parser::new().parse(source)
+}
+
+#[test]
+fn no_lifetime_annotations() {
+    let input = String::from("foo bar");
+
+    let tokens = identifiers(&input).unwrap();
+    assert_eq!(tokens[1].value, "bar");
+
+    let tokens: Ast = Parser::new().parse("foo").unwrap();
+    assert_eq!(tokens[0].value, "foo");
+}
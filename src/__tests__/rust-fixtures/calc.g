@@ -0,0 +1,36 @@
+/**
+ * Calculator grammar shared by Rust plugin tests.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+
+"("     return "(";
+")"     return ")";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Expr * Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 * $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    | ( Expr )      { $$ = $2 }
+    ;
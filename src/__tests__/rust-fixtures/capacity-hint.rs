@@ -0,0 +1,46 @@
+use super::*;
+
+fn nested(depth: usize) -> String {
+    format!("{}1{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+#[test]
+fn large_hint_avoids_reallocations() {
+    let input = nested(300);
+    let mut parser = Parser::new();
+    parser.set_capacity_hint(1000);
+
+    assert_eq!(parser.parse(&input).unwrap(), 1);
+
+    // The stacks grown past the hint would have reallocated.
+    assert_eq!(parser.states_stack.capacity(), 1000);
+    assert_eq!(parser.values_stack.capacity(), 1000);
+    assert_eq!(parser.locations_stack.capacity(), 1000);
+}
+
+#[test]
+fn small_parse_stays_lean() {
+    let input = nested(300);
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse(&input).unwrap(), 1);
+    assert!(parser.states_stack.capacity() > 300);
+
+    parser.set_capacity_hint(16);
+    assert_eq!(parser.parse("2 + 2 * 2").unwrap(), 6);
+
+    assert_eq!(parser.states_stack.capacity(), 16);
+    assert_eq!(parser.values_stack.capacity(), 16);
+}
+
+#[test]
+fn stacks_kept_without_hint() {
+    let input = nested(300);
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse(&input).unwrap(), 1);
+    let capacity = parser.states_stack.capacity();
+
+    assert_eq!(parser.parse("2 + 2 * 2").unwrap(), 6);
+    assert_eq!(parser.states_stack.capacity(), capacity);
+}
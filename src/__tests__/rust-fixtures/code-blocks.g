@@ -0,0 +1,51 @@
+/**
+ * Semantic actions call helpers defined in `%code` blocks, which are
+ * emitted with the `%{ %}` block in order.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\w+     return "WORD";
+","     return ",";
+
+/lex
+
+%code {
+use std::collections::BTreeSet;
+}
+
+%{
+
+type TResult = Words;
+
+%}
+
+%code {
+/**
+ * Sorted unique words.
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct Words(BTreeSet<String>);
+
+impl Words {
+    fn with(mut self, word: &str) -> Words {
+        // Braces in strings, and character literals: "}", '{'.
+        self.0.insert(word.trim_matches(|c| c == '{' || c == '}').to_lowercase());
+        self
+    }
+
+    pub fn joined(&self) -> String {
+        self.0.iter().cloned().collect::<Vec<_>>().join(" ")
+    }
+}
+}
+
+%%
+
+List
+    : List , WORD       { |$1: Words, $3: Token| -> Words; $$ = $1.with($3.value) }
+    | WORD              { |$1: Token| -> Words; $$ = Words::default().with($1.value) }
+    ;
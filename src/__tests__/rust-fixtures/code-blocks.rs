@@ -0,0 +1,9 @@
+use super::*;
+
+#[test]
+fn actions_call_code_block_helpers() {
+    let words = Parser::new().parse("b, A, c, a").unwrap();
+
+    assert_eq!(words.joined(), "a b c");
+    assert_eq!(words, Words::default().with("c").with("b").with("a"));
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+// Evaluated at compile time: a missing token, or table entry is a
+// build error.
+const NUMBER: i32 = match token_kind("NUMBER") {
+    Some(kind) => kind,
+    None => panic!("no NUMBER token"),
+};
+
+const _: () = assert!(table_entry(0, NUMBER).is_some());
+const _: () = assert!(token_kind("MISSING").is_none());
+
+#[test]
+fn parses_with_const_tables() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("2 + 2 * 2").unwrap(), 6);
+    assert_eq!(parser.parse("(2 + 2) * 2").unwrap(), 8);
+    assert!(parser.parse("2 + * 2").is_err());
+}
+
+#[test]
+fn finds_all_tokens() {
+    for (name, kind) in TOKENS_MAP.iter() {
+        assert_eq!(token_kind(name), Some(*kind));
+    }
+
+    assert_eq!(token_kind(""), None);
+    assert_eq!(token_kind("NUMBERS"), None);
+    assert_eq!(token_kind("NUMBE"), None);
+}
+
+#[test]
+fn finds_lex_rules_of_states() {
+    for (state, rules) in LEX_RULES_BY_START_CONDITIONS.iter() {
+        assert_eq!(lex_rules_of_state(state), *rules);
+    }
+}
@@ -0,0 +1,47 @@
+/**
+ * Sums of numbers, and variables, the actions report diagnostics into
+ * a sink of the caller (the `contextType` option), and the lex handler
+ * counts the identifiers.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+\d+         return "NUMBER";
+[a-z]+      self.context().identifiers += 1; return "ID";
+"+"         return "+";
+
+/lex
+
+%left +
+
+%{
+
+type TResult = i32;
+
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    pub warnings: Vec<(String, Loc)>,
+    pub identifiers: usize,
+}
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | NUMBER        { || -> i32;
+                      let value = yytext.parse::<i32>().unwrap();
+                      if value == 0 {
+                          let loc = @1;
+                          self.context().warnings.push(("Adding zero.".to_string(), loc));
+                      }
+                      $$ = value }
+    | ID            { || -> i32;
+                      let warning = (format!("Unknown variable {}, assumed 0.", yytext), @1);
+                      self.context().warnings.push(warning);
+                      $$ = 0 }
+    ;
@@ -0,0 +1,62 @@
+use super::*;
+
+fn warnings(diagnostics: &Diagnostics) -> Vec<(&str, i32)> {
+    diagnostics
+        .warnings
+        .iter()
+        .map(|(message, loc)| (message.as_str(), loc.start_offset))
+        .collect()
+}
+
+#[test]
+fn diagnostics_sink() {
+    let mut parser = Parser::new();
+    let mut diagnostics = Diagnostics::default();
+
+    assert_eq!(parser.parse_with("1 + x + 0 + 2", &mut diagnostics).unwrap(), 3);
+
+    assert_eq!(
+        warnings(&diagnostics),
+        vec![("Unknown variable x, assumed 0.", 4), ("Adding zero.", 8)]
+    );
+    assert_eq!(diagnostics.identifiers, 1);
+}
+
+#[test]
+fn accumulated_over_parses() {
+    let mut parser = Parser::new();
+    let mut diagnostics = Diagnostics::default();
+
+    parser.parse_with("a + b", &mut diagnostics).unwrap();
+    parser.parse_with("0", &mut diagnostics).unwrap();
+
+    assert_eq!(diagnostics.warnings.len(), 3);
+    assert_eq!(diagnostics.identifiers, 2);
+}
+
+#[test]
+fn context_of_parser() {
+    let mut parser = Parser::new();
+    let mut diagnostics = Diagnostics::default();
+
+    parser.parse_with("a", &mut diagnostics).unwrap();
+
+    // Other parses use the context of the parser.
+    parser.parse("b + c").unwrap();
+
+    assert_eq!(diagnostics.identifiers, 1);
+    assert_eq!(parser.context().identifiers, 2);
+    assert_eq!(parser.context().warnings.len(), 2);
+}
+
+#[test]
+fn syntax_error() {
+    let mut parser = Parser::new();
+    let mut diagnostics = Diagnostics::default();
+
+    assert!(parser.parse_with("x + + 1", &mut diagnostics).is_err());
+
+    // The context is returned after the error.
+    assert_eq!(warnings(&diagnostics), vec![("Unknown variable x, assumed 0.", 0)]);
+    assert_eq!(parser.context().identifiers, 0);
+}
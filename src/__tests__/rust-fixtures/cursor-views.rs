@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn views_follow_cursor() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("2 + 3 * 4");
+
+    assert_eq!(tokenizer.consumed(), "");
+    assert_eq!(tokenizer.remaining(), "2 + 3 * 4");
+
+    tokenizer.get_next_token().unwrap();
+    tokenizer.get_next_token().unwrap();
+
+    assert_eq!(tokenizer.consumed(), "2 +");
+    assert_eq!(tokenizer.remaining(), " 3 * 4");
+}
+
+#[test]
+fn views_at_end() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("2 + 3");
+
+    while !tokenizer.get_next_token().unwrap().is_eof() {}
+
+    assert_eq!(tokenizer.consumed(), "2 + 3");
+    assert_eq!(tokenizer.remaining(), "");
+}
+
+#[test]
+fn views_outlive_tokenizer() {
+    let string = String::from("2 + 3");
+
+    let (consumed, remaining) = {
+        let mut tokenizer = Tokenizer::new();
+        tokenizer.init_string(&string);
+        tokenizer.get_next_token().unwrap();
+        (tokenizer.consumed(), tokenizer.remaining())
+    };
+
+    assert_eq!((consumed, remaining), ("2", " + 3"));
+}
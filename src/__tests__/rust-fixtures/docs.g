@@ -0,0 +1,42 @@
+/**
+ * Sums of numbers, with the docs of the grammar, the tokens, and
+ * the rules emitted as the docs of the generated parser.
+ */
+
+//! Parser of the sums of numbers: `1 + 2 + 3`.
+//!
+//! The grammar docs are on the `Parser`.
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+"+"     return "PLUS";
+type    return "type";
+
+/lex
+
+/// A decimal number.
+%token <i32> NUMBER
+
+/// The `+` operator.
+%token PLUS
+
+%left PLUS
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+/// A sum of the numbers.
+Sum
+    : Sum PLUS NUMBER   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | NUMBER
+    | type              { || -> i32; $$ = 0 }
+    ;
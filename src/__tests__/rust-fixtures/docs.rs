@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn token_kinds() {
+    assert_eq!(TokenKind::NUMBER as i32, TOKENS_MAP["NUMBER"]);
+    assert_eq!(TokenKind::PLUS as i32, TOKENS_MAP["PLUS"]);
+    assert_eq!(TokenKind::r#type as i32, TOKENS_MAP["type"]);
+}
+
+#[test]
+fn kind_of_tokens() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1 + 2");
+
+    let kinds: Vec<i32> = tokenizer.map(|token| token.unwrap().kind).collect();
+
+    assert_eq!(
+        kinds[..3],
+        [TokenKind::NUMBER as i32, TokenKind::PLUS as i32, TokenKind::NUMBER as i32]
+    );
+}
+
+#[test]
+fn parse() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("1 + 2 + 3").unwrap(), 6);
+    assert_eq!(parser.parse("type").unwrap(), 0);
+}
+
+#[test]
+fn unparsable_typed_token() {
+    let mut parser = Parser::new();
+
+    let error = parser.parse("1 + 99999999999").unwrap_err();
+
+    assert_eq!(
+        error.message,
+        "Invalid value of token \"99999999999\" at 1:4: number too large to fit in target type."
+    );
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (4, 15));
+    assert_eq!(error.kind, SyntaxErrorKind::Other);
+
+    // The parser is reused after the error.
+    assert_eq!(parser.parse("1 + 2").unwrap(), 3);
+}
@@ -0,0 +1,37 @@
+/**
+ * Numbers with an optional sign: the sign rule matches empty before
+ * a number without a sign (with the empty tokens of the tokenizer).
+ */
+
+%lex
+
+%%
+
+\s+                 /* skip whitespace */ return "";
+","                 return "COMMA";
+[+-]?               return "SIGN";
+\d+                 return "DIGITS";
+
+/lex
+
+%{
+
+type TResult = Vec<i64>;
+
+%}
+
+%%
+
+Numbers
+    : Numbers COMMA Number  { |$1: Vec<i64>, $3: i64| -> Vec<i64>; $1.push($3); $$ = $1 }
+    | Number                { |$1: i64| -> Vec<i64>; $$ = vec![$1] }
+    ;
+
+Number
+    : SIGN DIGITS
+        {
+            |$1: Token, $2: Token| -> i64;
+            let value: i64 = $2.value.parse().unwrap();
+            $$ = if $1.value == "-" { -value } else { value }
+        }
+    ;
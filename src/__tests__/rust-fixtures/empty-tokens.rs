@@ -0,0 +1,61 @@
+use super::*;
+
+fn parser() -> Parser<'static> {
+    let mut parser = Parser::new();
+    parser.set_empty_tokens(true);
+    parser
+}
+
+#[test]
+fn optional_sign() {
+    let mut parser = parser();
+
+    assert_eq!(parser.parse("1, -2, +3").unwrap(), vec![1, -2, 3]);
+    assert_eq!(parser.parse("5").unwrap(), vec![5]);
+}
+
+#[test]
+fn empty_token_location() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_empty_tokens(true);
+    tokenizer.init_string("7, 8");
+
+    let tokens: Vec<_> = std::iter::from_fn(|| {
+        let token = tokenizer.get_next_token().unwrap();
+        (!token.is_eof()).then_some((token.value, token.start_offset, token.end_offset))
+    })
+    .collect();
+
+    assert_eq!(
+        tokens,
+        vec![("", 0, 0), ("7", 0, 1), (",", 1, 2), ("", 3, 3), ("8", 3, 4)]
+    );
+}
+
+#[test]
+fn no_empty_token_at_the_end() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_empty_tokens(true);
+    tokenizer.init_string("-1 ");
+
+    assert_eq!(tokenizer.get_next_token().unwrap().value, "-");
+    assert_eq!(tokenizer.get_next_token().unwrap().value, "1");
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
+
+#[test]
+fn unmatched_text_after_an_empty_token() {
+    // The sign matches empty once before `x`, then it's an error.
+    let error = parser().parse("1, x").unwrap_err();
+
+    assert_eq!(error.loc.start_offset, 3);
+}
+
+#[test]
+fn empty_matches_skipped_by_default() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1");
+
+    assert_eq!(tokenizer.get_next_token().unwrap().value, "1");
+    assert!(Parser::new().parse("1").is_err());
+}
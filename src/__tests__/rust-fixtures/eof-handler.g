@@ -0,0 +1,78 @@
+/**
+ * Custom layout-sensitive blocks: the indentation levels are kept as
+ * the data of the states, and the blocks open at the end of input are
+ * closed by the EOF handler.
+ */
+
+%lex
+
+%%
+
+(\n[ \t]*)+     return layout(self);
+[ \t]+          /* skip whitespace */ return "";
+":"             return "COLON";
+\w+             return "ID";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+/**
+ * Indentation level of the current block.
+ */
+fn level(tokenizer: &Tokenizer) -> usize {
+    tokenizer.get_state_data().map_or(0, |data| data.parse().unwrap())
+}
+
+/**
+ * A deeper line opens a block, a shallower one closes the blocks,
+ * and the lines of a block are separated with NEWLINE.
+ */
+fn layout(tokenizer: &mut Tokenizer) -> &'static str {
+    let yytext = tokenizer.yytext;
+    let width = yytext.len() - yytext.rfind('\n').unwrap() - 1;
+    let end = yytext.len();
+
+    if width > level(tokenizer) {
+        tokenizer.push_state_with("INITIAL", width.to_string());
+        return "INDENT";
+    }
+
+    while width < level(tokenizer) {
+        tokenizer.pop_state();
+        tokenizer.push_token("DEDENT", end..end);
+    }
+
+    tokenizer.push_token("NEWLINE", end..end);
+    ""
+}
+
+/**
+ * Closes the blocks open at the end of input.
+ */
+fn close_blocks(tokenizer: &mut Tokenizer) {
+    while tokenizer.get_state_data().is_some() {
+        tokenizer.pop_state();
+        tokenizer.push_synthetic_token("DEDENT");
+    }
+}
+
+fn on_parse_begin(parser: &mut Parser, _string: &str) {
+    parser.tokenizer.set_eof_handler(close_blocks);
+}
+
+%}
+
+%%
+
+Stmts
+    : Stmts NEWLINE Stmt  { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Stmt                { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Stmt
+    : ID                            { || -> i32; $$ = 1 }
+    | ID COLON INDENT Stmts DEDENT  { |$4: i32| -> i32; $$ = 1 + $4 }
+    ;
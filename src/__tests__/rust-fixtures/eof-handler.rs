@@ -0,0 +1,78 @@
+use super::*;
+
+fn token_types(string: &'static str) -> Vec<&'static str> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+    tokenizer.set_eof_handler(close_blocks);
+
+    let mut types = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            types.push(EOF);
+            break;
+        }
+        let (token_type, _) = TOKENS_MAP
+            .iter()
+            .find(|(_, kind)| **kind == token.kind)
+            .unwrap();
+        types.push(*token_type);
+    }
+    types
+}
+
+#[test]
+fn closes_blocks_at_end_of_input() {
+    assert_eq!(
+        token_types("a:\n  b:\n    c"),
+        vec![
+            "ID", "COLON", "INDENT",
+                "ID", "COLON", "INDENT",
+                    "ID",
+                "DEDENT",
+            "DEDENT",
+            EOF,
+        ]
+    );
+}
+
+#[test]
+fn closes_remaining_blocks() {
+    assert_eq!(
+        token_types("a:\n  b:\n    c\n  d"),
+        vec![
+            "ID", "COLON", "INDENT",
+                "ID", "COLON", "INDENT",
+                    "ID",
+                "DEDENT", "NEWLINE",
+                "ID",
+            "DEDENT",
+            EOF,
+        ]
+    );
+}
+
+#[test]
+fn called_once() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("a:\n  b");
+    tokenizer.set_eof_handler(close_blocks);
+
+    let tokens: Vec<Token> = (0..8).map(|_| tokenizer.get_next_token().unwrap()).collect();
+
+    assert!(tokens[..5].iter().all(|token| !token.is_eof()));
+    assert!(tokens[5..].iter().all(|token| token.is_eof()));
+
+    // The DEDENT is at the end of input.
+    assert_eq!((tokens[4].start_offset, tokens[4].end_offset), (6, 6));
+}
+
+#[test]
+fn parses_with_open_blocks() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("a:\n  b:\n    c").unwrap(), 3);
+    assert_eq!(parser.parse("a:\n  b:\n    c\nd").unwrap(), 4);
+    assert_eq!(parser.parse("a:\n  b:\n    c\n  d:\n    e").unwrap(), 5);
+    assert!(parser.parse("a:\n  b:\n    c:").is_err());
+}
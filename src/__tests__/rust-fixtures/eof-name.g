@@ -0,0 +1,28 @@
+/**
+ * Sums of numbers, the end of input token is named `$end` in the tokens
+ * map, and the error messages.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+/lex
+
+%eof "$end"
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Sum
+    : Sum '+' NUMBER    { |$1: i32, $3: Token| -> i32; $$ = $1 + $3.value.parse::<i32>().unwrap() }
+    | NUMBER            { |$1: Token| -> i32; $$ = $1.value.parse::<i32>().unwrap() }
+    ;
@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn tokens_map() {
+    assert_eq!(TOKENS_MAP["$end"], EOF_KIND);
+    assert!(!TOKENS_MAP.contains_key("$"));
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert!(token.is_eof());
+    assert_eq!(token.value, "$end");
+}
+
+#[test]
+fn unexpected_end_of_input() {
+    let error = Parser::new().parse("1 +").unwrap_err();
+
+    assert_eq!(error.message, "Unexpected $end; expected one of: NUMBER.");
+    assert_eq!(error.expected, vec!["NUMBER"]);
+}
+
+#[test]
+fn expected_end_of_input() {
+    let error = Parser::new().parse("1 + 2 3").unwrap_err();
+
+    assert!(error.message.ends_with("; expected one of: '+', $end."));
+    assert_eq!(error.expected, vec!["'+'", "$end"]);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Parser::new().parse("1 + 2 + 3").unwrap(), 6);
+}
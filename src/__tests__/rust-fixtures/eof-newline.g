@@ -0,0 +1,32 @@
+/**
+ * Statements terminated by a new line, a comment is skipped till
+ * the end of line. Used to test the `appendEofNewline` option.
+ */
+
+%lex
+
+%%
+
+[ \t]+              /* skip whitespace */ return "";
+"#"[^\n]*           /* skip comment */ return "";
+\n                  return "NL";
+\w+                 return "ID";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt    { |$1: i32| -> i32; $$ = $1 + 1 }
+    | Stmt          { || -> i32; $$ = 1 }
+    ;
+
+Stmt
+    : ID NL
+    ;
@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn final_statement_without_newline() {
+    assert_eq!(Parser::new().parse("a\nb").unwrap(), 2);
+    assert_eq!(Parser::new().parse("a\nb # last").unwrap(), 2);
+}
+
+#[test]
+fn final_newline_is_not_duplicated() {
+    assert_eq!(Parser::new().parse("a\nb\n").unwrap(), 2);
+    assert!(Parser::new().parse("a\n\n").is_err());
+}
+
+#[test]
+fn offsets_of_input() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("a\nbc");
+
+    tokenizer.get_next_token().unwrap();
+    tokenizer.get_next_token().unwrap();
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.value, token.start_offset, token.end_offset), ("bc", 2, 4));
+    assert_eq!((token.start_line, token.start_column), (2, 0));
+
+    // The new line at the end of the input, it's not in the string.
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.value, token.start_offset, token.end_offset), ("\n", 4, 4));
+    assert_eq!((token.end_line, token.end_column), (2, 2));
+    assert_eq!(tokenizer.consumed(), "a\nbc");
+
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
+
+#[test]
+fn disabled_at_runtime() {
+    let mut parser = Parser::new();
+    parser.tokenizer.set_append_eof_newline(false);
+
+    let error = parser.parse("a\nb").unwrap_err();
+    assert_eq!(error.message, "Unexpected end of input; expected one of: NL.");
+    assert_eq!(parser.parse("a\nb\n").unwrap(), 2);
+}
@@ -0,0 +1,35 @@
+/**
+ * Tokens at the end of input, including a `DOLLAR` token whose
+ * value is the same as the EOF value. Whitespace can match an empty
+ * string, which must not skip text. Used to test EOF handling.
+ */
+
+%lex
+
+%%
+
+\s*                     /* skip whitespace, can match empty */ return "";
+\$                      return "DOLLAR";
+\d+                     return "NUMBER";
+\"[^"]*\"               return "STRING";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Items
+    : Items Item  { |$1: i32, $2: i32| -> i32; $$ = $1 + $2 }
+    | Item        { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Item
+    : NUMBER          { || -> i32; $$ = 1 }
+    | STRING          { || -> i32; $$ = 1 }
+    | DOLLAR NUMBER   { || -> i32; $$ = 1 }
+    ;
@@ -0,0 +1,188 @@
+use super::*;
+
+// (kind is EOF, value, start_offset, end_offset, start_line, start_column)
+fn next<'t>(tokenizer: &mut Tokenizer<'t>) -> (bool, &'t str, i32, i32, i32, i32) {
+    let token = tokenizer.get_next_token().unwrap();
+    (
+        token.is_eof(),
+        token.value,
+        token.start_offset,
+        token.end_offset,
+        token.start_line,
+        token.start_column,
+    )
+}
+
+#[test]
+fn empty_input() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("");
+
+    assert_eq!(next(&mut tokenizer), (true, "$", 0, 0, 1, 0));
+    assert!(!tokenizer.has_more_tokens());
+    assert_eq!(tokenizer.cursor, 1);
+
+    // EOF is returned again on subsequent calls.
+    assert_eq!(next(&mut tokenizer), (true, "$", 0, 0, 1, 0));
+    assert_eq!(tokenizer.cursor, 1);
+}
+
+#[test]
+fn input_ending_at_rule_boundary() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1 $");
+
+    assert_eq!(next(&mut tokenizer), (false, "1", 0, 1, 1, 0));
+
+    // Same value as EOF, but a regular token.
+    assert_eq!(next(&mut tokenizer), (false, "$", 2, 3, 1, 2));
+    assert!(tokenizer.has_more_tokens());
+
+    assert_eq!(next(&mut tokenizer), (true, "$", 3, 3, 1, 3));
+    assert!(!tokenizer.has_more_tokens());
+    assert_eq!(tokenizer.cursor, 4);
+}
+
+#[test]
+fn input_ending_with_skipped_text() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1\n  ");
+
+    assert_eq!(next(&mut tokenizer), (false, "1", 0, 1, 1, 0));
+    assert_eq!(next(&mut tokenizer), (true, "$", 4, 4, 2, 2));
+    assert_eq!(tokenizer.cursor, 5);
+}
+
+#[test]
+fn single_character_input() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1");
+
+    assert!(tokenizer.has_more_tokens() && !tokenizer.is_eof());
+
+    assert_eq!(next(&mut tokenizer), (false, "1", 0, 1, 1, 0));
+    assert!(tokenizer.has_more_tokens() && tokenizer.is_eof());
+
+    assert_eq!(next(&mut tokenizer), (true, "$", 1, 1, 1, 1));
+    assert!(!tokenizer.has_more_tokens() && !tokenizer.is_eof());
+    assert_eq!(tokenizer.cursor, 2);
+}
+
+#[test]
+fn single_skipped_character() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(" ");
+
+    assert_eq!(next(&mut tokenizer), (true, "$", 1, 1, 1, 1));
+    assert!(!tokenizer.has_more_tokens() && !tokenizer.is_eof());
+    assert_eq!(tokenizer.cursor, 2);
+}
+
+#[test]
+fn input_ending_mid_whitespace() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1 2 ");
+
+    assert_eq!(next(&mut tokenizer), (false, "1", 0, 1, 1, 0));
+    assert_eq!(next(&mut tokenizer), (false, "2", 2, 3, 1, 2));
+
+    // The trailing whitespace is left.
+    assert!(tokenizer.has_more_tokens() && !tokenizer.is_eof());
+
+    assert_eq!(next(&mut tokenizer), (true, "$", 4, 4, 1, 4));
+    assert_eq!(tokenizer.cursor, 5);
+}
+
+#[test]
+fn one_eof_token_of_iterator() {
+    for input in ["", "1", " ", "1 $", "1\n  ", "1 2 "] {
+        let mut tokenizer = Tokenizer::new();
+        tokenizer.init_string(input);
+
+        // The EOF token is not yielded, and ends the iteration.
+        assert!(tokenizer.by_ref().all(|token| !token.unwrap().is_eof()), "{:?}", input);
+        assert!(tokenizer.next().is_none(), "{:?}", input);
+
+        // Only the EOF token is returned after the end of input.
+        for _ in 0..2 {
+            let token = tokenizer.get_next_token().unwrap();
+            assert!(token.is_eof(), "{:?}", input);
+            assert_eq!(token.start_offset, input.len() as i32, "{:?}", input);
+            assert_eq!(tokenizer.cursor, input.len() as i32 + 1, "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn input_ending_mid_token() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1 \"ab");
+
+    assert_eq!(next(&mut tokenizer), (false, "1", 0, 1, 1, 0));
+
+    let error = tokenizer.get_next_token().unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"\"\" at 1:2."));
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (2, 3));
+}
+
+#[test]
+fn parse_dollar_token() {
+    assert_eq!(Parser::new().parse("$ 1 \"a\" 2").unwrap(), 3);
+}
+
+#[test]
+fn parse_input_ending_at_boundaries() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("1").unwrap(), 1);
+    assert_eq!(parser.parse(" 1").unwrap(), 1);
+    assert_eq!(parser.parse("1 ").unwrap(), 1);
+    assert_eq!(parser.parse("1 2").unwrap(), 2);
+    assert_eq!(parser.parse("1 2\n  ").unwrap(), 2);
+    assert_eq!(parser.parse("\"a\" $ 1").unwrap(), 2);
+}
+
+#[test]
+fn parse_whitespace_only_input() {
+    let error = Parser::new().parse(" ").unwrap_err();
+    assert_eq!(
+        error.message,
+        "Unexpected end of input; expected one of: NUMBER, STRING, DOLLAR."
+    );
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (1, 1));
+}
+
+#[test]
+fn parse_empty_input() {
+    let error = Parser::new().parse("").unwrap_err();
+    assert_eq!(
+        error.message,
+        "Unexpected end of input; expected one of: NUMBER, STRING, DOLLAR."
+    );
+    assert_eq!(error.expected, vec!["NUMBER", "STRING", "DOLLAR"]);
+}
+
+#[test]
+fn parse_input_ending_after_dollar() {
+    let error = Parser::new().parse("1 $").unwrap_err();
+    assert_eq!(error.message, "Unexpected end of input; expected one of: NUMBER.");
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (3, 3));
+}
+
+#[test]
+fn parse_dollar_before_end_of_input() {
+    let error = Parser::new().parse("$ $").unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"$\" at 1:2; expected one of: NUMBER."));
+}
+
+#[test]
+fn eof_kind() {
+    assert_eq!(EOF_KIND, TOKENS_MAP[EOF]);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.kind, token.value), (EOF_KIND, EOF));
+    assert!(token.is_eof());
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+#[test]
+fn default_error_formatter_message() {
+    let error = Parser::new().parse("2 @").unwrap_err();
+    assert_eq!(error.message, "\n\n2 @\n  ^\n Unexpected token: \"@\" at 1:2.");
+    assert_eq!(error.to_string(), error.message);
+}
+
+#[test]
+fn multibyte_unexpected_character() {
+    let error = Parser::new().parse("2 é 3").unwrap_err();
+    assert_eq!(error.message, "\n\n2 é 3\n  ^\n Unexpected token: \"é\" at 1:2.");
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (2, 4));
+}
+
+#[test]
+fn custom_error_formatter() {
+    let mut parser = Parser::new();
+
+    parser.tokenizer.set_error_formatter(|token, line, column, _| {
+        format!("main.calc:{}:{}: unexpected \"{}\"", line, column, token)
+    });
+
+    let error = parser.parse("2\n+ @").unwrap_err();
+    assert_eq!(error.message, "main.calc:2:2: unexpected \"@\"");
+    assert_eq!((error.loc.start_line, error.loc.start_column), (2, 2));
+}
+
+#[test]
+fn custom_error_formatter_with_source_line() {
+    let mut parser = Parser::new();
+
+    parser.tokenizer.set_error_formatter(|_, _, _, line_data| {
+        format!("main.calc: {}", line_data)
+    });
+
+    let error = parser.parse("2 + *").unwrap_err();
+    assert_eq!(error.message, "main.calc: \n\n2 + *\n    ^\n");
+}
+
+#[test]
+fn expected_tokens_in_message() {
+    let error = Parser::new().parse("2 + * 2").unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"*\" at 1:4; expected one of: NUMBER, (."));
+    assert_eq!(error.expected, vec!["NUMBER", "("]);
+
+    let error = Parser::new().parse("2 +").unwrap_err();
+    assert_eq!(error.message, "Unexpected end of input; expected one of: NUMBER, (.");
+    assert_eq!(error.expected, vec!["NUMBER", "("]);
+
+    let error = Parser::new().parse("(2 + 3").unwrap_err();
+    assert_eq!(error.message, "Unexpected end of input; expected one of: +, *, ).");
+}
+
+#[test]
+fn expected_tokens_with_custom_error_formatter() {
+    let mut parser = Parser::new();
+
+    parser.tokenizer.set_error_formatter(|token, line, column, _| {
+        format!("main.calc:{}:{}: unexpected \"{}\"", line, column, token)
+    });
+
+    // Only in the structured error, the message is formatted as is.
+    let error = parser.parse("2 + * 2").unwrap_err();
+    assert_eq!(error.message, "main.calc:1:4: unexpected \"*\"");
+    assert_eq!(error.expected, vec!["NUMBER", "("]);
+}
+
+#[test]
+fn no_expected_tokens_for_tokenizer_errors() {
+    let error = Parser::new().parse("2 @").unwrap_err();
+    assert!(error.expected.is_empty());
+}
+
+#[test]
+fn truncated_input_is_unexpected_eof() {
+    let error = Parser::new().parse("(2 + 3").unwrap_err();
+
+    assert_eq!(error.kind, SyntaxErrorKind::UnexpectedEof);
+    assert_eq!(error.expected, vec!["+", "*", ")"]);
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (6, 6));
+}
+
+#[test]
+fn stray_token_is_unexpected_token() {
+    let error = Parser::new().parse("2 + ) 3").unwrap_err();
+
+    assert_eq!(error.kind, SyntaxErrorKind::UnexpectedToken { token: ")".to_string() });
+    assert_eq!(error.expected, vec!["NUMBER", "("]);
+
+    // A text no lex rule matches.
+    let error = Parser::new().parse("2 @").unwrap_err();
+    assert_eq!(error.kind, SyntaxErrorKind::UnexpectedToken { token: "@".to_string() });
+}
+
+#[test]
+fn exceeded_limit_is_other_error() {
+    let mut parser = Parser::new();
+    parser.set_max_input_len(Some(2));
+
+    let error = parser.parse("2 + 3").unwrap_err();
+    assert_eq!(error.kind, SyntaxErrorKind::Other);
+}
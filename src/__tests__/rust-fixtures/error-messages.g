@@ -0,0 +1,46 @@
+/**
+ * Statements terminated with `;`: a statement without the `;` is recovered
+ * by the `error` production with a custom message of the error, other
+ * malformed statements are skipped up to the `;`.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+type TResult = Vec<Option<i32>>;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt    { |$1: Vec<Option<i32>>, $2: Option<i32>| -> Vec<Option<i32>>; $1.push($2); $$ = $1 }
+    | Stmt          { |$1: Option<i32>| -> Vec<Option<i32>>; $$ = vec![$1] }
+    ;
+
+Stmt
+    : Expr ';'      { |$1: i32| -> Option<i32>; $$ = Some($1) }
+    | Expr error    %msg "expected ';' after expression"
+                    { |$1: i32| -> Option<i32>; $$ = Some($1) }
+    | error ';'     { || -> Option<i32>; $$ = None }
+    ;
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Expr * Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 * $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    ;
@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn missing_semicolon() {
+    // The statement ends without the `;`, the next one starts at `3`.
+    let (result, errors) = Parser::new().parse_all("1 + 2 3; 4;");
+
+    assert_eq!(result, Some(vec![Some(3), Some(3), Some(4)]));
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "expected ';' after expression");
+    assert_eq!((errors[0].loc.start_offset, errors[0].loc.end_offset), (6, 7));
+    assert_eq!(errors[0].expected, vec!["+", "*", "';'"]);
+}
+
+#[test]
+fn missing_semicolon_at_end_of_input() {
+    let (result, errors) = Parser::new().parse_all("1; 2 * 3");
+
+    assert_eq!(result, Some(vec![Some(1), Some(6)]));
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "expected ';' after expression");
+    assert_eq!(errors[0].loc.start_offset, 8);
+}
+
+#[test]
+fn default_message_without_msg() {
+    // The `error ';'` production has no message.
+    let (result, errors) = Parser::new().parse_all("1; * 2; 3;");
+
+    assert_eq!(result, Some(vec![Some(1), None, Some(3)]));
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.ends_with("Unexpected token: \"*\" at 1:3; expected one of: NUMBER, end of input."));
+}
+
+#[test]
+fn several_errors() {
+    let (result, errors) = Parser::new().parse_all("1 2; 3; 4; * 5; 6; 7; 8 9;");
+
+    assert_eq!(result, Some(vec![Some(1), Some(2), Some(3), Some(4), None, Some(6), Some(7), Some(8), Some(9)]));
+
+    let messages: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0], "expected ';' after expression");
+    assert!(messages[1].contains("Unexpected token: \"*\""));
+    assert_eq!(messages[2], "expected ';' after expression");
+}
+
+#[test]
+fn parse_keeps_default_message() {
+    let error = Parser::new().parse("1 + 2 3;").unwrap_err();
+
+    assert!(error.message.contains("Unexpected token: \"3\""));
+}
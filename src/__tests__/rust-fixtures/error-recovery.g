@@ -0,0 +1,44 @@
+/**
+ * Statements separated with `,`, a malformed statement is skipped up to
+ * the next `,` by the `error` production.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+","     return ",";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+type TResult = Vec<Option<i32>>;
+
+%}
+
+%%
+
+Stmts
+    : Stmts , Stmt  { |$1: Vec<Option<i32>>, $3: Option<i32>| -> Vec<Option<i32>>; $1.push($3); $$ = $1 }
+    | Stmt          { |$1: Option<i32>| -> Vec<Option<i32>>; $$ = vec![$1] }
+    ;
+
+Stmt
+    : Expr          { |$1: i32| -> Option<i32>; $$ = Some($1) }
+    | error         { || -> Option<i32>; $$ = None }
+    ;
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Expr * Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 * $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    ;
@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn all_errors_with_partial_result() {
+    let (result, errors) = Parser::new().parse_all("1 + 2, 3 + * 4, 5 * 6, 7 8, 9");
+
+    assert_eq!(result, Some(vec![Some(3), None, Some(30), None, Some(9)]));
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].message.ends_with("Unexpected token: \"*\" at 1:11; expected one of: NUMBER."));
+    assert_eq!((errors[0].loc.start_offset, errors[0].loc.end_offset), (11, 12));
+    assert_eq!(errors[0].expected, vec!["NUMBER"]);
+    assert_eq!((errors[1].loc.start_offset, errors[1].loc.end_offset), (25, 26));
+}
+
+#[test]
+fn no_errors() {
+    let (result, errors) = Parser::new().parse_all("1, 2 + 3");
+
+    assert_eq!(result, Some(vec![Some(1), Some(5)]));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn skipped_tokens_are_not_reported() {
+    // The tokens after the error are skipped up to the next `,`.
+    let (result, errors) = Parser::new().parse_all("+ + * 1 + 2, 3");
+
+    assert_eq!(result, Some(vec![None, Some(3)]));
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn failed_recovery() {
+    // A lexical error is not recovered.
+    let (result, errors) = Parser::new().parse_all("1 +, 2 ? 3");
+
+    assert_eq!(result, None);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[1].message.contains("Unexpected token: \"?\""));
+}
+
+#[test]
+fn parse_stops_at_first_error() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("1 +, 2 *").is_err());
+
+    // The recovery is only in `parse_all`.
+    let (result, errors) = parser.parse_all("1 +, 2 *");
+    assert_eq!(result, Some(vec![None, None]));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[1].message, "Unexpected end of input; expected one of: NUMBER.");
+}
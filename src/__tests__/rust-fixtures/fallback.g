@@ -0,0 +1,40 @@
+/**
+ * Sum of numbers, where an ERROR token (returned by the fallback
+ * of a state) counts as 0, and a comment state without own rules
+ * for the comment text.
+ */
+
+%lex
+
+%x comment
+
+%%
+
+\s+                 /* skip whitespace */ return "";
+\d+                 return "NUMBER";
+"+"                 return "+";
+
+"/*"                self.begin("comment"); return "";
+<comment>"*/"       self.pop_state(); return "";
+
+/lex
+
+%left +
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Expr
+    : Expr + Term   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Term          { $$ = $1 }
+    ;
+
+Term
+    : NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    | ERROR         { || -> i32; $$ = 0 }
+    ;
@@ -0,0 +1,58 @@
+use super::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn unmatched_character_without_fallback() {
+    let error = Parser::new().parse("1 + @").unwrap_err();
+    assert_eq!(error.message, "\n\n1 + @\n    ^\n Unexpected token: \"@\" at 1:4.");
+}
+
+#[test]
+fn fallback_error_tokens() {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let collected = errors.clone();
+
+    let mut parser = Parser::new();
+
+    parser.tokenizer.set_fallback("INITIAL", move |text| {
+        collected.borrow_mut().push(text.to_string());
+        "ERROR"
+    });
+
+    assert_eq!(parser.parse("1 + @ + 2 + é").unwrap(), 3);
+    assert_eq!(*errors.borrow(), vec!["@", "é"]);
+}
+
+#[test]
+fn fallback_token_location() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_fallback("INITIAL", |_| "ERROR");
+    tokenizer.init_string("1\n @2");
+
+    tokenizer.get_next_token().unwrap();
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!(token.kind, TOKENS_MAP["ERROR"]);
+    assert_eq!(token.value, "@");
+    assert_eq!((token.start_offset, token.end_offset), (3, 4));
+    assert_eq!((token.start_line, token.start_column), (2, 1));
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.value, token.start_column), ("2", 2));
+}
+
+#[test]
+fn fallback_per_state() {
+    let mut parser = Parser::new();
+
+    // Comment text is not matched by the rules of the comment state.
+    assert!(parser.parse("1 /* a + b */ + 2").is_err());
+
+    parser.tokenizer.set_fallback("comment", |_| "");
+    assert_eq!(parser.parse("1 /* a + b */ + 2").unwrap(), 3);
+
+    // The fallback of a state doesn't apply to other states.
+    assert!(parser.parse("1 + @").is_err());
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+use std::path::Path;
+
+// Must not compile: the parsed value can't outlive the buffer of the file.
+fn parse_file(path: &Path) -> Ast<'static> {
+    let mut contents = String::new();
+    Parser::new().parse_file(path, &mut contents).unwrap()
+}
+
+#[test]
+fn file_result_outlives_contents() {
+    assert_eq!(parse_file(Path::new("foo.txt"))[0].value, "foo");
+}
@@ -0,0 +1,107 @@
+use super::*;
+
+// A family emoji: 5 characters (3 emoji joined with ZWJ), 18 bytes.
+static FAMILY: &str = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+
+fn token_locations<'t>(tokenizer: &mut Tokenizer<'t>, string: &'t str) -> Vec<(&'t str, i32, i32, i32, i32)> {
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        tokens.push((
+            token.value,
+            token.start_offset,
+            token.end_offset,
+            token.start_column,
+            token.end_column,
+        ));
+    }
+    tokens
+}
+
+#[test]
+fn family_emoji_is_one_column() {
+    let string = format!("{} x", FAMILY);
+
+    assert_eq!(
+        token_locations(&mut Tokenizer::new(), &string),
+        vec![(FAMILY, 0, 18, 0, 1), ("x", 19, 20, 2, 3)]
+    );
+}
+
+#[test]
+fn modifiers_and_combining_marks() {
+    let tokens = token_locations(&mut Tokenizer::new(), "\u{1f44d}\u{1f3fd} cafe\u{301} x");
+
+    assert_eq!(tokens[0].1..tokens[0].2, 0..8);
+    assert_eq!(tokens[0].3..tokens[0].4, 0..1);
+
+    assert_eq!(tokens[1].1..tokens[1].2, 9..15);
+    assert_eq!(tokens[1].3..tokens[1].4, 2..6);
+
+    assert_eq!(tokens[2].3..tokens[2].4, 7..8);
+}
+
+#[test]
+fn columns_on_next_lines() {
+    let string = format!("a\n{} x", FAMILY);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(&string);
+
+    let tokens: Vec<Token> = tokenizer.map(|token| token.unwrap()).collect();
+    let x = tokens[2];
+
+    assert_eq!((x.start_offset, x.end_offset), (21, 22));
+    assert_eq!((x.start_line, x.start_column, x.end_column), (2, 2, 3));
+}
+
+#[test]
+fn tab_stops() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+
+    let tokens = token_locations(&mut tokenizer, "\u{1f44d}\u{1f3fd}\tx");
+
+    assert_eq!(tokens[1].3..tokens[1].4, 4..5);
+}
+
+#[test]
+fn utf16_offsets() {
+    let string = format!("{} x", FAMILY);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_utf16_offsets(true);
+
+    let tokens = token_locations(&mut tokenizer, &string);
+
+    assert_eq!(tokens[0].1..tokens[0].2, 0..8);
+    assert_eq!(tokens[1].1..tokens[1].2, 9..10);
+    assert_eq!(tokens[1].3..tokens[1].4, 2..3);
+}
+
+#[test]
+fn byte_columns() {
+    let string = format!("{} x", FAMILY);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_grapheme_columns(false);
+
+    let tokens = token_locations(&mut tokenizer, &string);
+
+    assert_eq!(tokens[1].3..tokens[1].4, 19..20);
+}
+
+#[test]
+fn error_location() {
+    let string = format!("{} 'x", FAMILY);
+    let error = Parser::new().parse(&string).unwrap_err();
+
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (19, 20));
+    assert_eq!((error.loc.start_column, error.loc.end_column), (2, 3));
+    assert!(error.message.contains(&format!("{} 'x\n  ^\n", FAMILY)));
+}
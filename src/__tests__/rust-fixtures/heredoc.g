@@ -0,0 +1,50 @@
+/**
+ * Words and heredocs, `<<TAG` on its own line, the body is read by the
+ * lex handler until a line with the same tag.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+"<<"\w+\n
+    %{
+        let tag = yytext[2..].trim_end().to_string();
+        let terminator = Regex::new(&format!("\n{}\\b", tag)).unwrap();
+
+        let text = self.read_until(&terminator);
+
+        let end = format!("\n{}", tag);
+
+        if text.ends_with(&end) {
+            yytext = &text[..text.len() - end.len()];
+        } else {
+            self.set_error(format!("Unterminated heredoc: {}", tag));
+        }
+
+        return "HEREDOC";
+    %}
+
+\w+         return "WORD";
+
+/lex
+
+%{
+
+type TResult<'t> = Vec<Token<'t>>;
+
+%}
+
+%%
+
+Items
+    : Items Item  { |$1: Vec<Token>, $2: Token| -> Vec<Token>; $1.push($2); $$ = $1 }
+    | Item        { |$1: Token| -> Vec<Token>; $$ = vec![$1] }
+    ;
+
+Item
+    : WORD     { |$1: Token| -> Token; $$ = $1 }
+    | HEREDOC  { |$1: Token| -> Token; $$ = $1 }
+    ;
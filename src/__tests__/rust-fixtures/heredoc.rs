@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn heredoc_body_until_terminator() {
+    let mut parser = Parser::new();
+
+    let tokens = parser
+        .parse("cat <<EOT\nline 1\n  line 2 EOT\nEOT\nend")
+        .unwrap();
+    let values: Vec<&str> = tokens.iter().map(|token| token.value).collect();
+
+    assert_eq!(values, vec!["cat", "line 1\n  line 2 EOT", "end"]);
+    assert_ne!(tokens[1].kind, tokens[0].kind);
+}
+
+#[test]
+fn terminator_is_the_captured_tag() {
+    let mut parser = Parser::new();
+
+    let tokens = parser.parse("<<A\nEOT\nB\nA <<B\nA\nB").unwrap();
+    let values: Vec<&str> = tokens.iter().map(|token| token.value).collect();
+
+    assert_eq!(values, vec!["EOT\nB", "A"]);
+}
+
+#[test]
+fn heredoc_location_spans_terminator() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("x <<EOT\na\nb\nEOT y");
+
+    tokenizer.get_next_token().unwrap();
+    let heredoc = tokenizer.get_next_token().unwrap();
+
+    assert_eq!((heredoc.start_offset, heredoc.end_offset), (2, 15));
+    assert_eq!((heredoc.start_line, heredoc.start_column), (1, 2));
+    assert_eq!((heredoc.end_line, heredoc.end_column), (4, 3));
+
+    let next = tokenizer.get_next_token().unwrap();
+
+    assert_eq!(next.value, "y");
+    assert_eq!(
+        (next.start_offset, next.start_line, next.start_column),
+        (16, 4, 4)
+    );
+}
+
+#[test]
+fn unterminated_heredoc() {
+    let mut parser = Parser::new();
+
+    let error = parser.parse("<<EOT\nabc\nEO").unwrap_err();
+
+    assert_eq!(error.message, "Unterminated heredoc: EOT");
+    assert_eq!(error.loc.start_offset, 0);
+}
@@ -0,0 +1,31 @@
+/**
+ * Calculator grammar without a whitespace rule: whitespace, and
+ * comments are skipped with the `%ignore` directive.
+ */
+
+%lex
+
+%ignore \s+
+%ignore "#".*
+
+%%
+
+\d+     return "NUMBER";
+"+"     return "+";
+
+/lex
+
+%left +
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    ;
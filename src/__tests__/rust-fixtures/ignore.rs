@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn spaced_input() {
+    assert_eq!(Parser::new().parse(" 1 +\n  2\t+ 3 ").unwrap(), 6);
+}
+
+#[test]
+fn ignored_comments() {
+    assert_eq!(Parser::new().parse("1 + # one\n2 # two").unwrap(), 3);
+}
+
+#[test]
+fn ignored_text_between_tokens() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("  12 # x\n 3");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.value, token.start_offset), ("12", 2));
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.value, token.start_line, token.start_column), ("3", 2, 1));
+
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
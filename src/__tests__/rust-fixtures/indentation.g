@@ -0,0 +1,33 @@
+/**
+ * Layout-sensitive blocks, the INDENT, DEDENT, and NEWLINE tokens
+ * are produced by the indentation tracking of the tokenizer.
+ */
+
+%lex
+
+%%
+
+(\n[ \t]*)+     return self.indentation();
+[ \t]+          /* skip whitespace */ return "";
+":"             return "COLON";
+\w+             return "ID";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt  { |$1: i32, $2: i32| -> i32; $$ = $1 + $2 }
+    | Stmt        { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Stmt
+    : ID NEWLINE                            { || -> i32; $$ = 1 }
+    | ID COLON NEWLINE INDENT Stmts DEDENT   { |$5: i32| -> i32; $$ = 1 + $5 }
+    ;
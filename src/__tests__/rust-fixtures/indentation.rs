@@ -0,0 +1,99 @@
+use super::*;
+
+fn token_types(
+    string: &'static str,
+    tab_width: i32,
+    policy: IndentPolicy,
+) -> Result<Vec<&'static str>, SyntaxError> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+    tokenizer.set_tab_width(tab_width);
+    tokenizer.set_indent_policy(policy);
+
+    let mut types = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token()?;
+        if token.is_eof() {
+            break;
+        }
+        let (token_type, _) = TOKENS_MAP
+            .iter()
+            .find(|(_, kind)| **kind == token.kind)
+            .unwrap();
+        types.push(*token_type);
+    }
+    Ok(types)
+}
+
+#[test]
+fn indented_blocks() {
+    assert_eq!(
+        token_types("if:\n  a\n  b:\n    c\nd", 1, IndentPolicy::TabStops).unwrap(),
+        vec![
+            "ID", "COLON", "NEWLINE", "INDENT",
+                "ID", "NEWLINE",
+                "ID", "COLON", "NEWLINE", "INDENT",
+                    "ID", "NEWLINE",
+                "DEDENT",
+            "DEDENT",
+            "ID", "NEWLINE",
+        ]
+    );
+}
+
+#[test]
+fn balanced_dedents_at_end_of_input() {
+    assert_eq!(
+        token_types("a:\n  b:\n    c", 1, IndentPolicy::TabStops).unwrap(),
+        vec![
+            "ID", "COLON", "NEWLINE", "INDENT",
+                "ID", "COLON", "NEWLINE", "INDENT",
+                    "ID", "NEWLINE",
+                "DEDENT",
+            "DEDENT",
+        ]
+    );
+}
+
+#[test]
+fn blank_lines() {
+    assert_eq!(
+        token_types("\n\na:\n\n  b\n\n", 1, IndentPolicy::TabStops).unwrap(),
+        vec![
+            "ID", "COLON", "NEWLINE", "INDENT",
+                "ID", "NEWLINE",
+            "DEDENT",
+        ]
+    );
+}
+
+#[test]
+fn tabs_expand_to_tab_stops() {
+    assert_eq!(
+        token_types("a:\n\tb\n        c\n  \td", 8, IndentPolicy::TabStops).unwrap(),
+        vec![
+            "ID", "COLON", "NEWLINE", "INDENT",
+                "ID", "NEWLINE",
+                "ID", "NEWLINE",
+                "ID", "NEWLINE",
+            "DEDENT",
+        ]
+    );
+}
+
+#[test]
+fn mixed_spaces_and_tabs() {
+    let error = token_types("a:\n\tb\n \tc", 8, IndentPolicy::NoMixing).unwrap_err();
+    assert_eq!(error.message, "Mixed spaces and tabs in indentation at 3:8.");
+}
+
+#[test]
+fn inconsistent_dedent() {
+    let error = token_types("a:\n    b\n  c", 1, IndentPolicy::TabStops).unwrap_err();
+    assert_eq!(error.message, "Inconsistent dedent at 3:2.");
+}
+
+#[test]
+fn parse_indented_blocks() {
+    assert_eq!(Parser::new().parse("a:\n  b\n  c:\n    d\ne\n").unwrap(), 5);
+}
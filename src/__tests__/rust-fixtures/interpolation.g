@@ -0,0 +1,79 @@
+/**
+ * Concatenation of names, numbers, and strings. An expression in `${...}`
+ * of a string is parsed from the action of the string with the same
+ * grammar (`parse_nested`), and its value is interpolated.
+ */
+
+%lex
+
+%%
+
+\s+                             /* skip whitespace */ return "";
+\d+                             return "NUMBER";
+\w+                             return "NAME";
+
+\"(\$\{[^}]*\}|[^"$])*\"        return "STRING";
+\'(\$\{[^}]*\}|[^'$])*\'        return "STRING";
+
+"+"                             return "+";
+
+/lex
+
+%left +
+
+%{
+
+type TResult = String;
+
+/**
+ * Offset of the `}` closing an expression, which can have nested `${...}`.
+ */
+fn closing_brace(string: &str, from: usize) -> usize {
+    let mut depth = 0;
+
+    for (i, c) in string[from..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return from + i,
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    string.len()
+}
+
+/**
+ * Contents of a string literal with the values of its `${...}` expressions.
+ */
+fn interpolate<'t>(parser: &mut Parser<'t>, literal: &'t str) -> String {
+    let mut rest = &literal[1..literal.len() - 1];
+    let mut result = String::new();
+
+    while let Some(start) = rest.find("${") {
+        let end = closing_brace(rest, start + 2);
+
+        result.push_str(&rest[..start]);
+
+        match parser.parse_nested(&rest[start + 2..end]) {
+            Ok(value) => result.push_str(&value),
+            Err(_) => return result,
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: String, $3: String| -> String; $$ = $1 + &$3 }
+    | NUMBER        { |$1: Token| -> String; $$ = $1.value.to_string() }
+    | NAME          { |$1: Token| -> String; $$ = $1.value.to_string() }
+    | STRING        { |$1: Token| -> String; $$ = interpolate(self, $1.value) }
+    ;
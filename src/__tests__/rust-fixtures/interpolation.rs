@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn interpolated_expression() {
+    let value = Parser::new().parse("\"a${b + 1}c\" + d").unwrap();
+
+    assert_eq!(value, "ab1cd");
+}
+
+#[test]
+fn nested_interpolation() {
+    let value = Parser::new().parse("\"<${'(${x + 2})' + y}>\"").unwrap();
+
+    assert_eq!(value, "<(x2)y>");
+}
+
+#[test]
+fn outer_parse_continues_after_nested_one() {
+    let mut parser = Parser::new();
+    let value = parser.parse("'${1 + 2}' + 3 + '${4}'").unwrap();
+
+    assert_eq!(value, "1234");
+    assert_eq!(parser.parse("'${x}' + y").unwrap(), "xy");
+}
+
+#[test]
+fn error_of_nested_parse() {
+    let error = Parser::new().parse("\"a${b +}\" + c").unwrap_err();
+
+    assert_eq!(error.message, "Unexpected end of input; expected one of: NUMBER, NAME, STRING.");
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (3, 3));
+}
+
+#[test]
+fn nested_parse_has_parser_settings() {
+    let mut parser = Parser::new();
+    parser.tokenizer.set_track_locations(false);
+
+    let error = parser.parse("'${+}'").unwrap_err();
+
+    assert_eq!(error.message, "Unexpected token: \"+\" at offset 0; expected one of: NUMBER, NAME, STRING.");
+}
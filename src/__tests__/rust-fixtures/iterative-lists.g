@@ -0,0 +1,55 @@
+/**
+ * Right-recursive lists without actions, which are parsed iteratively
+ * with the `iterativeLists` option: a list of numbers separated by
+ * commas, and a possibly empty list of words.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+\d+         return "NUMBER";
+\w+         return "WORD";
+
+/lex
+
+%{
+
+#[derive(Debug)]
+pub struct Program {
+    pub numbers: Vec<i32>,
+    pub words: Vec<String>,
+}
+
+type TResult = Program;
+
+%}
+
+%%
+
+Program
+    : Numbers ';' Words
+        {
+            |$1: Vec<i32>, $3: Vec<Token>| -> Program;
+
+            $$ = Program {
+                numbers: $1,
+                words: $3.iter().map(|word| word.value.to_string()).collect(),
+            }
+        }
+    ;
+
+Numbers
+    : Number ',' Numbers
+    | Number
+    ;
+
+Number
+    : NUMBER    { |$1: Token| -> i32; $$ = $1.value.parse::<i32>().unwrap() }
+    ;
+
+Words
+    : WORD Words
+    | /* empty */
+    ;
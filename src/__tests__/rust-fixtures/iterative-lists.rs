@@ -0,0 +1,90 @@
+use super::*;
+
+// The maximum size of the parsing stack.
+fn max_stack_depth(string: &str) -> usize {
+    let mut parser = Parser::new();
+    let mut depth: usize = 0;
+    let mut max_depth = 0;
+
+    for event in parser.parse_events(string) {
+        match event.unwrap() {
+            ParseEvent::Shift(_) => depth += 1,
+            ParseEvent::Reduce { production, .. } => {
+                depth = depth + 1 - PRODUCTIONS[production].1.len();
+            }
+            _ => {}
+        }
+        max_depth = max_depth.max(depth);
+    }
+
+    max_depth
+}
+
+#[test]
+fn lists_are_left_recursive() {
+    assert!(PRODUCTIONS.contains(&("Numbers", &["Numbers", "','", "Number"])));
+    assert!(PRODUCTIONS.contains(&("Words", &["Words", "WORD"])));
+}
+
+#[test]
+fn flat_lists() {
+    let mut parser = Parser::new();
+
+    let program = parser.parse("1, 2, 3; a b").unwrap();
+
+    assert_eq!(program.numbers, vec![1, 2, 3]);
+    assert_eq!(program.words, vec!["a", "b"]);
+
+    let program = parser.parse("1;").unwrap();
+
+    assert_eq!(program.numbers, vec![1]);
+    assert!(program.words.is_empty());
+}
+
+#[test]
+fn long_list() {
+    let numbers: Vec<String> = (0..50000).map(|n| n.to_string()).collect();
+    let words = vec!["w"; 50000];
+    let string = format!("{}; {}", numbers.join(", "), words.join(" "));
+
+    let mut parser = Parser::new();
+    let program = parser.parse(&string).unwrap();
+
+    assert_eq!(program.numbers.len(), 50000);
+    assert!(program.numbers.iter().enumerate().all(|(i, n)| *n == i as i32));
+    assert_eq!(program.words.len(), 50000);
+
+    // The stack doesn't grow with the list.
+    assert!(max_stack_depth(&string) < 10);
+}
+
+// Spans of the last reduced lists.
+fn list_spans(string: &str) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+    let mut parser = Parser::new();
+    let mut numbers = 0..0;
+    let mut words = 0..0;
+
+    for event in parser.parse_events(string) {
+        if let ParseEvent::Reduce { production, span } = event.unwrap() {
+            match PRODUCTIONS[production].0 {
+                "Numbers" => numbers = span.span(),
+                "Words" => words = span.span(),
+                _ => {}
+            }
+        }
+    }
+
+    (numbers, words)
+}
+
+#[test]
+fn list_spans_cover_items() {
+    // The empty start of the list is not a part of its span.
+    assert_eq!(list_spans("1, 2, 3;  a b"), (0..7, 10..13));
+}
+
+#[test]
+fn empty_list_span() {
+    // Zero-width span at the end of the previous symbol.
+    assert_eq!(list_spans("1;  "), (0..1, 2..2));
+}
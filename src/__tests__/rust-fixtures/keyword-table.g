@@ -0,0 +1,124 @@
+/**
+ * Keywords (40 of them), and identifiers, the keyword rules are matched
+ * by the identifier rule, and a lookup of its text (the `keywordTable`
+ * option). The `in` keyword is only in the INITIAL condition, so it's
+ * an identifier in the attributes, `#[...]`. Used to test (and benchmark)
+ * the same tokens with, and without the option.
+ */
+
+%lex
+
+%s attr
+
+%%
+
+\s+                     /* skip whitespace */ return "";
+
+abstract\b              return "ABSTRACT";
+as\b                    return "AS";
+async\b                 return "ASYNC";
+await\b                 return "AWAIT";
+break\b                 return "BREAK";
+case\b                  return "CASE";
+catch\b                 return "CATCH";
+class\b                 return "CLASS";
+const\b                 return "CONST";
+\bcontinue\b            return "CONTINUE";
+default\b               return "DEFAULT";
+do\b                    return "DO";
+else\b                  return "ELSE";
+enum\b                  return "ENUM";
+export\b                return "EXPORT";
+extends\b               return "EXTENDS";
+false\b                 return "FALSE";
+final\b                 return "FINAL";
+finally\b               return "FINALLY";
+fn\b                    return "FN";
+for\b                   return "FOR";
+if\b                    return "IF";
+impl\b                  return "IMPL";
+import\b                return "IMPORT";
+<INITIAL>in\b           return "IN";
+interface\b             return "INTERFACE";
+let\b                   return "LET";
+loop\b                  return "LOOP";
+match\b                 return "MATCH";
+mod\b                   return "MOD";
+new\b                   return "NEW";
+null\b                  return "NULL";
+return\b                return "RETURN";
+static\b                return "STATIC";
+struct\b                return "STRUCT";
+super\b                 return "SUPER";
+switch\b                return "SWITCH";
+this\b                  return "THIS";
+throw\b                 return "THROW";
+true\b                  return "TRUE";
+
+[a-zA-Z_]\w*            return "ID";
+\d+                     return "NUMBER";
+
+"#["                    self.begin("attr"); return "OP";
+<attr>"]"               self.pop_state(); return "OP";
+[-+*/=<>!;,.{}()\[\]]   return "OP";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Items
+    : Items Item  { |$1: i32, $2: i32| -> i32; $$ = $1 + $2 }
+    | Item        { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Item
+    : ABSTRACT    { || -> i32; $$ = 1 }
+    | AS          { || -> i32; $$ = 1 }
+    | ASYNC       { || -> i32; $$ = 1 }
+    | AWAIT       { || -> i32; $$ = 1 }
+    | BREAK       { || -> i32; $$ = 1 }
+    | CASE        { || -> i32; $$ = 1 }
+    | CATCH       { || -> i32; $$ = 1 }
+    | CLASS       { || -> i32; $$ = 1 }
+    | CONST       { || -> i32; $$ = 1 }
+    | CONTINUE    { || -> i32; $$ = 1 }
+    | DEFAULT     { || -> i32; $$ = 1 }
+    | DO          { || -> i32; $$ = 1 }
+    | ELSE        { || -> i32; $$ = 1 }
+    | ENUM        { || -> i32; $$ = 1 }
+    | EXPORT      { || -> i32; $$ = 1 }
+    | EXTENDS     { || -> i32; $$ = 1 }
+    | FALSE       { || -> i32; $$ = 1 }
+    | FINAL       { || -> i32; $$ = 1 }
+    | FINALLY     { || -> i32; $$ = 1 }
+    | FN          { || -> i32; $$ = 1 }
+    | FOR         { || -> i32; $$ = 1 }
+    | IF          { || -> i32; $$ = 1 }
+    | IMPL        { || -> i32; $$ = 1 }
+    | IMPORT      { || -> i32; $$ = 1 }
+    | IN          { || -> i32; $$ = 1 }
+    | INTERFACE   { || -> i32; $$ = 1 }
+    | LET         { || -> i32; $$ = 1 }
+    | LOOP        { || -> i32; $$ = 1 }
+    | MATCH       { || -> i32; $$ = 1 }
+    | MOD         { || -> i32; $$ = 1 }
+    | NEW         { || -> i32; $$ = 1 }
+    | NULL        { || -> i32; $$ = 1 }
+    | RETURN      { || -> i32; $$ = 1 }
+    | STATIC      { || -> i32; $$ = 1 }
+    | STRUCT      { || -> i32; $$ = 1 }
+    | SUPER       { || -> i32; $$ = 1 }
+    | SWITCH      { || -> i32; $$ = 1 }
+    | THIS        { || -> i32; $$ = 1 }
+    | THROW       { || -> i32; $$ = 1 }
+    | TRUE        { || -> i32; $$ = 1 }
+    | ID          { || -> i32; $$ = 1 }
+    | NUMBER      { || -> i32; $$ = 1 }
+    | OP          { || -> i32; $$ = 1 }
+    ;
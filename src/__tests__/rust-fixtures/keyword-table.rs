@@ -0,0 +1,90 @@
+use super::*;
+
+use std::time::Instant;
+
+static SOURCE: &str = "\
+    async fn main() { let x = await loop_ + 10; if (x) return x; else break; }
+    #[in import] for item in items { continue; } ifé iffy _if if9 true_ Final
+";
+
+// (kind, value, start_offset, end_offset)
+fn tokens(string: &str) -> Vec<(i32, &str, i32, i32)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        tokens.push((token.kind, token.value, token.start_offset, token.end_offset));
+    }
+    tokens
+}
+
+fn kinds(string: &str) -> Vec<i32> {
+    tokens(string).iter().map(|token| token.0).collect()
+}
+
+#[test]
+fn keywords() {
+    assert_eq!(
+        kinds("abstract as async await break case true"),
+        vec![
+            TOKENS_MAP["ABSTRACT"],
+            TOKENS_MAP["AS"],
+            TOKENS_MAP["ASYNC"],
+            TOKENS_MAP["AWAIT"],
+            TOKENS_MAP["BREAK"],
+            TOKENS_MAP["CASE"],
+            TOKENS_MAP["TRUE"],
+        ]
+    );
+
+    // Before a non-word char.
+    assert_eq!(
+        kinds("if(x)"),
+        vec![TOKENS_MAP["IF"], TOKENS_MAP["OP"], TOKENS_MAP["ID"], TOKENS_MAP["OP"]]
+    );
+}
+
+#[test]
+fn identifiers_with_keyword_prefix() {
+    for &string in &["iffy", "_if", "if9", "ifé", "true_", "Final", "continues"] {
+        assert_eq!(kinds(string)[0], TOKENS_MAP["ID"], "{}", string);
+    }
+
+    assert_eq!(tokens("ifé")[0], (TOKENS_MAP["ID"], "ifé", 0, 4));
+}
+
+#[test]
+fn keyword_of_start_condition() {
+    // The `in` keyword is only in the INITIAL condition.
+    assert_eq!(
+        kinds("in #[in if]"),
+        vec![
+            TOKENS_MAP["IN"],
+            TOKENS_MAP["OP"],
+            TOKENS_MAP["ID"],
+            TOKENS_MAP["IF"],
+            TOKENS_MAP["OP"],
+        ]
+    );
+}
+
+#[test]
+fn parse_keywords() {
+    assert_eq!(Parser::new().parse(SOURCE).unwrap(), 43);
+}
+
+// Run with: cargo test --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn benchmark() {
+    let source = SOURCE.repeat(2000);
+
+    let start = Instant::now();
+    let count = tokens(&source).len();
+    println!("tokens: {}, time: {:?}", count, start.elapsed());
+}
@@ -0,0 +1,60 @@
+/**
+ * Division, and regex literals: a `/` after an operand is the division,
+ * otherwise it starts a regex (the lex handler checks the kind of the
+ * previous token).
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+"/"
+    %{
+        if self.last_token_kind().is_some_and(is_operand) {
+            return "DIVIDE";
+        }
+
+        let text = self.read_until(&Regex::new("/").unwrap());
+
+        if let Some(body) = text.strip_suffix('/') {
+            yytext = body;
+        } else {
+            self.set_error("Unterminated regex".to_string());
+        }
+
+        return "REGEX";
+    %}
+
+\w+         return "ID";
+"("         return "(";
+")"         return ")";
+
+/lex
+
+%{
+
+type TResult = String;
+
+/**
+ * The division follows an operand, an identifier, or a closing paren.
+ */
+fn is_operand(kind: i32) -> bool {
+    Some(kind) == token_kind("ID") || Some(kind) == token_kind(")")
+}
+
+%}
+
+%%
+
+Expr
+    : Expr DIVIDE Term  { |$1: String, $3: String| -> String; $$ = format!("(div {} {})", $1, $3) }
+    | Term              { $$ = $1 }
+    ;
+
+Term
+    : ID                { || -> String; $$ = yytext.to_string() }
+    | REGEX             { || -> String; $$ = format!("/{}/", yytext) }
+    | ( Expr )          { $$ = $2 }
+    ;
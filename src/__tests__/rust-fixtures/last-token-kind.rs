@@ -0,0 +1,70 @@
+use super::*;
+
+fn kinds(string: &str) -> Vec<(i32, &str)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut kinds = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        kinds.push((token.kind, token.value));
+    }
+    kinds
+}
+
+#[test]
+fn division_or_regex() {
+    let id = token_kind("ID").unwrap();
+    let divide = token_kind("DIVIDE").unwrap();
+    let regex = token_kind("REGEX").unwrap();
+
+    assert_eq!(kinds("a / b"), vec![(id, "a"), (divide, "/"), (id, "b")]);
+    assert_eq!(kinds("/a b/"), vec![(regex, "a b")]);
+    assert_eq!(kinds("a / /b/"), vec![(id, "a"), (divide, "/"), (regex, "b")]);
+}
+
+#[test]
+fn after_paren() {
+    let divide = token_kind("DIVIDE").unwrap();
+    let regex = token_kind("REGEX").unwrap();
+
+    assert_eq!(kinds("(a) / b")[3].0, divide);
+    assert_eq!(kinds("(/a/)")[1].0, regex);
+}
+
+#[test]
+fn last_token_kind() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("  a (");
+
+    assert_eq!(tokenizer.last_token_kind(), None);
+
+    tokenizer.get_next_token().unwrap();
+    assert_eq!(tokenizer.last_token_kind(), token_kind("ID"));
+
+    tokenizer.get_next_token().unwrap();
+    assert_eq!(tokenizer.last_token_kind(), token_kind("("));
+
+    // The next string starts without the previous token.
+    tokenizer.init_string("/a/");
+    assert_eq!(tokenizer.last_token_kind(), None);
+    assert_eq!(tokenizer.get_next_token().unwrap().kind, token_kind("REGEX").unwrap());
+}
+
+#[test]
+fn parse() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("a / b / c").unwrap(), "(div (div a b) c)");
+    assert_eq!(parser.parse("(/x+/) / /y/").unwrap(), "(div /x+/ /y/)");
+}
+
+#[test]
+fn unterminated_regex() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("a / (/b").unwrap_err().message, "Unterminated regex");
+}
@@ -0,0 +1,58 @@
+/**
+ * Keywords, identifiers, numbers, and operators in the combinable
+ * INITIAL condition, and a string condition with a capturing group
+ * (not combinable). Whitespace can match an empty string. Used to
+ * test (and benchmark) the `lexAlternation` option.
+ */
+
+%lex
+
+%x string
+
+%%
+
+\s*                     /* skip whitespace, can match empty */ return "";
+"//".*                  /* skip comments */ return "";
+
+"if"\b                  return "IF";
+"else"\b                return "ELSE";
+"while"\b               return "WHILE";
+"return"\b              return "RETURN";
+"null"\b                return "NULL";
+
+[a-zA-Z_]\w*            return "ID";
+\d+(?:\.\d+)?           return "NUMBER";
+
+"=="                    return "OP";
+[-+*/=<>!;,{}()]        return "OP";
+
+\"                      self.begin("string"); return "";
+<string>(\\.|[^"\\])+   return "STRING";
+<string>\"              self.pop_state(); return "";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Items
+    : Items Item  { |$1: i32, $2: i32| -> i32; $$ = $1 + $2 }
+    | Item        { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Item
+    : IF        { || -> i32; $$ = 1 }
+    | ELSE      { || -> i32; $$ = 1 }
+    | WHILE     { || -> i32; $$ = 1 }
+    | RETURN    { || -> i32; $$ = 1 }
+    | NULL      { || -> i32; $$ = 1 }
+    | ID        { || -> i32; $$ = 1 }
+    | NUMBER    { || -> i32; $$ = 1 }
+    | OP        { || -> i32; $$ = 1 }
+    | STRING    { || -> i32; $$ = 1 }
+    ;
@@ -0,0 +1,98 @@
+use super::*;
+
+use std::time::Instant;
+
+static SOURCE: &str = "\
+    if (x == 1) { return \"a \\\" b\"; } // comment
+    else while (y2 < 3.5) { x = null; iffy = 10; }
+";
+
+// (kind, value, start_offset, end_offset, start_line, start_column)
+fn tokens(string: &str, combined_rules: bool) -> Vec<(i32, &str, i32, i32, i32, i32)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.combined_rules = combined_rules;
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        tokens.push((
+            token.kind,
+            token.value,
+            token.start_offset,
+            token.end_offset,
+            token.start_line,
+            token.start_column,
+        ));
+    }
+    tokens
+}
+
+#[test]
+fn combinable_conditions() {
+    assert!(COMBINED_REGEX_RULES.contains_key("INITIAL"));
+
+    // A rule with a capturing group.
+    assert!(!COMBINED_REGEX_RULES.contains_key("string"));
+}
+
+#[test]
+fn same_tokens_as_rules_loop() {
+    let combined = tokens(SOURCE, true);
+
+    assert_eq!(combined, tokens(SOURCE, false));
+    assert_eq!(combined.len(), 28);
+
+    let values: Vec<&str> = combined.iter().map(|token| token.1).take(8).collect();
+    assert_eq!(values, vec!["if", "(", "x", "==", "1", ")", "{", "return"]);
+}
+
+#[test]
+fn first_rule_wins() {
+    let kinds = |string| -> Vec<i32> {
+        tokens(string, true).iter().map(|token| token.0).collect()
+    };
+
+    // Keywords are before identifiers, `==` before `=`.
+    assert_eq!(kinds("if iffy"), vec![TOKENS_MAP["IF"], TOKENS_MAP["ID"]]);
+    assert_eq!(kinds("=="), vec![TOKENS_MAP["OP"]]);
+    assert_eq!(tokens("==", true)[0].1, "==");
+}
+
+#[test]
+fn empty_match_tries_next_rules() {
+    // The whitespace rule matches empty before each token.
+    assert_eq!(tokens("x1", true), vec![(TOKENS_MAP["ID"], "x1", 0, 2, 1, 0)]);
+}
+
+#[test]
+fn parse_with_combined_rules() {
+    assert_eq!(Parser::new().parse(SOURCE).unwrap(), 28);
+}
+
+#[test]
+fn error_with_combined_rules() {
+    let error = Parser::new().parse("x @").unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"@\" at 1:2."));
+}
+
+// Run with: cargo test --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn benchmark() {
+    let source = SOURCE.repeat(2000);
+
+    for &combined_rules in &[false, true] {
+        let start = Instant::now();
+        let count = tokens(&source, combined_rules).len();
+        println!(
+            "combined rules: {:5}, tokens: {}, time: {:?}",
+            combined_rules,
+            count,
+            start.elapsed()
+        );
+    }
+}
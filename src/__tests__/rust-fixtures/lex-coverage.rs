@@ -0,0 +1,100 @@
+use super::*;
+
+static CORPUS: [&str; 3] = [
+    "if x return x; else break;",
+    "async fn main() { let iffy = 10; }",
+    "#[in import] for item in items { continue; }",
+];
+
+fn hits(coverage: &[(&'static str, usize)], rule: &str) -> usize {
+    coverage.iter().find(|(regex, _)| *regex == rule).unwrap().1
+}
+
+fn corpus_coverage(parser: &mut Parser) -> Vec<(&'static str, usize)> {
+    parser.set_lex_coverage(true);
+
+    for source in CORPUS.iter() {
+        parser.parse(source).unwrap();
+    }
+
+    parser.lex_coverage()
+}
+
+#[test]
+fn unexercised_rules() {
+    let coverage = corpus_coverage(&mut Parser::new());
+
+    assert_eq!(coverage.len(), LEX_RULES.len());
+
+    let unexercised: Vec<&str> = coverage
+        .iter()
+        .filter(|(_, hits)| *hits == 0)
+        .map(|(regex, _)| *regex)
+        .collect();
+
+    assert!(unexercised.contains(&r"^abstract\b"));
+    assert!(unexercised.contains(&r"^true\b"));
+    assert!(!unexercised.contains(&r"^if\b"));
+}
+
+#[test]
+fn rule_hits() {
+    let coverage = corpus_coverage(&mut Parser::new());
+
+    // The keywords are counted for the keyword rules, not the identifiers.
+    assert_eq!(hits(&coverage, r"^if\b"), 1);
+    assert_eq!(hits(&coverage, r"^return\b"), 1);
+    assert_eq!(hits(&coverage, r"^[a-zA-Z_]\w*"), 7);
+    assert_eq!(hits(&coverage, r"^\d+"), 1);
+
+    // `in` is an identifier in the attributes.
+    assert_eq!(hits(&coverage, r"^in\b"), 1);
+}
+
+#[test]
+fn accumulated_over_resets() {
+    let mut parser = Parser::new();
+    parser.set_lex_coverage(true);
+    parser.parse("if").unwrap();
+
+    let mut parser = parser.reset();
+    parser.parse("if else").unwrap();
+
+    let coverage = parser.lex_coverage();
+
+    assert_eq!(hits(&coverage, r"^if\b"), 2);
+    assert_eq!(hits(&coverage, r"^else\b"), 1);
+
+    // Set again, the counts start from zero.
+    parser.set_lex_coverage(true);
+
+    assert!(parser.lex_coverage().iter().all(|(_, hits)| *hits == 0));
+}
+
+#[test]
+fn tokenizer_coverage() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_lex_coverage(true);
+    tokenizer.init_string("while (x) 1");
+
+    while !tokenizer.get_next_token().unwrap().is_eof() {}
+
+    let coverage = tokenizer.lex_coverage();
+
+    assert_eq!(hits(&coverage, r"^[a-zA-Z_]\w*"), 2);
+    assert_eq!(hits(&coverage, r"^[-+*/=<>!;,.{}()\[\]]"), 2);
+    assert_eq!(hits(&coverage, r"^\s+"), 2);
+}
+
+#[test]
+fn disabled() {
+    let mut parser = Parser::new();
+    parser.parse("if x").unwrap();
+
+    assert!(parser.lex_coverage().is_empty());
+
+    parser.set_lex_coverage(true);
+    parser.set_lex_coverage(false);
+
+    assert!(parser.lex_coverage().is_empty());
+}
@@ -0,0 +1,62 @@
+/**
+ * Config entries, `key = value`. A `#` is a comment if it's the first
+ * text of a line (possibly indented), otherwise it starts a color value,
+ * and a `;` is a comment only in the first column, otherwise it's a
+ * separator of values.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+"#"
+    %{
+        if self.after_indentation() {
+            self.read_until(&Regex::new("\n").unwrap());
+            return "";
+        }
+        return "HASH";
+    %}
+
+";"
+    %{
+        if self.at_line_start() {
+            self.read_until(&Regex::new("\n").unwrap());
+            return "";
+        }
+        return "SEMI";
+    %}
+
+\w+         return "WORD";
+"="         return "EQ";
+
+/lex
+
+%{
+
+type TResult = Vec<String>;
+
+%}
+
+%%
+
+Entries
+    : Entries Entry { |$1: Vec<String>, $2: String| -> Vec<String>; $1.push($2); $$ = $1 }
+    | Entry         { |$1: String| -> Vec<String>; $$ = vec![$1] }
+    ;
+
+Entry
+    : WORD EQ Values { |$1: Token, $3: String| -> String; $$ = format!("{}={}", $1.value, $3) }
+    ;
+
+Values
+    : Values SEMI Value { |$1: String, $3: String| -> String; $$ = format!("{},{}", $1, $3) }
+    | Value           { $$ = $1 }
+    ;
+
+Value
+    : WORD          { || -> String; $$ = yytext.to_string() }
+    | HASH WORD     { || -> String; $$ = format!("#{}", yytext) }
+    ;
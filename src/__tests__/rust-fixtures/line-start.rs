@@ -0,0 +1,70 @@
+use super::*;
+
+static CONFIG: &str = "\
+# colors
+fg = #fff
+  # indented comment
+bg = #000 ; black;#111
+; a comment
+sep = a;b
+";
+
+fn kinds(string: &str) -> Vec<(i32, &str)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut kinds = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        kinds.push((token.kind, token.value));
+    }
+    kinds
+}
+
+#[test]
+fn comments_at_line_start() {
+    let word = token_kind("WORD").unwrap();
+    let hash = token_kind("HASH").unwrap();
+
+    assert_eq!(kinds("# comment\nx"), vec![(word, "x")]);
+    assert_eq!(kinds("x\n\t  # comment\ny"), vec![(word, "x"), (word, "y")]);
+
+    // A `#` in the middle of a line.
+    assert_eq!(kinds("x #y"), vec![(word, "x"), (hash, "#"), (word, "y")]);
+}
+
+#[test]
+fn first_column() {
+    let word = token_kind("WORD").unwrap();
+    let semicolon = token_kind("SEMI").unwrap();
+
+    assert_eq!(kinds(";x\r\n; y\ny"), vec![(word, "y")]);
+
+    // An indented `;` isn't a comment.
+    assert_eq!(kinds("x\n ;y"), vec![(word, "x"), (semicolon, ";"), (word, "y")]);
+}
+
+#[test]
+fn without_locations() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_track_locations(false);
+    tokenizer.init_string("x\n  # comment\n;comment\n#y");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!(token.value, "x");
+
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
+
+#[test]
+fn parse() {
+    let mut parser = Parser::new();
+
+    assert_eq!(
+        parser.parse(CONFIG).unwrap(),
+        vec!["fg=#fff", "bg=#000,black,#111", "sep=a,b"]
+    );
+}
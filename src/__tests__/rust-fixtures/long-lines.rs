@@ -0,0 +1,48 @@
+use super::*;
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn error_on_long_single_line() {
+    // 1MB single-line input.
+    let input = format!("@{}", "1".repeat(1 << 20));
+
+    let start = Instant::now();
+    let error = Parser::new().parse(&input).unwrap_err();
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(error.message.starts_with("\n\n@111"));
+    assert!(error.message.ends_with("1\n^\n Unexpected token: \"@\" at 1:0."));
+}
+
+#[test]
+fn errors_after_long_line() {
+    let input = format!("{}\n2 @ 3\n4", "1".repeat(1 << 20));
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(&input);
+
+    let offset = input.find('@').unwrap() as i32;
+    let loc = Loc {
+        start_offset: offset,
+        end_offset: offset + 1,
+        start_line: 2,
+        end_line: 2,
+        start_column: 2,
+        end_column: 3,
+    };
+
+    // The long line is not scanned for each error.
+    let start = Instant::now();
+    for _ in 0..1000 {
+        let error = tokenizer.unexpected_token_error("@", loc, Vec::new());
+        assert_eq!(error.message, "\n\n2 @ 3\n  ^\n Unexpected token: \"@\" at 2:2.");
+    }
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn error_on_last_line() {
+    let error = Parser::new().parse("1 +\n2 +\n@").unwrap_err();
+    assert_eq!(error.message, "\n\n@\n^\n Unexpected token: \"@\" at 3:0.");
+}
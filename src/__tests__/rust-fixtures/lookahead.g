@@ -0,0 +1,37 @@
+/**
+ * Rules of a grammar without terminators: after the symbols of a rule, an
+ * `ID` is the next symbol of the rule, or the name of the next rule, if it's
+ * followed by `:`. The grammar needs two tokens of lookahead (it's LALR(2)),
+ * the shift-reduce conflict on `ID` is resolved with the `lookahead` option.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\w+     return "ID";
+
+/lex
+
+%{
+
+type TResult = String;
+
+%}
+
+%%
+
+Rules
+    : Rules Rule        { |$1: String, $2: String| -> String; $$ = format!("{}; {}", $1, $2) }
+    | Rule              { $$ = $1 }
+    ;
+
+Rule
+    : ID ':' Symbols    { |$1: Token, $3: String| -> String; $$ = format!("{}:{}", $1.value, $3) }
+    ;
+
+Symbols
+    : Symbols ID        { |$1: String, $2: Token| -> String; $$ = format!("{} {}", $1, $2.value) }
+    | /* empty */       { || -> String; $$ = String::new() }
+    ;
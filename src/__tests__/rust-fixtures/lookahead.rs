@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn lookahead_is_two_tokens() {
+    assert_eq!(LOOKAHEAD, 2);
+}
+
+#[test]
+fn chooses_shift_or_reduce_by_second_token() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("a: b c"), Ok("a: b c".to_string()));
+    assert_eq!(parser.parse("a: b c d: e"), Ok("a: b c; d: e".to_string()));
+    assert_eq!(parser.parse("a: b: c"), Ok("a:; b: c".to_string()));
+    assert_eq!(parser.parse("a: b:"), Ok("a:; b:".to_string()));
+}
+
+#[test]
+fn syntax_error_after_lookahead() {
+    let error = Parser::new().parse("a: : c").unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \":\" at 1:3; expected one of: ID, end of input."));
+
+    let error = Parser::new().parse("a: b c @").unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"@\" at 1:7."));
+}
+
+#[test]
+fn lookahead_tokens_are_shifted_once() {
+    let mut parser = Parser::new();
+
+    let shifted: Vec<&str> = parser
+        .parse_events("a: b c: d")
+        .filter_map(|event| match event {
+            Ok(ParseEvent::Shift(token)) => Some(token.value),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(shifted, vec!["a", ":", "b", "c", ":", "d"]);
+}
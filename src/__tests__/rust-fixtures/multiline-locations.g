@@ -0,0 +1,27 @@
+/**
+ * Identifiers separated by whitespace and block comments. Used to
+ * test location tracking of tokens spanning several lines.
+ */
+
+%lex
+
+%%
+
+\s+                     /* skip whitespace */ return "";
+\/\*(.|\n)*?\*\/        /* skip comments */ return "";
+\w+                     return "ID";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Items
+    : Items ID  { || -> i32; $$ = 0 }
+    | ID        { || -> i32; $$ = 0 }
+    ;
@@ -0,0 +1,56 @@
+use super::*;
+
+fn tokens(string: &'static str) -> Vec<Token> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+// (start_offset, end_offset, start_line, start_column, end_line, end_column)
+fn loc(token: &Token) -> (i32, i32, i32, i32, i32, i32) {
+    (
+        token.start_offset,
+        token.end_offset,
+        token.start_line,
+        token.start_column,
+        token.end_line,
+        token.end_column,
+    )
+}
+
+#[test]
+fn token_after_three_line_comment() {
+    let tokens = tokens("x /* a\n   b\n c */ y");
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(loc(&tokens[0]), (0, 1, 1, 0, 1, 1));
+    assert_eq!(loc(&tokens[1]), (18, 19, 3, 6, 3, 7));
+}
+
+#[test]
+fn token_at_line_start_after_multiline_comment() {
+    let tokens = tokens("/* a\nb */\nfoo\n  bar");
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(loc(&tokens[0]), (10, 13, 3, 0, 3, 3));
+    assert_eq!(loc(&tokens[1]), (16, 19, 4, 2, 4, 5));
+}
+
+#[test]
+fn comment_ending_with_newline_then_token_mid_line() {
+    let tokens = tokens("a /*\n*/b /* x\n\n */  c");
+
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(loc(&tokens[0]), (0, 1, 1, 0, 1, 1));
+    assert_eq!(loc(&tokens[1]), (7, 8, 2, 2, 2, 3));
+    assert_eq!(loc(&tokens[2]), (20, 21, 4, 5, 4, 6));
+}
@@ -0,0 +1,111 @@
+use super::*;
+
+use std::time::Instant;
+
+static SOURCE: &str = "(12 + 3) *\n\t4 + 5 +\n";
+
+// (value, start_offset, end_offset, start_line, end_line, start_column, end_column)
+fn tokens(string: &str, track_locations: bool) -> Vec<(&str, i32, i32, i32, i32, i32, i32)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_track_locations(track_locations);
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        tokens.push((
+            token.value,
+            token.start_offset,
+            token.end_offset,
+            token.start_line,
+            token.end_line,
+            token.start_column,
+            token.end_column,
+        ));
+        if token.is_eof() {
+            break;
+        }
+    }
+    tokens
+}
+
+fn parse(string: &str, track_locations: bool) -> Result<TResult, SyntaxError> {
+    let mut parser = Parser::new();
+    parser.tokenizer.set_track_locations(track_locations);
+    parser.parse(string)
+}
+
+#[test]
+fn locations_are_not_tracked_by_default() {
+    assert!(!Tokenizer::new().track_locations);
+
+    assert_eq!(
+        tokens("12 +\n 3", false),
+        vec![
+            ("12", 0, 2, 0, 0, 0, 0),
+            ("+", 3, 4, 0, 0, 0, 0),
+            ("3", 6, 7, 0, 0, 0, 0),
+            ("$", 7, 7, 0, 0, 0, 0),
+        ]
+    );
+}
+
+#[test]
+fn tracked_locations() {
+    assert_eq!(
+        tokens("12 +\n 3", true),
+        vec![
+            ("12", 0, 2, 1, 1, 0, 2),
+            ("+", 3, 4, 1, 1, 3, 4),
+            ("3", 6, 7, 2, 2, 1, 2),
+            ("$", 7, 7, 2, 2, 2, 2),
+        ]
+    );
+}
+
+#[test]
+fn same_offsets_and_results() {
+    let source = format!("{}0", SOURCE.repeat(10));
+
+    let untracked: Vec<_> = tokens(&source, false).iter().map(|t| (t.0, t.1, t.2)).collect();
+    let tracked: Vec<_> = tokens(&source, true).iter().map(|t| (t.0, t.1, t.2)).collect();
+
+    assert_eq!(untracked, tracked);
+    assert_eq!(parse(&source, false), Ok(650));
+    assert_eq!(parse(&source, true), Ok(650));
+}
+
+#[test]
+fn error_message_with_offset() {
+    let error = parse("(12 + 3) *\n\t4 + * 5", false).unwrap_err();
+
+    assert_eq!(error.message, "Unexpected token: \"*\" at offset 16; expected one of: NUMBER, (.");
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (16, 17));
+    assert_eq!((error.loc.start_line, error.loc.start_column), (0, 0));
+
+    let error = parse("12 +\n 3 @", false).unwrap_err();
+
+    assert_eq!(error.message, "Unexpected token: \"@\" at offset 8.");
+    assert_eq!((error.loc.start_line, error.loc.start_column), (0, 0));
+
+    let error = parse("12 +\n 3 @", true).unwrap_err();
+    assert!(error.message.ends_with("Unexpected token: \"@\" at 2:3."));
+}
+
+// Run with: cargo test --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn benchmark() {
+    let source = format!("{}0", SOURCE.repeat(200000));
+
+    for &track_locations in &[true, false] {
+        let start = Instant::now();
+        let result = parse(&source, track_locations);
+        println!(
+            "track locations: {:5}, result: {:?}, time: {:?}",
+            track_locations,
+            result,
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn equal_parse_results() {
+    let grammar = Grammar::new();
+
+    assert!(grammar.parse("foo bar").unwrap() == grammar.parse("foo bar").unwrap());
+
+    // The offsets of the tokens are compared as well.
+    assert!(grammar.parse("foo bar").unwrap() != grammar.parse("foo  bar").unwrap());
+}
+
+#[test]
+fn equal_tokens() {
+    let items = Grammar::new().parse("foo foo").unwrap();
+
+    assert_eq!(items[0], items[0]);
+    assert_ne!(items[0], items[1]);
+    assert_eq!(items[0].value, items[1].value);
+}
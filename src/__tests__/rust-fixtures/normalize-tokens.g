@@ -0,0 +1,33 @@
+/**
+ * Lists of identifiers, and strings: the `ID` tokens are normalized
+ * to the NFC form (the `normalizeTokens` option), the `STRING` ones
+ * are not.
+ */
+
+%lex
+
+%%
+
+\s+             /* skip whitespace */ return "";
+"'"[^']*"'"     return "STRING";
+[^\s']+         return "ID";
+
+/lex
+
+%{
+
+type TResult = Vec<String>;
+
+%}
+
+%%
+
+Items
+    : Items Item    { |$1: Vec<String>, $2: String| -> Vec<String>; $1.push($2); $$ = $1 }
+    | Item          { |$1: String| -> Vec<String>; $$ = vec![$1] }
+    ;
+
+Item
+    : ID            { || -> String; $$ = yytext.to_string() }
+    | STRING        { || -> String; $$ = yytext.to_string() }
+    ;
@@ -0,0 +1,57 @@
+use super::*;
+
+fn token_values(string: &str) -> Vec<String> {
+    let strings = StringBuffer::new();
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_string_buffer(&strings);
+    tokenizer.init_string(string);
+    tokenizer.map(|token| token.unwrap().value.to_string()).collect()
+}
+
+#[test]
+fn equivalent_identifiers_are_equal() {
+    let values = token_values("caf\u{e9} cafe\u{301}");
+
+    assert_eq!(values[0], "caf\u{e9}");
+    assert_eq!(values[0], values[1]);
+}
+
+#[test]
+fn other_tokens_are_not_normalized() {
+    let values = token_values("'cafe\u{301}'");
+
+    assert_eq!(values, vec!["'cafe\u{301}'"]);
+}
+
+#[test]
+fn normalized_values_in_actions() {
+    let strings = StringBuffer::new();
+    let mut parser = Parser::new();
+    parser.tokenizer.set_string_buffer(&strings);
+
+    let items = parser.parse("cafe\u{301} 'cafe\u{301}'").unwrap();
+
+    assert_eq!(items, vec!["caf\u{e9}", "'cafe\u{301}'"]);
+}
+
+#[test]
+fn nfc_values_without_string_buffer() {
+    let items = Parser::new().parse("caf\u{e9} x").unwrap();
+    assert_eq!(items, vec!["caf\u{e9}", "x"]);
+
+    let error = Parser::new().parse("x cafe\u{301}").unwrap_err();
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (2, 8));
+}
+
+#[test]
+fn locations_of_the_source() {
+    let strings = StringBuffer::new();
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_string_buffer(&strings);
+    tokenizer.init_string("cafe\u{301} x");
+
+    let token = tokenizer.get_next_token().unwrap();
+
+    assert_eq!(token.value, "caf\u{e9}");
+    assert_eq!((token.start_offset, token.end_offset), (0, 6));
+}
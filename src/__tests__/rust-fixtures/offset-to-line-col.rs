@@ -0,0 +1,111 @@
+use super::*;
+
+static SOURCE: &str = "1 +\n\t2 *\r\n  (3\n\n+\t4)";
+
+// (start_offset, start_line, start_column, end_offset, end_line, end_column)
+fn token_locations(tokenizer: &mut Tokenizer) -> Vec<(i32, i32, i32, i32, i32, i32)> {
+    let mut locations = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        locations.push((
+            token.start_offset,
+            token.start_line,
+            token.start_column,
+            token.end_offset,
+            token.end_line,
+            token.end_column,
+        ));
+    }
+    locations
+}
+
+fn assert_tokens_mapped(tokenizer: &mut Tokenizer) {
+    let locations = token_locations(tokenizer);
+
+    assert!(!locations.is_empty());
+
+    for (start_offset, start_line, start_column, end_offset, end_line, end_column) in locations {
+        assert_eq!(tokenizer.offset_to_line_col(start_offset), Some((start_line, start_column)));
+        assert_eq!(tokenizer.offset_to_line_col(end_offset), Some((end_line, end_column)));
+    }
+}
+
+#[test]
+fn offsets_of_tokens() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(SOURCE);
+
+    assert_tokens_mapped(&mut tokenizer);
+
+    assert_eq!(tokenizer.offset_to_line_col(0), Some((1, 0)));
+    assert_eq!(tokenizer.offset_to_line_col(15), Some((4, 0)));
+}
+
+#[test]
+fn tab_width() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+    tokenizer.init_string(SOURCE);
+
+    assert_tokens_mapped(&mut tokenizer);
+
+    // `4` after `+\t`.
+    assert_eq!(tokenizer.offset_to_line_col(18), Some((5, 4)));
+}
+
+#[test]
+fn utf16_offsets() {
+    let source = "1\u{3000}+\n\u{3000}\u{3000}2";
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_utf16_offsets(true);
+    tokenizer.init_string(source);
+
+    assert_tokens_mapped(&mut tokenizer);
+
+    // `2` is at the UTF-16 offset 6 (the byte offset 10).
+    assert_eq!(tokenizer.offset_to_line_col(6), Some((2, 2)));
+}
+
+#[test]
+fn after_parse() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse(SOURCE).unwrap(), 15);
+
+    assert_eq!(parser.offset_to_line_col(5), Some((2, 1)));
+    assert_eq!(parser.offset_to_line_col(SOURCE.len() as i32), Some((5, 4)));
+}
+
+#[test]
+fn after_syntax_error() {
+    let source = "1 +\n+ 2\n\n  3";
+
+    let mut parser = Parser::new();
+    let error = parser.parse(source).unwrap_err();
+
+    assert_eq!((error.loc.start_line, error.loc.start_column), (2, 0));
+
+    // The text after the error isn't tokenized.
+    assert_eq!(parser.offset_to_line_col(6), Some((2, 2)));
+    assert_eq!(parser.offset_to_line_col(11), Some((4, 2)));
+}
+
+#[test]
+fn invalid_offsets() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("1 +\u{3000}2");
+
+    assert_eq!(tokenizer.offset_to_line_col(-1), None);
+    assert_eq!(tokenizer.offset_to_line_col(5), None);
+    assert_eq!(tokenizer.offset_to_line_col(100), None);
+    assert_eq!(tokenizer.offset_to_line_col(7), Some((1, 7)));
+
+    tokenizer.set_track_locations(false);
+    tokenizer.init_string("1 + 2");
+
+    assert_eq!(tokenizer.offset_to_line_col(0), None);
+}
@@ -0,0 +1,26 @@
+/**
+ * Names collected as tokens with owned values (the `OwnedToken` type
+ * of the token arguments), so the result doesn't borrow the input.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\w+     return "NAME";
+
+/lex
+
+%{
+
+type TResult = Vec<OwnedToken>;
+
+%}
+
+%%
+
+Names
+    : Names NAME    { |$1: Vec<OwnedToken>, $2: OwnedToken| -> Vec<OwnedToken>; $1.push($2); $$ = $1 }
+    | NAME          { |$1: OwnedToken| -> Vec<OwnedToken>; $$ = vec![$1] }
+    ;
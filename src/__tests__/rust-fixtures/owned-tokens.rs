@@ -0,0 +1,48 @@
+use super::*;
+
+/**
+ * Diagnostics kept after the input is dropped.
+ */
+struct Diagnostics {
+    names: Vec<OwnedToken>,
+}
+
+fn diagnostics_of(input: String) -> Diagnostics {
+    let names = Parser::new().parse(&input).unwrap();
+
+    Diagnostics { names }
+}
+
+#[test]
+fn result_outlives_input() {
+    let diagnostics = diagnostics_of(String::from("foo\n  bar"));
+
+    assert_eq!(diagnostics.names.len(), 2);
+    assert_eq!(diagnostics.names[1].value, "bar");
+    assert_eq!(
+        (diagnostics.names[1].start_offset, diagnostics.names[1].start_line, diagnostics.names[1].start_column),
+        (6, 2, 2)
+    );
+}
+
+#[test]
+fn owned_tokens_of_tokenizer() {
+    let tokens: Vec<OwnedToken> = {
+        let input = String::from("a b");
+        let mut tokenizer = Tokenizer::new();
+        tokenizer.init_string(&input);
+        tokenizer.map(|token| token.unwrap().into()).collect()
+    };
+
+    assert_eq!(tokens[0].value, "a");
+    assert_eq!(tokens[1], Token {
+        kind: tokens[1].kind,
+        value: "b",
+        start_offset: 2,
+        end_offset: 3,
+        start_line: 1,
+        end_line: 1,
+        start_column: 2,
+        end_column: 3,
+    }.to_owned_token());
+}
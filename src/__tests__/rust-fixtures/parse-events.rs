@@ -0,0 +1,63 @@
+use super::*;
+
+fn describe(event: ParseEvent) -> String {
+    match event {
+        ParseEvent::Shift(token) => format!("shift {}", token.value),
+        ParseEvent::Reduce { production, span } => format!(
+            "reduce {} {}..{}",
+            production, span.start_offset, span.end_offset
+        ),
+        ParseEvent::Accept => "accept".to_string(),
+    }
+}
+
+#[test]
+fn events_sequence() {
+    let mut parser = Parser::new();
+    let events: Vec<String> = parser.parse_events("2+2").map(|event| describe(event.unwrap())).collect();
+
+    assert_eq!(
+        events,
+        vec![
+            "shift 2",
+            "reduce 3 0..1",
+            "shift +",
+            "shift 2",
+            "reduce 3 2..3",
+            "reduce 1 0..3",
+            "accept",
+        ]
+    );
+}
+
+#[test]
+fn reduce_spans_cover_lines() {
+    let mut parser = Parser::new();
+    let last_reduce = parser
+        .parse_events("(1 +\n  2)")
+        .filter_map(|event| match event {
+            Ok(ParseEvent::Reduce { span, .. }) => Some(span),
+            _ => None,
+        })
+        .last()
+        .unwrap();
+
+    assert_eq!(
+        last_reduce,
+        Loc {
+            start_offset: 0,
+            end_offset: 9,
+            start_line: 1,
+            end_line: 2,
+            start_column: 0,
+            end_column: 4,
+        }
+    );
+}
+
+#[test]
+fn parse_after_events() {
+    let mut parser = Parser::new();
+    assert_eq!(parser.parse_events("2 * 3").count(), 7);
+    assert_eq!(parser.parse("2 + 2 * 2").unwrap(), 6);
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+use std::fs;
+use std::path::PathBuf;
+
+// A file in the temporary directory, removed after the test.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &str) -> TempFile {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn parses_file() {
+    let file = TempFile::new("valid.calc", "2 + 2 * 2\n");
+    let mut contents = String::new();
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse_file(&file.0, &mut contents).unwrap(), 6);
+}
+
+#[test]
+fn file_name_in_errors() {
+    let file = TempFile::new("invalid.calc", "2 +\n3 * * 4\n");
+    let mut contents = String::new();
+    let mut parser = Parser::new();
+
+    let error = parser.parse_file(&file.0, &mut contents).unwrap_err();
+
+    assert!(error.message.contains(&format!(
+        "Unexpected token: \"*\" in {} at 2:4",
+        file.0.display()
+    )));
+    assert_eq!((error.loc.start_line, error.loc.start_column), (2, 4));
+}
+
+#[test]
+fn file_name_at_end_of_input() {
+    let file = TempFile::new("unfinished.calc", "2 +");
+    let mut contents = String::new();
+    let mut parser = Parser::new();
+
+    let error = parser.parse_file(&file.0, &mut contents).unwrap_err();
+
+    assert!(error
+        .message
+        .starts_with(&format!("Unexpected end of input in {};", file.0.display())));
+}
+
+#[test]
+fn strings_without_file_name() {
+    let file = TempFile::new("other.calc", "1 +\n");
+    let mut contents = String::new();
+    let mut parser = Parser::new();
+
+    assert!(parser.parse_file(&file.0, &mut contents).is_err());
+
+    let error = parser.parse("2 * * 2").unwrap_err();
+
+    assert!(error.message.contains("Unexpected token: \"*\" at 1:4"));
+}
+
+#[test]
+fn missing_file() {
+    let path = std::env::temp_dir().join("missing.calc");
+    let mut contents = String::new();
+    let mut parser = Parser::new();
+
+    let error = parser.parse_file(&path, &mut contents).unwrap_err();
+
+    assert!(error
+        .message
+        .starts_with(&format!("Can't read {}: ", path.display())));
+}
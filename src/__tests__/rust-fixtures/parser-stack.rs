@@ -0,0 +1,52 @@
+use super::*;
+
+#[test]
+fn stack_at_error() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("2 + (3 * )").is_err());
+
+    assert_eq!(parser.symbol_stack(), vec!["Expr", "+", "(", "Expr", "*"]);
+    assert_eq!(parser.state_stack().len(), 6);
+    assert_eq!(parser.state_stack()[0], 0);
+}
+
+#[test]
+fn stack_of_nested_error() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("((2 + )").is_err());
+
+    assert_eq!(parser.symbol_stack(), vec!["(", "(", "Expr", "+"]);
+}
+
+#[test]
+fn stack_after_events() {
+    let mut parser = Parser::new();
+    let mut events = parser.parse_events("(2");
+
+    events.next().unwrap().unwrap();
+    assert_eq!(events.parser().symbol_stack(), vec!["("]);
+
+    events.next().unwrap().unwrap();
+    assert_eq!(events.parser().symbol_stack(), vec!["(", "NUMBER"]);
+
+    events.next().unwrap().unwrap();
+    assert_eq!(events.parser().symbol_stack(), vec!["(", "Expr"]);
+}
+
+#[test]
+fn states_match_symbols() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("2 * (3 +").is_err());
+
+    let states = parser.state_stack();
+    let symbols = parser.symbol_stack();
+
+    assert_eq!(states.len(), symbols.len() + 1);
+
+    for (state, symbol) in states[1..].iter().zip(symbols) {
+        assert_eq!(STATE_SYMBOLS[*state], symbol);
+    }
+}
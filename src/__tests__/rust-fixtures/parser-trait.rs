@@ -0,0 +1,76 @@
+use super::*;
+
+/**
+ * Code using the parser by the trait.
+ */
+fn evaluate<'t, P: ParseDsl<'t>>(parser: &mut P, source: &'t str) -> String {
+    match parser.parse(source) {
+        Ok(value) => format!("= {}", value),
+        Err(error) => format!("error: {}", error.message),
+    }
+}
+
+/**
+ * A mock of the parser with the prepared results.
+ */
+struct MockParser {
+    results: Vec<Result<i32, SyntaxError>>,
+    parsed: Vec<String>,
+}
+
+impl<'t> ParseDsl<'t> for MockParser {
+    fn parse(&mut self, string: &'t str) -> Result<i32, SyntaxError> {
+        self.parsed.push(string.to_string());
+        self.results.remove(0)
+    }
+
+    fn parse_all(&mut self, string: &'t str) -> (Option<i32>, Vec<SyntaxError>) {
+        match self.parse(string) {
+            Ok(value) => (Some(value), Vec::new()),
+            Err(error) => (None, vec![error]),
+        }
+    }
+}
+
+#[test]
+fn parser_implements_trait() {
+    let mut parser = Parser::new();
+
+    assert_eq!(evaluate(&mut parser, "2 + 2 * 2"), "= 6");
+    assert!(evaluate(&mut parser, "2 +").starts_with("error: "));
+}
+
+#[test]
+fn mock_substitutes_parser() {
+    let mut mock = MockParser {
+        results: vec![
+            Ok(42),
+            Err(SyntaxError {
+                message: "Mocked.".to_string(),
+                loc: Loc::default(),
+                expected: Vec::new(),
+                limit: None,
+                kind: SyntaxErrorKind::Other,
+            }),
+        ],
+        parsed: Vec::new(),
+    };
+
+    assert_eq!(evaluate(&mut mock, "anything"), "= 42");
+    assert_eq!(evaluate(&mut mock, "2 +"), "error: Mocked.");
+    assert_eq!(mock.parsed, vec!["anything", "2 +"]);
+}
+
+#[test]
+fn trait_objects() {
+    let mock = MockParser {
+        results: vec![Ok(1)],
+        parsed: Vec::new(),
+    };
+
+    let mut parsers: Vec<Box<dyn ParseDsl<'_>>> = vec![Box::new(Parser::new()), Box::new(mock)];
+
+    let results: Vec<_> = parsers.iter_mut().map(|parser| parser.parse_all("1 + 2").0).collect();
+
+    assert_eq!(results, vec![Some(3), Some(1)]);
+}
@@ -0,0 +1,46 @@
+/**
+ * Words, and block comments, the text of a comment is matched in the
+ * comment state, so a range starting in a comment is tokenized in it.
+ */
+
+%lex
+
+%x comment
+
+%%
+
+\s+                 /* skip whitespace */ return "";
+
+"/*"                self.push_state("comment"); return "COMMENT_START";
+
+<comment>"*/"       self.pop_state(); return "COMMENT_END";
+<comment>\s+        /* skip whitespace */ return "";
+<comment>[^*\s]+    return "COMMENT_TEXT";
+<comment>"*"        return "COMMENT_TEXT";
+
+\w+                 return "WORD";
+
+/lex
+
+%{
+
+type TResult = usize;
+
+%}
+
+%%
+
+Items
+    : Items Item    { |$1: usize| -> usize; $$ = $1 + 1 }
+    | Item          { || -> usize; $$ = 1 }
+    ;
+
+Item
+    : WORD                              { || -> usize; $$ = 0 }
+    | COMMENT_START Text COMMENT_END    { || -> usize; $$ = 0 }
+    ;
+
+Text
+    : Text COMMENT_TEXT  { || -> usize; $$ = 0 }
+    | /* empty */        { || -> usize; $$ = 0 }
+    ;
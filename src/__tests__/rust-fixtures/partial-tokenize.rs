@@ -0,0 +1,110 @@
+use super::*;
+
+const SOURCE: &str = "one /* two\nthree */ four\n\tfive /* six */";
+
+type TokenData = (i32, String, i32, i32, i32, i32, i32, i32);
+
+fn tokens(tokenizer: &mut Tokenizer, from: i32) -> Vec<TokenData> {
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+
+        if token.is_eof() {
+            return tokens;
+        }
+
+        if token.start_offset >= from {
+            tokens.push((
+                token.kind,
+                token.value.to_string(),
+                token.start_offset,
+                token.end_offset,
+                token.start_line,
+                token.start_column,
+                token.end_line,
+                token.end_column,
+            ));
+        }
+    }
+}
+
+fn all_tokens(from: i32) -> Vec<TokenData> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+    tokenizer.init_string(SOURCE);
+
+    tokens(&mut tokenizer, from)
+}
+
+#[test]
+fn range_in_a_state() {
+    // The second line starts in a comment.
+    let offset = SOURCE.find("three").unwrap();
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+    tokenizer.init_string_at(SOURCE, offset, "comment", 2, 0);
+
+    let range = tokens(&mut tokenizer, 0);
+
+    assert_eq!(range, all_tokens(offset as i32));
+    assert_eq!(range[0].1, "three");
+    assert_eq!(range[1].1, "*/");
+    assert_eq!(tokenizer.get_current_state(), "INITIAL");
+}
+
+#[test]
+fn range_in_the_middle_of_a_line() {
+    let offset = SOURCE.find("four").unwrap();
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+    tokenizer.init_string_at(SOURCE, offset, "INITIAL", 2, 9);
+
+    let range = tokens(&mut tokenizer, 0);
+
+    assert_eq!(range, all_tokens(offset as i32));
+    assert_eq!((range[0].4, range[0].5), (2, 9));
+
+    // After the tab of the next line.
+    assert_eq!(range[1].1, "five");
+    assert_eq!((range[1].4, range[1].5), (3, 4));
+}
+
+#[test]
+fn lines_and_columns_of_the_range() {
+    let offset = SOURCE.find("four").unwrap();
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string_at(SOURCE, offset, "INITIAL", 2, 9);
+    tokens(&mut tokenizer, 0);
+
+    assert_eq!(tokenizer.offset_to_line_col(offset as i32 + 2), Some((2, 11)));
+    assert_eq!(tokenizer.offset_to_line_col(SOURCE.find("six").unwrap() as i32), Some((3, 9)));
+    assert_eq!(tokenizer.offset_to_line_col(0), None);
+}
+
+#[test]
+fn whole_string_after_a_range() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_tab_width(4);
+    tokenizer.init_string_at(SOURCE, SOURCE.find("five").unwrap(), "INITIAL", 3, 4);
+    tokens(&mut tokenizer, 0);
+
+    tokenizer.init_string(SOURCE);
+
+    assert_eq!(tokens(&mut tokenizer, 0), all_tokens(0));
+    assert_eq!(tokenizer.offset_to_line_col(4), Some((1, 4)));
+}
+
+#[test]
+#[should_panic(expected = "not at a char boundary")]
+fn offset_inside_a_char() {
+    Tokenizer::new().init_string_at("ä", 1, "INITIAL", 1, 1);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Parser::new().parse(SOURCE).unwrap(), 5);
+}
@@ -0,0 +1,36 @@
+/**
+ * Statements with an implicit module wrapper: the parser is primed with
+ * the synthetic `BEGIN` token (not matched by a lex rule), see the
+ * `Parser::prime_tokens`.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\w+     return "ID";
+
+/lex
+
+%{
+
+type TResult = String;
+
+%}
+
+%%
+
+Program
+    : BEGIN Stmts   { |$2: Vec<String>| -> String; $$ = format!("module({})", $2.join(", ")) }
+    | Stmts         { |$1: Vec<String>| -> String; $$ = format!("script({})", $1.join(", ")) }
+    ;
+
+Stmts
+    : Stmts Stmt    { |$1: Vec<String>, $2: String| -> Vec<String>; $1.push($2); $$ = $1 }
+    | Stmt          { |$1: String| -> Vec<String>; $$ = vec![$1] }
+    ;
+
+Stmt
+    : ID ';'        { |$1: Token| -> String; $$ = $1.value.to_string() }
+    ;
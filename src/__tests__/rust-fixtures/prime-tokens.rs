@@ -0,0 +1,61 @@
+use super::*;
+
+fn begin() -> Token<'static> {
+    Token {
+        kind: token_kind("BEGIN").unwrap(),
+        ..Token::default()
+    }
+}
+
+#[test]
+fn wrapper_rule() {
+    let mut parser = Parser::new();
+
+    parser.prime_tokens(vec![begin()]);
+    assert_eq!(parser.parse("a; b;").unwrap(), "module(a, b)");
+
+    // Only the next parse is primed.
+    assert_eq!(parser.parse("a; b;").unwrap(), "script(a, b)");
+}
+
+#[test]
+fn primed_token_location() {
+    let mut parser = Parser::new();
+
+    parser.prime_tokens(vec![Token {
+        start_offset: 5,
+        end_offset: 7,
+        start_line: 3,
+        start_column: 2,
+        ..begin()
+    }]);
+
+    let shifted: Vec<Token> = parser
+        .parse_events("\n  a;")
+        .filter_map(|event| match event.unwrap() {
+            ParseEvent::Shift(token) => Some(token),
+            _ => None,
+        })
+        .collect();
+
+    // Empty at the start of the string.
+    let primed = shifted[0];
+    assert_eq!(primed.kind, token_kind("BEGIN").unwrap());
+    assert_eq!((primed.start_offset, primed.end_offset), (0, 0));
+    assert_eq!((primed.start_line, primed.start_column), (1, 0));
+    assert_eq!((primed.end_line, primed.end_column), (1, 0));
+
+    // The tokens of the string follow.
+    assert_eq!((shifted[1].value, shifted[1].start_line, shifted[1].start_column), ("a", 2, 2));
+}
+
+#[test]
+fn unexpected_primed_token() {
+    let mut parser = Parser::new();
+
+    parser.prime_tokens(vec![begin(), begin()]);
+    let error = parser.parse("a;").unwrap_err();
+
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (0, 0));
+    assert_eq!(error.expected, vec!["ID"]);
+}
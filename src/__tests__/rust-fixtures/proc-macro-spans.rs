@@ -0,0 +1,63 @@
+use super::*;
+
+use proc_macro2::{Literal, TokenStream, TokenTree};
+
+fn literal(source: &str) -> Literal {
+    match source.parse::<TokenStream>().unwrap().into_iter().next() {
+        Some(TokenTree::Literal(literal)) => literal,
+        _ => panic!("Expected a literal: {}", source),
+    }
+}
+
+fn columns(span: proc_macro2::Span) -> (usize, usize) {
+    (span.start().column, span.end().column)
+}
+
+#[test]
+fn token_span_in_literal() {
+    let literal = literal(r#"  "2 + 3""#);
+
+    let mut parser = Parser::new();
+    let tokens: Vec<(usize, usize)> = parser
+        .parse_events("2 + 3")
+        .filter_map(|event| match event {
+            Ok(ParseEvent::Shift(token)) => Some(columns(token.span_in(&literal))),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(tokens, vec![(3, 4), (5, 6), (7, 8)]);
+}
+
+#[test]
+fn syntax_error_span_in_literal() {
+    let literal = literal(r#""2 + * 3""#);
+    let error = Parser::new().parse("2 + * 3").unwrap_err();
+
+    assert_eq!(columns(error.span_in(&literal)), (5, 6));
+}
+
+#[test]
+fn span_in_raw_literal() {
+    let literal = literal(r##"r#"2 + * 3"#"##);
+    let error = Parser::new().parse("2 + * 3").unwrap_err();
+
+    assert_eq!(columns(error.span_in(&literal)), (7, 8));
+}
+
+#[test]
+fn unexpected_end_of_input_span_in_literal() {
+    let literal = literal(r#""2 +""#);
+    let error = Parser::new().parse("2 +").unwrap_err();
+
+    assert_eq!(columns(error.span_in(&literal)), (4, 4));
+}
+
+#[test]
+fn literal_span_without_subspans() {
+    // Without a location in the source a subspan is not available.
+    let literal = Literal::string("2 + * 3");
+    let error = Parser::new().parse("2 + * 3").unwrap_err();
+
+    assert_eq!(error.span_in(&literal), literal.span());
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+fn describe(production: usize) -> String {
+    let (lhs, rhs) = PRODUCTIONS[production];
+    format!("{} -> {}", lhs, rhs.join(" "))
+}
+
+#[test]
+fn calc_productions() {
+    assert_eq!(
+        PRODUCTIONS,
+        &[
+            ("$accept", &["Expr"][..]),
+            ("Expr", &["Expr", "+", "Expr"][..]),
+            ("Expr", &["Expr", "*", "Expr"][..]),
+            ("Expr", &["NUMBER"][..]),
+            ("Expr", &["(", "Expr", ")"][..]),
+        ]
+    );
+}
+
+#[test]
+fn readable_reductions() {
+    let mut parser = Parser::new();
+    let reductions: Vec<String> = parser
+        .parse_events("2 + (3)")
+        .filter_map(|event| match event.unwrap() {
+            ParseEvent::Reduce { production, .. } => Some(describe(production)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        reductions,
+        vec![
+            "Expr -> NUMBER",
+            "Expr -> NUMBER",
+            "Expr -> ( Expr )",
+            "Expr -> Expr + Expr",
+        ]
+    );
+}
+
+#[test]
+fn encoded_productions_match() {
+    assert_eq!(PRODUCTIONS.len(), ENCODED_PRODUCTIONS.len());
+
+    for (production, (_, rhs)) in PRODUCTIONS.iter().enumerate() {
+        assert_eq!(ENCODED_PRODUCTIONS[production][1] as usize, rhs.len());
+    }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn parses_with_regex_crate() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("2 + 2 * 2"), Ok(6));
+    assert_eq!(parser.parse("(2 + 2) * 2"), Ok(8));
+}
+
+#[test]
+fn syntax_error() {
+    let error = Parser::new().parse("2 + * 2").unwrap_err();
+
+    assert!(error.message.ends_with("Unexpected token: \"*\" at 1:4; expected one of: NUMBER, (."));
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (4, 5));
+}
+
+#[test]
+fn unknown_token_type_is_error() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("2");
+    tokenizer.yytext = "2";
+
+    let error = tokenizer.to_token("UNKNOWN").unwrap_err();
+    assert_eq!(
+        error.message,
+        "Token UNKNOWN was reached, but there is no grammar rule for it."
+    );
+}
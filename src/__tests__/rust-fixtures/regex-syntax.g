@@ -0,0 +1,23 @@
+/**
+ * Lex rules with named groups, compiled with the Perl_NT syntax.
+ */
+
+{
+  lex: {
+    rules: [
+      [`\\s+`,                          `/* skip whitespace */ return "";`],
+      [`(?<key>\\w+)=(?<value>\\d+)`,   `return "PAIR";`],
+    ],
+  },
+
+  moduleInclude: `
+    type TResult = i32;
+  `,
+
+  bnf: {
+    Pairs: [
+      [`Pairs PAIR`,  `|$1: i32| -> i32; $$ = $1 + 1`],
+      [`PAIR`,        `|| -> i32; $$ = 1`],
+    ],
+  },
+}
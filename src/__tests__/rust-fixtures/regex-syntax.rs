@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn named_groups() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("a=1 bc=23");
+
+    assert_eq!(tokenizer.get_next_token().unwrap().value, "a=1");
+    assert_eq!(tokenizer.get_next_token().unwrap().value, "bc=23");
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
+
+#[test]
+fn parse_named_groups() {
+    assert_eq!(Parser::new().parse("a=1 b=2 c=3").unwrap(), 3);
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+fn nested(depth: usize) -> String {
+    format!("{}1{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+fn steps(input: &str) -> usize {
+    let mut parser = Parser::new();
+
+    parser
+        .parse_events(input)
+        .filter(|event| !matches!(event, Ok(ParseEvent::Accept)))
+        .count()
+}
+
+#[test]
+fn over_length_input() {
+    let mut parser = Parser::new();
+    parser.set_max_input_len(Some(5));
+
+    assert_eq!(parser.parse("1 + 2").unwrap(), 3);
+
+    let error = parser.parse("1 + 2 + 3").unwrap_err();
+
+    assert_eq!(error.limit, Some(ResourceLimit::InputLength));
+    assert_eq!(error.message, "Input of 9 bytes exceeds the limit of 5 bytes.");
+}
+
+#[test]
+fn over_length_input_is_not_tokenized() {
+    let mut parser = Parser::new();
+    parser.set_max_input_len(Some(4));
+
+    let mut events = parser.parse_events("1 $ 2");
+
+    assert_eq!(events.next().unwrap().unwrap_err().limit, Some(ResourceLimit::InputLength));
+    assert!(events.next().is_none());
+
+    parser.set_max_input_len(None);
+    assert_eq!(parser.parse("1 $ 2").unwrap_err().limit, None);
+}
+
+#[test]
+fn step_limit() {
+    let input = "2 + 2 * 2";
+    let max_steps = steps(input);
+
+    let mut parser = Parser::new();
+
+    parser.set_max_steps(Some(max_steps));
+    assert_eq!(parser.parse(input).unwrap(), 6);
+
+    parser.set_max_steps(Some(max_steps - 1));
+    let error = parser.parse(input).unwrap_err();
+
+    assert_eq!(error.limit, Some(ResourceLimit::Steps));
+    assert_eq!(error.message, format!("Parse exceeds the limit of {} steps.", max_steps - 1));
+}
+
+#[test]
+fn pathological_input_is_stopped() {
+    let input = nested(10000);
+
+    let mut parser = Parser::new();
+    parser.set_max_steps(Some(1000));
+
+    let events: Vec<_> = parser.parse_events(&input).collect();
+
+    assert_eq!(events.len(), 1001);
+    assert_eq!(events[1000].as_ref().unwrap_err().limit, Some(ResourceLimit::Steps));
+
+    // The limit error is at the token of the step.
+    let error = parser.parse(&input).unwrap_err();
+    assert_eq!(error.loc.start_offset, 1000);
+}
+
+#[test]
+fn limits_are_kept_by_reset() {
+    let mut parser = Parser::new();
+    parser.set_max_input_len(Some(1));
+
+    let mut parser = parser.reset();
+
+    assert_eq!(parser.parse("1 + 2").unwrap_err().limit, Some(ResourceLimit::InputLength));
+}
+
+#[test]
+fn syntax_error_is_not_a_limit() {
+    let mut parser = Parser::new();
+    parser.set_max_input_len(Some(100));
+    parser.set_max_steps(Some(100));
+
+    assert_eq!(parser.parse("1 +").unwrap_err().limit, None);
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+/**
+ * Tree of an expression built by the runtime actions.
+ */
+#[derive(Debug, PartialEq)]
+enum Node {
+    Number(i32),
+    Binary(String, Box<Node>, Box<Node>),
+}
+
+fn evaluator(parser: &mut Parser) {
+    fn binary(values: Vec<ActionValue>, op: fn(i32, i32) -> i32) -> ActionValue {
+        let mut values = values.into_iter();
+        let left: i32 = values.next().unwrap().take();
+        let right: i32 = values.nth(1).unwrap().take();
+        ActionValue::node(op(left, right))
+    }
+
+    // Expr -> Expr + Expr, Expr -> Expr * Expr, Expr -> NUMBER
+    parser.set_action(1, |values| binary(values, |a, b| a + b));
+    parser.set_action(2, |values| binary(values, |a, b| a * b));
+    parser.set_action(3, |values| {
+        let number = values.into_iter().next().unwrap().token();
+        ActionValue::node(number.value.parse::<i32>().unwrap())
+    });
+
+    // Expr -> ( Expr )
+    parser.set_action(4, |values| values.into_iter().nth(1).unwrap());
+}
+
+fn tree_builder(parser: &mut Parser) {
+    fn binary(values: Vec<ActionValue>) -> ActionValue {
+        let mut values = values.into_iter();
+        let left: Node = values.next().unwrap().take();
+        let op = values.next().unwrap().token().value.to_string();
+        let right: Node = values.next().unwrap().take();
+        ActionValue::node(Node::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    parser.set_action(1, binary);
+    parser.set_action(2, binary);
+    parser.set_action(3, |values| {
+        let number = values.into_iter().next().unwrap().token();
+        ActionValue::node(Node::Number(number.value.parse().unwrap()))
+    });
+    parser.set_action(4, |values| values.into_iter().nth(1).unwrap());
+}
+
+#[test]
+fn evaluated_number() {
+    let mut parser = Parser::new();
+    evaluator(&mut parser);
+
+    assert_eq!(parser.parse_with_actions::<i32, _>("2 + 2 * (3 + 1)"), Ok(10));
+}
+
+#[test]
+fn built_tree() {
+    let mut parser = Parser::new();
+    tree_builder(&mut parser);
+
+    let tree: Node = parser.parse_with_actions("2 + 2 * 2").unwrap();
+
+    assert_eq!(
+        tree,
+        Node::Binary(
+            "+".to_string(),
+            Box::new(Node::Number(2)),
+            Box::new(Node::Binary(
+                "*".to_string(),
+                Box::new(Node::Number(2)),
+                Box::new(Node::Number(2))
+            ))
+        )
+    );
+}
+
+#[test]
+fn grammar_actions_by_default() {
+    let mut parser = Parser::new();
+    tree_builder(&mut parser);
+
+    // The actions of the grammar are used by `parse`.
+    assert_eq!(parser.parse("2 + 2 * 2"), Ok(6));
+}
+
+#[test]
+fn default_runtime_action() {
+    let mut parser = Parser::new();
+
+    // Expr -> NUMBER, the value of `$accept -> Expr` is the one of `Expr`.
+    parser.set_action(3, |values| {
+        let number = values.into_iter().next().unwrap().token();
+        ActionValue::node(number.value.len())
+    });
+
+    assert_eq!(parser.parse_with_actions::<usize, _>("123"), Ok(3));
+}
+
+#[test]
+#[should_panic(expected = "Unexpected type of a value of an action.")]
+fn value_of_another_type() {
+    let mut parser = Parser::new();
+    evaluator(&mut parser);
+
+    let _ = parser.parse_with_actions::<String, _>("2 + 2");
+}
+
+#[test]
+fn syntax_error() {
+    let mut parser = Parser::new();
+    evaluator(&mut parser);
+
+    assert!(parser.parse_with_actions::<i32, _>("2 +").is_err());
+}
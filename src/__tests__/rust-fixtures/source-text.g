@@ -0,0 +1,46 @@
+/**
+ * Nodes which keep their locations (`@$`, and `@2` of the operator),
+ * to get the source text of a node.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+
+"("     return "(";
+")"     return ")";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+#[derive(Debug)]
+pub struct Node {
+    pub loc: Loc,
+    pub operator: Option<Loc>,
+    pub children: Vec<Node>,
+}
+
+type TResult = Node;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: Node, $3: Node| -> Node;
+                      $$ = Node { loc: @$, operator: Some(@2), children: vec![$1, $3] } }
+    | Expr * Expr   { |$1: Node, $3: Node| -> Node;
+                      $$ = Node { loc: @$, operator: Some(@2), children: vec![$1, $3] } }
+    | NUMBER        { || -> Node; $$ = Node { loc: @$, operator: None, children: vec![] } }
+    | ( Expr )      { |$2: Node| -> Node; $$ = Node { loc: @$, ..$2 } }
+    ;
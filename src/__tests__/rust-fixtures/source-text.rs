@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn node_text() {
+    let source = "1 + 2 * 2";
+    let node = Parser::new().parse(source).unwrap();
+
+    assert_eq!(node.loc.text(source), "1 + 2 * 2");
+    assert_eq!(node.operator.unwrap().text(source), "+");
+
+    let product = &node.children[1];
+    assert_eq!(product.loc.span(), 4..9);
+    assert_eq!(product.loc.text(source), "2 * 2");
+    assert_eq!(product.operator.unwrap().text(source), "*");
+    assert_eq!(product.children[0].loc.text(source), "2");
+}
+
+#[test]
+fn parenthesized_node_text() {
+    let source = "(2 * 2) + 1";
+    let node = Parser::new().parse(source).unwrap();
+
+    assert_eq!(node.children[0].loc.text(source), "(2 * 2)");
+    assert_eq!(node.children[0].operator.unwrap().text(source), "*");
+}
+
+#[test]
+fn multiline_node_text() {
+    let source = "1 +\n  2 *\n  2";
+    let node = Parser::new().parse(source).unwrap();
+    let product = &node.children[1];
+
+    assert_eq!(product.loc.text(source), "2 *\n  2");
+    assert_eq!((product.loc.start_line, product.loc.end_line), (2, 3));
+}
+
+#[test]
+fn merged_locations() {
+    let source = "1 +\n  2 *\n  2";
+    let node = Parser::new().parse(source).unwrap();
+    let product = &node.children[1];
+    let (left, right) = (&product.children[0].loc, &product.children[1].loc);
+
+    let merged = Loc::merge(left, right);
+    assert_eq!(merged, product.loc);
+    assert_eq!(merged.span(), 6..13);
+    assert_eq!((merged.start_line, merged.end_line), (2, 3));
+    assert_eq!((merged.start_column, merged.end_column), (2, 3));
+
+    assert_eq!(Loc::merge(right, left), merged);
+
+    // An empty location is not merged.
+    let empty = Loc::empty_at_end(right);
+    assert_eq!(Loc::merge(&Loc::default(), left), *left);
+    assert_eq!(Loc::merge(left, &empty), *left);
+}
@@ -0,0 +1,42 @@
+/**
+ * Generic types, `>>` is split by the lex handler into two GT
+ * tokens, closing nested type arguments.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+">>"
+    %{
+        self.push_token("GT", 0..1);
+        self.push_token("GT", 1..2);
+        return "";
+    %}
+
+">"         return "GT";
+"<"         return "LT";
+","         return "COMMA";
+\w+         return "ID";
+
+/lex
+
+%{
+
+type TResult = String;
+
+%}
+
+%%
+
+Type
+    : ID                { |$1: Token| -> String; $$ = $1.value.to_string() }
+    | ID LT Types GT    { |$1: Token, $3: String| -> String; $$ = format!("{}<{}>", $1.value, $3) }
+    ;
+
+Types
+    : Types COMMA Type  { |$1: String, $3: String| -> String; $$ = format!("{}, {}", $1, $3) }
+    | Type              { |$1: String| -> String; $$ = $1 }
+    ;
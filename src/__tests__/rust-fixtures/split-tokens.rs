@@ -0,0 +1,66 @@
+use super::*;
+
+fn tokens(string: &'static str) -> Vec<Token<'static>> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = tokenizer.get_next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+// (value, start_offset, end_offset, start_line, start_column, end_line, end_column)
+fn token_loc<'t>(token: &Token<'t>) -> (&'t str, i32, i32, i32, i32, i32, i32) {
+    (
+        token.value,
+        token.start_offset,
+        token.end_offset,
+        token.start_line,
+        token.start_column,
+        token.end_line,
+        token.end_column,
+    )
+}
+
+#[test]
+fn nested_type_arguments() {
+    let mut parser = Parser::new();
+
+    assert_eq!(
+        parser.parse("Map<K, Vec<Vec<V>>>").unwrap(),
+        "Map<K, Vec<Vec<V>>>"
+    );
+    assert_eq!(parser.parse("Vec<Vec<i32> >").unwrap(), "Vec<Vec<i32>>");
+}
+
+#[test]
+fn split_token_spans() {
+    let tokens = tokens("Vec<Vec<i32>> x");
+
+    assert_eq!(tokens.len(), 8);
+    assert_eq!(token_loc(&tokens[5]), (">", 11, 12, 1, 11, 1, 12));
+    assert_eq!(token_loc(&tokens[6]), (">", 12, 13, 1, 12, 1, 13));
+    assert_eq!(token_loc(&tokens[7]), ("x", 14, 15, 1, 14, 1, 15));
+    assert_eq!(tokens[5].kind, tokens[6].kind);
+}
+
+#[test]
+fn split_token_spans_on_next_line() {
+    let tokens = tokens("A<B<C\n  >>");
+
+    assert_eq!(token_loc(&tokens[5]), (">", 8, 9, 2, 2, 2, 3));
+    assert_eq!(token_loc(&tokens[6]), (">", 9, 10, 2, 3, 2, 4));
+}
+
+#[test]
+fn unclosed_after_split_token() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("A<B<C>>>").is_err());
+}
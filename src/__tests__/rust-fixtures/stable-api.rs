@@ -0,0 +1,27 @@
+use super::*;
+
+// Exhaustive matches still compile in the crate of the parser.
+
+fn event_name(event: &ParseEvent) -> &'static str {
+    match event {
+        ParseEvent::Shift(_) => "shift",
+        ParseEvent::Reduce { .. } => "reduce",
+        ParseEvent::Accept => "accept",
+    }
+}
+
+fn policy_name(policy: IndentPolicy) -> &'static str {
+    match policy {
+        IndentPolicy::TabStops => "tab stops",
+        IndentPolicy::NoMixing => "no mixing",
+    }
+}
+
+#[test]
+fn exhaustive_matches() {
+    let mut parser = Parser::new();
+    let events: Vec<&str> = parser.parse_events("2").map(|event| event_name(&event.unwrap())).collect();
+
+    assert_eq!(events, vec!["shift", "reduce", "accept"]);
+    assert_eq!(policy_name(IndentPolicy::NoMixing), "no mixing");
+}
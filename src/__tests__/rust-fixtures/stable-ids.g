@@ -0,0 +1,42 @@
+/**
+ * The calc grammar with the subtraction added between its rules: the
+ * productions after it are renumbered, and keep their stable IDs. The
+ * parser of the calc grammar is the `calc` module of the crate.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"-"     return "-";
+"*"     return "*";
+
+"("     return "(";
+")"     return ")";
+
+/lex
+
+%left + -
+%left *
+
+%{
+
+mod calc;
+
+type TResult = i32;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Expr - Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 - $3 }
+    | Expr * Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 * $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    | ( Expr )      { $$ = $2 }
+    ;
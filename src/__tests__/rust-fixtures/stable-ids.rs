@@ -0,0 +1,59 @@
+use super::*;
+
+/**
+ * The production number of the same production in the calc grammar.
+ */
+fn calc_production(number: usize) -> usize {
+    calc::PRODUCTIONS
+        .iter()
+        .position(|production| *production == PRODUCTIONS[number])
+        .unwrap()
+}
+
+#[test]
+fn production_ids_are_kept() {
+    // `Expr -> Expr * Expr` is renumbered after the added subtraction.
+    assert_eq!(PRODUCTIONS[3], ("Expr", &["Expr", "*", "Expr"][..]));
+    assert_eq!(calc_production(3), 2);
+
+    for number in [0, 1, 3, 4, 5] {
+        assert_eq!(
+            PRODUCTION_IDS[number],
+            calc::PRODUCTION_IDS[calc_production(number)]
+        );
+    }
+
+    // The subtraction is new.
+    assert!(!calc::PRODUCTION_IDS.contains(&PRODUCTION_IDS[2]));
+}
+
+#[test]
+fn production_ids_are_unique() {
+    assert_eq!(PRODUCTION_IDS.len(), PRODUCTIONS.len());
+
+    for (number, id) in PRODUCTION_IDS.iter().enumerate() {
+        assert_eq!(production_number(*id), Some(number));
+    }
+
+    assert_eq!(production_number(0), None);
+}
+
+#[test]
+fn state_ids_are_kept() {
+    assert_eq!(STATE_IDS.len(), TABLE_BASE.len());
+
+    for (number, id) in STATE_IDS.iter().enumerate() {
+        assert_eq!(state_number(*id), Some(number));
+    }
+
+    // The start state `$accept -> • Expr`.
+    assert_eq!(STATE_IDS[0], calc::STATE_IDS[0]);
+
+    // A state with an item of the subtraction is new, the others are kept.
+    let kept = STATE_IDS
+        .iter()
+        .filter(|id| calc::STATE_IDS.contains(id))
+        .count();
+
+    assert!(kept > 1 && kept < STATE_IDS.len());
+}
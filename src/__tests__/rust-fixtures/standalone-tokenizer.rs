@@ -0,0 +1,46 @@
+use super::*;
+
+fn values(string: &str) -> Vec<&str> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+    tokenizer.map(|token| token.unwrap().value).collect()
+}
+
+#[test]
+fn get_next_token() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("2 + 3");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!(token.value, "2");
+    assert_eq!(token.kind, token_kind("NUMBER").unwrap());
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!(token.kind, token_kind("+").unwrap());
+
+    tokenizer.get_next_token().unwrap();
+    assert!(tokenizer.get_next_token().unwrap().is_eof());
+}
+
+#[test]
+fn iterates_until_eof() {
+    assert_eq!(values("(2 + 3) * 4"), vec!["(", "2", "+", "3", ")", "*", "4"]);
+    assert_eq!(values(""), Vec::<&str>::new());
+}
+
+#[test]
+fn error_ends_iteration() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("2 # 3");
+
+    let tokens: Vec<_> = tokenizer.collect();
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].as_ref().unwrap().value, "2");
+    assert!(tokens[1].is_err());
+}
+
+#[test]
+fn unknown_token_kind() {
+    assert_eq!(token_kind("UNKNOWN"), None);
+}
@@ -0,0 +1,52 @@
+/**
+ * Heredocs, `<<TAG` to the end of the line: the lines of the body are
+ * matched in the heredoc state, which keeps the tag as its data, until
+ * a line of the tag.
+ */
+
+%lex
+
+%x heredoc
+
+%%
+
+\s+                 /* skip whitespace */ return "";
+
+"<<"\w+\n           self.push_state_with("heredoc", yytext[2..].trim_end()); return "HEREDOC_START";
+
+<heredoc>[^\n]*\n?
+    %{
+        if yytext.trim_end() == self.get_state_data().unwrap() {
+            self.pop_state();
+            return "HEREDOC_END";
+        }
+        return "LINE";
+    %}
+
+\w+                 return "WORD";
+
+/lex
+
+%{
+
+type TResult = Vec<String>;
+
+%}
+
+%%
+
+Items
+    : Items Item    { |$1: Vec<String>, $2: String| -> Vec<String>; $1.push($2); $$ = $1 }
+    | Item          { |$1: String| -> Vec<String>; $$ = vec![$1] }
+    ;
+
+Item
+    : WORD                              { |$1: Token| -> String; $$ = $1.value.to_string() }
+    | HEREDOC_START Lines HEREDOC_END   { |$2: String| -> String; $$ = $2 }
+    | HEREDOC_START HEREDOC_END         { || -> String; $$ = String::new() }
+    ;
+
+Lines
+    : Lines LINE    { |$1: String, $2: Token| -> String; $1.push_str($2.value); $$ = $1 }
+    | LINE          { |$1: Token| -> String; $$ = $1.value.to_string() }
+    ;
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn closing_tag_of_opener() {
+    let items = Parser::new()
+        .parse("a <<END\nline 1\nEOF\nEND\nb <<EOF\nline 2\nEOF\nc")
+        .unwrap();
+
+    // The other tag is a line of the body.
+    assert_eq!(items, vec!["a", "line 1\nEOF\n", "b", "line 2\n", "c"]);
+}
+
+#[test]
+fn empty_heredoc() {
+    assert_eq!(Parser::new().parse("<<X\nX").unwrap(), vec![""]);
+}
+
+#[test]
+fn unterminated_heredoc() {
+    let error = Parser::new().parse("<<END\nline\nEN").unwrap_err();
+    assert!(error.message.contains("Unexpected end of input"));
+}
+
+#[test]
+fn state_data() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("");
+
+    assert_eq!(tokenizer.get_state_data(), None);
+
+    tokenizer.push_state_with("heredoc", "A");
+    tokenizer.push_state("heredoc");
+    assert_eq!(tokenizer.get_state_data(), None);
+
+    tokenizer.pop_state();
+    assert_eq!((tokenizer.get_current_state(), tokenizer.get_state_data()), ("heredoc", Some("A")));
+
+    tokenizer.pop_state();
+    assert_eq!(tokenizer.get_state_data(), None);
+
+    // Reset for a new string.
+    tokenizer.push_state_with("heredoc", "B");
+    tokenizer.init_string("");
+    assert_eq!(tokenizer.get_state_data(), None);
+}
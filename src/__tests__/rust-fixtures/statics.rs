@@ -0,0 +1,50 @@
+use super::*;
+
+// The same for the `lazy_static`, `once_cell`, and `std` statics.
+
+#[test]
+fn parses_with_statics() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("2 + 2 * 2").unwrap(), 6);
+    assert_eq!(parser.parse("(2 + 2) * 2").unwrap(), 8);
+    assert!(parser.parse("2 + * 2").is_err());
+}
+
+#[test]
+fn token_kinds() {
+    let number = token_kind("NUMBER").unwrap();
+
+    assert_eq!(TOKENS_MAP["NUMBER"], number);
+    assert_eq!(token_kind("MISSING"), None);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("42");
+
+    assert_eq!(tokenizer.get_next_token().unwrap().kind, number);
+}
+
+#[test]
+fn initializes_shared_data() {
+    initialize_shared_data();
+
+    assert_eq!(REGEX_RULES.len(), LEX_RULES.len());
+    assert!(!lex_rules_of_state("INITIAL").is_empty());
+}
+
+#[test]
+fn shared_between_threads() {
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let source = format!("{} * 2", i);
+                let mut parser = Parser::new();
+                parser.parse(&source).unwrap()
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+    assert_eq!(results, vec![0, 2, 4, 6]);
+}
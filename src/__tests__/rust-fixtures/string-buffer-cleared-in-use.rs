@@ -0,0 +1,14 @@
+use super::*;
+
+// Must not compile: the string buffer can't be cleared while the tokens borrow it.
+#[test]
+fn string_buffer_cleared_in_use() {
+    let mut strings = StringBuffer::new();
+    let mut parser = Parser::new();
+    parser.tokenizer.set_string_buffer(&strings);
+
+    let values = parser.parse(r#""a""#).unwrap();
+    strings.clear();
+
+    assert_eq!(values, vec!["A"]);
+}
@@ -0,0 +1,31 @@
+/**
+ * Strings with the quotes removed by the lex handler, the values
+ * are kept in the string buffer of the caller.
+ */
+
+%lex
+
+%%
+
+\s+         /* skip whitespace */ return "";
+
+\"[^"]*\"
+    %{
+        self.set_yytext(yytext[1..yytext.len() - 1].to_uppercase());
+        return "STRING";
+    %}
+
+/lex
+
+%{
+
+type TResult<'t> = Vec<&'t str>;
+
+%}
+
+%%
+
+Strings
+    : Strings STRING    { |$1: Vec<&'t str>, $2: Token| -> Vec<&'t str>; $1.push($2.value); $$ = $1 }
+    | STRING            { |$1: Token| -> Vec<&'t str>; $$ = vec![$1.value] }
+    ;
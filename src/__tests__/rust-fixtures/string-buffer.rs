@@ -0,0 +1,72 @@
+use super::*;
+
+#[test]
+fn strings_in_buffer_of_caller() {
+    let strings = StringBuffer::new();
+    let mut parser = Parser::new();
+    parser.tokenizer.set_string_buffer(&strings);
+
+    assert_eq!(parser.parse(r#""a" "b""#).unwrap(), vec!["A", "B"]);
+    assert_eq!(strings.len(), 2);
+}
+
+#[test]
+fn results_of_previous_parses() {
+    let strings = StringBuffer::new();
+    let mut parser = Parser::new();
+    parser.tokenizer.set_string_buffer(&strings);
+
+    let first = parser.parse(r#""x""#).unwrap();
+    let second = parser.parse(r#""y" "x""#).unwrap();
+
+    // The strings of the previous parses are not dropped.
+    assert_eq!(first, vec!["X"]);
+    assert_eq!(second, vec!["Y", "X"]);
+}
+
+#[test]
+fn distinct_strings_of_each_parse() {
+    let mut strings = StringBuffer::new();
+    let mut parser = Parser::new();
+
+    for i in 0..1000 {
+        let input = format!(r#""a{}" "b{}""#, i, i);
+
+        let mut session = parser.reset();
+        session.tokenizer.set_string_buffer(&strings);
+
+        assert_eq!(
+            session.parse(&input).unwrap(),
+            vec![format!("A{}", i), format!("B{}", i)]
+        );
+        assert_eq!(strings.len(), 2);
+
+        // The results of the parse are dropped, the strings can be too.
+        parser = session.reset();
+        strings.clear();
+    }
+
+    assert!(strings.is_empty());
+}
+
+#[test]
+fn no_string_buffer() {
+    let error = Parser::new().parse(r#"  "a""#).unwrap_err();
+
+    assert_eq!(error.message, "No string buffer for the text of a token at 1:5.");
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (2, 5));
+}
+
+#[test]
+fn init_string_resets_yytext() {
+    let strings = StringBuffer::new();
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_string_buffer(&strings);
+
+    tokenizer.init_string(r#""old""#);
+    tokenizer.get_next_token().unwrap();
+    assert_eq!((tokenizer.yytext, tokenizer.yyleng), ("OLD", 5));
+
+    tokenizer.init_string("");
+    assert_eq!((tokenizer.yytext, tokenizer.yyleng), ("", 0));
+}
@@ -0,0 +1,45 @@
+/**
+ * Statements terminated with `;`, and blocks, without `error` productions:
+ * a malformed statement is skipped up to a `%sync` token.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+
+/lex
+
+%left +
+%left *
+
+%sync ';' '}'
+
+%{
+
+type TResult = Vec<i32>;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt    { |$1: Vec<i32>, $2: i32| -> Vec<i32>; $1.push($2); $$ = $1 }
+    | Stmt          { |$1: i32| -> Vec<i32>; $$ = vec![$1] }
+    ;
+
+Stmt
+    : Expr ';'      { |$1: i32| -> i32; $$ = $1 }
+    | '{' Stmts '}' { |$2: Vec<i32>| -> i32; $$ = $2.iter().sum() }
+    ;
+
+Expr
+    : Expr + Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }
+    | Expr * Expr   { |$1: i32, $3: i32| -> i32; $$ = $1 * $3 }
+    | NUMBER        { || -> i32; $$ = yytext.parse::<i32>().unwrap() }
+    ;
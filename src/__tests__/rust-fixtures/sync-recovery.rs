@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn resynchronizes_at_semicolon() {
+    // The input is skipped up to the `;`, the statement ends at it.
+    let (result, errors) = Parser::new().parse_all("1 + 2; 3 + * 4; 5;");
+
+    assert_eq!(result, Some(vec![3, 3, 5]));
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.ends_with("Unexpected token: \"*\" at 1:11; expected one of: NUMBER."));
+    assert_eq!((errors[0].loc.start_offset, errors[0].loc.end_offset), (11, 12));
+}
+
+#[test]
+fn several_errors() {
+    let (result, errors) = Parser::new().parse_all("1 + ; 2 * * 3; 4;");
+
+    assert_eq!(result, Some(vec![1, 2, 4]));
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].loc.start_offset, 4);
+    assert_eq!(errors[1].loc.start_offset, 10);
+}
+
+#[test]
+fn resynchronizes_at_closing_brace() {
+    // The `2 +` can't end at `}`, the block ends after `1;`.
+    let (result, errors) = Parser::new().parse_all("{ 1; 2 + * 3 } 4;");
+
+    assert_eq!(result, Some(vec![1, 4]));
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn skips_unexpected_sync_token() {
+    // No state has an action for the `}`, it's skipped too.
+    let (result, errors) = Parser::new().parse_all("1; } 2;");
+
+    assert_eq!(result, Some(vec![1, 2]));
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.contains("Unexpected token: \"}\""));
+}
+
+#[test]
+fn fails_at_end_of_input() {
+    let (result, errors) = Parser::new().parse_all("1; 2 + * 3");
+
+    assert_eq!(result, None);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.contains("Unexpected token: \"*\""));
+}
+
+#[test]
+fn parse_stops_at_first_error() {
+    assert!(Parser::new().parse("1 + ; 2;").is_err());
+}
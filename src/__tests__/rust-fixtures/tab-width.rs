@@ -0,0 +1,49 @@
+use super::*;
+
+// (start_column, end_column)
+fn columns(tokenizer: &mut Tokenizer) -> (i32, i32) {
+    let token = tokenizer.get_next_token().unwrap();
+    (token.start_column, token.end_column)
+}
+
+#[test]
+fn default_tab_width_from_options() {
+    assert_eq!(DEFAULT_TAB_WIDTH, 4);
+}
+
+#[test]
+fn tabs_expand_to_next_tab_stop() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("\t1 \t22\n  \t3");
+
+    assert_eq!(columns(&mut tokenizer), (4, 5));
+    assert_eq!(columns(&mut tokenizer), (8, 10));
+    assert_eq!(columns(&mut tokenizer), (4, 5));
+}
+
+#[test]
+fn tab_width_setter() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("\t1 \t22");
+
+    tokenizer.set_tab_width(8);
+    assert_eq!(columns(&mut tokenizer), (8, 9));
+
+    tokenizer.set_tab_width(1);
+    assert_eq!(columns(&mut tokenizer), (4, 6));
+}
+
+#[test]
+fn offsets_do_not_depend_on_tab_width() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("\t1");
+
+    let token = tokenizer.get_next_token().unwrap();
+    assert_eq!((token.start_offset, token.end_offset), (1, 2));
+}
+
+#[test]
+fn error_after_leading_tab() {
+    let error = Parser::new().parse("\t@").unwrap_err();
+    assert_eq!(error.message, "\n\n    @\n    ^\n Unexpected token: \"@\" at 1:4.");
+}
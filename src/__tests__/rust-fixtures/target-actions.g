@@ -0,0 +1,36 @@
+/**
+ * Actions for several targets: only the `rust` blocks are generated.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+
+"+"     return "+";
+"*"     return "*";
+
+"("     return "(";
+")"     return ")";
+
+/lex
+
+%left +
+%left *
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Expr
+    : Expr + Expr   {{js: $$ = $1 + $3} {rust: |$1: i32, $3: i32| -> i32; $$ = $1 + $3 }}
+    | Expr * Expr   {{rust: |$1: i32, $3: i32| -> i32; $$ = $1 * $3 } {js: $$ = $1 * $3}}
+    | NUMBER        {{js: $$ = Number(yytext)} {rust: || -> i32; $$ = yytext.parse::<i32>().unwrap() }}
+    | ( Expr )      {{rust: $$ = $2 }}
+    ;
@@ -0,0 +1,9 @@
+use super::*;
+
+#[test]
+fn runs_rust_actions() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("2 + 3 * 4").unwrap(), 14);
+    assert_eq!(parser.parse("(2 + 3) * 4").unwrap(), 20);
+}
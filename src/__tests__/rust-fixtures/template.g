@@ -0,0 +1,49 @@
+/**
+ * Templates of text, and `{{ ... }}` expressions. An expression is parsed
+ * by the parser of the calc grammar (the `expr` module of the crate) at
+ * its range of the template, so the locations are in the template.
+ */
+
+%lex
+
+%%
+
+\{\{([^}]|\}[^}])*\}\}      return "EXPR";
+[^{]+|\{                    return "TEXT";
+
+/lex
+
+%{
+
+mod expr;
+
+#[derive(Debug, PartialEq)]
+pub enum Part {
+    Text(String),
+    Expr(Result<i32, expr::SyntaxError>),
+}
+
+type TResult = Vec<Part>;
+
+/**
+ * Value of an expression token, parsed without its braces.
+ */
+fn expression(parser: &Parser, token: &Token) -> Result<i32, expr::SyntaxError> {
+    let range = token.start_offset as usize + 2..token.end_offset as usize - 2;
+
+    expr::Parser::new().parse_at(parser.source(), range, token.start_line, token.start_column + 2)
+}
+
+%}
+
+%%
+
+Template
+    : Template Part     { |$1: Vec<Part>, $2: Part| -> Vec<Part>; $1.push($2); $$ = $1 }
+    | /* empty */       { || -> Vec<Part>; $$ = Vec::new() }
+    ;
+
+Part
+    : TEXT      { |$1: Token| -> Part; $$ = Part::Text($1.value.to_string()) }
+    | EXPR      { |$1: Token| -> Part; $$ = Part::Expr(expression(self, &$1)) }
+    ;
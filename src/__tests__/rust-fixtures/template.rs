@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn expressions_of_a_template() {
+    let parts = Parser::new().parse("a {{ 1 + 2 * 3 }} b {{(1 + 2) * 3}}").unwrap();
+
+    assert_eq!(
+        parts,
+        vec![
+            Part::Text("a ".to_string()),
+            Part::Expr(Ok(7)),
+            Part::Text(" b ".to_string()),
+            Part::Expr(Ok(9)),
+        ]
+    );
+}
+
+#[test]
+fn single_brace_is_text() {
+    let parts = Parser::new().parse("{x} {{ 2 }}").unwrap();
+
+    assert_eq!(
+        parts,
+        vec![
+            Part::Text("{".to_string()),
+            Part::Text("x} ".to_string()),
+            Part::Expr(Ok(2)),
+        ]
+    );
+}
+
+#[test]
+fn error_location_in_the_template() {
+    let source = "line\n  x = {{ 1 + * 2 }}\n";
+    let parts = Parser::new().parse(source).unwrap();
+
+    let error = match &parts[1] {
+        Part::Expr(Err(error)) => error.clone(),
+        part => panic!("not an error: {:?}", part),
+    };
+
+    let star = source.find('*').unwrap() as i32;
+
+    assert_eq!(error.loc.start_offset, star);
+    assert_eq!((error.loc.start_line, error.loc.start_column), (2, 13));
+
+    // The source line of the error is the line of the template (up to
+    // the end of the expression).
+    assert!(error.message.contains("\n  x = {{ 1 + * 2 \n             ^\n"));
+}
+
+#[test]
+fn expression_tokens_are_in_the_template() {
+    let source = "ab {{ 12 + 3 }}";
+    let mut parser = expr::Parser::new();
+
+    assert_eq!(parser.parse_at(source, 5..13, 1, 5).unwrap(), 15);
+
+    // The string after the range is not read.
+    assert_eq!(parser.source(), "ab {{ 12 + 3 ");
+}
@@ -0,0 +1,60 @@
+/**
+ * Calls with the arguments, and the lists of names, separated by the
+ * parameterized `sep_list` rule, which builds a `Vec` of the items.
+ * The `Item` parameter is used as the type of the items in the actions.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+\w+     return "ID";
+
+/lex
+
+%{
+
+type TResult = Vec<Call>;
+
+#[derive(Debug, PartialEq)]
+pub struct Call {
+    pub name: String,
+    pub args: Vec<Arg>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Arg {
+    Number(i32),
+    Names(Vec<String>),
+}
+
+pub type Name = String;
+
+%}
+
+%%
+
+Calls
+    : sep_list(Call, ';')   { |$1: Vec<Call>| -> Vec<Call>; $$ = $1 }
+    ;
+
+Call
+    : ID '(' sep_list(Arg, ',') ')'  { |$1: Token, $3: Vec<Arg>| -> Call; $$ = Call { name: $1.value.to_string(), args: $3 } }
+    | ID '(' ')'                     { |$1: Token| -> Call; $$ = Call { name: $1.value.to_string(), args: vec![] } }
+    ;
+
+Arg
+    : NUMBER                         { |$1: Token| -> Arg; $$ = Arg::Number($1.value.parse().unwrap()) }
+    | '[' sep_list(Name, ',') ']'    { |$2: Vec<Name>| -> Arg; $$ = Arg::Names($2) }
+    ;
+
+Name
+    : ID                             { |$1: Token| -> Name; $$ = $1.value.to_string() }
+    ;
+
+sep_list(Item, Sep)
+    : Item                           { |$1: Item| -> Vec<Item>; $$ = vec![$1] }
+    | sep_list(Item, Sep) Sep Item   { |$1: Vec<Item>, $3: Item| -> Vec<Item>; $1.push($3); $$ = $1 }
+    ;
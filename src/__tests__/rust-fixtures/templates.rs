@@ -0,0 +1,44 @@
+use super::*;
+
+fn call(name: &str, args: Vec<Arg>) -> Call {
+    Call {
+        name: name.to_string(),
+        args,
+    }
+}
+
+#[test]
+fn separated_lists() {
+    let mut parser = Parser::new();
+
+    assert_eq!(
+        parser.parse("f(1, 2, [a, b]); g(); h([c])").unwrap(),
+        vec![
+            call("f", vec![
+                Arg::Number(1),
+                Arg::Number(2),
+                Arg::Names(vec!["a".to_string(), "b".to_string()]),
+            ]),
+            call("g", vec![]),
+            call("h", vec![Arg::Names(vec!["c".to_string()])]),
+        ]
+    );
+}
+
+#[test]
+fn instances() {
+    let names: Vec<&str> = PRODUCTIONS.iter().map(|(name, _)| *name).collect();
+
+    // Each instance once, for the uses of `sep_list(Arg, ',')`.
+    assert_eq!(names.iter().filter(|name| **name == "sep_list(Arg,',')").count(), 2);
+    assert_eq!(names.iter().filter(|name| **name == "sep_list(Call,';')").count(), 2);
+    assert_eq!(names.iter().filter(|name| **name == "sep_list(Name,',')").count(), 2);
+}
+
+#[test]
+fn separator_of_instance() {
+    let mut parser = Parser::new();
+
+    let error = parser.parse("f(1; 2)").unwrap_err();
+    assert_eq!(error.expected, vec!["')'", "','"]);
+}
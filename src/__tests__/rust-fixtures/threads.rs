@@ -0,0 +1,54 @@
+use super::*;
+
+use std::sync::mpsc;
+use std::thread;
+
+fn assert_send_sync<T: Send + Sync + Clone>() {}
+
+#[test]
+fn grammar_is_send_sync() {
+    assert_send_sync::<Grammar>();
+}
+
+#[test]
+fn parse_with_grammar() {
+    let grammar = Grammar::new();
+
+    assert_eq!(grammar.parse("2 + 2 * 2").unwrap(), 6);
+    assert!(grammar.parse("2 +").is_err());
+
+    let mut parser = grammar.parser();
+    assert_eq!(parser.parse("(1 + 2) * 3").unwrap(), 9);
+}
+
+#[test]
+fn parse_in_threads() {
+    let grammar = Grammar::new();
+    let (sender, receiver) = mpsc::channel();
+
+    let workers: Vec<_> = (0..8)
+        .map(|worker| {
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                for i in 0..100 {
+                    let input = format!("{} + {} * 2", worker, i);
+                    sender.send((worker, i, grammar.parse(&input).unwrap())).unwrap();
+                }
+            })
+        })
+        .collect();
+
+    drop(sender);
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let results: Vec<_> = receiver.iter().collect();
+    assert_eq!(results.len(), 800);
+
+    for (worker, i, result) in results {
+        assert_eq!(result, worker + i * 2);
+    }
+}
@@ -0,0 +1,36 @@
+/**
+ * Statements ending with `;`, the tokens have display names in the
+ * error messages.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+\d+     return "NUMBER";
+\w+     return "ID";
+";"     return "T_SEMICOLON";
+"="     return "T_ASSIGN";
+
+/lex
+
+%token T_SEMICOLON ";" T_ASSIGN "="
+%token NUMBER "number"
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt    { |$1: i32| -> i32; $$ = $1 + 1 }
+    | Stmt          { || -> i32; $$ = 1 }
+    ;
+
+Stmt
+    : ID T_ASSIGN NUMBER T_SEMICOLON
+    ;
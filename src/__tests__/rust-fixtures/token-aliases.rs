@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn expected_tokens_by_alias() {
+    let error = Parser::new().parse("x = 1; y = 2").unwrap_err();
+
+    assert_eq!(error.expected, vec![";"]);
+    assert_eq!(error.message, "Unexpected end of input; expected one of: ;.");
+
+    let error = Parser::new().parse("x 1;").unwrap_err();
+
+    assert_eq!(error.expected, vec!["="]);
+    assert!(error.message.ends_with("expected one of: =."));
+
+    let error = Parser::new().parse("x = y;").unwrap_err();
+
+    assert_eq!(error.expected, vec!["number"]);
+}
+
+#[test]
+fn symbol_stack_by_alias() {
+    let mut parser = Parser::new();
+
+    assert!(parser.parse("x = 1 y").is_err());
+    assert_eq!(parser.symbol_stack(), vec!["ID", "=", "number"]);
+}
@@ -0,0 +1,49 @@
+/**
+ * Functions declared with `fn`, `function`, or `def`: the `FUNCTION`, and
+ * `DEF` tokens are in the class of the `FN` terminal (the `tokenClasses`
+ * option), so one rule parses all of them.
+ */
+
+%lex
+
+%%
+
+\s+             /* skip whitespace */ return "";
+
+fn\b            return "FN";
+function\b      return "FUNCTION";
+def\b           return "DEF";
+
+[a-z_]\w*       return "ID";
+
+"("             return "(";
+")"             return ")";
+";"             return "SEMI";
+
+/lex
+
+%{
+
+/**
+ * A function by its name, and the kind of its keyword token.
+ */
+#[derive(Debug, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub kind: i32,
+}
+
+type TResult = Vec<Function>;
+
+%}
+
+%%
+
+Functions
+    : Functions Function    { |$1: Vec<Function>, $2: Function| -> Vec<Function>; $1.push($2); $$ = $1 }
+    | /* empty */           { || -> Vec<Function>; $$ = Vec::new() }
+    ;
+
+Function
+    : FN ID ( ) SEMI        { |$1: Token, $2: Token| -> Function; $$ = Function { name: $2.value.to_string(), kind: $1.kind } }
+    ;
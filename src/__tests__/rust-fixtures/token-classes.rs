@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn tokens_of_a_class_are_parsed_by_its_rule() {
+    let functions = Parser::new()
+        .parse("fn a(); function b(); def c();")
+        .unwrap();
+
+    // The kinds of the tokens are kept.
+    assert_eq!(
+        functions,
+        vec![
+            Function { name: "a".to_string(), kind: TokenKind::FN as i32 },
+            Function { name: "b".to_string(), kind: TokenKind::FUNCTION as i32 },
+            Function { name: "c".to_string(), kind: TokenKind::DEF as i32 },
+        ]
+    );
+}
+
+#[test]
+fn tokens_of_a_class_are_not_expected() {
+    let error = Parser::new().parse("fn a(); b").unwrap_err();
+
+    assert_eq!(error.expected, vec!["FN", "$"]);
+}
+
+#[test]
+fn token_of_a_class_is_unexpected() {
+    let error = Parser::new().parse("fn function();").unwrap_err();
+
+    assert!(error.message.contains("function"), "{}", error.message);
+    assert_eq!(error.expected, vec!["ID"]);
+}
+
+#[test]
+fn tokens_of_a_class_are_in_the_tokens_map() {
+    assert_eq!(token_kind("FUNCTION"), Some(EOF_KIND + 1));
+    assert_eq!(token_kind("DEF"), Some(EOF_KIND + 2));
+
+    let fn_kind = token_kind("FN").unwrap();
+
+    assert_eq!(
+        table_entry(0, token_kind("DEF").unwrap()).is_some(),
+        table_entry(0, fn_kind).is_some()
+    );
+}
@@ -0,0 +1,33 @@
+/**
+ * Statements with the contextual `async` keyword, which is an identifier
+ * as well, depending on a flag of the token filter (see the
+ * `Parser::set_token_filter`).
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */ return "";
+async   return "ASYNC";
+\w+     return "ID";
+
+/lex
+
+%{
+
+type TResult = String;
+
+%}
+
+%%
+
+Stmts
+    : Stmts Stmt    { |$1: String, $2: String| -> String; $$ = format!("{} {}", $1, $2) }
+    | Stmt          { |$1: String| -> String; $$ = $1 }
+    ;
+
+Stmt
+    : ASYNC ID ';'  { |$2: Token| -> String; $$ = format!("async({})", $2.value) }
+    | ID ';'        { |$1: Token| -> String; $$ = $1.value.to_string() }
+    ;
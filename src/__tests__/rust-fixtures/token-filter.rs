@@ -0,0 +1,100 @@
+use super::*;
+
+// Demotes the `async` keyword to an identifier, unless it's enabled.
+struct ContextualKeywords {
+    async_functions: bool,
+}
+
+impl<'t> TokenFilter<'t> for ContextualKeywords {
+    fn filter(&mut self, token: Token<'t>, tokens: &mut VecDeque<Token<'t>>) {
+        if !self.async_functions && token.kind == token_kind("ASYNC").unwrap() {
+            tokens.push_back(Token {
+                kind: token_kind("ID").unwrap(),
+                ..token
+            });
+        } else {
+            tokens.push_back(token);
+        }
+    }
+}
+
+// Drops empty statements, and inserts the missing `;` at the end of input.
+struct Semicolons {
+    last_kind: i32,
+}
+
+impl<'t> TokenFilter<'t> for Semicolons {
+    fn filter(&mut self, token: Token<'t>, tokens: &mut VecDeque<Token<'t>>) {
+        let semicolon = token_kind("';'").unwrap();
+
+        if token.kind == semicolon && self.last_kind == semicolon {
+            return;
+        }
+
+        if token.is_eof() && self.last_kind != semicolon {
+            tokens.push_back(Token {
+                kind: semicolon,
+                value: ";",
+                ..token
+            });
+        }
+
+        self.last_kind = token.kind;
+        tokens.push_back(token);
+    }
+}
+
+#[test]
+fn contextual_keyword() {
+    let mut parser = Parser::new();
+
+    parser.set_token_filter(ContextualKeywords { async_functions: true });
+    assert_eq!(parser.parse("async f; g;").unwrap(), "async(f) g");
+    assert!(parser.parse("async;").is_err());
+
+    parser.set_token_filter(ContextualKeywords { async_functions: false });
+    assert_eq!(parser.parse("async; g;").unwrap(), "async g");
+    assert!(parser.parse("async f;").is_err());
+}
+
+#[test]
+fn dropped_and_injected_tokens() {
+    let mut parser = Parser::new();
+
+    parser.set_token_filter(Semicolons { last_kind: -1 });
+    assert_eq!(parser.parse("a;; b;;; c").unwrap(), "a b c");
+
+    let error = parser.parse("a b").unwrap_err();
+    assert_eq!(error.loc.start_offset, 2);
+}
+
+#[test]
+fn no_filter() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("async f; g;").unwrap(), "async(f) g");
+    assert!(parser.parse("a;; b").is_err());
+}
+
+#[test]
+fn primed_tokens_are_not_filtered() {
+    let mut parser = Parser::new();
+
+    parser.set_token_filter(ContextualKeywords { async_functions: false });
+    parser.prime_tokens(vec![Token {
+        kind: token_kind("ASYNC").unwrap(),
+        ..Token::default()
+    }]);
+
+    assert_eq!(parser.parse("f;").unwrap(), "async(f)");
+}
+
+#[test]
+fn filter_is_dropped_by_reset() {
+    let mut parser = Parser::new();
+
+    parser.set_token_filter(ContextualKeywords { async_functions: false });
+    let mut parser = parser.reset();
+
+    assert_eq!(parser.parse("async f;").unwrap(), "async(f)");
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+// Must not compile: the tokens can't outlive the parsing string.
+#[test]
+fn token_outlives_input() {
+    let mut parser = Parser::new();
+
+    let tokens = {
+        let input = String::from("foo");
+        parser.parse(&input).unwrap()
+    };
+
+    assert_eq!(tokens[0].value, "foo");
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+// Must not compile: the tokens can't outlive the string buffer.
+#[test]
+fn token_outlives_string_buffer() {
+    let mut parser = Parser::new();
+
+    let values = {
+        let strings = StringBuffer::new();
+        parser.tokenizer.set_string_buffer(&strings);
+        parser.parse(r#""a""#).unwrap()
+    };
+
+    assert_eq!(values, vec!["A"]);
+}
@@ -0,0 +1,38 @@
+/**
+ * Calculator with the `f64` value type of the `NUMBER` token, and the
+ * precedence of the operators declared with the tokens.
+ */
+
+%lex
+
+%%
+
+\s+             /* skip whitespace */ return "";
+\d+(\.\d+)?     return "NUMBER";
+
+/lex
+
+%token <f64> NUMBER
+
+%left '+' '-'
+%left '*' '/'
+%right UMINUS
+
+%{
+
+type TResult = f64;
+
+%}
+
+%%
+
+Expr
+    : Expr '+' Expr         { |$1: f64, $3: f64| -> f64; $$ = $1 + $3 }
+    | Expr '-' Expr         { |$1: f64, $3: f64| -> f64; $$ = $1 - $3 }
+    | Expr '*' Expr         { |$1: f64, $3: f64| -> f64; $$ = $1 * $3 }
+    | Expr '/' Expr         { |$1: f64, $3: f64| -> f64; $$ = $1 / $3 }
+    | '-' NUMBER %prec UMINUS
+                            { || -> f64; $$ = -$2 }
+    | '(' Expr ')'          { |$2: f64| -> f64; $$ = $2 }
+    | NUMBER
+    ;
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn typed_token_values() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("2.5").unwrap(), 2.5);
+    assert_eq!(parser.parse("-1.5 + 1").unwrap(), -0.5);
+    assert_eq!(parser.parse("(1 + 2) * 3").unwrap(), 9.0);
+}
+
+#[test]
+fn precedence() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("1 + 2 * 3 - 4 / 2").unwrap(), 5.0);
+    assert_eq!(parser.parse("8 - 4 - 2").unwrap(), 2.0);
+    assert_eq!(parser.parse("8 / 4 / 2").unwrap(), 1.0);
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+fn token_locations(string: &str) -> Vec<(&str, i32, i32, i32, i32)> {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string(string);
+
+    tokenizer
+        .map(|token| {
+            let token = token.unwrap();
+            (token.value, token.start_offset, token.end_offset, token.start_column, token.end_column)
+        })
+        .collect()
+}
+
+#[test]
+fn astral_character_takes_two_units() {
+    assert_eq!(
+        token_locations("a \u{1f600}b c"),
+        vec![("a", 0, 1, 0, 1), ("\u{1f600}b", 2, 5, 2, 5), ("c", 6, 7, 6, 7)]
+    );
+}
+
+#[test]
+fn columns_on_next_lines() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("caf\u{e9}\n\u{1f600} x");
+
+    let tokens: Vec<Token> = tokenizer.map(|token| token.unwrap()).collect();
+    let x = tokens[2];
+
+    assert_eq!((x.start_offset, x.end_offset), (8, 9));
+    assert_eq!((x.start_line, x.start_column, x.end_column), (2, 3, 4));
+}
+
+#[test]
+fn byte_offsets() {
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.set_utf16_offsets(false).init_string("\u{1f600} c");
+
+    let tokens: Vec<Token> = tokenizer.map(|token| token.unwrap()).collect();
+
+    assert_eq!((tokens[1].start_offset, tokens[1].start_column), (5, 5));
+}
+
+#[test]
+fn error_location() {
+    let error = Parser::new().parse("\u{1f600} 'x").unwrap_err();
+
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (3, 4));
+    assert_eq!((error.loc.start_column, error.loc.end_column), (3, 4));
+    assert!(error.message.contains("\u{1f600} 'x\n   ^\n"));
+}
+
+#[test]
+fn parse_utf16_string() {
+    let mut buffer = String::new();
+
+    let string: Vec<u16> = "\u{1f600} 'x".encode_utf16().collect();
+    let error = Parser::new().parse_utf16(&string, &mut buffer).unwrap_err();
+
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (3, 4));
+
+    let string: Vec<u16> = "a \u{1f600}".encode_utf16().collect();
+    let items = Parser::new().parse_utf16(&string, &mut buffer).unwrap();
+
+    assert_eq!(items, vec!["a", "\u{1f600}"]);
+}
+
+#[test]
+fn unpaired_surrogate_takes_one_unit() {
+    let mut buffer = String::new();
+    let mut parser = Parser::new();
+    parser.tokenizer.set_utf16_offsets(false);
+
+    let error = parser.parse_utf16(&[0xd800, 0x20, 0x27], &mut buffer).unwrap_err();
+
+    assert_eq!((error.loc.start_offset, error.loc.end_offset), (2, 3));
+    assert!(!parser.tokenizer.utf16_offsets);
+}
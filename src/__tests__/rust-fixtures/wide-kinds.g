@@ -0,0 +1,289 @@
+/**
+ * A grammar with more tokens than fit `u8` (260 keywords), so the
+ * encoded symbols of the static tables are `u16`. Used to test the
+ * width of the tables.
+ */
+
+%lex
+
+%%
+
+\s+                     /* skip whitespace */ return "";
+
+/lex
+
+%{
+
+type TResult = i32;
+
+%}
+
+%%
+
+Items
+    : Items Item  { |$1: i32, $2: i32| -> i32; $$ = $1 + $2 }
+    | Item        { |$1: i32| -> i32; $$ = $1 }
+    ;
+
+Item
+    : 'k000'  { || -> i32; $$ = 0 }
+    | 'k001'  { || -> i32; $$ = 1 }
+    | 'k002'  { || -> i32; $$ = 2 }
+    | 'k003'  { || -> i32; $$ = 3 }
+    | 'k004'  { || -> i32; $$ = 4 }
+    | 'k005'  { || -> i32; $$ = 5 }
+    | 'k006'  { || -> i32; $$ = 6 }
+    | 'k007'  { || -> i32; $$ = 7 }
+    | 'k008'  { || -> i32; $$ = 8 }
+    | 'k009'  { || -> i32; $$ = 9 }
+    | 'k010'  { || -> i32; $$ = 10 }
+    | 'k011'  { || -> i32; $$ = 11 }
+    | 'k012'  { || -> i32; $$ = 12 }
+    | 'k013'  { || -> i32; $$ = 13 }
+    | 'k014'  { || -> i32; $$ = 14 }
+    | 'k015'  { || -> i32; $$ = 15 }
+    | 'k016'  { || -> i32; $$ = 16 }
+    | 'k017'  { || -> i32; $$ = 17 }
+    | 'k018'  { || -> i32; $$ = 18 }
+    | 'k019'  { || -> i32; $$ = 19 }
+    | 'k020'  { || -> i32; $$ = 20 }
+    | 'k021'  { || -> i32; $$ = 21 }
+    | 'k022'  { || -> i32; $$ = 22 }
+    | 'k023'  { || -> i32; $$ = 23 }
+    | 'k024'  { || -> i32; $$ = 24 }
+    | 'k025'  { || -> i32; $$ = 25 }
+    | 'k026'  { || -> i32; $$ = 26 }
+    | 'k027'  { || -> i32; $$ = 27 }
+    | 'k028'  { || -> i32; $$ = 28 }
+    | 'k029'  { || -> i32; $$ = 29 }
+    | 'k030'  { || -> i32; $$ = 30 }
+    | 'k031'  { || -> i32; $$ = 31 }
+    | 'k032'  { || -> i32; $$ = 32 }
+    | 'k033'  { || -> i32; $$ = 33 }
+    | 'k034'  { || -> i32; $$ = 34 }
+    | 'k035'  { || -> i32; $$ = 35 }
+    | 'k036'  { || -> i32; $$ = 36 }
+    | 'k037'  { || -> i32; $$ = 37 }
+    | 'k038'  { || -> i32; $$ = 38 }
+    | 'k039'  { || -> i32; $$ = 39 }
+    | 'k040'  { || -> i32; $$ = 40 }
+    | 'k041'  { || -> i32; $$ = 41 }
+    | 'k042'  { || -> i32; $$ = 42 }
+    | 'k043'  { || -> i32; $$ = 43 }
+    | 'k044'  { || -> i32; $$ = 44 }
+    | 'k045'  { || -> i32; $$ = 45 }
+    | 'k046'  { || -> i32; $$ = 46 }
+    | 'k047'  { || -> i32; $$ = 47 }
+    | 'k048'  { || -> i32; $$ = 48 }
+    | 'k049'  { || -> i32; $$ = 49 }
+    | 'k050'  { || -> i32; $$ = 50 }
+    | 'k051'  { || -> i32; $$ = 51 }
+    | 'k052'  { || -> i32; $$ = 52 }
+    | 'k053'  { || -> i32; $$ = 53 }
+    | 'k054'  { || -> i32; $$ = 54 }
+    | 'k055'  { || -> i32; $$ = 55 }
+    | 'k056'  { || -> i32; $$ = 56 }
+    | 'k057'  { || -> i32; $$ = 57 }
+    | 'k058'  { || -> i32; $$ = 58 }
+    | 'k059'  { || -> i32; $$ = 59 }
+    | 'k060'  { || -> i32; $$ = 60 }
+    | 'k061'  { || -> i32; $$ = 61 }
+    | 'k062'  { || -> i32; $$ = 62 }
+    | 'k063'  { || -> i32; $$ = 63 }
+    | 'k064'  { || -> i32; $$ = 64 }
+    | 'k065'  { || -> i32; $$ = 65 }
+    | 'k066'  { || -> i32; $$ = 66 }
+    | 'k067'  { || -> i32; $$ = 67 }
+    | 'k068'  { || -> i32; $$ = 68 }
+    | 'k069'  { || -> i32; $$ = 69 }
+    | 'k070'  { || -> i32; $$ = 70 }
+    | 'k071'  { || -> i32; $$ = 71 }
+    | 'k072'  { || -> i32; $$ = 72 }
+    | 'k073'  { || -> i32; $$ = 73 }
+    | 'k074'  { || -> i32; $$ = 74 }
+    | 'k075'  { || -> i32; $$ = 75 }
+    | 'k076'  { || -> i32; $$ = 76 }
+    | 'k077'  { || -> i32; $$ = 77 }
+    | 'k078'  { || -> i32; $$ = 78 }
+    | 'k079'  { || -> i32; $$ = 79 }
+    | 'k080'  { || -> i32; $$ = 80 }
+    | 'k081'  { || -> i32; $$ = 81 }
+    | 'k082'  { || -> i32; $$ = 82 }
+    | 'k083'  { || -> i32; $$ = 83 }
+    | 'k084'  { || -> i32; $$ = 84 }
+    | 'k085'  { || -> i32; $$ = 85 }
+    | 'k086'  { || -> i32; $$ = 86 }
+    | 'k087'  { || -> i32; $$ = 87 }
+    | 'k088'  { || -> i32; $$ = 88 }
+    | 'k089'  { || -> i32; $$ = 89 }
+    | 'k090'  { || -> i32; $$ = 90 }
+    | 'k091'  { || -> i32; $$ = 91 }
+    | 'k092'  { || -> i32; $$ = 92 }
+    | 'k093'  { || -> i32; $$ = 93 }
+    | 'k094'  { || -> i32; $$ = 94 }
+    | 'k095'  { || -> i32; $$ = 95 }
+    | 'k096'  { || -> i32; $$ = 96 }
+    | 'k097'  { || -> i32; $$ = 97 }
+    | 'k098'  { || -> i32; $$ = 98 }
+    | 'k099'  { || -> i32; $$ = 99 }
+    | 'k100'  { || -> i32; $$ = 100 }
+    | 'k101'  { || -> i32; $$ = 101 }
+    | 'k102'  { || -> i32; $$ = 102 }
+    | 'k103'  { || -> i32; $$ = 103 }
+    | 'k104'  { || -> i32; $$ = 104 }
+    | 'k105'  { || -> i32; $$ = 105 }
+    | 'k106'  { || -> i32; $$ = 106 }
+    | 'k107'  { || -> i32; $$ = 107 }
+    | 'k108'  { || -> i32; $$ = 108 }
+    | 'k109'  { || -> i32; $$ = 109 }
+    | 'k110'  { || -> i32; $$ = 110 }
+    | 'k111'  { || -> i32; $$ = 111 }
+    | 'k112'  { || -> i32; $$ = 112 }
+    | 'k113'  { || -> i32; $$ = 113 }
+    | 'k114'  { || -> i32; $$ = 114 }
+    | 'k115'  { || -> i32; $$ = 115 }
+    | 'k116'  { || -> i32; $$ = 116 }
+    | 'k117'  { || -> i32; $$ = 117 }
+    | 'k118'  { || -> i32; $$ = 118 }
+    | 'k119'  { || -> i32; $$ = 119 }
+    | 'k120'  { || -> i32; $$ = 120 }
+    | 'k121'  { || -> i32; $$ = 121 }
+    | 'k122'  { || -> i32; $$ = 122 }
+    | 'k123'  { || -> i32; $$ = 123 }
+    | 'k124'  { || -> i32; $$ = 124 }
+    | 'k125'  { || -> i32; $$ = 125 }
+    | 'k126'  { || -> i32; $$ = 126 }
+    | 'k127'  { || -> i32; $$ = 127 }
+    | 'k128'  { || -> i32; $$ = 128 }
+    | 'k129'  { || -> i32; $$ = 129 }
+    | 'k130'  { || -> i32; $$ = 130 }
+    | 'k131'  { || -> i32; $$ = 131 }
+    | 'k132'  { || -> i32; $$ = 132 }
+    | 'k133'  { || -> i32; $$ = 133 }
+    | 'k134'  { || -> i32; $$ = 134 }
+    | 'k135'  { || -> i32; $$ = 135 }
+    | 'k136'  { || -> i32; $$ = 136 }
+    | 'k137'  { || -> i32; $$ = 137 }
+    | 'k138'  { || -> i32; $$ = 138 }
+    | 'k139'  { || -> i32; $$ = 139 }
+    | 'k140'  { || -> i32; $$ = 140 }
+    | 'k141'  { || -> i32; $$ = 141 }
+    | 'k142'  { || -> i32; $$ = 142 }
+    | 'k143'  { || -> i32; $$ = 143 }
+    | 'k144'  { || -> i32; $$ = 144 }
+    | 'k145'  { || -> i32; $$ = 145 }
+    | 'k146'  { || -> i32; $$ = 146 }
+    | 'k147'  { || -> i32; $$ = 147 }
+    | 'k148'  { || -> i32; $$ = 148 }
+    | 'k149'  { || -> i32; $$ = 149 }
+    | 'k150'  { || -> i32; $$ = 150 }
+    | 'k151'  { || -> i32; $$ = 151 }
+    | 'k152'  { || -> i32; $$ = 152 }
+    | 'k153'  { || -> i32; $$ = 153 }
+    | 'k154'  { || -> i32; $$ = 154 }
+    | 'k155'  { || -> i32; $$ = 155 }
+    | 'k156'  { || -> i32; $$ = 156 }
+    | 'k157'  { || -> i32; $$ = 157 }
+    | 'k158'  { || -> i32; $$ = 158 }
+    | 'k159'  { || -> i32; $$ = 159 }
+    | 'k160'  { || -> i32; $$ = 160 }
+    | 'k161'  { || -> i32; $$ = 161 }
+    | 'k162'  { || -> i32; $$ = 162 }
+    | 'k163'  { || -> i32; $$ = 163 }
+    | 'k164'  { || -> i32; $$ = 164 }
+    | 'k165'  { || -> i32; $$ = 165 }
+    | 'k166'  { || -> i32; $$ = 166 }
+    | 'k167'  { || -> i32; $$ = 167 }
+    | 'k168'  { || -> i32; $$ = 168 }
+    | 'k169'  { || -> i32; $$ = 169 }
+    | 'k170'  { || -> i32; $$ = 170 }
+    | 'k171'  { || -> i32; $$ = 171 }
+    | 'k172'  { || -> i32; $$ = 172 }
+    | 'k173'  { || -> i32; $$ = 173 }
+    | 'k174'  { || -> i32; $$ = 174 }
+    | 'k175'  { || -> i32; $$ = 175 }
+    | 'k176'  { || -> i32; $$ = 176 }
+    | 'k177'  { || -> i32; $$ = 177 }
+    | 'k178'  { || -> i32; $$ = 178 }
+    | 'k179'  { || -> i32; $$ = 179 }
+    | 'k180'  { || -> i32; $$ = 180 }
+    | 'k181'  { || -> i32; $$ = 181 }
+    | 'k182'  { || -> i32; $$ = 182 }
+    | 'k183'  { || -> i32; $$ = 183 }
+    | 'k184'  { || -> i32; $$ = 184 }
+    | 'k185'  { || -> i32; $$ = 185 }
+    | 'k186'  { || -> i32; $$ = 186 }
+    | 'k187'  { || -> i32; $$ = 187 }
+    | 'k188'  { || -> i32; $$ = 188 }
+    | 'k189'  { || -> i32; $$ = 189 }
+    | 'k190'  { || -> i32; $$ = 190 }
+    | 'k191'  { || -> i32; $$ = 191 }
+    | 'k192'  { || -> i32; $$ = 192 }
+    | 'k193'  { || -> i32; $$ = 193 }
+    | 'k194'  { || -> i32; $$ = 194 }
+    | 'k195'  { || -> i32; $$ = 195 }
+    | 'k196'  { || -> i32; $$ = 196 }
+    | 'k197'  { || -> i32; $$ = 197 }
+    | 'k198'  { || -> i32; $$ = 198 }
+    | 'k199'  { || -> i32; $$ = 199 }
+    | 'k200'  { || -> i32; $$ = 200 }
+    | 'k201'  { || -> i32; $$ = 201 }
+    | 'k202'  { || -> i32; $$ = 202 }
+    | 'k203'  { || -> i32; $$ = 203 }
+    | 'k204'  { || -> i32; $$ = 204 }
+    | 'k205'  { || -> i32; $$ = 205 }
+    | 'k206'  { || -> i32; $$ = 206 }
+    | 'k207'  { || -> i32; $$ = 207 }
+    | 'k208'  { || -> i32; $$ = 208 }
+    | 'k209'  { || -> i32; $$ = 209 }
+    | 'k210'  { || -> i32; $$ = 210 }
+    | 'k211'  { || -> i32; $$ = 211 }
+    | 'k212'  { || -> i32; $$ = 212 }
+    | 'k213'  { || -> i32; $$ = 213 }
+    | 'k214'  { || -> i32; $$ = 214 }
+    | 'k215'  { || -> i32; $$ = 215 }
+    | 'k216'  { || -> i32; $$ = 216 }
+    | 'k217'  { || -> i32; $$ = 217 }
+    | 'k218'  { || -> i32; $$ = 218 }
+    | 'k219'  { || -> i32; $$ = 219 }
+    | 'k220'  { || -> i32; $$ = 220 }
+    | 'k221'  { || -> i32; $$ = 221 }
+    | 'k222'  { || -> i32; $$ = 222 }
+    | 'k223'  { || -> i32; $$ = 223 }
+    | 'k224'  { || -> i32; $$ = 224 }
+    | 'k225'  { || -> i32; $$ = 225 }
+    | 'k226'  { || -> i32; $$ = 226 }
+    | 'k227'  { || -> i32; $$ = 227 }
+    | 'k228'  { || -> i32; $$ = 228 }
+    | 'k229'  { || -> i32; $$ = 229 }
+    | 'k230'  { || -> i32; $$ = 230 }
+    | 'k231'  { || -> i32; $$ = 231 }
+    | 'k232'  { || -> i32; $$ = 232 }
+    | 'k233'  { || -> i32; $$ = 233 }
+    | 'k234'  { || -> i32; $$ = 234 }
+    | 'k235'  { || -> i32; $$ = 235 }
+    | 'k236'  { || -> i32; $$ = 236 }
+    | 'k237'  { || -> i32; $$ = 237 }
+    | 'k238'  { || -> i32; $$ = 238 }
+    | 'k239'  { || -> i32; $$ = 239 }
+    | 'k240'  { || -> i32; $$ = 240 }
+    | 'k241'  { || -> i32; $$ = 241 }
+    | 'k242'  { || -> i32; $$ = 242 }
+    | 'k243'  { || -> i32; $$ = 243 }
+    | 'k244'  { || -> i32; $$ = 244 }
+    | 'k245'  { || -> i32; $$ = 245 }
+    | 'k246'  { || -> i32; $$ = 246 }
+    | 'k247'  { || -> i32; $$ = 247 }
+    | 'k248'  { || -> i32; $$ = 248 }
+    | 'k249'  { || -> i32; $$ = 249 }
+    | 'k250'  { || -> i32; $$ = 250 }
+    | 'k251'  { || -> i32; $$ = 251 }
+    | 'k252'  { || -> i32; $$ = 252 }
+    | 'k253'  { || -> i32; $$ = 253 }
+    | 'k254'  { || -> i32; $$ = 254 }
+    | 'k255'  { || -> i32; $$ = 255 }
+    | 'k256'  { || -> i32; $$ = 256 }
+    | 'k257'  { || -> i32; $$ = 257 }
+    | 'k258'  { || -> i32; $$ = 258 }
+    | 'k259'  { || -> i32; $$ = 259 }
+    ;
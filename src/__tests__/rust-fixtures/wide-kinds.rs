@@ -0,0 +1,39 @@
+use super::*;
+
+use std::mem::size_of;
+
+#[test]
+fn encoded_symbols_fit_u16() {
+    assert_eq!(size_of::<EncodedSymbol>(), 2);
+    assert_eq!(ENCODED_PRODUCTIONS[0][0], EncodedSymbol::MAX);
+}
+
+#[test]
+fn kinds_above_u8() {
+    let kind = TOKENS_MAP["'k259'"];
+
+    assert!(kind > u8::MAX as i32);
+
+    let mut tokenizer = Tokenizer::new();
+    tokenizer.init_string("k259");
+
+    assert_eq!(tokenizer.get_next_token().unwrap().kind, kind);
+}
+
+#[test]
+fn parse() {
+    let mut parser = Parser::new();
+
+    assert_eq!(parser.parse("k000").unwrap(), 0);
+    assert_eq!(parser.parse("k001 k255 k256").unwrap(), 512);
+    assert_eq!(parser.parse("k259 k100").unwrap(), 359);
+}
+
+#[test]
+fn syntax_error() {
+    let mut parser = Parser::new();
+
+    let error = parser.parse("k001 k300").unwrap_err();
+
+    assert_eq!(error.loc.start_offset, 5);
+}
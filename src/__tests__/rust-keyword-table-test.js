@@ -0,0 +1,93 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import buildKeywordTable from '../plugins/rust/rust-keyword-table';
+import LexGrammar from '../grammar/lex-grammar';
+
+function keywordTable(rules, startConditions = {}) {
+  const lexGrammar = new LexGrammar({rules, startConditions});
+  const {rulesByConditions, keywords} = buildKeywordTable(lexGrammar);
+
+  const matchers = {};
+  for (const condition in rulesByConditions) {
+    matchers[condition] = rulesByConditions[condition].map(rule =>
+      rule.getOriginalMatcher()
+    );
+  }
+
+  const lookups = {};
+  keywords.forEach((words, rule) => {
+    lookups[rule.getOriginalMatcher()] = words;
+  });
+
+  return {matchers, lookups};
+}
+
+describe('rust keyword table', () => {
+  it('keywords before the identifier', () => {
+    expect(
+      keywordTable([
+        ['\\s+', "return ''"],
+        ['if\\b', "return 'IF'"],
+        ['\\belse\\b', 'return "ELSE";'],
+        ['[a-zA-Z_]\\w*', "return 'ID'"],
+        ['\\d+', "return 'NUMBER'"],
+      ])
+    ).toEqual({
+      matchers: {INITIAL: ['\\s+', '[a-zA-Z_]\\w*', '\\d+']},
+      lookups: {'[a-zA-Z_]\\w*': [['if', 'IF'], ['else', 'ELSE']]},
+    });
+  });
+
+  it('not keyword rules', () => {
+    const rules = [
+      ['if', "return 'IF'"],
+      ['else\\b', "return 'ELSE'"],
+      ['(?i)while\\b', "return 'WHILE'"],
+      ['do\\b', "this.popState(); return 'DO'"],
+      ['\\w+', "return 'ID'"],
+    ];
+
+    // The `do` rule changes the state, the keywords are not right before
+    // the identifier.
+    expect(keywordTable(rules).lookups).toEqual({});
+    expect(keywordTable(rules.slice(1, 2).concat(rules.slice(4))).lookups)
+      .toEqual({'\\w+': [['else', 'ELSE']]});
+  });
+
+  it('identifier matching the keywords only', () => {
+    const lookups = identifier =>
+      keywordTable([['if\\b', "return 'IF'"], [identifier, "return 'ID'"]])
+        .lookups;
+
+    expect(lookups('\\w+')).toEqual({'\\w+': [['if', 'IF']]});
+    expect(lookups('[A-Z_a-z]\\w*')).toEqual({
+      '[A-Z_a-z]\\w*': [['if', 'IF']],
+    });
+
+    // A different text after the keyword, a non-ASCII word char, or
+    // not a JS regex.
+    expect(lookups('[a-z]+')).toEqual({});
+    expect(lookups('[a-z0-9_]+')).toEqual({});
+    expect(lookups('[\\w.]+')).toEqual({});
+    expect(lookups('[[:alpha:]]+')).toEqual({});
+  });
+
+  it('keywords in all conditions of the identifier', () => {
+    expect(
+      keywordTable(
+        [
+          ['if\\b', "return 'IF'"],
+          [['INITIAL'], 'in\\b', "return 'IN'"],
+          [['*'], '\\w+', "return 'ID'"],
+        ],
+        {attr: 0}
+      )
+    ).toEqual({
+      matchers: {INITIAL: ['in\\b', '\\w+'], attr: ['\\w+']},
+      lookups: {'\\w+': [['if', 'IF']]},
+    });
+  });
+});
@@ -1,4 +1,9 @@
 import * as shelljs from 'shelljs';
+import Grammar from '../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../grammar/grammar-mode';
+import crypto from 'crypto';
+import fs from 'fs';
+import os from 'os';
 import path from 'path';
 
 // rust-calc test package uses Rust 2018, minimum version for that rust edition is 1.31
@@ -10,6 +15,7 @@ const whichMake = shelljs.which('make');
 const rustInstalled = whichCargo && whichCargo.code === 0;
 const makeInstalled = whichMake && whichMake.code === 0;
 const rustCalcDir = path.join(__dirname, 'rust-calc');
+const rustFixturesDir = path.join(__dirname, 'rust-fixtures');
 
 function getRustVersion() {
   const child = shelljs.exec('cargo --version');
@@ -44,6 +50,293 @@ function semverGte(aNumbers, bNumbers) {
   return eq;
 }
 
+/**
+ * Generates a parser for the grammar file into a scratch crate
+ * `<tmpdir>/syntax-rust-fixtures/<name>`, with the `tests` module
+ * (the test module has access to all private items of the parser).
+ */
+function generateRustCrate(
+  name,
+  grammarFile,
+  {mode = GRAMMAR_MODE.LALR1, options = {}} = {}
+) {
+  // Rust plugin generator requires modules from the root.
+  global.ROOT = path.join(__dirname, '..') + '/';
+  const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+    .default;
+
+  const crateDir = path.join(os.tmpdir(), 'syntax-rust-fixtures', name);
+  const outputFile = path.join(crateDir, 'src', 'lib.rs');
+  shelljs.mkdir('-p', path.join(crateDir, 'src'));
+
+  // Semantic actions are not JS code, don't try evaluating them.
+  global.globalOptions = {output: outputFile};
+
+  const regexDependency =
+    options.regexBackend === 'regex' ? `regex = "1"` : `onig = "4"`;
+
+  // Spans of the macro input are tested outside of a macro, with the
+  // locations of the `proc-macro2` fallback implementation.
+  const procMacroDependency = options.procMacroSpans
+    ? `proc-macro2 = { version = "1", features = ["span-locations"], ` +
+      `optional = true }\n`
+    : '';
+
+  const normalizationDependency = options.normalizeTokens
+    ? `unicode-normalization = { version = "0.1", optional = true }\n`
+    : '';
+
+  const segmentationDependency = options.graphemeColumns
+    ? `unicode-segmentation = { version = "1", optional = true }\n`
+    : '';
+
+  const futuresDependency = options.asyncStream
+    ? `futures = { version = "0.3", optional = true }\n`
+    : '';
+  // The std statics need no crate.
+  const statics = options.constTables ? null : options.statics || 'lazy_static';
+  const staticsDependency =
+    statics === 'lazy_static'
+      ? `lazy_static = "1"\n`
+      : statics === 'once_cell' ? `once_cell = "1"\n` : '';
+
+  // The wasm entry point is compiled only for the `wasm` feature, the
+  // spans for the `proc-macro2` one, the normalization of tokens for
+  // the `unicode-normalization` one, the grapheme columns for the
+  // `unicode-segmentation` one, and the async parse for the `futures` one.
+  const defaultFeatures = [
+    options.procMacroSpans && `"proc-macro2"`,
+    options.normalizeTokens && `"unicode-normalization"`,
+    options.graphemeColumns && `"unicode-segmentation"`,
+    options.asyncStream && `"futures"`,
+  ].filter(Boolean);
+
+  const featureList =
+    (options.wasm ? `wasm = []\n` : '') +
+    (defaultFeatures.length > 0
+      ? `default = [${defaultFeatures.join(', ')}]\n`
+      : '');
+  const features = featureList ? `[features]\n${featureList}\n` : '';
+
+  fs.writeFileSync(
+    path.join(crateDir, 'Cargo.toml'),
+    `[package]\n` +
+      `name = "${name}"\n` +
+      `version = "0.1.0"\n` +
+      `edition = "2018"\n\n` +
+      `[dependencies]\n` +
+      `${regexDependency}\n` +
+      staticsDependency +
+      procMacroDependency +
+      normalizationDependency +
+      segmentationDependency +
+      futuresDependency +
+      `\n` +
+      features +
+      `[workspace]\n`,
+    'utf-8'
+  );
+
+  // Same dependency versions as in the calc example.
+  shelljs.cp(
+    path.join(rustCalcDir, 'Cargo.lock'),
+    path.join(crateDir, 'Cargo.lock')
+  );
+
+  const parserCode = new LRParserGeneratorRust({
+    grammar: Grammar.fromGrammarFile(grammarFile, {mode}),
+    outputFile: null,
+    options,
+  }).generate();
+
+  fs.writeFileSync(
+    outputFile,
+    `${parserCode}\n\n#[cfg(test)]\nmod tests;\n`,
+    'utf-8'
+  );
+
+  return crateDir;
+}
+
+/**
+ * Generates a crate for the `rust-fixtures/<grammar>.g` grammar with the
+ * `emitCrate` option into `<tmpdir>/syntax-rust-fixtures/<name>` (an existing
+ * crate is removed, unless `clean` is false).
+ */
+function emitRustCrate(
+  name,
+  {grammar = 'calc', options = {}, clean = true} = {}
+) {
+  global.ROOT = path.join(__dirname, '..') + '/';
+  const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+    .default;
+
+  const crateDir = path.join(os.tmpdir(), 'syntax-rust-fixtures', name);
+  const outputFile = path.join(crateDir, 'src', 'lib.rs');
+  if (clean) {
+    shelljs.rm('-rf', crateDir);
+  }
+  shelljs.mkdir('-p', path.join(crateDir, 'src'));
+
+  global.globalOptions = {output: outputFile};
+
+  new LRParserGeneratorRust({
+    grammar: Grammar.fromGrammarFile(
+      path.join(rustFixturesDir, `${grammar}.g`),
+      {mode: GRAMMAR_MODE.LALR1}
+    ),
+    outputFile,
+    options: Object.assign({emitCrate: true}, options),
+  }).generate();
+
+  return crateDir;
+}
+
+/**
+ * Runs `cargo` in a scratch crate.
+ */
+function execCargo(command, crateDir) {
+  return shelljs.exec(command, {
+    silent: true,
+    cwd: crateDir,
+    env: Object.assign({}, process.env, {
+      // Share compiled dependencies between all fixtures.
+      CARGO_TARGET_DIR: path.join(rustCalcDir, 'target', 'fixtures'),
+    }),
+  });
+}
+
+/**
+ * Generates a parser for the `rust-fixtures/<grammar>.g` grammar, and
+ * runs `rust-fixtures/<name>.rs` as its unit tests. The `modules` are
+ * the parsers of other grammars, generated into the modules of the crate:
+ * {expr: {grammar: 'calc', options: {...}}}.
+ */
+function runRustFixture(
+  name,
+  {grammar = name, options = {}, modules = {}, compileFail = false} = {}
+) {
+  const crateDir = generateRustCrate(
+    name,
+    path.join(rustFixturesDir, `${grammar}.g`),
+    {options}
+  );
+
+  const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+    .default;
+
+  Object.keys(modules).forEach(moduleName => {
+    const moduleCode = new LRParserGeneratorRust({
+      grammar: Grammar.fromGrammarFile(
+        path.join(rustFixturesDir, `${modules[moduleName].grammar}.g`),
+        {mode: GRAMMAR_MODE.LALR1}
+      ),
+      outputFile: null,
+      options: modules[moduleName].options || {},
+    }).generate();
+
+    fs.writeFileSync(
+      path.join(crateDir, 'src', `${moduleName}.rs`),
+      moduleCode,
+      'utf-8'
+    );
+  });
+
+  shelljs.cp(
+    path.join(rustFixturesDir, `${name}.rs`),
+    path.join(crateDir, 'src', 'tests.rs')
+  );
+
+  // A compile-fail fixture is only built, and is expected to fail.
+  const command = compileFail
+    ? 'cargo test --no-run --quiet'
+    : 'cargo test --quiet';
+
+  const testResult = execCargo(command, crateDir);
+
+  if (testResult.code !== 0 && !compileFail) {
+    console.error(testResult.stdout, testResult.stderr);
+  }
+
+  return testResult;
+}
+
+/**
+ * Conformance suite: each case generates a parser for a grammar, parses
+ * the inputs of `rust-conformance/<name>.cases` (separated by `---`
+ * lines), and returns the results, one line per input: `Ok(<value>)`
+ * (the `Debug` of the parsed value), or `Err(<message>)`. The results
+ * are compared with `rust-conformance/<name>.expected`.
+ */
+const rustConformanceDir = path.join(__dirname, 'rust-conformance');
+
+const rustConformanceCases = [
+  {name: 'calc', grammarFile: 'examples/calc.rs.g'},
+  {
+    name: 'calc',
+    grammarFile: 'examples/calc.rs.g',
+    mode: GRAMMAR_MODE.CLR1,
+  },
+  {name: 'calc-ast', grammarFile: 'examples/calc-ast.rs.g'},
+  {
+    name: 'calc-actions',
+    grammarFile: 'examples/calc-actions.rs.g',
+    options: {actionsModule: 'actions'},
+    modules: ['examples/actions.rs'],
+  },
+  {name: 'eof', grammarFile: 'src/__tests__/rust-fixtures/eof.g'},
+  {
+    name: 'indentation',
+    grammarFile: 'src/__tests__/rust-fixtures/indentation.g',
+  },
+  {
+    name: 'lex-alternation',
+    grammarFile: 'src/__tests__/rust-fixtures/lex-alternation.g',
+    options: {lexAlternation: true},
+  },
+];
+
+function runRustConformance({
+  name,
+  grammarFile,
+  mode = GRAMMAR_MODE.LALR1,
+  options = {},
+  modules = [],
+}) {
+  const crateDir = generateRustCrate(
+    `conformance-${name}-${mode.toLowerCase()}`,
+    path.join(__dirname, '..', '..', grammarFile),
+    {mode, options}
+  );
+
+  // Modules used by the parser, placed next to it.
+  modules.forEach(module => {
+    shelljs.cp(
+      path.join(__dirname, '..', '..', module),
+      path.join(crateDir, 'src', path.basename(module))
+    );
+  });
+
+  shelljs.cp(
+    path.join(rustConformanceDir, 'conformance.rs'),
+    path.join(crateDir, 'src', 'tests.rs')
+  );
+
+  shelljs.cp(
+    path.join(rustConformanceDir, `${name}.cases`),
+    path.join(crateDir, 'input.cases')
+  );
+
+  const testResult = execCargo('cargo test --quiet', crateDir);
+
+  if (testResult.code !== 0) {
+    console.error(testResult.stdout, testResult.stderr);
+    return null;
+  }
+
+  return fs.readFileSync(path.join(crateDir, 'output.txt'), 'utf-8');
+}
+
 const rustVersion = getRustVersion();
 const minimumVersionSatisfied = semverGte(rustVersion, rustVersionRequired);
 
@@ -79,6 +372,1310 @@ if (makeInstalled && rustInstalled && minimumVersionSatisfied) {
       expect(match).not.toBeNull();
       expect(match[1]).toEqual('6');
     });
+
+    it('calc rust example should build without warnings', () => {
+      const buildResult = shelljs.exec('cargo build', {
+        silent: true,
+        cwd: rustCalcDir,
+      });
+
+      expect(buildResult.code).toEqual(0);
+      expect(buildResult.stderr).not.toContain('warning');
+    }, 60000);
+
+    it('calc rust example should report a syntax error', () => {
+      const runResult = shelljs.exec('cargo run --quiet -- "2 + * 2"', {
+        silent: true,
+        cwd: rustCalcDir,
+      });
+
+      expect(runResult.code).toEqual(1);
+      expect(runResult.stdout).not.toContain('parse result');
+      expect(runResult.stderr).toContain(
+        'Unexpected token: "*" at 1:4; expected one of: NUMBER, (.'
+      );
+    }, 60000);
+
+    it('multi-line token locations', () => {
+      expect(runRustFixture('multiline-locations').code).toEqual(0);
+    }, 60000);
+
+    it('read until a pattern in lex handlers', () => {
+      expect(runRustFixture('heredoc').code).toEqual(0);
+    }, 60000);
+
+    it('remaining and consumed input', () => {
+      expect(runRustFixture('cursor-views', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('several tokens from one lex match', () => {
+      expect(runRustFixture('split-tokens').code).toEqual(0);
+    }, 60000);
+
+    it('parse file', () => {
+      expect(runRustFixture('parse-file', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('emit crate', () => {
+      const crateDir = emitRustCrate('emit-crate');
+      const manifest = fs.readFileSync(
+        path.join(crateDir, 'Cargo.toml'),
+        'utf-8'
+      );
+
+      expect(manifest).toContain('name = "emit-crate"');
+      expect(manifest).toContain('onig = "4"');
+      expect(manifest).toContain('lazy_static = "1"');
+
+      // Same dependency versions as in the calc example.
+      shelljs.cp(
+        path.join(rustCalcDir, 'Cargo.lock'),
+        path.join(crateDir, 'Cargo.lock')
+      );
+
+      const buildResult = execCargo('cargo build --quiet', crateDir);
+
+      if (buildResult.code !== 0) {
+        console.error(buildResult.stdout, buildResult.stderr);
+      }
+
+      expect(buildResult.code).toEqual(0);
+    }, 60000);
+
+    it('emit crate manifest for options', () => {
+      const crateDir = emitRustCrate('emit-crate-options', {
+        options: {regexBackend: 'regex', wasm: true, procMacroSpans: true},
+      });
+      const manifestFile = path.join(crateDir, 'Cargo.toml');
+      const manifest = fs.readFileSync(manifestFile, 'utf-8');
+
+      expect(manifest).toContain('regex = "1"');
+      expect(manifest).not.toContain('onig');
+      expect(manifest).toContain('crate-type = ["cdylib", "rlib"]');
+      expect(manifest).toContain('wasm = ["wasm-bindgen"]');
+      expect(manifest).toContain('default = ["proc-macro2"]');
+
+      const normalizationManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-normalization', {
+            options: {procMacroSpans: true, normalizeTokens: ['NUMBER']},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(normalizationManifest).toContain(
+        'unicode-normalization = { version = "0.1", optional = true }'
+      );
+      expect(normalizationManifest).toContain(
+        'default = ["proc-macro2", "unicode-normalization"]'
+      );
+
+      const segmentationManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-segmentation', {
+            options: {graphemeColumns: true},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(segmentationManifest).toContain(
+        'unicode-segmentation = { version = "1", optional = true }'
+      );
+      expect(segmentationManifest).toContain(
+        'default = ["unicode-segmentation"]'
+      );
+
+      const futuresManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-futures', {
+            options: {asyncStream: true},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(futuresManifest).toContain(
+        'futures = { version = "0.3", optional = true }'
+      );
+      expect(futuresManifest).not.toContain('default =');
+
+      const constTablesManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-const-tables', {
+            options: {constTables: true},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(constTablesManifest).not.toContain('lazy_static');
+
+      const onceCellManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-once-cell', {
+            options: {statics: 'once_cell'},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(onceCellManifest).toContain('once_cell = "1"');
+      expect(onceCellManifest).not.toContain('lazy_static');
+
+      const stdStaticsManifest = fs.readFileSync(
+        path.join(
+          emitRustCrate('emit-crate-std-statics', {
+            options: {statics: 'std'},
+          }),
+          'Cargo.toml'
+        ),
+        'utf-8'
+      );
+
+      expect(stdStaticsManifest).not.toContain('lazy_static');
+      expect(stdStaticsManifest).not.toContain('once_cell');
+
+      // An existing manifest is not overwritten.
+      fs.writeFileSync(manifestFile, '# custom\n', 'utf-8');
+      emitRustCrate('emit-crate-options', {clean: false});
+      expect(fs.readFileSync(manifestFile, 'utf-8')).toEqual('# custom\n');
+    });
+
+    it('emit test', () => {
+      const runSmokeTest = sample => {
+        const crateDir = emitRustCrate('emit-test', {
+          options: {emitTest: sample},
+        });
+
+        shelljs.cp(
+          path.join(rustCalcDir, 'Cargo.lock'),
+          path.join(crateDir, 'Cargo.lock')
+        );
+
+        return execCargo('cargo test --quiet', crateDir);
+      };
+
+      const testResult = runSmokeTest('2 + 2 * 2');
+
+      if (testResult.code !== 0) {
+        console.error(testResult.stdout, testResult.stderr);
+      }
+
+      expect(testResult.code).toEqual(0);
+      expect(testResult.stdout).toContain('1 passed');
+
+      // A sample with a syntax error fails the test.
+      const failedResult = runSmokeTest('2 +');
+
+      expect(failedResult.code).not.toEqual(0);
+      expect(failedResult.stdout + failedResult.stderr).toContain(
+        'Unexpected end of input'
+      );
+    }, 60000);
+
+    it('emit repl', () => {
+      const crateDir = emitRustCrate('emit-repl', {
+        options: {emitRepl: true},
+      });
+
+      shelljs.cp(
+        path.join(rustCalcDir, 'Cargo.lock'),
+        path.join(crateDir, 'Cargo.lock')
+      );
+
+      // A valid line, an invalid one, and the parser is reused after it.
+      const replResult = execCargo(
+        `printf '2 + 2 * 2\\n2 + * 2\\n(2 + 2) * 2\\n' | cargo run --quiet`,
+        crateDir
+      );
+
+      if (replResult.code !== 0) {
+        console.error(replResult.stdout, replResult.stderr);
+      }
+
+      expect(replResult.code).toEqual(0);
+      expect(replResult.stdout).toEqual(
+        '> 6\n' +
+          '> \n\n2 + * 2\n    ^\n' +
+          ' Unexpected token: "*" at 1:4; expected one of: NUMBER, (.\n' +
+          '> 8\n' +
+          '> \n'
+      );
+    }, 60000);
+
+    it('emit bench', () => {
+      const sampleFile = path.join(
+        os.tmpdir(),
+        'syntax-rust-fixtures',
+        'sample.calc'
+      );
+      shelljs.mkdir('-p', path.dirname(sampleFile));
+      fs.writeFileSync(sampleFile, '(1 + 2) * 3 + 4 * (5 + 6)\n', 'utf-8');
+
+      const crateDir = emitRustCrate('emit-bench', {
+        options: {emitBench: sampleFile},
+      });
+
+      const manifest = fs.readFileSync(
+        path.join(crateDir, 'Cargo.toml'),
+        'utf-8'
+      );
+
+      expect(manifest).toContain('[dev-dependencies]\ncriterion = "0.5"');
+      expect(manifest).toContain(
+        '[[bench]]\nname = "parse"\nharness = false'
+      );
+      expect(
+        fs.readFileSync(path.join(crateDir, 'benches', 'sample.calc'), 'utf-8')
+      ).toEqual('(1 + 2) * 3 + 4 * (5 + 6)\n');
+
+      // Each benchmark runs once in the test mode.
+      const benchResult = execCargo('cargo test --benches --quiet', crateDir);
+
+      if (benchResult.code !== 0) {
+        console.error(benchResult.stdout, benchResult.stderr);
+      }
+
+      expect(benchResult.code).toEqual(0);
+
+      expect(() =>
+        emitRustCrate('emit-bench', {
+          options: {emitCrate: false, emitBench: sampleFile},
+        })
+      ).toThrow(/--emit-crate/);
+    }, 120000);
+
+    it('lex coverage of a corpus', () => {
+      const crateDir = emitRustCrate('lex-coverage-repl', {
+        options: {emitRepl: true},
+      });
+
+      shelljs.cp(
+        path.join(rustCalcDir, 'Cargo.lock'),
+        path.join(crateDir, 'Cargo.lock')
+      );
+
+      // The `*` is never matched, and the tokens before an error count.
+      shelljs.mkdir('-p', path.join(crateDir, 'corpus', 'nested'));
+      fs.writeFileSync(path.join(crateDir, 'corpus', 'a.calc'), '2 + 2\n');
+      fs.writeFileSync(path.join(crateDir, 'corpus', 'b.calc'), '2 +\n');
+      fs.writeFileSync(
+        path.join(crateDir, 'corpus', 'nested', 'c.calc'),
+        '(1 + 2)\n'
+      );
+
+      const coverageResult = execCargo(
+        'cargo run --quiet -- --lex-coverage corpus',
+        crateDir
+      );
+
+      if (coverageResult.code !== 0) {
+        console.error(coverageResult.stdout, coverageResult.stderr);
+      }
+
+      expect(coverageResult.code).toEqual(0);
+      expect(coverageResult.stdout).toEqual(
+        'corpus/b.calc: Unexpected end of input; expected one of: NUMBER, (.\n' +
+          '    0        8  ^\\s+\n' +
+          '    1        5  ^\\d+\n' +
+          '    2        3  ^\\+\n' +
+          '    3        0  ^\\*\n' +
+          '    4        1  ^\\(\n' +
+          '    5        1  ^\\)\n' +
+          '\n' +
+          '3 files, 1 of 6 lex rules are never matched.\n' +
+          '  ^\\*\n'
+      );
+    }, 60000);
+
+    it('standalone tokenizer', () => {
+      const RustTokenizerGenerator = require('../plugins/rust/rust-tokenizer-generator')
+        .default;
+
+      const crateDir = path.join(
+        os.tmpdir(),
+        'syntax-rust-fixtures',
+        'standalone-tokenizer'
+      );
+      const outputFile = path.join(crateDir, 'src', 'lib.rs');
+      shelljs.rm('-rf', crateDir);
+      shelljs.mkdir('-p', path.join(crateDir, 'src'));
+
+      new RustTokenizerGenerator({
+        grammar: Grammar.fromGrammarFile(
+          path.join(rustFixturesDir, 'calc.g'),
+          {mode: GRAMMAR_MODE.LALR1}
+        ),
+        outputFile,
+        options: {emitCrate: true},
+      }).generate();
+
+      // No parser code, only the tokenizer.
+      const tokenizerCode = fs.readFileSync(outputFile, 'utf-8');
+      expect(tokenizerCode).toContain('pub struct Tokenizer');
+      expect(tokenizerCode).not.toContain('struct Parser');
+      expect(tokenizerCode).not.toContain('TABLE');
+
+      fs.appendFileSync(outputFile, '\n#[cfg(test)]\nmod tests;\n', 'utf-8');
+      shelljs.cp(
+        path.join(rustFixturesDir, 'standalone-tokenizer.rs'),
+        path.join(crateDir, 'src', 'tests.rs')
+      );
+      shelljs.cp(
+        path.join(rustCalcDir, 'Cargo.lock'),
+        path.join(crateDir, 'Cargo.lock')
+      );
+
+      const testResult = execCargo('cargo test --quiet', crateDir);
+
+      if (testResult.code !== 0) {
+        console.error(testResult.stdout, testResult.stderr);
+      }
+
+      expect(testResult.code).toEqual(0);
+    }, 60000);
+
+    it('standalone tokenizer of a lexical grammar', () => {
+      const RustTokenizerGenerator = require('../plugins/rust/rust-tokenizer-generator')
+        .default;
+
+      const tokenizerCode = new RustTokenizerGenerator({
+        grammar: Grammar.fromData({
+          lex: {
+            rules: [
+              ['\\s+', '/* skip whitespace */ return "";'],
+              ['\\d+', 'return "NUMBER";'],
+              ['\\w+', 'return "ID";'],
+            ],
+          },
+          bnf: {},
+        }),
+        outputFile: null,
+        options: {},
+      }).generate();
+
+      // Token types are inferred from the lex rule handlers.
+      expect(tokenizerCode).toContain(
+        'hashmap! { "NUMBER" => 0, "ID" => 1, "$" => 2 }'
+      );
+      expect(tokenizerCode).toContain('const EOF_KIND: i32 = 2;');
+    });
+
+    it('parse events', () => {
+      expect(runRustFixture('parse-events', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('productions', () => {
+      expect(runRustFixture('productions', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('runtime actions', () => {
+      expect(runRustFixture('runtime-actions', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('parser stack', () => {
+      expect(runRustFixture('parser-stack', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('capacity hint', () => {
+      expect(runRustFixture('capacity-hint', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('resource limits', () => {
+      expect(runRustFixture('resource-limits', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('parser trait', () => {
+      expect(
+        runRustFixture('parser-trait', {
+          grammar: 'calc',
+          options: {parserTrait: 'ParseDsl:parse,parse_all'},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('invalid parser trait', () => {
+      expect(() =>
+        runRustFixture('parser-trait', {
+          grammar: 'calc',
+          options: {parserTrait: 'ParseDsl:parse,eval'},
+        })
+      ).toThrow('Unknown parser trait method');
+
+      expect(() =>
+        runRustFixture('parser-trait', {
+          grammar: 'calc',
+          options: {parserTrait: 'Parse Dsl'},
+        })
+      ).toThrow('Invalid parser trait name');
+    });
+
+    it('prime tokens', () => {
+      expect(runRustFixture('prime-tokens').code).toEqual(0);
+    }, 60000);
+
+    it('token filter', () => {
+      expect(runRustFixture('token-filter').code).toEqual(0);
+    }, 60000);
+
+    it('parameterized rules', () => {
+      expect(runRustFixture('templates').code).toEqual(0);
+    }, 60000);
+
+    it('docs from the grammar', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const code = new LRParserGeneratorRust({
+        grammar: Grammar.fromGrammarFile(
+          path.join(rustFixturesDir, 'docs.g'),
+          {mode: GRAMMAR_MODE.LALR1}
+        ),
+        outputFile: null,
+        options: {},
+      }).generate();
+
+      expect(code).toContain(
+        '/// Parser of the sums of numbers: `1 + 2 + 3`.\n' +
+        '///\n' +
+        '/// The grammar docs are on the `Parser`.\n' +
+        '///\n' +
+        '/**\n' +
+        ' * Parser: the parsing state'
+      );
+
+      expect(code).toContain('    /// A decimal number.\n    NUMBER = ');
+      expect(code).toContain('    /// The `+` operator.\n    PLUS = ');
+      expect(code).toContain('/// A sum of the numbers.\nfn _handler');
+
+      expect(runRustFixture('docs').code).toEqual(0);
+    }, 60000);
+
+    it('sync tokens recovery', () => {
+      expect(runRustFixture('sync-recovery').code).toEqual(0);
+    }, 60000);
+
+    it('error messages', () => {
+      expect(runRustFixture('error-messages').code).toEqual(0);
+    }, 60000);
+
+    it('target actions', () => {
+      expect(runRustFixture('target-actions').code).toEqual(0);
+    }, 60000);
+
+    it('const tables', () => {
+      expect(
+        runRustFixture('const-tables', {
+          grammar: 'calc',
+          options: {constTables: true},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('statics', () => {
+      ['lazy_static', 'once_cell', 'std'].forEach(statics => {
+        expect(
+          runRustFixture('statics', {grammar: 'calc', options: {statics}}).code
+        ).toEqual(0);
+      });
+    }, 180000);
+
+    it('threads', () => {
+      expect(runRustFixture('threads', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('end of input', () => {
+      expect(runRustFixture('eof').code).toEqual(0);
+    }, 60000);
+
+    it('end of input new line', () => {
+      expect(
+        runRustFixture('eof-newline', {options: {appendEofNewline: true}})
+          .code
+      ).toEqual(0);
+    }, 60000);
+
+    it('borrowed input', () => {
+      expect(runRustFixture('borrowed-input').code).toEqual(0);
+    }, 60000);
+
+    it('token can not outlive input', () => {
+      const result = runRustFixture('token-outlives-input', {
+        grammar: 'borrowed-input',
+        compileFail: true,
+      });
+
+      expect(result.code).not.toEqual(0);
+      expect(result.stderr).toContain('`input` does not live long enough');
+    }, 60000);
+
+    it('ast can not outlive input', () => {
+      const result = runRustFixture('ast-outlives-input', {
+        grammar: 'borrowed-input',
+        compileFail: true,
+      });
+
+      expect(result.code).not.toEqual(0);
+      expect(result.stderr).toContain(
+        'cannot return value referencing local variable `input`'
+      );
+    }, 60000);
+
+    it('file result can not outlive contents', () => {
+      const result = runRustFixture('file-result-outlives-contents', {
+        grammar: 'borrowed-input',
+        compileFail: true,
+      });
+
+      expect(result.code).not.toEqual(0);
+      expect(result.stderr).toContain(
+        'cannot return value referencing local variable `contents`'
+      );
+    }, 60000);
+
+    it('indentation', () => {
+      expect(runRustFixture('indentation').code).toEqual(0);
+    }, 60000);
+
+    it('eof handler', () => {
+      expect(runRustFixture('eof-handler').code).toEqual(0);
+    }, 60000);
+
+    it('error formatter', () => {
+      expect(
+        runRustFixture('error-formatter', {grammar: 'calc'}).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('long lines', () => {
+      expect(runRustFixture('long-lines', {grammar: 'calc'}).code).toEqual(0);
+    }, 60000);
+
+    it('regex syntax', () => {
+      expect(
+        runRustFixture('regex-syntax', {options: {regexSyntax: 'perl_ng'}})
+          .code
+      ).toEqual(0);
+
+      const parserCode = fs.readFileSync(
+        path.join(
+          os.tmpdir(),
+          'syntax-rust-fixtures',
+          'regex-syntax',
+          'src',
+          'lib.rs'
+        ),
+        'utf-8'
+      );
+      expect(parserCode).toContain('Syntax::perl_ng()');
+    }, 60000);
+
+    it('unknown regex syntax', () => {
+      expect(() =>
+        runRustFixture('regex-syntax', {options: {regexSyntax: 'pcre'}})
+      ).toThrow('Unknown regex syntax');
+    });
+
+    it('stable api', () => {
+      expect(
+        runRustFixture('stable-api', {
+          grammar: 'calc',
+          options: {stableApi: true},
+        }).code
+      ).toEqual(0);
+
+      const parserCode = fs.readFileSync(
+        path.join(
+          os.tmpdir(),
+          'syntax-rust-fixtures',
+          'stable-api',
+          'src',
+          'lib.rs'
+        ),
+        'utf-8'
+      );
+      expect(parserCode).toContain('#[non_exhaustive]\npub enum ParseEvent');
+      expect(parserCode).toContain('#[non_exhaustive]\npub enum IndentPolicy');
+    }, 60000);
+
+    it('node attributes', () => {
+      expect(
+        runRustFixture('node-attributes', {
+          grammar: 'borrowed-input',
+          options: {nodeAttributes: '#[derive(Debug, Clone, PartialEq)]'},
+        }).code
+      ).toEqual(0);
+
+      const parserCode = fs.readFileSync(
+        path.join(
+          os.tmpdir(),
+          'syntax-rust-fixtures',
+          'node-attributes',
+          'src',
+          'lib.rs'
+        ),
+        'utf-8'
+      );
+      expect(parserCode).toContain(
+        "#[derive(Debug, Clone, PartialEq, Copy, Default)]\npub struct Token<'t>"
+      );
+      expect(parserCode).toContain(
+        '#[derive(Debug, Clone, PartialEq, Copy, Default)]\npub struct Loc'
+      );
+    }, 60000);
+
+    it('node attributes without debug', () => {
+      expect(
+        runRustFixture('borrowed-input', {
+          options: {nodeAttributes: '#[derive(Clone)]'},
+        }).code
+      ).toEqual(0);
+
+      const parserCode = fs.readFileSync(
+        path.join(
+          os.tmpdir(),
+          'syntax-rust-fixtures',
+          'borrowed-input',
+          'src',
+          'lib.rs'
+        ),
+        'utf-8'
+      );
+      expect(parserCode).toContain(
+        "#[derive(Clone, Debug, Copy, Default)]\npub struct Token<'t>"
+      );
+      expect(parserCode).toContain(
+        '#[derive(Clone, Debug, Copy, Default, PartialEq)]\npub struct Loc'
+      );
+    }, 60000);
+
+    it('invalid node attributes', () => {
+      expect(() =>
+        runRustFixture('node-attributes', {
+          grammar: 'borrowed-input',
+          options: {nodeAttributes: 'derive(PartialEq)'},
+        })
+      ).toThrow('Invalid node attributes');
+    });
+
+    it('ignore directive', () => {
+      expect(runRustFixture('ignore').code).toEqual(0);
+    }, 60000);
+
+    it('lex alternation', () => {
+      expect(
+        runRustFixture('lex-alternation', {options: {lexAlternation: true}})
+          .code
+      ).toEqual(0);
+    }, 60000);
+
+    it('keyword table', () => {
+      expect(runRustFixture('keyword-table').code).toEqual(0);
+      expect(
+        runRustFixture('keyword-table', {options: {keywordTable: false}}).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('lex coverage', () => {
+      expect(
+        runRustFixture('lex-coverage', {grammar: 'keyword-table'}).code
+      ).toEqual(0);
+      expect(
+        runRustFixture('lex-coverage', {
+          grammar: 'keyword-table',
+          options: {keywordTable: false},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('offset to line and column', () => {
+      expect(runRustFixture('offset-to-line-col').code).toEqual(0);
+    }, 60000);
+
+    it('last token kind', () => {
+      expect(runRustFixture('last-token-kind').code).toEqual(0);
+    }, 60000);
+
+    it('context of a parse', () => {
+      expect(
+        runRustFixture('context', {
+          grammar: 'context',
+          options: {contextType: 'Diagnostics'},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('line start in lex handlers', () => {
+      expect(runRustFixture('line-start').code).toEqual(0);
+    }, 60000);
+
+    it('partial tokenize', () => {
+      expect(
+        runRustFixture('partial-tokenize', {grammar: 'partial-tokenize'}).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('empty tokens', () => {
+      expect(runRustFixture('empty-tokens').code).toEqual(0);
+    }, 60000);
+
+    it('composed grammars', () => {
+      // A parser in a module has no `#[macro_use]` of `lazy_static`.
+      expect(
+        runRustFixture('template', {
+          modules: {expr: {grammar: 'calc', options: {statics: 'std'}}},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('stable ids', () => {
+      // The calc grammar is the `calc` module, without the subtraction.
+      expect(
+        runRustFixture('stable-ids', {
+          options: {stableIds: true},
+          modules: {
+            calc: {grammar: 'calc', options: {stableIds: true, statics: 'std'}},
+          },
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('token classes', () => {
+      expect(
+        runRustFixture('token-classes', {
+          options: {tokenClasses: 'FN:FUNCTION,DEF'},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('token class of an unknown token', () => {
+      expect(() =>
+        runRustFixture('token-classes', {
+          options: {tokenClasses: 'FUN:FUNCTION,DEF'},
+        })
+      ).toThrow('Unknown token');
+    }, 60000);
+
+    it('async stream', () => {
+      expect(
+        runRustFixture('async-stream', {
+          grammar: 'calc',
+          options: {asyncStream: true},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('lex alternation with unsupported regex syntax', () => {
+      expect(() =>
+        runRustFixture('lex-alternation', {
+          options: {lexAlternation: true, regexSyntax: 'posix_basic'},
+        })
+      ).toThrow('The lex alternation is not supported');
+    });
+
+    it('regex backend', () => {
+      expect(
+        runRustFixture('regex-backend', {
+          grammar: 'calc',
+          options: {regexBackend: 'regex', wasm: true},
+        }).code
+      ).toEqual(0);
+
+      const parserCode = fs.readFileSync(
+        path.join(
+          os.tmpdir(),
+          'syntax-rust-fixtures',
+          'regex-backend',
+          'src',
+          'lib.rs'
+        ),
+        'utf-8'
+      );
+      expect(parserCode).toContain('extern crate regex;');
+      expect(parserCode).not.toContain('onig');
+      expect(parserCode).toContain(
+        '#[cfg(feature = "wasm")]\n#[wasm_bindgen::prelude::wasm_bindgen]'
+      );
+    }, 60000);
+
+    it('lex alternation with regex backend', () => {
+      expect(
+        runRustFixture('lex-alternation', {
+          options: {lexAlternation: true, regexBackend: 'regex'},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('regex syntax with regex backend', () => {
+      expect(() =>
+        runRustFixture('regex-syntax', {
+          options: {regexBackend: 'regex', regexSyntax: 'perl_ng'},
+        })
+      ).toThrow('The regex syntax is supported only by the onig');
+    });
+
+    it('unknown regex backend', () => {
+      expect(() =>
+        runRustFixture('regex-backend', {
+          grammar: 'calc',
+          options: {regexBackend: 'pcre2'},
+        })
+      ).toThrow('Unknown regex backend');
+    });
+
+    it('proc-macro spans', () => {
+      expect(
+        runRustFixture('proc-macro-spans', {
+          grammar: 'calc',
+          options: {procMacroSpans: true},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('normalize tokens', () => {
+      expect(
+        runRustFixture('normalize-tokens', {options: {normalizeTokens: 'ID'}})
+          .code
+      ).toEqual(0);
+    }, 60000);
+
+    it('normalize unknown tokens', () => {
+      expect(() =>
+        runRustFixture('normalize-tokens', {options: {normalizeTokens: 'NAME'}})
+      ).toThrow('Unknown token');
+    });
+
+    it('grapheme columns', () => {
+      expect(
+        runRustFixture('grapheme-columns', {
+          grammar: 'normalize-tokens',
+          options: {graphemeColumns: true},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('utf16 offsets', () => {
+      expect(
+        runRustFixture('utf16-offsets', {
+          grammar: 'normalize-tokens',
+          options: {offsetEncoding: 'utf16'},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('unknown offset encoding', () => {
+      expect(() =>
+        runRustFixture('utf16-offsets', {
+          grammar: 'normalize-tokens',
+          options: {offsetEncoding: 'ucs2'},
+        })
+      ).toThrow('Unknown offset encoding');
+    });
+
+    it('nested parse in actions', () => {
+      expect(runRustFixture('interpolation').code).toEqual(0);
+    }, 60000);
+
+    it('no locations', () => {
+      expect(
+        runRustFixture('no-locations', {
+          grammar: 'calc',
+          options: {locations: false},
+        }).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('code blocks', () => {
+      expect(runRustFixture('code-blocks').code).toEqual(0);
+    }, 60000);
+
+    it('fallback', () => {
+      expect(runRustFixture('fallback').code).toEqual(0);
+    }, 60000);
+
+    it('tab width', () => {
+      expect(
+        runRustFixture('tab-width', {grammar: 'calc', options: {tabWidth: 4}})
+          .code
+      ).toEqual(0);
+    }, 60000);
+
+    it('lookahead', () => {
+      expect(
+        runRustFixture('lookahead', {options: {lookahead: 2}}).code
+      ).toEqual(0);
+    }, 60000);
+
+    it('unresolved conflict without lookahead', () => {
+      expect(() => runRustFixture('lookahead')).toThrow(
+        'Unresolved conflict r3/s7 in state 6'
+      );
+    });
+
+    it('invalid lookahead', () => {
+      expect(() =>
+        runRustFixture('lookahead', {options: {lookahead: 0}})
+      ).toThrow('Invalid lookahead');
+    });
+
+    it('source text of nodes', () => {
+      expect(runRustFixture('source-text').code).toEqual(0);
+    }, 60000);
+
+    it('location out of the production', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const grammar = Grammar.fromString(
+        `%% E : 'a' { || -> i32; $$ = @2.start_offset };`,
+        {mode: GRAMMAR_MODE.LALR1}
+      );
+
+      expect(() =>
+        new LRParserGeneratorRust({
+          grammar,
+          outputFile: null,
+          options: {},
+        }).generate()
+      ).toThrow('is out of the RHS of the production');
+    });
+
+    it('expected conflicts', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const generate = expect =>
+        new LRParserGeneratorRust({
+          grammar: Grammar.fromString(
+            `%expect ${expect}
+             %{ type TResult = i32; %}
+             %% Stmt : 'if' Stmt | 'if' Stmt 'else' Stmt | 'x' { || -> i32; $$ = 1 };`,
+            {mode: GRAMMAR_MODE.LALR1}
+          ),
+          outputFile: null,
+          options: {},
+        }).generate();
+
+      expect(() => generate(1)).not.toThrow();
+      expect(() => generate(0)).toThrow(
+        'Expected 0 shift-reduce conflicts (%expect 0), found 1.'
+      );
+    });
+
+    it('end of input name', () => {
+      expect(runRustFixture('eof-name').code).toEqual(0);
+    }, 60000);
+
+    it('end of input name of another token', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const grammar = Grammar.fromString(`%eof ID\n%%\nE : ID;`, {
+        mode: GRAMMAR_MODE.LALR1,
+      });
+
+      expect(() =>
+        new LRParserGeneratorRust({
+          grammar,
+          outputFile: null,
+          options: {},
+        }).generate()
+      ).toThrow('is the name of another token');
+    });
+
+    it('error recovery', () => {
+      expect(runRustFixture('error-recovery').code).toEqual(0);
+    }, 60000);
+
+    it('owned tokens', () => {
+      expect(runRustFixture('owned-tokens').code).toEqual(0);
+    }, 60000);
+
+    it('state data', () => {
+      expect(runRustFixture('state-data').code).toEqual(0);
+    }, 60000);
+
+    it('string buffer', () => {
+      expect(runRustFixture('string-buffer').code).toEqual(0);
+    }, 60000);
+
+    it('token can not outlive string buffer', () => {
+      const result = runRustFixture('token-outlives-string-buffer', {
+        grammar: 'string-buffer',
+        compileFail: true,
+      });
+
+      expect(result.code).not.toEqual(0);
+      expect(result.stderr).toContain('`strings` does not live long enough');
+    }, 60000);
+
+    it('string buffer can not be cleared in use', () => {
+      const result = runRustFixture('string-buffer-cleared-in-use', {
+        grammar: 'string-buffer',
+        compileFail: true,
+      });
+
+      expect(result.code).not.toEqual(0);
+      expect(result.stderr).toContain(
+        'cannot borrow `strings` as mutable because it is also borrowed as immutable'
+      );
+    }, 60000);
+
+    it('token aliases', () => {
+      expect(runRustFixture('token-aliases').code).toEqual(0);
+    }, 60000);
+
+    it('typed tokens', () => {
+      expect(runRustFixture('typed-tokens').code).toEqual(0);
+    }, 60000);
+
+    it('typed token in generated actions', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const parserCode = new LRParserGeneratorRust({
+        grammar: Grammar.fromGrammarFile(
+          path.join(rustFixturesDir, 'typed-tokens.g'),
+          {mode: GRAMMAR_MODE.LALR1}
+        ),
+        outputFile: null,
+        options: {},
+      }).generate();
+
+      expect(parserCode).toContain(
+        'let mut _1 = match self.parse_token_value::<f64>(_1) ' +
+          '{ Some(value) => value, None => return SV::Undefined };'
+      );
+    });
+
+    it('typed action helpers', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const generate = grammar =>
+        new LRParserGeneratorRust({
+          grammar,
+          outputFile: null,
+          options: {},
+        }).generate();
+
+      // The types of the actions, and of the typed tokens.
+      const typedTokensCode = generate(
+        Grammar.fromGrammarFile(path.join(rustFixturesDir, 'typed-tokens.g'), {
+          mode: GRAMMAR_MODE.LALR1,
+        })
+      );
+
+      expect(typedTokensCode).toContain(
+        "/// Expr -> Expr '+' Expr\n" +
+          'fn _action1(&mut self, mut _1: f64, mut _3: f64) -> f64 {'
+      );
+      expect(typedTokensCode).toContain('SV::_1(self._action1(_1, _3))');
+      expect(typedTokensCode).toContain(
+        'fn _action5(&mut self, mut _2: f64) -> f64 {'
+      );
+
+      // Tokens borrow from the parsing string.
+      const tokensCode = generate(
+        Grammar.fromString(
+          `%{ type TResult<'t> = Vec<Token<'t>>; %}
+           %% L : L 'a' { |$1: Vec<Token>, $2: Token| -> Vec<Token>; $1.push($2); $$ = $1 }
+                | 'a'   { |$1: Token| -> Vec<Token>; $$ = vec![$1] };`,
+          {mode: GRAMMAR_MODE.LALR1}
+        )
+      );
+
+      expect(tokensCode).toContain(
+        "fn _action1(&mut self, mut _1: Vec<Token<'t>>, mut _2: Token<'t>) " +
+          "-> Vec<Token<'t>> {"
+      );
+      expect(tokensCode).toContain(
+        "fn _action2(&mut self, mut _1: Token<'t>) -> Vec<Token<'t>> {"
+      );
+    });
+
+    it('grammar hash', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const grammarHash = source => {
+        const code = new LRParserGeneratorRust({
+          grammar: Grammar.fromString(source, {mode: GRAMMAR_MODE.LALR1}),
+          outputFile: null,
+          options: {},
+        }).generate();
+
+        const hash = code.match(/pub const GRAMMAR_HASH: &str = "(\w+)";/)[1];
+
+        // The same hash in the header comment.
+        expect(code).toContain(`// ${hash}\n`);
+        return hash;
+      };
+
+      const source = `%{ type TResult = i32; %}
+        %% E : E '+' 'n' { |$1: i32| -> i32; $$ = $1 + 1 } | 'n' { $$ = 1 };`;
+
+      const hash = grammarHash(source);
+
+      // The sha256 of the grammar source.
+      expect(hash).toEqual(
+        crypto.createHash('sha256').update(source).digest('hex')
+      );
+
+      // A changed action, or a comment is a different grammar.
+      expect(grammarHash(source.replace('$1 + 1', '$1 + 2'))).not.toEqual(
+        hash
+      );
+      expect(grammarHash(`// E -> E + n\n${source}`)).not.toEqual(hash);
+    });
+
+    it('only rust blocks of target actions', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const code = new LRParserGeneratorRust({
+        grammar: Grammar.fromGrammarFile(
+          path.join(rustFixturesDir, 'target-actions.g'),
+          {mode: GRAMMAR_MODE.LALR1}
+        ),
+        outputFile: null,
+        options: {},
+      }).generate();
+
+      expect(code).toContain('yytext.parse::<i32>().unwrap()');
+      expect(code).not.toContain('Number(yytext)');
+      expect(code).not.toContain('{js:');
+      expect(code).not.toContain('{rust:');
+    });
+
+    it('const tables without lazy_static', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const generate = options =>
+        new LRParserGeneratorRust({
+          grammar: Grammar.fromGrammarFile(
+            path.join(rustFixturesDir, 'calc.g'),
+            {mode: GRAMMAR_MODE.LALR1}
+          ),
+          outputFile: null,
+          options,
+        }).generate();
+
+      expect(generate({})).toContain('lazy_static');
+
+      const code = generate({constTables: true});
+
+      expect(code).not.toContain('lazy_static');
+      expect(code).not.toContain('once_cell');
+      expect(code).toContain('pub const fn token_kind');
+      expect(code).toContain('const fn table_entry');
+    });
+
+    it('statics without lazy_static', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const generate = options =>
+        new LRParserGeneratorRust({
+          grammar: Grammar.fromGrammarFile(
+            path.join(rustFixturesDir, 'calc.g'),
+            {mode: GRAMMAR_MODE.LALR1}
+          ),
+          outputFile: null,
+          options,
+        }).generate();
+
+      const onceCell = generate({statics: 'once_cell'});
+
+      expect(onceCell).not.toContain('lazy_static');
+      expect(onceCell).toContain('::once_cell::sync::Lazy::force(&TOKENS_MAP);');
+
+      const std = generate({statics: 'std'});
+
+      expect(std).not.toContain('lazy_static');
+      expect(std).not.toContain('once_cell');
+      expect(std).toContain('::std::sync::LazyLock::force(&REGEX_RULES);');
+
+      expect(() => generate({statics: 'thread_local'})).toThrow(
+        /Unknown statics/
+      );
+    });
+
+    it('width of the tables', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const generate = grammarFile =>
+        new LRParserGeneratorRust({
+          grammar: Grammar.fromGrammarFile(
+            path.join(rustFixturesDir, grammarFile),
+            {mode: GRAMMAR_MODE.LALR1}
+          ),
+          outputFile: null,
+          options: {},
+        }).generate();
+
+      const code = generate('calc.g');
+
+      expect(code).toContain('type EncodedSymbol = u8;');
+      expect(code).toContain('static TABLE_BASE: [u8; ');
+      expect(code).toContain('static TABLE_CHECK: [u8; ');
+
+      expect(generate('wide-kinds.g')).toContain('type EncodedSymbol = u16;');
+    });
+
+    it('wide kinds', () => {
+      expect(runRustFixture('wide-kinds').code).toEqual(0);
+    }, 60000);
+
+    it('iterative lists', () => {
+      expect(
+        runRustFixture('iterative-lists', {options: {iterativeLists: true}})
+          .code
+      ).toEqual(0);
+    }, 60000);
+
+    it('iterative list of items without a type', () => {
+      const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+        .default;
+
+      const grammar = Grammar.fromString(
+        `%% Pairs : Pair Pairs | Pair; Pair : 'a' 'b';`,
+        {mode: GRAMMAR_MODE.LALR1}
+      );
+
+      expect(
+        () =>
+          new LRParserGeneratorRust({
+            grammar,
+            outputFile: null,
+            options: {iterativeLists: true},
+          })
+      ).toThrow('Unknown type of the "Pair" items of the "Pairs" list');
+    });
+
+    describe('conformance', () => {
+      rustConformanceCases.forEach(conformanceCase => {
+        const {name, mode = GRAMMAR_MODE.LALR1} = conformanceCase;
+
+        it(`${name} (${mode})`, () => {
+          const expected = fs.readFileSync(
+            path.join(rustConformanceDir, `${name}.expected`),
+            'utf-8'
+          );
+
+          expect(runRustConformance(conformanceCase)).toEqual(expected);
+        }, 60000);
+      });
+    });
   });
 } else {
   describe('rust plugin mock', () => {
@@ -0,0 +1,141 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import SarifReport from '../sarif-report';
+import Grammar from '../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../grammar/grammar-mode';
+import LRParser from '../lr/lr-parser';
+
+const grammar = Grammar.fromGrammarFile(
+  __dirname + '/../grammar/__tests__/calc.g',
+  {mode: GRAMMAR_MODE.LALR1}
+);
+
+function parseError(string) {
+  try {
+    new LRParser({grammar}).parse(string);
+  } catch (error) {
+    return error;
+  }
+  return null;
+}
+
+function report(string, uri = 'input.calc') {
+  return SarifReport.create({error: parseError(string), string, uri});
+}
+
+/**
+ * Checks the required properties, and the value constraints of the
+ * SARIF 2.1.0 schema for the parts used in the reports.
+ */
+function expectValidSarif(sarif) {
+  expect(sarif.version).toBe('2.1.0');
+  expect(sarif.$schema).toMatch(/sarif-schema-2\.1\.0\.json$/);
+  expect(sarif.runs.length).toBeGreaterThan(0);
+
+  sarif.runs.forEach(run => {
+    expect(typeof run.tool.driver.name).toBe('string');
+    expect(['utf16CodeUnits', 'unicodeCodePoints']).toContain(run.columnKind);
+
+    run.results.forEach(result => {
+      expect(typeof result.message.text).toBe('string');
+      expect(run.tool.driver.rules[result.ruleIndex].id).toBe(result.ruleId);
+      expect(['none', 'note', 'warning', 'error']).toContain(result.level);
+
+      (result.locations || []).forEach(({physicalLocation}) => {
+        const {region} = physicalLocation;
+
+        ['startLine', 'startColumn', 'endLine', 'endColumn'].forEach(key => {
+          expect(Number.isInteger(region[key])).toBe(true);
+          expect(region[key]).toBeGreaterThanOrEqual(1);
+        });
+
+        ['charOffset', 'charLength', 'byteOffset', 'byteLength'].forEach(
+          key => {
+            expect(Number.isInteger(region[key])).toBe(true);
+            expect(region[key]).toBeGreaterThanOrEqual(0);
+          }
+        );
+      });
+    });
+  });
+}
+
+describe('sarif report', () => {
+  it('unexpected token', () => {
+    const sarif = report('2 +\n3 & 4');
+    expectValidSarif(sarif);
+
+    const [result] = sarif.runs[0].results;
+
+    expect(result.ruleId).toBe('syntax-error');
+    expect(result.message.text).toMatch(/Unexpected token: "&" at 2:2\.$/);
+    expect(result.locations).toEqual([
+      {
+        physicalLocation: {
+          artifactLocation: {uri: 'input.calc'},
+          region: {
+            startLine: 2,
+            startColumn: 3,
+            endLine: 2,
+            endColumn: 4,
+            charOffset: 6,
+            charLength: 1,
+            byteOffset: 6,
+            byteLength: 1,
+          },
+        },
+      },
+    ]);
+  });
+
+  it('byte span of multibyte characters', () => {
+    const sarif = report('2 + é');
+    expectValidSarif(sarif);
+
+    const {region} = sarif.runs[0].results[0].locations[0].physicalLocation;
+
+    expect(region.charOffset).toBe(4);
+    expect(region.charLength).toBe(1);
+    expect(region.byteOffset).toBe(4);
+    expect(region.byteLength).toBe(2);
+  });
+
+  it('unexpected end of input', () => {
+    const sarif = report('2 +\n3 *');
+    expectValidSarif(sarif);
+
+    const [result] = sarif.runs[0].results;
+
+    expect(result.message.text).toBe('Unexpected end of input.');
+    expect(result.locations[0].physicalLocation.region).toEqual({
+      startLine: 2,
+      startColumn: 4,
+      endLine: 2,
+      endColumn: 4,
+      charOffset: 7,
+      charLength: 0,
+      byteOffset: 7,
+      byteLength: 0,
+    });
+  });
+
+  it('no results for a parsed string', () => {
+    const sarif = report('2 + 3');
+    expectValidSarif(sarif);
+
+    expect(sarif.runs[0].results).toEqual([]);
+  });
+
+  it('error without location', () => {
+    const sarif = SarifReport.create({
+      error: new SyntaxError('Grammar has conflicts.'),
+      string: '',
+    });
+    expectValidSarif(sarif);
+
+    expect(sarif.runs[0].results[0].locations).toBeUndefined();
+  });
+});
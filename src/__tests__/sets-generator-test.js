@@ -278,4 +278,84 @@ describe('sets-generator', () => {
       });
     });
   });
+
+  describe('nullable', () => {
+    it('all nullable', () => {
+      const grammar = Grammar.fromString(`
+        %%
+        S : A B C;
+        A : 'a' | B C;
+        B : 'b' | /* empty */;
+        C : 'c' | /* empty */;
+      `);
+
+      const setsGenerator = new SetsGenerator({grammar});
+
+      expect(setsGenerator.getNullableSymbols()).toEqual({
+        $accept: true,
+        S: true,
+        A: true,
+        B: true,
+        C: true,
+      });
+
+      const nonNullable = new SetsGenerator({
+        grammar: Grammar.fromString(`
+          %%
+          S : A B;
+          A : 'a' | /* empty */;
+          B : 'b';
+        `),
+      });
+
+      expect(nonNullable.getNullableSymbols()).toEqual({
+        $accept: false,
+        S: false,
+        A: true,
+        B: false,
+      });
+    });
+  });
+
+  describe('print', () => {
+    it('printed sets', () => {
+      const grammar = Grammar.fromString(`
+        %%
+        S : A 'c';
+        A : 'a' | /* empty */;
+      `);
+
+      const setsGenerator = new SetsGenerator({grammar});
+
+      const info = console.info;
+      const output = [];
+      console.info = (...args) => output.push(args.join(' '));
+
+      try {
+        setsGenerator.printSet(setsGenerator.getFirstSets());
+        setsGenerator.printSet(setsGenerator.getFollowSets());
+        setsGenerator.printSet(setsGenerator.getNullableSymbols());
+      } finally {
+        console.info = info;
+      }
+
+      const printed = output.join('\n');
+
+      // A row of a symbol, and its set.
+      const row = (symbol, set) =>
+        expect(printed).toMatch(new RegExp(`\\b${symbol}\\b[^\\n]*${set}`));
+
+      expect(printed).toContain('First set:');
+      row('S', "'a', 'c'");
+      row('A', "'a', ε");
+
+      expect(printed).toContain('Follow set:');
+      row('S', '\\$');
+      row('A', "'c'");
+
+      expect(printed).toContain('Nullable:');
+      row('S', 'no');
+      row('A', 'yes');
+    });
+  });
 });
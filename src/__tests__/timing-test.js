@@ -0,0 +1,108 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import timing from '../timing';
+import Grammar from '../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../grammar/grammar-mode';
+
+const LRParserGeneratorRust = require('../plugins/rust/lr/lr-parser-generator-rust')
+  .default;
+
+const PHASES = [
+  'Grammar loading',
+  'LR states',
+  'First sets',
+  'Follow sets',
+  'Parsing table',
+  'Code emission',
+];
+
+/**
+ * Runs a function with the timing enabled.
+ */
+function withTiming(fn) {
+  global.SYNTAX_TIMING = true;
+  timing.reset();
+
+  try {
+    fn();
+  } finally {
+    global.SYNTAX_TIMING = false;
+    timing.reset();
+  }
+}
+
+/**
+ * Output of the printed table.
+ */
+function printed() {
+  const info = console.info;
+  const output = [];
+
+  console.info = (...args) => output.push(args.join(' '));
+
+  try {
+    timing.print();
+  } finally {
+    console.info = info;
+  }
+
+  return output.join('\n');
+}
+
+describe('timing', () => {
+  it('phases of generation', () => withTiming(() => {
+    // Handlers of other languages are not compiled as JS.
+    global.globalOptions = {output: 'lib.rs'};
+
+    new LRParserGeneratorRust({
+      grammar: Grammar.fromGrammarFile(
+        __dirname + '/rust-fixtures/calc.g',
+        {mode: GRAMMAR_MODE.LALR1}
+      ),
+      outputFile: null,
+      options: {},
+    }).generate();
+
+    const phases = timing.getPhases();
+
+    expect(phases.map(({phase}) => phase).sort()).toEqual(
+      [...PHASES].sort()
+    );
+    phases.forEach(({ms}) => expect(ms).toBeGreaterThanOrEqual(0));
+
+    const output = printed();
+
+    PHASES.concat('Total').forEach(phase => {
+      expect(output).toContain(phase);
+    });
+  }));
+
+  it('nested phases', () => withTiming(() => {
+    timing.start('Parsing table');
+    timing.start('Follow sets');
+    timing.start('Follow sets');
+    timing.end('Follow sets');
+    timing.end('Follow sets');
+    timing.end('Parsing table');
+
+    expect(timing.getPhases().map(({phase}) => phase)).toEqual([
+      'Parsing table',
+      'Follow sets',
+    ]);
+
+    expect(() => timing.end('Parsing table')).toThrow(
+      'Timing: phase "Parsing table" is not started.'
+    );
+  }));
+
+  it('disabled', () => {
+    timing.start('Parsing table');
+    timing.end('Parsing table');
+
+    expect(timing.getPhases()).toEqual([]);
+    expect(printed()).toEqual('');
+  });
+});
@@ -6,6 +6,7 @@
 import CodeUnit from './code-unit';
 import {EOF} from './special-symbols';
 import debug from './debug';
+import timing from './timing';
 
 import fs from 'fs';
 
@@ -142,11 +143,14 @@ export default class BaseParserGenerator {
    * Generates parser code and writes it to disk as a reusable module.
    */
   generate() {
+    timing.start('Code emission');
     this.generateParserData();
     if (!this._outputFile) {
+      timing.end('Code emission');
       return this._resultData;
     }
     fs.writeFileSync(this._outputFile, this._resultData, 'utf-8');
+    timing.end('Code emission');
     debug.timeEnd('Generating parser module');
     try {
       return require(this._outputFile);
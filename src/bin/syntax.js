@@ -56,7 +56,8 @@ const options = require('nomnom')
     },
     sets: {
       abbr: 's',
-      help: 'Generate and output parsing sets (all/first/follow/predict)',
+      help: 'Generate and output parsing sets ' +
+        '(all/first/follow/predict/nullable)',
     },
     parse: {
       abbr: 'p',
@@ -69,6 +70,21 @@ const options = require('nomnom')
       type: 'string',
       metavar: 'FILE',
     },
+    'print-tree': {
+      help: 'Print the parse tree of a parsed string, or file (LR modes)',
+      flag: true,
+    },
+    'grammar-ast': {
+      help: 'Write the grammar data (lex rules, productions, precedence, ' +
+        'and directives) in the JSON format to the file',
+      type: 'string',
+      metavar: 'FILE',
+    },
+    'error-report': {
+      help: 'Write a parse error report in the SARIF format to the file',
+      type: 'string',
+      metavar: 'FILE',
+    },
     output: {
       abbr: 'o',
       help: 'Output file for a generated parser module',
@@ -82,7 +98,8 @@ const options = require('nomnom')
       metavar: 'FILE',
     },
     'tokenizer-only': {
-      help: 'Whether to generate only standalone tokenizer output file',
+      help: 'Whether to generate only standalone tokenizer output file ' +
+        '(Rust)',
       flag: true,
     },
     tokenize: {
@@ -107,11 +124,21 @@ const options = require('nomnom')
       help: 'Capture token locations (offsets, line and column numbers)',
       flag: true,
     },
+    features: {
+      help: 'Comma-separated features, productions tagged with ' +
+        '%feature of other features are excluded',
+      type: 'string',
+      metavar: 'LIST',
+    },
     'case-insensitive': {
       help: 'Sets case-insensitive mode to lexical grammar',
       abbr: 'i',
       flag: true,
     },
+    timing: {
+      help: 'Print the time of the generation phases',
+      flag: true,
+    },
     debug: {
       help: 'Debug mode (outputs steps and timing)',
       abbr: 'd',
@@ -125,6 +152,136 @@ const options = require('nomnom')
       help: 'Append a wrapping namespace to generated code',
       type: 'string',
     },
+    'append-eof-newline': {
+      help: 'Tokenize input as if it ends with a new line (Rust)',
+      flag: true,
+    },
+    'actions-module': {
+      help: 'Module with functions called from semantic actions (Rust)',
+      metavar: 'MODULE',
+    },
+    'async-stream': {
+      help: 'Generate async parse_stream of a token stream for the ' +
+        '`futures` feature (Rust)',
+      flag: true,
+    },
+    lookahead: {
+      help: 'Lookahead tokens to resolve conflicts in generated parser (Rust)',
+      metavar: 'K',
+    },
+    locations: {
+      help: 'Track lines and columns in generated tokenizer, ' +
+        '--no-locations for offsets only (Rust)',
+      flag: true,
+      default: true,
+    },
+    'const-tables': {
+      help: 'Generate tables with const fn accessors, without ' +
+        'lazy_static (Rust)',
+      flag: true,
+    },
+    'context-type': {
+      help: 'Type of a context of the caller in semantic actions, ' +
+        'and lex handlers, passed to parse_with (Rust)',
+      metavar: 'TYPE',
+    },
+    'emit-bench': {
+      help: 'Generate a criterion benchmark of parsing a sample file into ' +
+        'benches of the crate, with --emit-crate (Rust)',
+      metavar: 'FILE',
+    },
+    'emit-crate': {
+      help: 'Generate a crate into the output directory, ' +
+        'with the parser in src/lib.rs (Rust)',
+      flag: true,
+    },
+    'emit-repl': {
+      help: 'Generate a REPL binary, which parses lines of the stdin, ' +
+        'into the crate of --emit-crate (Rust)',
+      flag: true,
+    },
+    'emit-test': {
+      help: 'Generate a test which parses a sample input (Rust)',
+      metavar: 'INPUT',
+    },
+    'grapheme-columns': {
+      help: 'Count columns in grapheme clusters with the ' +
+        'unicode-segmentation feature (Rust)',
+      flag: true,
+    },
+    'iterative-lists': {
+      help: 'Parse right-recursive lists without actions iteratively (Rust)',
+      flag: true,
+    },
+    'keyword-table': {
+      help: 'Match keyword rules with the identifier rule, and a lookup ' +
+        'of its text, --no-keyword-table to match them one by one (Rust)',
+      flag: true,
+      default: true,
+    },
+    'lex-alternation': {
+      help: 'Match lex rules of a state with one combined regex (Rust)',
+      flag: true,
+    },
+    'node-attributes': {
+      help: 'Attributes of the node types of the parse results (Token, ' +
+        'OwnedToken, Loc), default #[derive(Debug, Clone)] (Rust)',
+      metavar: 'ATTRIBUTES',
+    },
+    'normalize-tokens': {
+      help: 'Token types normalized to NFC in generated tokenizer, ' +
+        'e.g. ID,NAME (Rust)',
+      metavar: 'TOKENS',
+    },
+    'offset-encoding': {
+      help: 'Units of offsets, and columns in generated tokenizer: ' +
+        'utf8, or utf16 (Rust)',
+      metavar: 'ENCODING',
+    },
+    'parser-trait': {
+      help: 'Generate a trait of the parse methods implemented by ' +
+        'the parser, e.g. ParseDsl, or ParseDsl:parse,parse_file (Rust)',
+      metavar: 'NAME[:METHODS]',
+    },
+    'proc-macro-spans': {
+      help: 'Convert locations to proc-macro2 spans of a string literal (Rust)',
+      flag: true,
+    },
+    'regex-backend': {
+      help: 'Regex crate of generated tokenizer: onig, or regex (Rust)',
+      metavar: 'BACKEND',
+    },
+    'regex-syntax': {
+      help: 'Regex syntax of lex rules in generated tokenizer (Rust)',
+      metavar: 'SYNTAX',
+    },
+    'stable-api': {
+      help: 'Mark public enums in generated code as non-exhaustive (Rust)',
+      flag: true,
+    },
+    'stable-ids': {
+      help: 'Generate stable production and state IDs derived from their ' +
+        'contents (Rust)',
+      flag: true,
+    },
+    statics: {
+      help: 'Lazily initialized statics of generated code: lazy_static, ' +
+        'once_cell, or std (Rust)',
+      metavar: 'STATICS',
+    },
+    'tab-width': {
+      help: 'Tab width for column numbers in generated tokenizer (Rust)',
+      metavar: 'WIDTH',
+    },
+    'token-classes': {
+      help: 'Classes of lexer tokens parsed as one grammar token, ' +
+        'e.g. FN:FUNCTION,DEF;ID:NAME (Rust)',
+      metavar: 'CLASSES',
+    },
+    wasm: {
+      help: 'Generate wasm-bindgen entry point for the `wasm` feature (Rust)',
+      flag: true,
+    },
   })
   .parse();
 
@@ -133,12 +290,18 @@ const options = require('nomnom')
  */
 global.SYNTAX_DEBUG = options.debug;
 
+/**
+ * Time of the generation phases.
+ */
+global.SYNTAX_TIMING = options.timing;
+
 // NOTE: all Syntax tool requires go after debug `global.SYNTAX_DEBUG`
 // is set, since debug module uses it at load time.
 
 const GRAMMAR_MODE = require(ROOT + 'grammar/grammar-mode').MODES;
 const Grammar = require(ROOT + 'grammar/grammar').default;
 const debug = require(ROOT + 'debug').default;
+const timing = require(ROOT + 'timing').default;
 
 if (global.SYNTAX_DEBUG) {
   console.info(colors.bold('\nDEBUG mode is: ON\n'));
@@ -151,6 +314,34 @@ const generatorOptions = {
   customTokenizer: options['custom-tokenizer'],
   resolveConflicts: options['resolve-conflicts'],
   namespace: options['namespace'],
+  actionsModule: options['actions-module'],
+  appendEofNewline: options['append-eof-newline'],
+  asyncStream: options['async-stream'],
+  constTables: options['const-tables'],
+  contextType: options['context-type'],
+  emitBench: options['emit-bench'],
+  emitCrate: options['emit-crate'],
+  emitRepl: options['emit-repl'],
+  emitTest: options['emit-test'],
+  graphemeColumns: options['grapheme-columns'],
+  iterativeLists: options['iterative-lists'],
+  keywordTable: options['keyword-table'],
+  lexAlternation: options['lex-alternation'],
+  locations: options['locations'],
+  lookahead: options['lookahead'],
+  nodeAttributes: options['node-attributes'],
+  normalizeTokens: options['normalize-tokens'],
+  offsetEncoding: options['offset-encoding'],
+  parserTrait: options['parser-trait'],
+  procMacroSpans: options['proc-macro-spans'],
+  regexBackend: options['regex-backend'],
+  regexSyntax: options['regex-syntax'],
+  stableApi: options['stable-api'],
+  stableIds: options['stable-ids'],
+  statics: options['statics'],
+  tabWidth: options['tab-width'],
+  tokenClasses: options['token-classes'],
+  wasm: options['wasm'],
 };
 
 /**
@@ -363,7 +554,7 @@ const parsers = {
 
     // Parse a file.
     if (provided('file')) {
-      parse(fs.readFileSync(options.file, 'utf-8'), grammar);
+      parse(fs.readFileSync(options.file, 'utf-8'), grammar, options.file);
     }
 
     // Output information about tokens.
@@ -373,8 +564,15 @@ const parsers = {
 
     // Generate parser module.
     if (options.output) {
-      const outputFile = options.output;
-      const language = path.extname(outputFile).slice(1);
+      let outputFile = options.output;
+      let language = path.extname(outputFile).slice(1);
+
+      // The output is a crate directory, the parser goes to `src/lib.rs`.
+      if (options['emit-crate']) {
+        outputFile = path.join(options.output, 'src', 'lib.rs');
+        language = 'rs';
+        fs.mkdirSync(path.dirname(outputFile), {recursive: true});
+      }
 
       // Generator is language agnostic.
       const GENERATORS = {
@@ -435,7 +633,7 @@ const parsers = {
 
     // Parse a file.
     if (provided('file')) {
-      parse(fs.readFileSync(options.file, 'utf-8'), grammar);
+      parse(fs.readFileSync(options.file, 'utf-8'), grammar, options.file);
     }
 
     // Output information about tokens.
@@ -486,7 +684,7 @@ function showGeneratedSuccessMessage(filePath) {
   );
 }
 
-function parse(string, grammar) {
+function parse(string, grammar, file = null) {
   console.info(`\n${colors.bold('Parsing:')}\n\n${string}\n`);
 
   try {
@@ -505,12 +703,64 @@ function parse(string, grammar) {
         '\n'
       );
     }
+
+    if (parsed.tree) {
+      const ParseTree = require(ROOT + 'parse-tree').default;
+
+      console.info(
+        colors.bold('Parse tree:'),
+        '\n\n' + ParseTree.pretty(parsed.tree),
+        '\n'
+      );
+    }
+
+    writeErrorReport({string, file});
   } catch (e) {
+    writeErrorReport({error: e, string, file});
     console.info(`${colors.red(e.stack)}\n`);
     process.exit(1);
   }
 }
 
+/**
+ * Writes the parse error report in the SARIF format (--error-report),
+ * a report without results if the string is parsed.
+ */
+function writeErrorReport({error = null, string, file}) {
+  if (!options['error-report']) {
+    return;
+  }
+
+  const SarifReport = require(ROOT + 'sarif-report').default;
+  const report = SarifReport.create({error, string, uri: file});
+
+  fs.writeFileSync(
+    options['error-report'],
+    JSON.stringify(report, null, 2) + '\n',
+    'utf-8'
+  );
+}
+
+/**
+ * Writes the grammar data in the JSON format (--grammar-ast), the grammar
+ * is loaded back from it as a JSON grammar.
+ */
+function writeGrammarAst() {
+  const grammar = getGrammar(options.grammar, extractMode(options));
+
+  if (!grammar) {
+    return;
+  }
+
+  fs.writeFileSync(
+    options['grammar-ast'],
+    JSON.stringify(grammar.toData(), null, 2) + '\n',
+    'utf-8'
+  );
+
+  showGeneratedSuccessMessage(options['grammar-ast']);
+}
+
 function lrParse(string, grammar) {
   const LRParser = require(ROOT + 'lr/lr-parser').default;
 
@@ -521,6 +771,7 @@ function lrParse(string, grammar) {
   return new LRParser({
     grammar,
     resolveConflicts: options['resolve-conflicts'],
+    parseTree: options['print-tree'],
   }).parse(string);
 }
 
@@ -572,9 +823,32 @@ function getGrammar(grammarFile, mode) {
      * Whether to capture locations.
      */
     captureLocations: options.loc,
+
+    /**
+     * Enabled features of the `%feature` tags.
+     */
+    features: options.features ? options.features.split(/\s*,\s*/) : [],
   };
 
-  return Grammar.fromData(grammarData, grammarOptions);
+  const grammar = Grammar.fromData(grammarData, grammarOptions);
+  showUnreachableLexRules(grammar.getLexGrammar());
+
+  return grammar;
+}
+
+/**
+ * Warns about the lex rules which are shadowed by earlier rules.
+ */
+function showUnreachableLexRules(lexGrammar) {
+  lexGrammar.getUnreachableRules().forEach(({rule, shadowedBy, state}) => {
+    console.info(
+      colors.yellow(
+        `\nWarning: lex rule "${rule.getOriginalMatcher()}" is unreachable ` +
+          `in the state "${state}", it's shadowed by the earlier rule ` +
+          `"${shadowedBy.getOriginalMatcher()}".`
+      )
+    );
+  });
 }
 
 function getLexGrammarData(options) {
@@ -672,6 +946,10 @@ function handleSets() {
   if (sets.indexOf('predict') !== -1 || sets === 'all') {
     sg.printSet(sg.getPredictSets());
   }
+
+  if (sets.indexOf('nullable') !== -1 || sets === 'all') {
+    sg.printSet(sg.getNullableSymbols());
+  }
 }
 
 function error(message) {
@@ -704,6 +982,46 @@ function handleStandaloneTokenizer() {
   if (options['tokenize']) {
     tokenizeFromOptions(options, lexGrammar);
   }
+
+  if (options.output) {
+    generateStandaloneTokenizer(lexGrammarData);
+  }
+}
+
+/**
+ * Generates a standalone tokenizer module (supported for Rust), from
+ * the lexical grammar, or the `lex` part of the --grammar.
+ */
+function generateStandaloneTokenizer(lexGrammarData) {
+  let outputFile = options.output;
+  let language = path.extname(outputFile).slice(1);
+
+  // The output is a crate directory, the tokenizer goes to `src/lib.rs`.
+  if (options['emit-crate']) {
+    outputFile = path.join(options.output, 'src', 'lib.rs');
+    language = 'rs';
+    fs.mkdirSync(path.dirname(outputFile), {recursive: true});
+  }
+
+  if (language !== 'rs') {
+    error('\nError: standalone tokenizer is generated only for Rust.\n');
+    return;
+  }
+
+  const grammar = options.grammar
+    ? getGrammar(options.grammar, options.mode)
+    : Grammar.fromData({lex: lexGrammarData, bnf: {}});
+
+  const RustTokenizerGenerator = require(ROOT +
+    'plugins/rust/rust-tokenizer-generator.js').default;
+
+  new RustTokenizerGenerator({
+    grammar,
+    outputFile,
+    options: generatorOptions,
+  }).generate();
+
+  showGeneratedSuccessMessage(options.output);
 }
 
 function provided(option) {
@@ -762,7 +1080,9 @@ function main() {
   // Generating a standalone tokenizer, either from direct --lex
   // parameter, or from the `lex` part of the --grammar parameter.
   if (isTokenizerOnly(options)) {
-    return handleStandaloneTokenizer();
+    handleStandaloneTokenizer();
+    timing.print();
+    return;
   }
 
   // Sets.
@@ -770,7 +1090,13 @@ function main() {
     handleSets();
   }
 
+  // Grammar data for external tools.
+  if (options['grammar-ast']) {
+    writeGrammarAst();
+  }
+
   parsers[extractMode(options)](options);
+  timing.print();
 }
 
 module.exports = main;
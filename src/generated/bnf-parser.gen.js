@@ -75,60 +75,129 @@ const productions = [[-1,1,(_1) => { __ = _1 }],
                         spec.operators = __bnfParserOperators;
                       }
 
+                      if (__bnfParserPreferences.length) {
+                        spec.prefer = __bnfParserPreferences;
+                      }
+
+                      // Synchronization tokens of the error recovery.
+                      if (__bnfParserSync.length) {
+                        spec.sync = __bnfParserSync;
+                      }
+
+                      // %{ %} and %code { } blocks, in order.
+                      if (__bnfParserCode.length) {
+                        spec.moduleInclude = __bnfParserCode.join('\n\n');
+                      }
+
+                      // Value types of tokens: %token <type> NAME.
+                      if (Object.keys(__bnfParserTokenTypes).length) {
+                        spec.tokenTypes = __bnfParserTokenTypes;
+                      }
+
+                      // Display names of tokens: %token NAME "alias".
+                      if (Object.keys(__bnfParserTokenAliases).length) {
+                        spec.tokenAliases = __bnfParserTokenAliases;
+                      }
+
+                      // Parameterized rules: sep_list(Item, Sep) : ...
+                      if (Object.keys(__bnfParserTemplates).length) {
+                        spec.templates = __bnfParserTemplates;
+                      }
+
+                      // Docs of the grammar (//!), tokens, and rules (///).
+                      if (__bnfParserDoc.length) {
+                        spec.doc = __bnfParserDoc.join('\n');
+                      }
+
+                      if (Object.keys(__bnfParserTokenDocs).length) {
+                        spec.tokenDocs = __bnfParserTokenDocs;
+                      }
+
+                      if (Object.keys(__bnfParserRuleDocs).length) {
+                        spec.ruleDocs = __bnfParserRuleDocs;
+                      }
+
                       if (tokens.length) {
                         spec.tokens = __bnfParserTokens.join(' ');
                       }
 
                       __ = spec;
                      }],
-[1,1,(_1) => { __ = _1 }],
+[1,1,(_1) => { __bnfParserDocs = [] }],
 [1,0],
-[2,1,(_1) => { __ = _1 }],
+[2,1,(_1) => { __bnfParserDocs = [] }],
+[2,1],
+[2,2,(_1,_2) => { __bnfParserDocs = [] }],
 [2,2],
 [3,1,(_1) => { __bnfParserExtra.lex = _1 }],
-[3,1,(_1) => { __bnfParserExtra.moduleInclude = _1 }],
+[3,1,(_1) => { __bnfParserDoc.push(_1) }],
+[3,1,(_1) => { __bnfParserCode.push(_1) }],
+[3,3,(_1,_2,_3) => { __bnfParserCode.push(_2.trim()) }],
 [3,2,(_1,_2) => { __bnfParserExtra.start = _2 }],
+[3,2,(_1,_2) => { __bnfParserExtra.eof = _2 }],
+[3,2,(_1,_2) => { __bnfParserExtra.eof = _2.slice(1, -1) }],
+[3,1,(_1) => { __bnfParserExtra.expect = Number(_1) }],
+[3,2,(_1,_2) => { __bnfParserSync.push(..._2) }],
 [3,2,(_1,_2) => { __bnfParserOperators.push(['left'].concat(_2)) }],
 [3,2,(_1,_2) => { __bnfParserOperators.push(['right'].concat(_2)) }],
 [3,2,(_1,_2) => { __bnfParserOperators.push(['nonassoc'].concat(_2)) }],
-[3,2,(_1,_2) => { __bnfParserTokens.push(..._2) }],
-[4,1,(_1) => { __ = [_1] }],
-[4,2,(_1,_2) => { __ = _1; _1.push(_2) }],
-[5,2,(_1,_2) => { __ = _1; __[_2[0]] = _2[1] }],
-[5,1,(_1) => { __ = {}; __[_1[0]] = _1[1] }],
-[6,4,(_1,_2,_3,_4) => { __ = [_1, _3] }],
-[7,1,(_1) => { __ = _1 }],
-[8,3,(_1,_2,_3) => { __ = _1; _1.push(_3) }],
-[8,1,(_1) => { __ = [_1] }],
-[9,2,(_1,_2) => { __ = [_1[0], _2]; _1[1] && __.push({prec: _1[1]}) }],
-[10,2,(_1,_2) => { __ = [_1, _2] }],
-[10,0,() => { __ = '' }],
-[11,2,(_1,_2) => { __ = _2 }],
-[11,0],
-[12,2,(_1,_2) => { __ = _1 + ' ' + _2 }],
+[3,2,(_1,_2) => { __bnfParserPreferences.push(['shift'].concat(_2)) }],
+[3,2,(_1,_2) => { __bnfParserPreferences.push(['reduce'].concat(_2)) }],
+[3,2,(_1,_2) => { __bnfParserTokens.push(...__bnfParserTakeDocs(__bnfParserTokenDocs, __bnfParserDeclareTokens(_2))) }],
+[3,3,(_1,_2,_3) => { __bnfParserTakeDocs(__bnfParserTokenDocs, __bnfParserDeclareTokens(_3)).forEach(token => __bnfParserTokenTypes[token] = _2) }],
+[4,1,(_1) => { __bnfParserDocs.push(_1) }],
+[5,2,(_1,_2) => { __ = _1 + _2 }],
+[5,0,() => { __ = '' }],
+[6,1,(_1) => { __ = [_1] }],
+[6,2,(_1,_2) => { __ = _1; _1.push(_2) }],
+[7,2,(_1,_2) => { __ = _1; _2 && (__[_2[0]] = _2[1]) }],
+[7,1,(_1) => { __ = {}; _1 && (__[_1[0]] = _1[1]) }],
+[7,2,(_1,_2) => { __ = _1 }],
+[7,1,(_1) => { __ = {} }],
+[8,4,(_1,_2,_3,_4) => { __bnfParserTakeDocs(__bnfParserRuleDocs, [_1]); __ = [_1, _3] }],
+[8,4,(_1,_2,_3,_4) => { __bnfParserDocs = []; __bnfParserTemplates[_1] = _3; __ = null }],
+[9,1,(_1) => { __ = _1 }],
+[10,3,(_1,_2,_3) => { __ = _1 + '(' + _2.join(',') + ')' }],
+[11,3,(_1,_2,_3) => { __ = _1; _1.push(_3) }],
+[11,1,(_1) => { __ = [_1] }],
+[12,1,(_1) => { __ = _1 }],
+[12,1,(_1) => { __ = _1 }],
 [12,1,(_1) => { __ = _1 }],
-[13,1,(_1) => { __ = _1 }],
-[13,1,(_1) => { __ = _1 }],
-[13,1,(_1) => { __ = _1 }],
-[13,1,(_1) => { __ = null }],
-[14,3,(_1,_2,_3) => { __ = _2 }],
-[14,0,() => { __ = null }],
-[15,1,(_1) => { __ = _1 }],
-[15,5,(_1,_2,_3,_4,_5) => { __ = _1 + _2 + _3 + _4 + _5 }],
-[15,4,(_1,_2,_3,_4) => { __ = _1 + _2 + _3 + _4 }],
+[13,3,(_1,_2,_3) => { __ = _1; _1.push(_3) }],
+[13,1,(_1) => { __ = [_1] }],
+[14,2,(_1,_2) => { __ = [_1[0], _2]; _1[1] && __.push(_1[1]) }],
+[15,2,(_1,_2) => { __ = [_1, _2] }],
 [15,0,() => { __ = '' }],
-[16,2,(_1,_2) => { __ = _1 + _2 }],
-[16,1,(_1) => { __ = _1 }]];
+[16,2,(_1,_2) => { __ = Object.assign(_1, _2) }],
+[16,0,() => { __ = null }],
+[17,2,(_1,_2) => { __ = {prec: _2} }],
+[17,2,(_1,_2) => { __ = {feature: _2} }],
+[17,2,(_1,_2) => { __ = {msg: _2.slice(1, -1)} }],
+[18,2,(_1,_2) => { __ = _1 + ' ' + _2 }],
+[18,1,(_1) => { __ = _1 }],
+[19,1,(_1) => { __ = _1 }],
+[19,1,(_1) => { __ = _1 }],
+[19,1,(_1) => { __ = _1 }],
+[19,1,(_1) => { __ = _1 }],
+[19,1,(_1) => { __ = null }],
+[20,3,(_1,_2,_3) => { __ = _2 }],
+[20,0,() => { __ = null }],
+[21,1,(_1) => { __ = _1 }],
+[21,5,(_1,_2,_3,_4,_5) => { __ = _1 + _2 + _3 + _4 + _5 }],
+[21,4,(_1,_2,_3,_4) => { __ = _1 + _2 + _3 + _4 }],
+[21,0,() => { __ = '' }],
+[22,2,(_1,_2) => { __ = _1 + _2 }],
+[22,1,(_1) => { __ = _1 }]];
 
 /**
  * Encoded tokens map.
  */
-const tokens = {"%%":"17","LEX_BLOCK":"18","MODULE_INCLUDE":"19","%start":"20","%left":"21","%right":"22","%nonassoc":"23","%token":"24","SPLITTER":"25",";":"26","ID":"27","|":"28","%prec":"29","SPECIAL_CHAR":"30","STRING":"31","%empty":"32","{":"33","}":"34","CODE":"35","$":"36"};
+const tokens = {"%%":"23","LEX_BLOCK":"24","GRAMMAR_DOC":"25","MODULE_INCLUDE":"26","%code":"27","}":"28","%start":"29","%eof":"30","ID":"31","STRING":"32","EXPECT":"33","%sync":"34","%left":"35","%right":"36","%nonassoc":"37","%prefer-shift":"38","%prefer-reduce":"39","%token":"40","TYPE":"41","DOC":"42","CODE":"43","SPLITTER":"44",";":"45","TEMPLATE":"46",")":"47",",":"48","|":"49","%prec":"50","%feature":"51","%msg":"52","SPECIAL_CHAR":"53","%empty":"54","{":"55","$":"56"};
 
 /**
  * Parsing table (generated by Syntax tool).
  */
-const table = [{"0":1,"1":2,"2":3,"3":4,"17":"r3","18":"s5","19":"s6","20":"s7","21":"s8","22":"s9","23":"s10","24":"s11"},{"36":"acc"},{"17":"s12"},{"3":46,"17":"r2","18":"s5","19":"s6","20":"s7","21":"s8","22":"s9","23":"s10","24":"s11"},{"17":"r4","18":"r4","19":"r4","20":"r4","21":"r4","22":"r4","23":"r4","24":"r4"},{"17":"r6","18":"r6","19":"r6","20":"r6","21":"r6","22":"r6","23":"r6","24":"r6"},{"17":"r7","18":"r7","19":"r7","20":"r7","21":"r7","22":"r7","23":"r7","24":"r7"},{"7":47,"27":"s16"},{"4":48,"13":49,"27":"s24","30":"s25","31":"s26","32":"s27"},{"4":51,"13":49,"27":"s24","30":"s25","31":"s26","32":"s27"},{"4":52,"13":49,"27":"s24","30":"s25","31":"s26","32":"s27"},{"4":53,"13":49,"27":"s24","30":"s25","31":"s26","32":"s27"},{"5":13,"6":14,"7":15,"27":"s16"},{"6":17,"7":15,"27":"s16","36":"r1"},{"27":"r16","36":"r16"},{"25":"s18"},{"17":"r18","18":"r18","19":"r18","20":"r18","21":"r18","22":"r18","23":"r18","24":"r18","25":"r18"},{"27":"r15","36":"r15"},{"8":19,"9":20,"10":21,"12":22,"13":23,"26":"r23","27":"s24","28":"r23","30":"s25","31":"s26","32":"s27","33":"r23"},{"26":"s28","28":"s29"},{"26":"r20","28":"r20"},{"14":31,"26":"r33","28":"r33","33":"s32"},{"11":42,"13":43,"26":"r25","27":"s24","28":"r25","29":"s44","30":"s25","31":"s26","32":"s27","33":"r25"},{"26":"r27","27":"r27","28":"r27","29":"r27","30":"r27","31":"r27","32":"r27","33":"r27"},{"17":"r28","18":"r28","19":"r28","20":"r28","21":"r28","22":"r28","23":"r28","24":"r28","26":"r28","27":"r28","28":"r28","29":"r28","30":"r28","31":"r28","32":"r28","33":"r28"},{"17":"r29","18":"r29","19":"r29","20":"r29","21":"r29","22":"r29","23":"r29","24":"r29","26":"r29","27":"r29","28":"r29","29":"r29","30":"r29","31":"r29","32":"r29","33":"r29"},{"17":"r30","18":"r30","19":"r30","20":"r30","21":"r30","22":"r30","23":"r30","24":"r30","26":"r30","27":"r30","28":"r30","29":"r30","30":"r30","31":"r30","32":"r30","33":"r30"},{"17":"r31","18":"r31","19":"r31","20":"r31","21":"r31","22":"r31","23":"r31","24":"r31","26":"r31","27":"r31","28":"r31","29":"r31","30":"r31","31":"r31","32":"r31","33":"r31"},{"27":"r17","36":"r17"},{"9":30,"10":21,"12":22,"13":23,"26":"r23","27":"s24","28":"r23","30":"s25","31":"s26","32":"s27","33":"r23"},{"26":"r19","28":"r19"},{"26":"r21","28":"r21"},{"15":33,"16":34,"33":"r37","34":"r37","35":"s35"},{"33":"s37","34":"s36"},{"33":"r34","34":"r34","35":"s41"},{"33":"r39","34":"r39","35":"r39"},{"26":"r32","28":"r32"},{"15":38,"16":34,"33":"r37","34":"r37","35":"s35"},{"33":"s37","34":"s39"},{"16":40,"33":"r36","34":"r36","35":"s35"},{"33":"r35","34":"r35","35":"s41"},{"33":"r38","34":"r38","35":"r38"},{"26":"r22","28":"r22","33":"r22"},{"26":"r26","27":"r26","28":"r26","29":"r26","30":"r26","31":"r26","32":"r26","33":"r26"},{"13":45,"27":"s24","30":"s25","31":"s26","32":"s27"},{"26":"r24","28":"r24","33":"r24"},{"17":"r5","18":"r5","19":"r5","20":"r5","21":"r5","22":"r5","23":"r5","24":"r5"},{"17":"r8","18":"r8","19":"r8","20":"r8","21":"r8","22":"r8","23":"r8","24":"r8"},{"13":50,"17":"r9","18":"r9","19":"r9","20":"r9","21":"r9","22":"r9","23":"r9","24":"r9","27":"s24","30":"s25","31":"s26","32":"s27"},{"17":"r13","18":"r13","19":"r13","20":"r13","21":"r13","22":"r13","23":"r13","24":"r13","27":"r13","30":"r13","31":"r13","32":"r13"},{"17":"r14","18":"r14","19":"r14","20":"r14","21":"r14","22":"r14","23":"r14","24":"r14","27":"r14","30":"r14","31":"r14","32":"r14"},{"13":50,"17":"r10","18":"r10","19":"r10","20":"r10","21":"r10","22":"r10","23":"r10","24":"r10","27":"s24","30":"s25","31":"s26","32":"s27"},{"13":50,"17":"r11","18":"r11","19":"r11","20":"r11","21":"r11","22":"r11","23":"r11","24":"r11","27":"s24","30":"s25","31":"s26","32":"s27"},{"13":50,"17":"r12","18":"r12","19":"r12","20":"r12","21":"r12","22":"r12","23":"r12","24":"r12","27":"s24","30":"s25","31":"s26","32":"s27"}];
+const table = [{"0":1,"1":2,"2":3,"3":4,"4":5,"23":"r3","24":"s6","25":"s7","26":"s8","27":"s9","29":"s10","30":"s11","33":"s12","34":"s13","35":"s14","36":"s15","37":"s16","38":"s17","39":"s18","40":"s19","42":"s20"},{"56":"acc"},{"23":"s21"},{"3":77,"4":78,"23":"r2","24":"s6","25":"s7","26":"s8","27":"s9","29":"s10","30":"s11","33":"s12","34":"s13","35":"s14","36":"s15","37":"s16","38":"s17","39":"s18","40":"s19","42":"s20"},{"23":"r4","24":"r4","25":"r4","26":"r4","27":"r4","29":"r4","30":"r4","33":"r4","34":"r4","35":"r4","36":"r4","37":"r4","38":"r4","39":"r4","40":"r4","42":"r4"},{"23":"r5","24":"r5","25":"r5","26":"r5","27":"r5","29":"r5","30":"r5","33":"r5","34":"r5","35":"r5","36":"r5","37":"r5","38":"r5","39":"r5","40":"r5","42":"r5"},{"23":"r8","24":"r8","25":"r8","26":"r8","27":"r8","29":"r8","30":"r8","33":"r8","34":"r8","35":"r8","36":"r8","37":"r8","38":"r8","39":"r8","40":"r8","42":"r8"},{"23":"r9","24":"r9","25":"r9","26":"r9","27":"r9","29":"r9","30":"r9","33":"r9","34":"r9","35":"r9","36":"r9","37":"r9","38":"r9","39":"r9","40":"r9","42":"r9"},{"23":"r10","24":"r10","25":"r10","26":"r10","27":"r10","29":"r10","30":"r10","33":"r10","34":"r10","35":"r10","36":"r10","37":"r10","38":"r10","39":"r10","40":"r10","42":"r10"},{"5":79,"28":"r26","43":"r26"},{"9":82,"31":"s27"},{"31":"s83","32":"s84"},{"23":"r15","24":"r15","25":"r15","26":"r15","27":"r15","29":"r15","30":"r15","33":"r15","34":"r15","35":"r15","36":"r15","37":"r15","38":"r15","39":"r15","40":"r15","42":"r15"},{"6":85,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":88,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":89,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":90,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":91,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":92,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"6":93,"10":40,"19":86,"31":"s37","32":"s39","41":"s94","46":"s28","53":"s38","54":"s41"},{"23":"r24","24":"r24","25":"r24","26":"r24","27":"r24","29":"r24","30":"r24","31":"r24","33":"r24","34":"r24","35":"r24","36":"r24","37":"r24","38":"r24","39":"r24","40":"r24","42":"r24","46":"r24","56":"r24"},{"4":24,"7":22,"8":23,"9":25,"10":26,"31":"s27","42":"s20","46":"s28"},{"4":30,"8":29,"9":25,"10":26,"31":"s27","42":"s20","46":"s28","56":"r1"},{"31":"r30","42":"r30","46":"r30","56":"r30"},{"31":"r32","42":"r32","46":"r32","56":"r32"},{"44":"s31"},{"44":"s74"},{"23":"r35","24":"r35","25":"r35","26":"r35","27":"r35","29":"r35","30":"r35","33":"r35","34":"r35","35":"r35","36":"r35","37":"r35","38":"r35","39":"r35","40":"r35","42":"r35","44":"r35"},{"10":68,"11":64,"12":65,"31":"s66","32":"s67","46":"s28"},{"31":"r29","42":"r29","46":"r29","56":"r29"},{"31":"r31","42":"r31","46":"r31","56":"r31"},{"10":40,"13":32,"14":33,"15":34,"18":35,"19":36,"31":"s37","32":"s39","45":"r46","46":"s28","49":"r46","53":"s38","54":"s41","55":"r46"},{"45":"s42","49":"s43"},{"45":"r43","49":"r43"},{"20":45,"45":"r60","49":"r60","55":"s46"},{"10":40,"16":56,"17":58,"19":57,"31":"s37","32":"s39","45":"r48","46":"s28","49":"r48","50":"s59","51":"s60","52":"s61","53":"s38","54":"s41","55":"r48"},{"31":"r53","32":"r53","45":"r53","46":"r53","49":"r53","50":"r53","51":"r53","52":"r53","53":"r53","54":"r53","55":"r53"},{"23":"r54","24":"r54","25":"r54","26":"r54","27":"r54","29":"r54","30":"r54","31":"r54","32":"r54","33":"r54","34":"r54","35":"r54","36":"r54","37":"r54","38":"r54","39":"r54","40":"r54","42":"r54","45":"r54","46":"r54","49":"r54","50":"r54","51":"r54","52":"r54","53":"r54","54":"r54","55":"r54"},{"23":"r55","24":"r55","25":"r55","26":"r55","27":"r55","29":"r55","30":"r55","31":"r55","32":"r55","33":"r55","34":"r55","35":"r55","36":"r55","37":"r55","38":"r55","39":"r55","40":"r55","42":"r55","45":"r55","46":"r55","49":"r55","50":"r55","51":"r55","52":"r55","53":"r55","54":"r55","55":"r55"},{"23":"r56","24":"r56","25":"r56","26":"r56","27":"r56","29":"r56","30":"r56","31":"r56","32":"r56","33":"r56","34":"r56","35":"r56","36":"r56","37":"r56","38":"r56","39":"r56","40":"r56","42":"r56","45":"r56","46":"r56","49":"r56","50":"r56","51":"r56","52":"r56","53":"r56","54":"r56","55":"r56"},{"23":"r57","24":"r57","25":"r57","26":"r57","27":"r57","29":"r57","30":"r57","31":"r57","32":"r57","33":"r57","34":"r57","35":"r57","36":"r57","37":"r57","38":"r57","39":"r57","40":"r57","42":"r57","45":"r57","46":"r57","49":"r57","50":"r57","51":"r57","52":"r57","53":"r57","54":"r57","55":"r57"},{"23":"r58","24":"r58","25":"r58","26":"r58","27":"r58","29":"r58","30":"r58","31":"r58","32":"r58","33":"r58","34":"r58","35":"r58","36":"r58","37":"r58","38":"r58","39":"r58","40":"r58","42":"r58","45":"r58","46":"r58","49":"r58","50":"r58","51":"r58","52":"r58","53":"r58","54":"r58","55":"r58"},{"31":"r33","42":"r33","46":"r33","56":"r33"},{"10":40,"14":44,"15":34,"18":35,"19":36,"31":"s37","32":"s39","45":"r46","46":"s28","49":"r46","53":"s38","54":"s41","55":"r46"},{"45":"r42","49":"r42"},{"45":"r44","49":"r44"},{"21":47,"22":48,"28":"r64","43":"s49","55":"r64"},{"28":"s50","55":"s51"},{"28":"r61","43":"s55","55":"r61"},{"28":"r66","43":"r66","55":"r66"},{"45":"r59","49":"r59"},{"21":52,"22":48,"28":"r64","43":"s49","55":"r64"},{"28":"s53","55":"s51"},{"22":54,"28":"r63","43":"s49","55":"r63"},{"28":"r62","43":"s55","55":"r62"},{"28":"r65","43":"r65","55":"r65"},{"45":"r45","49":"r45","55":"r45"},{"31":"r52","32":"r52","45":"r52","46":"r52","49":"r52","50":"r52","51":"r52","52":"r52","53":"r52","54":"r52","55":"r52"},{"16":62,"17":58,"45":"r48","49":"r48","50":"s59","51":"s60","52":"s61","55":"r48"},{"10":40,"19":63,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"10":40,"19":72,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"32":"s73"},{"45":"r47","49":"r47","55":"r47"},{"45":"r49","49":"r49","50":"r49","51":"r49","52":"r49","55":"r49"},{"47":"s69","48":"s70"},{"47":"r38","48":"r38"},{"47":"r39","48":"r39"},{"47":"r40","48":"r40"},{"47":"r41","48":"r41"},{"23":"r36","24":"r36","25":"r36","26":"r36","27":"r36","29":"r36","30":"r36","31":"r36","32":"r36","33":"r36","34":"r36","35":"r36","36":"r36","37":"r36","38":"r36","39":"r36","40":"r36","42":"r36","44":"r36","45":"r36","46":"r36","47":"r36","48":"r36","49":"r36","50":"r36","51":"r36","52":"r36","53":"r36","54":"r36","55":"r36"},{"10":68,"12":71,"31":"s66","32":"s67","46":"s28"},{"47":"r37","48":"r37"},{"45":"r50","49":"r50","50":"r50","51":"r50","52":"r50","55":"r50"},{"45":"r51","49":"r51","50":"r51","51":"r51","52":"r51","55":"r51"},{"10":40,"13":75,"14":33,"15":34,"18":35,"19":36,"31":"s37","32":"s39","45":"r46","46":"s28","49":"r46","53":"s38","54":"s41","55":"r46"},{"45":"s76","49":"s43"},{"31":"r34","42":"r34","46":"r34","56":"r34"},{"23":"r6","24":"r6","25":"r6","26":"r6","27":"r6","29":"r6","30":"r6","33":"r6","34":"r6","35":"r6","36":"r6","37":"r6","38":"r6","39":"r6","40":"r6","42":"r6"},{"23":"r7","24":"r7","25":"r7","26":"r7","27":"r7","29":"r7","30":"r7","33":"r7","34":"r7","35":"r7","36":"r7","37":"r7","38":"r7","39":"r7","40":"r7","42":"r7"},{"28":"s80","43":"s81"},{"23":"r11","24":"r11","25":"r11","26":"r11","27":"r11","29":"r11","30":"r11","33":"r11","34":"r11","35":"r11","36":"r11","37":"r11","38":"r11","39":"r11","40":"r11","42":"r11"},{"28":"r25","43":"r25"},{"23":"r12","24":"r12","25":"r12","26":"r12","27":"r12","29":"r12","30":"r12","33":"r12","34":"r12","35":"r12","36":"r12","37":"r12","38":"r12","39":"r12","40":"r12","42":"r12"},{"23":"r13","24":"r13","25":"r13","26":"r13","27":"r13","29":"r13","30":"r13","33":"r13","34":"r13","35":"r13","36":"r13","37":"r13","38":"r13","39":"r13","40":"r13","42":"r13"},{"23":"r14","24":"r14","25":"r14","26":"r14","27":"r14","29":"r14","30":"r14","33":"r14","34":"r14","35":"r14","36":"r14","37":"r14","38":"r14","39":"r14","40":"r14","42":"r14"},{"10":40,"19":87,"23":"r16","24":"r16","25":"r16","26":"r16","27":"r16","29":"r16","30":"r16","31":"s37","32":"s39","33":"r16","34":"r16","35":"r16","36":"r16","37":"r16","38":"r16","39":"r16","40":"r16","42":"r16","46":"s28","53":"s38","54":"s41"},{"23":"r27","24":"r27","25":"r27","26":"r27","27":"r27","29":"r27","30":"r27","31":"r27","32":"r27","33":"r27","34":"r27","35":"r27","36":"r27","37":"r27","38":"r27","39":"r27","40":"r27","42":"r27","46":"r27","53":"r27","54":"r27"},{"23":"r28","24":"r28","25":"r28","26":"r28","27":"r28","29":"r28","30":"r28","31":"r28","32":"r28","33":"r28","34":"r28","35":"r28","36":"r28","37":"r28","38":"r28","39":"r28","40":"r28","42":"r28","46":"r28","53":"r28","54":"r28"},{"10":40,"19":87,"23":"r17","24":"r17","25":"r17","26":"r17","27":"r17","29":"r17","30":"r17","31":"s37","32":"s39","33":"r17","34":"r17","35":"r17","36":"r17","37":"r17","38":"r17","39":"r17","40":"r17","42":"r17","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r18","24":"r18","25":"r18","26":"r18","27":"r18","29":"r18","30":"r18","31":"s37","32":"s39","33":"r18","34":"r18","35":"r18","36":"r18","37":"r18","38":"r18","39":"r18","40":"r18","42":"r18","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r19","24":"r19","25":"r19","26":"r19","27":"r19","29":"r19","30":"r19","31":"s37","32":"s39","33":"r19","34":"r19","35":"r19","36":"r19","37":"r19","38":"r19","39":"r19","40":"r19","42":"r19","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r20","24":"r20","25":"r20","26":"r20","27":"r20","29":"r20","30":"r20","31":"s37","32":"s39","33":"r20","34":"r20","35":"r20","36":"r20","37":"r20","38":"r20","39":"r20","40":"r20","42":"r20","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r21","24":"r21","25":"r21","26":"r21","27":"r21","29":"r21","30":"r21","31":"s37","32":"s39","33":"r21","34":"r21","35":"r21","36":"r21","37":"r21","38":"r21","39":"r21","40":"r21","42":"r21","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r22","24":"r22","25":"r22","26":"r22","27":"r22","29":"r22","30":"r22","31":"s37","32":"s39","33":"r22","34":"r22","35":"r22","36":"r22","37":"r22","38":"r22","39":"r22","40":"r22","42":"r22","46":"s28","53":"s38","54":"s41"},{"6":95,"10":40,"19":86,"31":"s37","32":"s39","46":"s28","53":"s38","54":"s41"},{"10":40,"19":87,"23":"r23","24":"r23","25":"r23","26":"r23","27":"r23","29":"r23","30":"r23","31":"s37","32":"s39","33":"r23","34":"r23","35":"r23","36":"r23","37":"r23","38":"r23","39":"r23","40":"r23","42":"r23","46":"s28","53":"s38","54":"s41"}];
 
 /**
  * Parsing stack.
@@ -147,16 +216,26 @@ let tokenizer;
  * See `--custom-tokinzer` to skip this generation, and use a custom one.
  */
 
-const lexRules = [[/^\/\/.*/, function() { /* skip comments */ }],
+const lexRules = [[/^\/\/!.*(?!.)(?=(?:\s*\/\/.*(?!.))*\s*%(?!prec\b|empty\b))/, function() { yytext = yytext.slice(3).replace(/^ /, ''); return 'GRAMMAR_DOC' }],
+[/^\/\/\/(?!\/).*(?!.)(?=(?:\s*\/\/.*(?!.))*\s*(?:%(?!prec\b|empty\b)|[a-zA-Z][a-zA-Z0-9_\-']*(?:\([^)]*\))?(?:\s*\/\/.*(?!.))*\s*(?:->|:)|$))/, function() { yytext = yytext.slice(3).replace(/^ /, ''); return 'DOC' }],
+[/^\/\/.*/, function() { /* skip comments */ }],
 [/^\/\*(.|\s)*?\*\//, function() { /* skip comments */ }],
 [/^\s+/, function() { /* skip whitespace */ }],
 [/^%start\b/, function() { return '%start' }],
 [/^%prec\b/, function() { return '%prec' }],
+[/^%feature\b/, function() { return '%feature' }],
+[/^%msg\b/, function() { return '%msg' }],
 [/^%left\b/, function() { return '%left' }],
 [/^%right\b/, function() { return '%right' }],
 [/^%nonassoc\b/, function() { return '%nonassoc' }],
+[/^%prefer-shift\b/, function() { return '%prefer-shift' }],
+[/^%prefer-reduce\b/, function() { return '%prefer-reduce' }],
 [/^%token/, function() { return '%token' }],
+[/^%eof\b/, function() { return '%eof' }],
+[/^%sync\b/, function() { return '%sync' }],
+[/^%expect\s+\d+\b/, function() { yytext = yytext.slice(7).trim(); return 'EXPECT' }],
 [/^%empty/, function() { return '%empty' }],
+[/^%code\s*\{/, function() { yy.depth = 0; this.pushState('code'); return '%code' }],
 [/^\/\*(.|\n|\r)*?\*\//, function() { return 'CODE' }],
 [/^\/\/.*/, function() { return 'CODE' }],
 [/^\/[^ /]*?['"{}'][^ ]*?\//, function() { return 'CODE' }],
@@ -166,6 +245,20 @@ const lexRules = [[/^\/\/.*/, function() { /* skip comments */ }],
 [/^[^{}/"']+/, function() { return 'CODE' }],
 [/^\{/, function() { yy.depth++; return '{'; }],
 [/^\}/, function() { if (yy.depth==0) this.popState(); else yy.depth--; return '}' }],
+[/^\/\*(.|\n|\r)*?\*\//, function() { return 'CODE' }],
+[/^\/\/.*/, function() { return 'CODE' }],
+[/^"(\\\\|\\"|[^"])*"/, function() { return 'CODE' }],
+[/^'(\\.|[^\\'])'/, function() { return 'CODE' }],
+[/^[^{}/"']+/, function() { return 'CODE' }],
+[/^[/"']/, function() { return 'CODE' }],
+[/^\{/, function() { yy.depth++; return 'CODE' }],
+[/^\}/, function() { if (yy.depth==0) { this.popState(); return '}'; } yy.depth--; return 'CODE' }],
+[/^[a-zA-Z][a-zA-Z0-9_\-']*\(/, function() { yytext = yytext.slice(0, -1); this.pushState('template'); return 'TEMPLATE' }],
+[/^\s+/, function() { /* skip whitespace */ }],
+[/^[a-zA-Z][a-zA-Z0-9_\-']*/, function() { return 'ID' }],
+[/^(?:"[^"]*"|'[^']*')/, function() { return 'STRING' }],
+[/^,/, function() { return ',' }],
+[/^\)/, function() { this.popState(); return ')' }],
 [/^[a-zA-Z][a-zA-Z0-9_\-']*/, function() { return 'ID' }],
 [/^(?:->|:(:=)?)/, function() { return 'SPLITTER' }],
 [/^;/, function() { return ';' }],
@@ -177,9 +270,10 @@ const lexRules = [[/^\/\/.*/, function() { /* skip comments */ }],
 [/^%\{(.|\r|\n)*?%\}/, function() { yytext = yytext.slice(2, -2).trim(); return 'MODULE_INCLUDE' }],
 [/^\{\{[\w\W]*?\}\}/, function() { yytext = yytext.slice(2, -2); return 'CODE'; }],
 [/^%[a-zA-Z]+[^\r\n]*/, function() { /* skip unrecognized options */ }],
-[/^(?:"|')([^"']*)(?:"|')/, function() { return 'STRING' }],
+[/^(?:"[^"]*"|'[^']*')/, function() { return 'STRING' }],
+[/^<(?:[^<>\n]|<[^<>\n]*>)+>/, function() { yytext = yytext.slice(1, -1); return 'TYPE' }],
 [/^[-+!%$#@&*(){}~`^|\\:;/,]+/, function() { return 'SPECIAL_CHAR' }]];
-const lexRulesByConditions = {"INITIAL":[0,1,2,3,4,5,6,7,8,9,19,20,21,22,23,24,25,26,27,28,29,30,31],"action":[10,11,12,13,14,15,16,17,18]};
+const lexRulesByConditions = {"INITIAL":[0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,37,43,44,45,46,47,48,49,50,51,52,53,54,55,56],"action":[20,21,22,23,24,25,26,27,28],"code":[29,30,31,32,33,34,35,36],"template":[37,38,39,40,41,42]};
 
 const EOF_TOKEN = {
   type: EOF,
@@ -621,12 +715,57 @@ const yyparse = {
 
     let __bnfParserTokens;
     let __bnfParserOperators;
+    let __bnfParserPreferences;
+    let __bnfParserCode;
     let __bnfParserExtra;
+    let __bnfParserSync;
+    let __bnfParserTokenTypes;
+    let __bnfParserTokenAliases;
+    let __bnfParserTemplates;
+    let __bnfParserDoc;
+    let __bnfParserDocs;
+    let __bnfParserTokenDocs;
+    let __bnfParserRuleDocs;
+
+    // Names of the declared tokens, a string after a name is its alias:
+    // %token SEMI ";" NUMBER.
+    function __bnfParserDeclareTokens(list) {
+      const names = [];
+
+      list.forEach(item => {
+        if (/^["']/.test(item) && names.length > 0) {
+          __bnfParserTokenAliases[names[names.length - 1]] = item.slice(1, -1);
+          return;
+        }
+        names.push(item);
+      });
+
+      return names;
+    }
+
+    // The pending `///` docs are of the next token declaration, or rule.
+    function __bnfParserTakeDocs(target, names) {
+      if (__bnfParserDocs.length) {
+        names.forEach(name => target[name] = __bnfParserDocs.join('\n'));
+      }
+      __bnfParserDocs = [];
+      return names;
+    }
 
     yyparse.onParseBegin = () => {
       __bnfParserTokens = [];
       __bnfParserOperators = [];
+      __bnfParserPreferences = [];
+      __bnfParserCode = [];
       __bnfParserExtra = {};
+      __bnfParserSync = [];
+      __bnfParserTokenTypes = {};
+      __bnfParserTokenAliases = {};
+      __bnfParserTemplates = {};
+      __bnfParserDoc = [];
+      __bnfParserDocs = [];
+      __bnfParserTokenDocs = {};
+      __bnfParserRuleDocs = {};
     };
   
 
@@ -8,17 +8,39 @@
 
 {
   "lex": {
+    // Doc comments are lexed only where the grammar takes them, otherwise
+    // they are comments: a `//!` doc before a declaration, and a `///`
+    // doc before a declaration, or a rule (other comments may be between
+    // them), or at the end. `%prec`, and `%empty` are in a rule. `(?!.)`
+    // is the end of a line, so a comment isn't matched in part.
+
+    "macros": {
+      "before_comments": "(?:\\s*\\/\\/.*(?!.))*\\s*",
+      "declaration":     "%(?!prec\\b|empty\\b)",
+      "rule_name":       "[a-zA-Z][a-zA-Z0-9_\\-']*(?:\\([^)]*\\))?",
+    },
+
     "rules": [
+      ["\\/\\/!.*(?!.)(?={before_comments}{declaration})",            "yytext = yytext.slice(3).replace(/^ /, ''); return 'GRAMMAR_DOC'"],
+      ["\\/\\/\\/(?!\\/).*(?!.)(?={before_comments}(?:{declaration}|{rule_name}{before_comments}(?:->|:)|$))", "yytext = yytext.slice(3).replace(/^ /, ''); return 'DOC'"],
       ["\\/\\/.*",                                  "/* skip comments */"],
       ["\/\\*(.|\\s)*?\\*\/",                       "/* skip comments */"],
       ["\\s+",                                      "/* skip whitespace */"],
       ["%start\\b",                                 "return '%start'"],
       ["%prec\\b",                                  "return '%prec'"],
+      ["%feature\\b",                               "return '%feature'"],
+      ["%msg\\b",                                   "return '%msg'"],
       ["%left\\b",                                  "return '%left'"],
       ["%right\\b",                                 "return '%right'"],
       ["%nonassoc\\b",                              "return '%nonassoc'"],
+      ["%prefer-shift\\b",                          "return '%prefer-shift'"],
+      ["%prefer-reduce\\b",                         "return '%prefer-reduce'"],
       ["%token",                                    "return '%token'"],
+      ["%eof\\b",                                   "return '%eof'"],
+      ["%sync\\b",                                  "return '%sync'"],
+      ["%expect\\s+\\d+\\b",                        "yytext = yytext.slice(7).trim(); return 'EXPECT'"],
       ["%empty",                                    "return '%empty'"],
+      ["%code\\s*\\{",                              "yy.depth = 0; this.pushState('code'); return '%code'"],
 
       // Code inside an action block { } may contain { } from the language
       // itself, so we collect the action block piece by piece, handling
@@ -34,6 +56,30 @@
       [["action"], "\\{",                           "yy.depth++; return '{';"],
       [["action"], "\\}",                           "if (yy.depth==0) this.popState(); else yy.depth--; return '}'"],
 
+      // A `%code { }` block is collected the same way, but its braces are
+      // code, and only character literals are single-quoted (Rust, etc.),
+      // since a single quote may also start a lifetime.
+
+      [["code"], "\\/\\*(.|\\n|\\r)*?\\*\\/",       "return 'CODE'"],
+      [["code"], "\\/\\/.*",                        "return 'CODE'"],
+      [["code"], "\"(\\\\\\\\|\\\\\"|[^\"])*\"",    "return 'CODE'"],
+      [["code"], "'(\\\\.|[^\\\\'])'",              "return 'CODE'"],
+      [["code"], "[^{}/\"']+",                      "return 'CODE'"],
+      [["code"], "[/\"']",                          "return 'CODE'"],
+      [["code"], "\\{",                             "yy.depth++; return 'CODE'"],
+      [["code"], "\\}",                             "if (yy.depth==0) { this.popState(); return '}'; } yy.depth--; return 'CODE'"],
+
+      // A parameterized rule, and its use: `sep_list(Item, Sep)`. The
+      // arguments are collected in the `template` state, since `,` and `)`
+      // are special chars otherwise (nested uses push the state again).
+
+      [["INITIAL", "template"], "[a-zA-Z][a-zA-Z0-9_\\-']*\\(", "yytext = yytext.slice(0, -1); this.pushState('template'); return 'TEMPLATE'"],
+      [["template"], "\\s+",                        "/* skip whitespace */"],
+      [["template"], "[a-zA-Z][a-zA-Z0-9_\\-']*",   "return 'ID'"],
+      [["template"], "(?:\"[^\"]*\"|'[^']*')",       "return 'STRING'"],
+      [["template"], ",",                           "return ','"],
+      [["template"], "\\)",                         "this.popState(); return ')'"],
+
       ["[a-zA-Z][a-zA-Z0-9_\\-']*",                 "return 'ID'"],
       ["(?:->|:(:=)?)",                             "return 'SPLITTER'"],
       [";",                                         "return ';'"],
@@ -47,24 +93,72 @@
       ["%\\{(.|\\r|\\n)*?%\\}",                     "yytext = yytext.slice(2, -2).trim(); return 'MODULE_INCLUDE'"],
       ["\\{\\{[\\w\\W]*?\\}\\}",                    "yytext = yytext.slice(2, -2); return 'CODE';"],
       ["%[a-zA-Z]+[^\\r\\n]*",                      "/* skip unrecognized options */"],
-      ["(?:\"|')([^\"']*)(?:\"|')",                 "return 'STRING'"],
+      ["(?:\"[^\"]*\"|'[^']*')",                     "return 'STRING'"],
+      ["<(?:[^<>\\n]|<[^<>\\n]*>)+>",                "yytext = yytext.slice(1, -1); return 'TYPE'"],
       ["[\-+!%$#@&*(){}\~`^|\\\\:;/,]+",            "return 'SPECIAL_CHAR'"],
     ],
 
     "startConditions": {
       "action": 1, // exclusive condition
+      "code": 1,   // exclusive condition
+      "template": 1, // exclusive condition
     },
   },
 
   "moduleInclude": `
     let __bnfParserTokens;
     let __bnfParserOperators;
+    let __bnfParserPreferences;
+    let __bnfParserCode;
     let __bnfParserExtra;
+    let __bnfParserSync;
+    let __bnfParserTokenTypes;
+    let __bnfParserTokenAliases;
+    let __bnfParserTemplates;
+    let __bnfParserDoc;
+    let __bnfParserDocs;
+    let __bnfParserTokenDocs;
+    let __bnfParserRuleDocs;
+
+    // Names of the declared tokens, a string after a name is its alias:
+    // %token SEMI ";" NUMBER.
+    function __bnfParserDeclareTokens(list) {
+      const names = [];
+
+      list.forEach(item => {
+        if (/^["']/.test(item) && names.length > 0) {
+          __bnfParserTokenAliases[names[names.length - 1]] = item.slice(1, -1);
+          return;
+        }
+        names.push(item);
+      });
+
+      return names;
+    }
+
+    // The pending \`///\` docs are of the next token declaration, or rule.
+    function __bnfParserTakeDocs(target, names) {
+      if (__bnfParserDocs.length) {
+        names.forEach(name => target[name] = __bnfParserDocs.join('\\n'));
+      }
+      __bnfParserDocs = [];
+      return names;
+    }
 
     yyparse.onParseBegin = () => {
       __bnfParserTokens = [];
       __bnfParserOperators = [];
+      __bnfParserPreferences = [];
+      __bnfParserCode = [];
       __bnfParserExtra = {};
+      __bnfParserSync = [];
+      __bnfParserTokenTypes = {};
+      __bnfParserTokenAliases = {};
+      __bnfParserTemplates = {};
+      __bnfParserDoc = [];
+      __bnfParserDocs = [];
+      __bnfParserTokenDocs = {};
+      __bnfParserRuleDocs = {};
     };
   `,
 
@@ -76,6 +170,48 @@
                         spec.operators = __bnfParserOperators;
                       }
 
+                      if (__bnfParserPreferences.length) {
+                        spec.prefer = __bnfParserPreferences;
+                      }
+
+                      // Synchronization tokens of the error recovery.
+                      if (__bnfParserSync.length) {
+                        spec.sync = __bnfParserSync;
+                      }
+
+                      // %{ %} and %code { } blocks, in order.
+                      if (__bnfParserCode.length) {
+                        spec.moduleInclude = __bnfParserCode.join('\\n\\n');
+                      }
+
+                      // Value types of tokens: %token <type> NAME.
+                      if (Object.keys(__bnfParserTokenTypes).length) {
+                        spec.tokenTypes = __bnfParserTokenTypes;
+                      }
+
+                      // Display names of tokens: %token NAME "alias".
+                      if (Object.keys(__bnfParserTokenAliases).length) {
+                        spec.tokenAliases = __bnfParserTokenAliases;
+                      }
+
+                      // Parameterized rules: sep_list(Item, Sep) : ...
+                      if (Object.keys(__bnfParserTemplates).length) {
+                        spec.templates = __bnfParserTemplates;
+                      }
+
+                      // Docs of the grammar (//!), tokens, and rules (///).
+                      if (__bnfParserDoc.length) {
+                        spec.doc = __bnfParserDoc.join('\\n');
+                      }
+
+                      if (Object.keys(__bnfParserTokenDocs).length) {
+                        spec.tokenDocs = __bnfParserTokenDocs;
+                      }
+
+                      if (Object.keys(__bnfParserRuleDocs).length) {
+                        spec.ruleDocs = __bnfParserRuleDocs;
+                      }
+
                       if (tokens.length) {
                         spec.tokens = __bnfParserTokens.join(' ');
                       }
@@ -83,40 +219,79 @@
                       $$ = spec;
                     `]],
 
-    "DeclList":     ["Declarations",
-                     "ε"],
+    "DeclList":     [["Declarations",               "__bnfParserDocs = []"],
+                     ["ε",                          ""]],
 
-    "Declarations": ["Declaration",
-                     "Declarations Declaration"],
+    // The docs are taken by the next declaration, other declarations
+    // (and the end of them) drop them.
+
+    "Declarations": [["Declaration",                "__bnfParserDocs = []"],
+                     ["Doc",                        ""],
+                     ["Declarations Declaration",   "__bnfParserDocs = []"],
+                     ["Declarations Doc",           ""]],
 
     "Declaration":  [["LEX_BLOCK",                  "__bnfParserExtra.lex = $1"],
-                     ["MODULE_INCLUDE",             "__bnfParserExtra.moduleInclude = $1"],
+                     ["GRAMMAR_DOC",                "__bnfParserDoc.push($1)"],
+                     ["MODULE_INCLUDE",             "__bnfParserCode.push($1)"],
+                     ["%code CodeBody }",           "__bnfParserCode.push($2.trim())"],
                      ["%start LHS",                 "__bnfParserExtra.start = $2"],
+                     ["%eof ID",                    "__bnfParserExtra.eof = $2"],
+                     ["%eof STRING",                "__bnfParserExtra.eof = $2.slice(1, -1)"],
+                     ["EXPECT",                     "__bnfParserExtra.expect = Number($1)"],
+                     ["%sync OperatorList",         "__bnfParserSync.push(...$2)"],
                      ["%left OperatorList",         "__bnfParserOperators.push(['left'].concat($2))"],
                      ["%right OperatorList",        "__bnfParserOperators.push(['right'].concat($2))"],
                      ["%nonassoc OperatorList",     "__bnfParserOperators.push(['nonassoc'].concat($2))"],
-                     ["%token OperatorList",        "__bnfParserTokens.push(...$2)"]],
+                     ["%prefer-shift OperatorList", "__bnfParserPreferences.push(['shift'].concat($2))"],
+                     ["%prefer-reduce OperatorList", "__bnfParserPreferences.push(['reduce'].concat($2))"],
+                     ["%token OperatorList",        "__bnfParserTokens.push(...__bnfParserTakeDocs(__bnfParserTokenDocs, __bnfParserDeclareTokens($2)))"],
+                     ["%token TYPE OperatorList",   "__bnfParserTakeDocs(__bnfParserTokenDocs, __bnfParserDeclareTokens($3)).forEach(token => __bnfParserTokenTypes[token] = $2)"]],
+
+    "Doc":          [["DOC",                        "__bnfParserDocs.push($1)"]],
+
+    "CodeBody":     [["CodeBody CODE",              "$$ = $1 + $2"],
+                     ["ε",                          "$$ = ''"]],
 
     "OperatorList": [["Primary",                    "$$ = [$1]"],
                      ["OperatorList Primary",       "$$ = $1; $1.push($2)"]],
 
-    "Productions":  [["Productions Production",     "$$ = $1; $$[$2[0]] = $2[1]"],
-                     ["Production",                 "$$ = {}; $$[$1[0]] = $1[1]"]],
+    "Productions":  [["Productions Production",     "$$ = $1; $2 && ($$[$2[0]] = $2[1])"],
+                     ["Production",                 "$$ = {}; $1 && ($$[$1[0]] = $1[1])"],
+                     ["Productions Doc",            "$$ = $1"],
+                     ["Doc",                        "$$ = {}"]],
 
-    "Production":   [["LHS SPLITTER HandleList ;",  "$$ = [$1, $3]"]],
+    "Production":   [["LHS SPLITTER HandleList ;",  "__bnfParserTakeDocs(__bnfParserRuleDocs, [$1]); $$ = [$1, $3]"],
+                     ["Template SPLITTER HandleList ;", "__bnfParserDocs = []; __bnfParserTemplates[$1] = $3; $$ = null"]],
 
     "LHS":          [["ID",                         "$$ = $1"]],
 
+    // A parameterized rule, or its use, without spaces: `sep_list(Arg,',')`.
+
+    "Template":     [["TEMPLATE TemplateArgs )",    "$$ = $1 + '(' + $2.join(',') + ')'"]],
+
+    "TemplateArgs": [["TemplateArgs , TemplateArg", "$$ = $1; $1.push($3)"],
+                     ["TemplateArg",                "$$ = [$1]"]],
+
+    "TemplateArg":  [["ID",                         "$$ = $1"],
+                     ["STRING",                     "$$ = $1"],
+                     ["Template",                   "$$ = $1"]],
+
     "HandleList":   [["HandleList | HandleAction",  "$$ = $1; $1.push($3)"],
                      ["HandleAction",               "$$ = [$1]"]],
 
-    "HandleAction": [["Handle Action",              "$$ = [$1[0], $2]; $1[1] && $$.push({prec: $1[1]})"]],
+    "HandleAction": [["Handle Action",              "$$ = [$1[0], $2]; $1[1] && $$.push($1[1])"]],
 
-    "Handle":       [["Entries Prec",               "$$ = [$1, $2]"],
+    "Handle":       [["Entries Tags",               "$$ = [$1, $2]"],
                      ["ε",                          "$$ = ''"]],
 
-    "Prec":         [["%prec Primary",              "$$ = $2"],
-                     ["ε"]],
+    // `%prec`, `%feature`, and `%msg` tags of a production, in any order.
+
+    "Tags":         [["Tag Tags",                   "$$ = Object.assign($1, $2)"],
+                     ["ε",                          "$$ = null"]],
+
+    "Tag":          [["%prec Primary",              "$$ = {prec: $2}"],
+                     ["%feature Primary",           "$$ = {feature: $2}"],
+                     ["%msg STRING",                "$$ = {msg: $2.slice(1, -1)}"]],
 
     "Entries":      [["Entries Primary",            "$$ = $1 + ' ' + $2"],
                      ["Primary",                    "$$ = $1"]],
@@ -124,6 +299,7 @@
     "Primary":      [["ID",                         "$$ = $1"],
                      ["SPECIAL_CHAR",               "$$ = $1"],
                      ["STRING",                     "$$ = $1"],
+                     ["Template",                   "$$ = $1"],
                      ["%empty",                     "$$ = null"]],
 
     "Action":       [["{ ActionBody }",             "$$ = $2"],
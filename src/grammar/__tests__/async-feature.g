@@ -0,0 +1,36 @@
+/**
+ * The `async` functions are the `async` feature of the language,
+ * otherwise `async` is an identifier.
+ */
+
+%lex
+
+%%
+
+\s+           /* skip whitespace */
+async\b       return 'ASYNC'
+function\b    return 'FUNCTION'
+\w+           return 'ID'
+
+/lex
+
+%%
+
+Items
+  : Items Item        { $$ = $1.concat([$2]) }
+  | Item              { $$ = [$1] }
+  ;
+
+Item
+  : Function
+  | AsyncFunction
+  | ID ';'            { $$ = $1 }
+  ;
+
+AsyncFunction
+  : ASYNC Function %feature async    { $$ = Object.assign($2, {async: true}) }
+  ;
+
+Function
+  : FUNCTION ID '(' ')'              { $$ = {name: $2, async: false} }
+  ;
@@ -0,0 +1,67 @@
+/**
+ * A grammar with all directives, for the round-trip of the grammar data.
+ */
+
+%lex
+
+digit   [0-9]
+
+%x comment
+
+%%
+
+\s+                   /* skip whitespace */
+"/*"                  this.begin('comment');
+<comment>"*/"         this.popState();
+<comment>[^*]+        /* skip comment */
+{digit}+              return 'NUMBER'
+[a-z]+                return 'ID'
+";"                   return 'SEMI'
+
+/lex
+
+%{
+
+const nodes = [];
+
+%}
+
+%token <f64> NUMBER "number"
+%token ID SEMI
+
+%start Program
+%eof END
+%expect 1
+%sync SEMI
+
+%left '+' '-'
+%left '*'
+%right UMINUS
+
+%prefer-shift ID
+%prefer-reduce Stmt
+
+%%
+
+Program
+    : Stmts                         { $$ = $1 }
+    ;
+
+Stmts
+    : Stmts Stmt                    { $1.push($2); $$ = $1 }
+    | %empty                        { $$ = [] }
+    ;
+
+Stmt
+    : Expr SEMI                     { $$ = $1 }
+    | Expr error %msg "expected ';'" { $$ = $1 }
+    | ID
+    ;
+
+Expr
+    : Expr '+' Expr                 { $$ = $1 + $3 }
+    | Expr '-' Expr                 { $$ = $1 - $3 }
+    | Expr '*' Expr                 { $$ = $1 * $3 }
+    | '-' Expr %prec UMINUS         { $$ = -$2 }
+    | NUMBER                        { $$ = Number(yytext) }
+    ;
@@ -7,6 +7,7 @@ import Grammar from '../grammar';
 import GrammarMode from '../grammar-mode';
 import GrammarSymbol from '../grammar-symbol';
 import Production from '../production';
+import LRParser from '../../lr/lr-parser';
 import {MODES as GRAMMAR_MODE} from '../grammar-mode';
 
 const Grammars = {
@@ -317,4 +318,634 @@ describe('grammar', () => {
 
     expect(grammar.getProduction(3).getRawSemanticAction()).toBe(customAction);
   });
+
+  // -------------------------------------------------------------
+  // Ignore directive.
+
+  it('ignore directive', () => {
+    const grammar = Grammar.fromString(
+      // Lex rules can't be indented.
+      `
+%lex
+
+%ignore \\s+
+
+%%
+
+\\d+    return 'NUMBER'
+
+/lex
+
+      %left '+'
+
+      %%
+
+      E
+        : E '+' E { $$ = $1 + $3 }
+        | NUMBER  { $$ = Number($1) }
+        ;
+    `,
+      {
+        mode: GRAMMAR_MODE.LALR1,
+      }
+    );
+
+    const lexGrammar = grammar.getLexGrammar();
+    expect(lexGrammar.getRuleByIndex(0).toData()).toEqual(['\\s+', '']);
+
+    const parsed = new LRParser({grammar}).parse(' 1 +\n  2 +\t3 ');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toBe(6);
+  });
+
+  // -------------------------------------------------------------
+  // Raw lex patterns.
+
+  it('raw lex patterns', () => {
+    const grammar = Grammar.fromString(
+      // Quotes, slashes, and escapes of raw patterns are not escaped.
+      `
+%lex
+
+QUOTED  r#"'[^'\\n]*'"#
+
+%%
+
+\\s+                  /* skip whitespace */ return ''
+{QUOTED}              return 'QUOTED'
+r#"\\d+/\\d+"#        return 'RATIO'
+r##""#[^"]*"#"##      return 'HASHED'
+\\d+                  return 'NUMBER'
+
+/lex
+
+      %%
+
+      Items
+        : Items Item { $$ = $1.concat($2) }
+        | Item       { $$ = [$1] }
+        ;
+
+      Item
+        : QUOTED     { $$ = 'quoted ' + $1 }
+        | RATIO      { $$ = 'ratio ' + $1 }
+        | HASHED     { $$ = 'hashed ' + $1 }
+        | NUMBER     { $$ = 'number ' + $1 }
+        ;
+    `,
+      {
+        mode: GRAMMAR_MODE.LALR1,
+      }
+    );
+
+    const lexGrammar = grammar.getLexGrammar();
+    expect(lexGrammar.getMacros()).toEqual({QUOTED: "'[^'\\n]*'"});
+    expect(lexGrammar.getRuleByIndex(2).toData()).toEqual([
+      '\\d+/\\d+',
+      "return 'RATIO'",
+    ]);
+    expect(lexGrammar.getRuleByIndex(3).toData()).toEqual([
+      '"#[^"]*"#',
+      "return 'HASHED'",
+    ]);
+
+    const parsed = new LRParser({grammar}).parse(`'a/b' 1/2 "#c'd"# 3`);
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toEqual([
+      "quoted 'a/b'",
+      'ratio 1/2',
+      'hashed "#c\'d"#',
+      'number 3',
+    ]);
+  });
+
+  // -------------------------------------------------------------
+  // Code blocks.
+
+  it('code blocks', () => {
+    const grammar = Grammar.fromString(
+      `
+      %{ const one = 1; %}
+
+      %code {
+        function two() {
+          return {value: '}'.length + 1}.value;
+        }
+      }
+
+      %code { const three = "{" && 3; }
+
+      %%
+
+      E : 'a' { $$ = one + two() + three };
+    `,
+      {
+        mode: GRAMMAR_MODE.LALR1,
+      }
+    );
+
+    expect(grammar.getModuleInclude()).toBe(
+      'const one = 1;\n\n' +
+        'function two() {\n' +
+        "          return {value: '}'.length + 1}.value;\n" +
+        '        }\n\n' +
+        'const three = "{" && 3;'
+    );
+
+    const parsed = new LRParser({grammar}).parse('a');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toBe(6);
+  });
+
+  it('code block with lifetimes', () => {
+    const moduleInclude = Grammar.dataFromString(
+      `
+      %code {
+        struct Name<'t>(&'t str);
+
+        impl<'t> Name<'t> {
+          fn get(&self) -> &'t str { self.0 }
+        }
+      }
+
+      %%
+
+      E : 'a';
+    `,
+      'bnf'
+    ).moduleInclude;
+
+    expect(moduleInclude).toBe(
+      "struct Name<'t>(&'t str);\n\n" +
+        "        impl<'t> Name<'t> {\n" +
+        "          fn get(&self) -> &'t str { self.0 }\n" +
+        '        }'
+    );
+  });
+
+  it('token types', () => {
+    const grammar = Grammar.fromString(
+      `
+      %token <f64> NUMBER
+      %token <Vec<(i32, u8)>> LIST
+
+      %%
+
+      E : NUMBER | LIST;
+    `,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    expect(grammar.getTokenTypes()).toEqual({
+      NUMBER: 'f64',
+      LIST: 'Vec<(i32, u8)>',
+    });
+  });
+
+  it('token aliases', () => {
+    const grammar = Grammar.fromString(
+      `
+      %token SEMI ";" ID
+      %token <f64> NUMBER "number"
+
+      %%
+
+      E : ID SEMI | NUMBER SEMI;
+    `,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    expect(grammar.getTokenAliases()).toEqual({
+      SEMI: ';',
+      NUMBER: 'number',
+    });
+
+    expect(grammar.getTokenTypes()).toEqual({NUMBER: 'f64'});
+
+    expect(grammar.getTokenDisplayName('SEMI')).toBe(';');
+    expect(grammar.getTokenDisplayName('ID')).toBe('ID');
+  });
+
+  it('end of input name', () => {
+    const grammarOf = directive =>
+      Grammar.fromString(`${directive}\n%%\nE : ID;`, {
+        mode: GRAMMAR_MODE.LALR1,
+      });
+
+    expect(grammarOf('%eof "$end"').getEofName()).toBe('$end');
+    expect(grammarOf('%eof END').getEofName()).toBe('END');
+    expect(grammarOf('%eof "$end"').getTokenDisplayName('$')).toBe('$end');
+
+    expect(grammarOf('').getEofName()).toBe(null);
+    expect(grammarOf('').getTokenDisplayName('$')).toBe('$');
+  });
+
+  it('sync tokens', () => {
+    const grammarOf = directive =>
+      Grammar.fromString(
+        `${directive}\n%%\nS : S E ';' | E ';' | '{' S '}';`,
+        {mode: GRAMMAR_MODE.LALR1}
+      );
+
+    expect(grammarOf("%sync ';' '}'").getSyncTokens()).toEqual(["';'", "'}'"]);
+    expect(grammarOf("%sync ';'\n%sync '}'").getSyncTokens()).toEqual([
+      "';'",
+      "'}'",
+    ]);
+    expect(grammarOf('').getSyncTokens()).toEqual([]);
+
+    expect(() => grammarOf("%sync ','")).toThrow(
+      "Unknown token ',' in %sync."
+    );
+  });
+
+  it('error messages', () => {
+    const grammar = Grammar.fromString(
+      `%%\nS : E ';' | E error %msg "expected ';' after expression" | error;`,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    expect(
+      grammar.getProductions().map(production => production.getErrorMessage())
+    ).toEqual([null, null, "expected ';' after expression", null]);
+
+    expect(() =>
+      Grammar.fromString(`%%\nS : E ';' %msg "expected expression";`, {
+        mode: GRAMMAR_MODE.LALR1,
+      })
+    ).toThrow(
+      `%msg "expected expression" of S -> E ';' is only allowed ` +
+        `in a production with the error token.`
+    );
+  });
+
+  // -------------------------------------------------------------
+  // Features.
+
+  it('enabled feature', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/async-feature.g', {
+      mode: GRAMMAR_MODE.LALR1,
+      features: ['async'],
+    });
+
+    expect(grammar.getTokens().map(token => token.getSymbol())).toContain(
+      'ASYNC'
+    );
+
+    const parsed = new LRParser({grammar}).parse('async function f() x;');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toEqual([{name: 'f', async: true}, 'x']);
+  });
+
+  it('disabled feature', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/async-feature.g', {
+      mode: GRAMMAR_MODE.LALR1,
+    });
+
+    // `AsyncFunction` is excluded with the `Item : AsyncFunction`.
+    expect(grammar.getProductions().map(p => p.toFullString())).toEqual([
+      "$accept -> Items",
+      "Items -> Items Item",
+      "Items -> Item",
+      "Item -> Function",
+      "Item -> ID ';'",
+      "Function -> FUNCTION ID '(' ')'",
+    ]);
+
+    // The `ASYNC` token, and its lex rule are excluded.
+    expect(grammar.getTokens().map(token => token.getSymbol())).not.toContain(
+      'ASYNC'
+    );
+    expect(
+      grammar
+        .getLexGrammar()
+        .getRules()
+        .map(rule => rule.getOriginalMatcher())
+    ).not.toContain('async\\b');
+
+    const parsed = new LRParser({grammar}).parse('async; function f()');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toEqual(['async', {name: 'f', async: false}]);
+
+    expect(() => new LRParser({grammar}).parse('async function f()')).toThrow(
+      /Unexpected token: "function"/
+    );
+  });
+
+  it('excluded start symbol', () => {
+    expect(() =>
+      Grammar.fromString(`%% E : 'a' %feature a | 'b' %feature b;`, {
+        mode: GRAMMAR_MODE.LALR1,
+      })
+    ).toThrow('The start symbol "E" is excluded');
+  });
+
+  // -------------------------------------------------------------
+  // Parameterized rules.
+
+  it('parameterized rules', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/templates.g', {
+      mode: GRAMMAR_MODE.LALR1,
+    });
+
+    // The instances follow the rules, the `Item` parameter is not
+    // the `Item` rule.
+    expect(grammar.getProductions().map(p => p.toFullString())).toEqual([
+      "$accept -> Items",
+      "Items -> Items Item",
+      "Items -> Item",
+      "Item -> ID '(' sep_list(ID,',') ')'",
+      "Item -> '{' sep_list(Item,';') '}'",
+      "sep_list(ID,',') -> ID",
+      "sep_list(ID,',') -> sep_list(ID,',') ',' ID",
+      "sep_list(Item,';') -> Item",
+      "sep_list(Item,';') -> sep_list(Item,';') ';' Item",
+    ]);
+
+    const parsed = new LRParser({grammar}).parse('f(a, b) { g(c); h(d) }');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toEqual([
+      {call: 'f', args: ['a', 'b']},
+      {block: [{call: 'g', args: ['c']}, {call: 'h', args: ['d']}]},
+    ]);
+  });
+
+  it('instantiation of parameterized rules', () => {
+    const grammar = Grammar.fromData(
+      {
+        bnf: {
+          S: ['pair(A, opt(B)) pair(A,opt(B))', 'pair(opt(B), A)'],
+        },
+        templates: {
+          'pair(X, Y)': [['X Y', '$$ = [$1, $2] /* X, XY */']],
+          'opt(X)': ['X', ''],
+        },
+      },
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    // Each instance is generated once (the arguments first), the
+    // parameters are replaced in the actions as whole words.
+    expect(
+      grammar
+        .getProductions()
+        .slice(1)
+        .map(p => [p.toFullString(), p.getOriginalSemanticAction()])
+    ).toEqual([
+      ['S -> pair(A,opt(B)) pair(A,opt(B))', null],
+      ['S -> pair(opt(B),A)', '$$ = $1'],
+      ['opt(B) -> B', '$$ = $1'],
+      ['opt(B) -> ε', null],
+      ['pair(A,opt(B)) -> A opt(B)', '$$ = [$1, $2] /* A, XY */'],
+      ['pair(opt(B),A) -> opt(B) A', '$$ = [$1, $2] /* opt(B), XY */'],
+    ]);
+  });
+
+  it('invalid parameterized rules', () => {
+    const fromData = (bnf, templates) =>
+      Grammar.fromData({bnf, templates}, {mode: GRAMMAR_MODE.LALR1});
+
+    expect(() => fromData({S: ['list(A)']}, {'opt(X)': ['X']})).toThrow(
+      'Unknown parameterized rule "list" in list(A).'
+    );
+
+    expect(() => fromData({S: ['opt(A, B)']}, {'opt(X)': ['X']})).toThrow(
+      'The "opt" rule expects 1 arguments, 2 given in opt(A,B).'
+    );
+
+    expect(() => fromData({S: ['opt(A)']}, {"opt('x')": ['X']})).toThrow(
+      `The parameters of the "opt('x')" rule should be names.`
+    );
+
+    expect(() =>
+      fromData({S: ['list(A)']}, {'list(X)': ['X', 'list(opt(X))'], 'opt(X)': ['X']})
+    ).toThrow('Infinite expansion of the "opt" rule.');
+  });
+
+  // -------------------------------------------------------------
+  // Docs.
+
+  it('docs of grammar, tokens, and rules', () => {
+    const grammar = Grammar.fromString(
+      `
+      //! Sums.
+      //!
+      //! Of numbers.
+
+      /// Operators.
+      %left '+'
+
+      /// A number,
+      /// or an identifier.
+      %token NUMBER ID
+
+      //// Not a doc.
+      %token <string> STRING
+
+      %%
+
+      /// A sum.
+      Sum
+        : Sum '+' Term
+        | Term
+        ;
+
+      Term : NUMBER | ID | STRING ;
+      `,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    expect(grammar.getDoc()).toBe('Sums.\n\nOf numbers.');
+
+    // The docs before other declarations are dropped.
+    expect(grammar.getTokenDocs()).toEqual({
+      NUMBER: 'A number,\nor an identifier.',
+      ID: 'A number,\nor an identifier.',
+    });
+
+    expect(grammar.getRuleDocs()).toEqual({Sum: 'A sum.'});
+
+    const data = grammar.toData();
+
+    expect(data.doc).toBe(grammar.getDoc());
+    expect(data.tokenDocs).toEqual(grammar.getTokenDocs());
+    expect(data.ruleDocs).toEqual(grammar.getRuleDocs());
+  });
+
+  it('doc comments inside rules are comments', () => {
+    const grammar = Grammar.fromString(
+      `
+      %%
+
+      /// A sum.
+      E
+        /// The sum.
+        : E '+' T   /// Of two terms.
+        | T         //! Trailing.
+        ;
+
+      //! Not a grammar doc.
+      T : NUMBER %prec NUMBER   /// Of a number.
+        ;
+
+      /// The end.
+      `,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    expect(grammar.getDoc()).toBe(null);
+    expect(grammar.getRuleDocs()).toEqual({E: 'A sum.'});
+    expect(grammar.getProductionsForSymbol('E').length).toBe(2);
+  });
+
+  // -------------------------------------------------------------
+  // Grammar data.
+
+  it('round-trip of grammar data', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/grammar-ast.g', {
+      mode: GRAMMAR_MODE.LALR1,
+    });
+
+    const data = JSON.parse(JSON.stringify(grammar.toData()));
+    const loaded = Grammar.fromData(data, {mode: GRAMMAR_MODE.LALR1});
+
+    expect(loaded.toData()).toEqual(data);
+
+    const productionsOf = grammar =>
+      grammar
+        .getProductions()
+        .map(production => [
+          production.toFullString(),
+          production.getOriginalSemanticAction(),
+          production.getPrecedence(),
+          production.getErrorMessage(),
+        ]);
+
+    expect(productionsOf(loaded)).toEqual(productionsOf(grammar));
+
+    const lexRulesOf = grammar =>
+      grammar
+        .getLexGrammar()
+        .getRules()
+        .map(rule => rule.toData());
+
+    expect(lexRulesOf(loaded)).toEqual(lexRulesOf(grammar));
+    expect(loaded.getLexGrammar().getStartConditions()).toEqual({
+      INITIAL: 0,
+      comment: 1,
+    });
+
+    expect(loaded.getStartSymbol()).toBe('Program');
+    expect(loaded.getTokenSymbols()).toEqual(grammar.getTokenSymbols());
+    expect(loaded.getOperators()).toEqual(grammar.getOperators());
+    expect(loaded.getPreferences()).toEqual({ID: 'shift', Stmt: 'reduce'});
+    expect(loaded.getExpectedConflicts()).toBe(1);
+    expect(loaded.getSyncTokens()).toEqual(['SEMI']);
+    expect(loaded.getTokenTypes()).toEqual({NUMBER: 'f64'});
+    expect(loaded.getTokenAliases()).toEqual({NUMBER: 'number'});
+    expect(loaded.getEofName()).toBe('END');
+    expect(loaded.getModuleInclude()).toBe(grammar.getModuleInclude());
+  });
+
+  it('grammar data', () => {
+    const data = Grammar.fromGrammarFile(__dirname + '/grammar-ast.g', {
+      mode: GRAMMAR_MODE.LALR1,
+    }).toData();
+
+    // The rules of the terminals in quotes are inferred.
+    expect(data.lex.rules.map(rule => rule[rule.length - 2])).toEqual([
+      '\\s+',
+      '\\/\\*',
+      '\\*\\/',
+      '[^*]+',
+      '[0-9]+',
+      '[a-z]+',
+      ';',
+    ]);
+
+    expect(data.bnf.Stmt).toEqual([
+      ['Expr SEMI', ' $$ = $1 '],
+      ['Expr error', ' $$ = $1 ', {msg: "expected ';'"}],
+      ['ID', '$$ = $1'],
+    ]);
+    expect(data.bnf.Stmts[1]).toEqual(['', ' $$ = [] ']);
+    expect(data.bnf.Expr[3]).toEqual([
+      "'-' Expr",
+      ' $$ = -$2 ',
+      {prec: 'UMINUS'},
+    ]);
+
+    expect(data.operators).toEqual([
+      ['left', "'+'", "'-'"],
+      ['left', "'*'"],
+      ['right', 'UMINUS'],
+    ]);
+    expect(data.prefer).toEqual([['shift', 'ID'], ['reduce', 'Stmt']]);
+  });
+
+  // -------------------------------------------------------------
+  // Includes.
+
+  it('includes', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/calc-include.bnf', {
+      mode: GRAMMAR_MODE.LALR1,
+    });
+
+    expect(grammar.getLexGrammar().getRules().length).toBe(6);
+    expect(grammar.getProductions().length).toBe(5);
+
+    const parsed = new LRParser({grammar}).parse('2 + 2 * (1 + 2)');
+    expect(parsed.status).toBe('accept');
+    expect(parsed.value).toBe(8);
+  });
+
+  it('cyclic includes', () => {
+    expect(() =>
+      Grammar.dataFromGrammarFile(__dirname + '/include-cycle-a.bnf')
+    ).toThrow(
+      'Cyclic %include: ' +
+        [
+          __dirname + '/include-cycle-a.bnf',
+          __dirname + '/include-cycle-b.bnf',
+          __dirname + '/include-cycle-a.bnf',
+        ].join(' -> ')
+    );
+  });
+
+  // -------------------------------------------------------------
+  // Lex rules.
+
+  it('unreachable lex rules', () => {
+    const grammar = Grammar.fromGrammarFile(__dirname + '/unreachable-rule.g', {
+      mode: GRAMMAR_MODE.LALR1,
+    });
+
+    const unreachable = grammar
+      .getLexGrammar()
+      .getUnreachableRules()
+      .map(({rule, shadowedBy, state}) => [
+        rule.getOriginalMatcher(),
+        shadowedBy.getOriginalMatcher(),
+        state,
+      ]);
+
+    expect(unreachable).toEqual([['if', '\\w+', 'INITIAL']]);
+  });
+
+  it('duplicate lex rules', () => {
+    expect(() =>
+      Grammar.fromString(
+        [
+          '%lex',
+          '%%',
+          "\\d+    return 'NUMBER'",
+          '"+"    return "+"',
+          '/lex',
+          '%%',
+          "E : E '+' NUMBER | NUMBER;",
+        ].join('\n'),
+        {mode: GRAMMAR_MODE.LALR1}
+      )
+    ).toThrow('Duplicate lex rule "\\+" in the state "INITIAL".');
+  });
 });
@@ -90,6 +90,29 @@ describe('lex-grammar', () => {
     });
   });
 
+  it('ignore rules', () => {
+    const ignoreGrammar = new LexGrammar({
+      rules: [[['comment'], '.', ''], ['\\d+', "return 'NUMBER'"]],
+      startConditions: {comment: 1, string: 0},
+      ignore: ['\\s+', '//.*'],
+    });
+
+    expect(ignoreGrammar.getOriginalRules().slice(0, 2)).toEqual([
+      ['\\s+', ''],
+      ['//.*', ''],
+    ]);
+
+    const rulesForState = state =>
+      ignoreGrammar
+        .getRulesForState(state)
+        .map(rule => ignoreGrammar.getRuleIndex(rule));
+
+    // Active in all inclusive conditions, before other rules.
+    expect(rulesForState('INITIAL')).toEqual([0, 1, 3]);
+    expect(rulesForState('string')).toEqual([0, 1, 3]);
+    expect(rulesForState('comment')).toEqual([2]);
+  });
+
   it('options', () => {
     const options = lexGrammarData.options;
 
@@ -97,4 +120,97 @@ describe('lex-grammar', () => {
     expect(lexGrammar.getRuleByIndex(0).getOptions()).toEqual(options);
   });
 
+  it('unreachable rules', () => {
+    const unreachableRules = rules =>
+      new LexGrammar({
+        rules,
+        startConditions: {string: 0, comment: 1},
+      })
+        .getUnreachableRules()
+        .map(({rule, shadowedBy, state}) => [
+          rule.getOriginalMatcher(),
+          shadowedBy.getOriginalMatcher(),
+          state,
+        ]);
+
+    expect(
+      unreachableRules([
+        ['[+-]', "return 'OP'"],
+        ['\\+', "return '+'"],
+        ['\\w+(?=\\()', "return 'CALL'"],
+        ['f', "return 'F'"],
+        [['comment'], '.', ''],
+        [['comment'], '\\*\\/', "this.popState()"],
+      ])
+    ).toEqual([
+      ['\\+', '[+-]', 'INITIAL'],
+      ['\\+', '[+-]', 'string'],
+      ['\\*\\/', '.', 'comment'],
+    ]);
+
+    // A rule of an inclusive state overrides a rule without conditions.
+    expect(
+      unreachableRules([
+        [['string'], '\\w', "return 'CHAR'"],
+        ['if', "return 'IF'"],
+      ])
+    ).toEqual([]);
+  });
+
+  it('duplicate rules', () => {
+    expect(
+      () =>
+        new LexGrammar({
+          rules: [[['comment'], '.', ''], [['comment'], '.', '']],
+          startConditions: {comment: 1},
+        })
+    ).toThrow('Duplicate lex rule "." in the state "comment".');
+
+    // Overridden in an inclusive state, or with different options.
+    expect(
+      () =>
+        new LexGrammar({
+          rules: [
+            [['string'], '\\{', "return 'LBRACE'"],
+            ['\\{', "return '{'"],
+            ['a', "return 'A'", {'case-insensitive': true}],
+            ['a', "return 'a'"],
+          ],
+          startConditions: {string: 0},
+        })
+    ).not.toThrow();
+  });
+
+  it('undeclared start conditions', () => {
+    expect(
+      () =>
+        new LexGrammar({
+          rules: [[['coment'], '.', '']],
+          startConditions: {comment: 1},
+        })
+    ).toThrow('Undeclared start condition "coment" of the lex rule ".".');
+
+    expect(
+      () =>
+        new LexGrammar({
+          rules: [['\\/\\*', "this.begin('coment');"]],
+          startConditions: {comment: 1},
+        })
+    ).toThrow(
+      'Undeclared start condition "coment" entered from the lex rule "\\/\\*".'
+    );
+
+    expect(
+      () =>
+        new LexGrammar({
+          rules: [
+            ['"', 'self.push_state("string"); return "";'],
+            [['string'], '"', "this.popState(); this.begin('INITIAL');"],
+            [['*'], '\\s+', ''],
+          ],
+          startConditions: {string: 1},
+        })
+    ).not.toThrow();
+  });
+
 });
\ No newline at end of file
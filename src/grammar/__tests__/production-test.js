@@ -134,6 +134,33 @@ describe('production', () => {
     expect(production.getRawSemanticAction()).toBe('$$ = $1 + $3');
   });
 
+  it('target actions', () => {
+    const productionData = [...defaultProductionData];
+    productionData[/* semanticAction */ 3] =
+      '{js: $$ = $E + $F} {rust: $$ = format!("{}", "}") }';
+
+    const production = new Production(...productionData);
+
+    expect(production.getTargetActions()).toEqual({
+      js: '$$ = $E + $F',
+      rust: '$$ = format!("{}", "}")',
+    });
+
+    // The `js` block is the action of the JS targets.
+    expect(production.getRawSemanticAction()).toBe('$$ = $1 + $3');
+    expect(production.runSemanticAction([10, '+', 20])).toBe(30);
+
+    // Not only the target blocks.
+    productionData[/* semanticAction */ 3] = '{js: $$ = $1} $$ = $3';
+    expect(new Production(...productionData).getTargetActions()).toBe(null);
+
+    productionData[/* semanticAction */ 3] = '$$ = {js: $1}';
+    expect(new Production(...productionData).getTargetActions()).toBe(null);
+
+    productionData[/* semanticAction */ 3] = '{ $$ = $1 }';
+    expect(new Production(...productionData).getTargetActions()).toBe(null);
+  });
+
   it('semantic action', () => {
     // Has semantic action.
     let production = new Production(...defaultProductionData);
@@ -0,0 +1,30 @@
+/**
+ * Calls with the arguments separated by `,`, and the blocks with the
+ * statements separated by `;`, with the parameterized `sep_list` rule.
+ */
+
+%lex
+
+%%
+
+\s+           /* skip whitespace */
+\w+           return 'ID'
+
+/lex
+
+%%
+
+Items
+  : Items Item                    { $$ = $1.concat([$2]) }
+  | Item                          { $$ = [$1] }
+  ;
+
+Item
+  : ID '(' sep_list(ID, ',') ')'  { $$ = {call: $1, args: $3} }
+  | '{' sep_list(Item, ';') '}'   { $$ = {block: $2} }
+  ;
+
+sep_list(Item, Sep)
+  : Item                          { $$ = [$1] }
+  | sep_list(Item, Sep) Sep Item  { $$ = $1.concat([$3]) }
+  ;
@@ -0,0 +1,21 @@
+/**
+ * The `if` keyword is never matched: the earlier `\w+` rule
+ * matches it first, so the generator warns about it.
+ */
+
+%lex
+
+%%
+
+\s+     /* skip whitespace */
+\w+     return 'ID'
+"if"    return 'IF'
+
+/lex
+
+%%
+
+Stmt
+  : IF ID
+  | ID
+  ;
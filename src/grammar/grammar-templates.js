@@ -0,0 +1,221 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+/**
+ * Maximum nesting of the instantiations, a deeper one is an infinite
+ * expansion, e.g. `list(X) : list(pair(X, X))`.
+ */
+const MAX_DEPTH = 100;
+
+/**
+ * Expands the parameterized rules (templates) of the grammar:
+ *
+ *   sep_list(Item, Sep)
+ *       : Item
+ *       | sep_list(Item, Sep) Sep Item
+ *       ;
+ *
+ * A use of a template, `sep_list(Arg, ',')`, is instantiated into the
+ * productions of a concrete rule named `sep_list(Arg,',')`, with the
+ * parameters replaced by the arguments in the RHS, and in the semantic
+ * actions (as whole words, so an action may use a parameter as a type).
+ * Each instantiation is generated once, in the order of the first use
+ * (the arguments first), after the rules of the grammar.
+ *
+ * The templates are keyed by the name with the parameters, as the rules
+ * of the `bnf`: {"sep_list(Item, Sep)": ["Item", ...]}.
+ */
+export default function expandTemplates(bnf, templates) {
+  if (!templates || Object.keys(templates).length === 0) {
+    return bnf;
+  }
+
+  const definitions = {};
+
+  Object.keys(templates).forEach(key => {
+    const symbol = parseSymbols(key);
+
+    if (symbol.length !== 1 || !symbol[0].args) {
+      throw new Error(`Invalid parameterized rule "${key}".`);
+    }
+
+    const {name, args} = symbol[0];
+    const params = args.map(arg => arg.name);
+
+    if (args.some(arg => arg.args || !/^[a-zA-Z]/.test(arg.name))) {
+      throw new Error(
+        `The parameters of the "${key}" rule should be names.`
+      );
+    }
+
+    definitions[name] = {params, productions: templates[key]};
+  });
+
+  const instances = {};
+
+  // Name of the rule of a symbol, instantiating a template use.
+  const resolve = (symbol, bindings, depth) => {
+    if (!symbol.args) {
+      return bindings.hasOwnProperty(symbol.name)
+        ? bindings[symbol.name]
+        : symbol.name;
+    }
+
+    const args = symbol.args.map(arg => resolve(arg, bindings, depth));
+    const name = `${symbol.name}(${args.join(',')})`;
+
+    // Already instantiated (or defined as a usual rule).
+    if (bnf.hasOwnProperty(name) || instances.hasOwnProperty(name)) {
+      return name;
+    }
+
+    const definition = definitions[symbol.name];
+
+    if (!definition) {
+      throw new Error(`Unknown parameterized rule "${symbol.name}" in ${name}.`);
+    }
+
+    if (definition.params.length !== args.length) {
+      throw new Error(
+        `The "${symbol.name}" rule expects ${definition.params.length} ` +
+          `arguments, ${args.length} given in ${name}.`
+      );
+    }
+
+    if (depth >= MAX_DEPTH) {
+      throw new Error(`Infinite expansion of the "${symbol.name}" rule.`);
+    }
+
+    const instanceBindings = {};
+    definition.params.forEach((param, i) => (instanceBindings[param] = args[i]));
+
+    // Reserved before the productions, which may use the instance.
+    instances[name] = [];
+    instances[name] = definition.productions.map(production =>
+      expandProduction(production, instanceBindings, depth + 1)
+    );
+
+    return name;
+  };
+
+  const expandProduction = (production, bindings, depth) => {
+    const RHS = Array.isArray(production) ? production[0] : production;
+
+    const expandedRHS = parseSymbols(RHS || '')
+      .map(symbol => resolve(symbol, bindings, depth))
+      .join(' ');
+
+    if (!Array.isArray(production)) {
+      return expandedRHS;
+    }
+
+    const expanded = production.slice();
+    expanded[0] = expandedRHS;
+
+    if (typeof expanded[1] === 'string') {
+      expanded[1] = replaceParams(expanded[1], bindings);
+    }
+
+    return expanded;
+  };
+
+  const expandedBnf = {};
+
+  Object.keys(bnf).forEach(LHS => {
+    expandedBnf[LHS] = bnf[LHS].map(production =>
+      expandProduction(production, {}, 0)
+    );
+  });
+
+  return Object.assign(expandedBnf, instances);
+}
+
+/**
+ * Replaces the parameters in a semantic action, as whole words.
+ */
+function replaceParams(action, bindings) {
+  const params = Object.keys(bindings);
+
+  if (params.length === 0) {
+    return action;
+  }
+
+  const paramsRe = new RegExp(
+    `(^|[^\\w$])(${params.join('|')})(?![\\w])`,
+    'g'
+  );
+
+  return action.replace(
+    paramsRe,
+    (_, before, param) => before + bindings[param]
+  );
+}
+
+/**
+ * Splits the RHS of a production into symbols: `{name}` for a usual
+ * symbol, and `{name, args}` for a use of a template, which may have
+ * spaces between the arguments: `sep_list(Arg, ',')`.
+ */
+function parseSymbols(RHS) {
+  let pos = 0;
+
+  const skipSpaces = () => {
+    while (pos < RHS.length && /\s/.test(RHS[pos])) {
+      pos++;
+    }
+  };
+
+  const parseSymbol = isArg => {
+    const use = /^[a-zA-Z][\w\-']*\(/.exec(RHS.slice(pos));
+
+    if (!use) {
+      // In the arguments `,`, and `)` end a symbol, unless it's quoted.
+      const symbolRe = isArg ? /^(?:"[^"]*"|'[^']*'|[^\s,)]+)/ : /^\S+/;
+      const symbol = symbolRe.exec(RHS.slice(pos));
+
+      if (!symbol) {
+        throw new Error(`Invalid parameterized rule use in "${RHS}".`);
+      }
+
+      pos += symbol[0].length;
+      return {name: symbol[0]};
+    }
+
+    pos += use[0].length;
+
+    const args = [];
+
+    skipSpaces();
+
+    while (RHS[pos] !== ')') {
+      if (pos >= RHS.length) {
+        throw new Error(`Unterminated parameterized rule use in "${RHS}".`);
+      }
+
+      args.push(parseSymbol(true));
+      skipSpaces();
+
+      if (RHS[pos] === ',') {
+        pos++;
+        skipSpaces();
+      }
+    }
+
+    pos++;
+
+    return {name: use[0].slice(0, -1), args};
+  };
+
+  const symbols = [];
+
+  skipSpaces();
+
+  while (pos < RHS.length) {
+    symbols.push(parseSymbol(false));
+    skipSpaces();
+  }
+
+  return symbols;
+}
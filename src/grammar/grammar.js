@@ -10,12 +10,16 @@ import LexGrammar from './lex-grammar';
 import LexRule from './lex-rule';
 import LexParser from '../generated/lex-parser.gen.js';
 import Production from './production';
+import expandTemplates from './grammar-templates';
 
 import colors from 'colors';
 import fs from 'fs';
+import path from 'path';
 import vm from 'vm';
 
 import debug from '../debug';
+import timing from '../timing';
+import {EOF} from '../special-symbols';
 
 /**
  * Class encapsulates operations with a grammar.
@@ -101,16 +105,67 @@ export default class Grammar {
      */
     tokens,
 
+    /**
+     * Value types of tokens (`%token <type> NAME`), used by plugins
+     * of typed languages.
+     */
+    tokenTypes = {},
+
+    /**
+     * Display names of tokens in error messages (`%token NAME "alias"`).
+     */
+    tokenAliases = {},
+
+    /**
+     * Symbolic name of the end of input token (`%eof NAME`), used by
+     * plugins in the tokens map, and error messages.
+     */
+    eof = null,
+
+    /**
+     * Docs of the grammar (`//!` comments), of the tokens, and of the
+     * rules (`///` comments before `%token`, or a rule), which plugins
+     * emit as the docs of the generated code.
+     */
+    doc = null,
+    tokenDocs = {},
+    ruleDocs = {},
+
     /**
      * BNF grammar.
      */
     bnf,
 
+    /**
+     * Parameterized rules, instantiated on use in the BNF grammar:
+     * `sep_list(Item, Sep)`, see `grammar-templates.js`.
+     */
+    templates,
+
     /**
      * Precedence, and associativity.
      */
     operators,
 
+    /**
+     * Explicit resolutions of conflicts: `shift` or `reduce` preferred
+     * for a lookahead token, a `%prec` tag, or a rule (non-terminal).
+     */
+    prefer,
+
+    /**
+     * Expected number of shift-reduce conflicts, which are not resolved
+     * by precedence, or preferences (`%expect N`). They are resolved as
+     * shift, and a different number of them is an error.
+     */
+    expect = null,
+
+    /**
+     * Synchronization tokens of the error recovery (`%sync ';' '}'`): on
+     * a syntax error the input is skipped up to one of them.
+     */
+    sync = [],
+
     /**
      * Start symbol. If not provided, it's inferred from the first
      * production's LHS.
@@ -132,17 +187,49 @@ export default class Grammar {
      * Whether to capture locations.
      */
     captureLocations = false,
+
+    /**
+     * Enabled features: the productions tagged with `%feature <name>`
+     * of other features are excluded from the grammar.
+     */
+    features = [],
+
+    /**
+     * Source text of the grammar (with the included files), if it's
+     * loaded from a string, or a file.
+     */
+    source = null,
   }) {
     this._mode = new GrammarMode(mode);
     this._startSymbol = start;
 
     this._captureLocations = captureLocations;
+    this._source = source;
+
+    this._tokenTypes = tokenTypes;
+    this._tokenAliases = tokenAliases;
+    this._eofName = eof;
+
+    this._doc = doc;
+    this._tokenDocs = tokenDocs;
+    this._ruleDocs = ruleDocs;
 
     // Operators and precedence.
     this._operators = this._processOperators(operators);
 
+    // Conflict resolution directives.
+    this._preferences = this._processPreferences(prefer);
+
+    this._expectedConflicts = expect;
+
+    // Tokens used only in the excluded productions.
+    this._disabledTokens = {};
+
     // Actual BNF grammar.
-    this._originalBnf = bnf;
+    this._originalBnf = this._excludeDisabledFeatures(
+      expandTemplates(bnf, templates),
+      features
+    );
     this._bnf = this._processBnf(this._originalBnf);
 
     // Injecting user code, including handlers for `yyparse.onParseBegin`,
@@ -158,6 +245,8 @@ export default class Grammar {
     // Lexical grammar.
     this._lexGrammar = this._createLexGrammar(lex);
 
+    this._syncTokens = this._processSyncTokens(sync);
+
     // Caching maps.
     this._productionsForSymbol = {};
     this._productionsWithSymbol = {};
@@ -178,17 +267,55 @@ export default class Grammar {
    */
   static dataFromGrammarFile(grammarFile, grammarType = 'bnf') {
     return Grammar.dataFromString(
-      fs.readFileSync(grammarFile, 'utf-8'),
+      Grammar.readGrammarFile(grammarFile),
       grammarType
     );
   }
 
+  /**
+   * Reads grammar file contents, inlining all included files.
+   *
+   * A grammar may include other files with the `%include` directive
+   * on a separate line, e.g.:
+   *
+   *   %include "tokens.bnf"
+   *
+   * The included file contents replaces the directive as is, so it can
+   * contain any part of the grammar (e.g. the whole `%lex` section, or
+   * a set of productions). Relative paths are resolved against the
+   * directory of the including file.
+   */
+  static readGrammarFile(grammarFile, includeStack = []) {
+    const filePath = path.resolve(grammarFile);
+
+    if (includeStack.indexOf(filePath) !== -1) {
+      const cycle = includeStack
+        .slice(includeStack.indexOf(filePath))
+        .concat(filePath);
+      throw new Error(`Cyclic %include: ${cycle.join(' -> ')}`);
+    }
+
+    const includeRe = /^[ \t]*%include[ \t]+(["'])(.+?)\1[ \t]*;?[ \t]*$/gm;
+
+    return fs
+      .readFileSync(filePath, 'utf-8')
+      .replace(includeRe, (_, quote, includeFile) =>
+        Grammar.readGrammarFile(
+          path.resolve(path.dirname(filePath), includeFile),
+          includeStack.concat(filePath)
+        )
+      );
+  }
+
   /**
    * Creates Grammar instance from grammar data for
    * a particular parsing options.
    */
   static fromData(grammarData, options = {}) {
-    return new Grammar(Object.assign({}, grammarData, options));
+    timing.start('Grammar loading');
+    const grammar = new Grammar(Object.assign({}, grammarData, options));
+    timing.end('Grammar loading');
+    return grammar;
   }
 
   /**
@@ -206,6 +333,7 @@ export default class Grammar {
     let grammarData = null;
 
     debug.time('Grammar loaded in');
+    timing.start('Grammar loading');
 
     try {
       // Pure JSON representation.
@@ -257,6 +385,11 @@ export default class Grammar {
       }
     }
 
+    if (grammarType === 'bnf') {
+      grammarData.source = grammarString;
+    }
+
+    timing.end('Grammar loading');
     debug.timeEnd('Grammar loaded in');
     return grammarData;
   }
@@ -312,6 +445,70 @@ export default class Grammar {
     return this._moduleInclude;
   }
 
+  /**
+   * Returns value types of tokens: `{NUMBER: 'f64'}`.
+   */
+  getTokenTypes() {
+    return this._tokenTypes;
+  }
+
+  /**
+   * Returns display names of tokens: `{SEMI: ';'}`.
+   */
+  getTokenAliases() {
+    return this._tokenAliases;
+  }
+
+  /**
+   * Returns the name of the end of input token set with `%eof NAME`,
+   * or `null` if it's not set.
+   */
+  getEofName() {
+    return this._eofName;
+  }
+
+  /**
+   * Returns the doc of the grammar (`//!` comments), or `null`.
+   */
+  getDoc() {
+    return this._doc;
+  }
+
+  /**
+   * Returns docs of tokens: `{NUMBER: 'A decimal number.'}`.
+   */
+  getTokenDocs() {
+    return this._tokenDocs;
+  }
+
+  /**
+   * Returns docs of rules: `{Expr: 'An expression.'}`.
+   */
+  getRuleDocs() {
+    return this._ruleDocs;
+  }
+
+  /**
+   * Display name of a token in error messages: its alias, or the name
+   * (the `%eof` name for the end of input).
+   */
+  getTokenDisplayName(token) {
+    if (token === EOF && this._eofName) {
+      return this._eofName;
+    }
+    return this._tokenAliases.hasOwnProperty(token)
+      ? this._tokenAliases[token]
+      : token;
+  }
+
+  /**
+   * Returns the source text of the grammar, or `null` if the grammar
+   * is created from data.
+   */
+  getSource() {
+    return this._source;
+  }
+
   /**
    * Whther should capture locations.
    */
@@ -326,6 +523,29 @@ export default class Grammar {
     return this._operators;
   }
 
+  /**
+   * Returns preferred conflict resolutions (`shift` or `reduce`)
+   * by a token, a `%prec` tag, or a non-terminal.
+   */
+  getPreferences() {
+    return this._preferences;
+  }
+
+  /**
+   * Returns the expected number of shift-reduce conflicts (`%expect N`),
+   * or `null` if it's not set.
+   */
+  getExpectedConflicts() {
+    return this._expectedConflicts;
+  }
+
+  /**
+   * Returns the synchronization tokens of the error recovery (`%sync`).
+   */
+  getSyncTokens() {
+    return this._syncTokens;
+  }
+
   /**
    * Returns list of terminals in this grammar.
    */
@@ -471,6 +691,36 @@ export default class Grammar {
     return this._productionsWithSymbol[symbol];
   }
 
+  /**
+   * Replaces the target-guarded actions (`{{rust: ...} {js: ...}}`) with
+   * the actions of the target language. A production without a block of
+   * the target has no action (the default `$$ = $1` for one symbol).
+   */
+  selectTargetActions(target) {
+    this._bnf = this._bnf.map(production => {
+      const targetActions = production.getTargetActions();
+
+      if (!targetActions) {
+        return production;
+      }
+
+      return new Production(
+        production.getLHS().getSymbol(),
+        production.isEpsilon() ? '' : production.getRHSSymbols().join(' '),
+        production.getNumber(),
+        targetActions[target] || null,
+        /* isShort */ production.toString() !== production.toFullString(),
+        this,
+        production.getPrecedence(),
+        production.getPrecedenceTag(),
+        production.getErrorMessage()
+      );
+    });
+
+    this._productionsForSymbol = {};
+    this._productionsWithSymbol = {};
+  }
+
   /**
    * Gets a production by number.
    */
@@ -517,6 +767,114 @@ export default class Grammar {
     return this._nonTerminalsMap.hasOwnProperty(symbol);
   }
 
+  /**
+   * Generates the grammar data (in the format of the constructor) from
+   * the grammar: the lexical grammar, productions with their actions, and
+   * tags, precedence, and all directives. The grammar is loaded back from
+   * the data with `Grammar.fromData` (the `--grammar-ast` option exports
+   * it as JSON for external tools).
+   */
+  toData() {
+    const data = {lex: this._lexGrammarToData(), bnf: {}};
+
+    this._bnf.forEach(production => {
+      if (production.isAugmented()) {
+        return;
+      }
+
+      const LHS = production.getLHS().getSymbol();
+      const tags = {};
+
+      if (production.getPrecedenceTag()) {
+        tags.prec = production.getPrecedenceTag();
+      }
+
+      if (production.getErrorMessage()) {
+        tags.msg = production.getErrorMessage();
+      }
+
+      const RHS = [
+        production.isEpsilon() ? '' : production.getRHSSymbols().join(' '),
+        production.getOriginalSemanticAction(),
+      ];
+
+      if (Object.keys(tags).length) {
+        RHS.push(tags);
+      }
+
+      (data.bnf[LHS] || (data.bnf[LHS] = [])).push(RHS);
+    });
+
+    data.start = this._startSymbol;
+    data.tokens = this.getTokenSymbols().join(' ');
+
+    // Levels of precedence in the order of the grammar: [assoc, ...ops].
+    const operators = [];
+
+    Object.keys(this._operators).forEach(op => {
+      const {precedence, assoc} = this._operators[op];
+      (operators[precedence - 1] || (operators[precedence - 1] = [assoc])).push(
+        op
+      );
+    });
+
+    if (operators.length) {
+      data.operators = operators.filter(Boolean);
+    }
+
+    const prefer = ['shift', 'reduce']
+      .map(action =>
+        [action].concat(
+          Object.keys(this._preferences).filter(
+            symbol => this._preferences[symbol] === action
+          )
+        )
+      )
+      .filter(preferData => preferData.length > 1);
+
+    if (prefer.length) {
+      data.prefer = prefer;
+    }
+
+    if (this._expectedConflicts !== null) {
+      data.expect = this._expectedConflicts;
+    }
+
+    if (this._syncTokens.length) {
+      data.sync = this._syncTokens;
+    }
+
+    if (Object.keys(this._tokenTypes).length) {
+      data.tokenTypes = this._tokenTypes;
+    }
+
+    if (Object.keys(this._tokenAliases).length) {
+      data.tokenAliases = this._tokenAliases;
+    }
+
+    if (this._eofName) {
+      data.eof = this._eofName;
+    }
+
+    if (this._doc) {
+      data.doc = this._doc;
+    }
+
+    if (Object.keys(this._tokenDocs).length) {
+      data.tokenDocs = this._tokenDocs;
+    }
+
+    if (Object.keys(this._ruleDocs).length) {
+      data.ruleDocs = this._ruleDocs;
+    }
+
+    if (this._moduleInclude) {
+      data.moduleInclude = this._moduleInclude;
+    }
+
+    return data;
+  }
+
   /**
    * Pretty prints the grammar.
    */
@@ -564,6 +922,39 @@ export default class Grammar {
     return processedOperators;
   }
 
+  _processSyncTokens(sync) {
+    sync.forEach(token => {
+      if (!this.isTokenSymbol(token)) {
+        throw new Error(`Unknown token ${token} in %sync.`);
+      }
+    });
+
+    return sync;
+  }
+
+  _processPreferences(prefer) {
+    let preferences = {};
+
+    if (prefer) {
+      prefer.forEach(preferData => {
+        const action = preferData[0];
+
+        if (action !== 'shift' && action !== 'reduce') {
+          throw new Error(
+            `Unknown conflict resolution "${action}", ` +
+              `expected "shift" or "reduce".`
+          );
+        }
+
+        preferData.slice(1).forEach(symbol => {
+          preferences[symbol] = action;
+        });
+      });
+    }
+
+    return preferences;
+  }
+
   /**
    * Generates data arrays for lex rules inferred from terminals.
    */
@@ -584,6 +975,13 @@ export default class Grammar {
       };
     }
 
+    // Lex rules of the tokens of disabled features are excluded, e.g.
+    // a keyword is matched by a more generic rule (an identifier). The
+    // grammar data is not changed, so it can be loaded again.
+    lex = Object.assign({}, lex, {
+      rules: lex.rules.filter(rule => !this._isDisabledLexRule(rule)),
+    });
+
     // Infer automatic lex-rules from raw terminals
     // (symbols in quotes) in BNF productions RHS.
     lex.rules.unshift(...this._generateLexRulesDataForTerminals());
@@ -591,6 +989,73 @@ export default class Grammar {
     return new LexGrammar(lex);
   }
 
+  /**
+   * Lexical grammar data without the rules inferred from the terminals,
+   * which are inferred again when the grammar is loaded from the data.
+   */
+  _lexGrammarToData() {
+    const inferredRules = {};
+
+    this._generateLexRulesDataForTerminals().forEach(rule => {
+      inferredRules[JSON.stringify(rule)] = true;
+    });
+
+    const data = {
+      rules: this._lexGrammar
+        .getOriginalRules()
+        .filter(rule => !inferredRules.hasOwnProperty(JSON.stringify(rule))),
+    };
+
+    const macros = this._lexGrammar.getMacros();
+
+    if (macros && Object.keys(macros).length) {
+      data.macros = macros;
+    }
+
+    const startConditions = Object.assign(
+      {},
+      this._lexGrammar.getStartConditions()
+    );
+
+    delete startConditions.INITIAL;
+
+    if (Object.keys(startConditions).length) {
+      data.startConditions = startConditions;
+    }
+
+    const options = this._lexGrammar.getOptions();
+
+    if (options && Object.keys(options).length) {
+      data.options = options;
+    }
+
+    return data;
+  }
+
+  /**
+   * Whether a lex rule returns only the tokens of disabled features.
+   */
+  _isDisabledLexRule(rule) {
+    const handler = rule[rule.length - 1];
+
+    if (typeof handler !== 'string') {
+      return false;
+    }
+
+    const returnedTokens = [];
+    const returnRe = /\breturn\s+(['"])(.+?)\1/g;
+    let match;
+
+    while ((match = returnRe.exec(handler))) {
+      returnedTokens.push(match[2]);
+    }
+
+    return (
+      returnedTokens.length > 0 &&
+      returnedTokens.every(token => this._disabledTokens.hasOwnProperty(token))
+    );
+  }
+
   /**
    * Processes tokens.
    */
@@ -599,6 +1064,12 @@ export default class Grammar {
       tokens = tokens.split(/\s+/);
     }
 
+    if (Array.isArray(tokens)) {
+      tokens = tokens.filter(
+        token => !this._disabledTokens.hasOwnProperty(token)
+      );
+    }
+
     this._tokensMap = {};
 
     return Array.isArray(tokens)
@@ -609,6 +1080,109 @@ export default class Grammar {
       : this.getTokens();
   }
 
+  /**
+   * Tags of a production in the BNF data: `%prec`, and `%feature`.
+   */
+  _getProductionTags(RHS) {
+    if (!Array.isArray(RHS)) {
+      return {};
+    }
+
+    // A production without an action still may have tags.
+    const tags = typeof RHS[1] === 'string' || RHS[1] === null
+      ? RHS[2]
+      : RHS[1];
+
+    return tags !== null && typeof tags === 'object' ? tags : {};
+  }
+
+  /**
+   * Excludes the productions tagged with `%feature <name>`, which are
+   * not in the enabled features. A rule without productions is excluded
+   * together with the productions using it, and the tokens used only in
+   * the excluded productions are disabled (with their lex rules).
+   */
+  _excludeDisabledFeatures(originalBnf, features) {
+    const symbolsOf = RHS =>
+      ((Array.isArray(RHS) ? RHS[0] : RHS) || '')
+        .split(/\s+/)
+        .filter(Boolean);
+
+    const isEnabled = RHS => {
+      const {feature} = this._getProductionTags(RHS);
+      return !feature || features.indexOf(feature) !== -1;
+    };
+
+    const bnf = {};
+    const excludedRules = {};
+    const excludedSymbols = {};
+
+    // Keeps the productions of a rule which satisfy the predicate.
+    const keepProductions = (LHS, productions, predicate) => {
+      bnf[LHS] = productions.filter(RHS => {
+        if (predicate(RHS)) {
+          return true;
+        }
+        symbolsOf(RHS).forEach(symbol => (excludedSymbols[symbol] = true));
+        return false;
+      });
+      return bnf[LHS].length !== productions.length;
+    };
+
+    Object.keys(originalBnf).forEach(LHS =>
+      keepProductions(LHS, originalBnf[LHS], isEnabled)
+    );
+
+    let changed = true;
+
+    while (changed) {
+      changed = false;
+
+      Object.keys(bnf).forEach(LHS => {
+        if (bnf[LHS].length === 0) {
+          delete bnf[LHS];
+          excludedRules[LHS] = true;
+          changed = true;
+        }
+      });
+
+      Object.keys(bnf).forEach(LHS => {
+        const usesExcludedRule = RHS =>
+          symbolsOf(RHS).some(symbol => excludedRules[symbol]);
+
+        if (keepProductions(LHS, bnf[LHS], RHS => !usesExcludedRule(RHS))) {
+          changed = true;
+        }
+      });
+    }
+
+    const startSymbol = this._startSymbol || Object.keys(originalBnf)[0];
+
+    if (excludedRules[startSymbol]) {
+      throw new Error(
+        `The start symbol "${startSymbol}" is excluded, all its ` +
+          `productions are tagged with disabled features.`
+      );
+    }
+
+    // Tokens which are still used are not disabled.
+    const usedSymbols = {};
+
+    Object.keys(bnf).forEach(LHS =>
+      bnf[LHS].forEach(RHS =>
+        symbolsOf(RHS).forEach(symbol => (usedSymbols[symbol] = true))
+      )
+    );
+
+    Object.keys(excludedSymbols).forEach(symbol => {
+      if (!usedSymbols[symbol] && !originalBnf.hasOwnProperty(symbol)) {
+        this._disabledTokens[symbol] = true;
+      }
+    });
+
+    return bnf;
+  }
+
   _processBnf(originalBnf) {
     let processedBnf = [];
     let nonTerminals = Object.keys(originalBnf);
@@ -640,22 +1214,37 @@ export default class Grammar {
       originalBnf[LHS].forEach((RHS, k) => {
         let semanticAction = null;
         let precedence = null;
+        let precedenceTag = null;
+        let errorMessage = null;
 
         if (Array.isArray(RHS)) {
-          let precedenceTag = null;
 
-          if (typeof RHS[1] === 'string') {
+          // A production without an action still may have a `%prec`.
+          if (typeof RHS[1] === 'string' || RHS[1] === null) {
             semanticAction = RHS[1];
-            if (RHS[2] !== null && typeof RHS[2] === 'object') {
-              precedenceTag = RHS[2].prec;
-            }
-          } else if (RHS[1] !== null && typeof RHS[1] === 'object') {
-            precedenceTag = RHS[1].prec;
           }
 
+          const tags = this._getProductionTags(RHS);
+
+          precedenceTag = tags.prec || null;
+          errorMessage = tags.msg || null;
+
           RHS = RHS[0];
 
-          if (precedenceTag && this._operators) {
+          // A `%msg` is the message of the error the production recovers.
+          if (errorMessage && !/(^|\s)error(\s|$)/.test(RHS)) {
+            throw new Error(
+              `%msg "${errorMessage}" of ${LHS} -> ${RHS} is only ` +
+                `allowed in a production with the error token.`
+            );
+          }
+
+          // A `%prec` tag may also be used only to reference the
+          // production from a conflict resolution directive.
+          if (
+            precedenceTag &&
+            this._operators.hasOwnProperty(precedenceTag)
+          ) {
             precedence = this._operators[precedenceTag].precedence;
           }
         }
@@ -668,7 +1257,9 @@ export default class Grammar {
             semanticAction,
             /* isShort */ k > 0,
             /* grammar */ this,
-            precedence
+            precedence,
+            precedenceTag,
+            errorMessage
           )
         );
       });
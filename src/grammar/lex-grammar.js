@@ -16,6 +16,17 @@ const StandardMacros = {
   '<<EOF>>': EOF,
 };
 
+/**
+ * A literal pattern: regular characters, and escaped punctuation.
+ */
+const LITERAL_PATTERN = /^(?:\\[^a-zA-Z0-9]|[^\\^$.|?*+()[\]{}])+$/;
+
+/**
+ * A start condition entered from a lex rule handler, e.g. `this.begin('x')`,
+ * or `self.push_state("x")`.
+ */
+const ENTER_STATE_PATTERN = /\b(?:begin|pushState|push_state)\s*\(\s*(["'])(\w+)\1/g;
+
 /**
  * Class encapsulates operations with a lexical grammar.
  */
@@ -46,11 +57,15 @@ export default class LexGrammar {
    *     "string": 1, // inclusive condition %s
    *     "code": 0,   // exclusive consition %x
    *   },
+   *
+   *   // Skipped between tokens in all inclusive conditions (%ignore),
+   *   // a string, or a list of patterns.
+   *   "ignore": "\\s+",
    * }
    */
-  constructor({macros, rules, startConditions, options}) {
+  constructor({macros, rules, startConditions, options, ignore}) {
     this._macros = macros;
-    this._originalRules = rules;
+    this._originalRules = this._prependIgnoreRules(ignore, rules);
     this._options = options;
     this._extractMacros(macros, this._originalRules);
 
@@ -59,6 +74,9 @@ export default class LexGrammar {
 
     this._startConditions = Object.assign({INITIAL: 0}, startConditions);
     this._rulesByStartConditions = this._processRulesByStartConditions();
+
+    this._checkStartConditions();
+    this._checkDuplicateRules();
   }
 
   /**
@@ -124,6 +142,125 @@ export default class LexGrammar {
     return this._rulesByStartConditions;
   }
 
+  /**
+   * Returns the rules which can never match, since an earlier rule of the
+   * same state matches the same text: `{rule, shadowedBy, state}` records.
+   *
+   * It's a heuristic, only the literal rules (e.g. "if") are checked, which
+   * are shadowed if an earlier rule (e.g. `\w+`) matches the literal: the
+   * tokenizer uses the first matched rule, not the longest match. A rule
+   * of an inclusive state can override a rule without start conditions.
+   */
+  getUnreachableRules() {
+    const unreachable = [];
+
+    for (const state in this._rulesByStartConditions) {
+      const rules = this._rulesByStartConditions[state];
+
+      rules.forEach((rule, index) => {
+        const literal = this._getLiteral(rule);
+
+        if (literal === null) {
+          return;
+        }
+
+        const shadowedBy = rules.slice(0, index).find(earlier => {
+          if (this._overrides(earlier, rule)) {
+            return false;
+          }
+
+          const matcher = earlier.getMatcher();
+          const matched = matcher && matcher.exec(literal);
+          return matched && matched[0].length > 0;
+        });
+
+        if (shadowedBy) {
+          unreachable.push({rule, shadowedBy, state});
+        }
+      });
+    }
+
+    return unreachable;
+  }
+
+  /**
+   * The text matched by a literal rule, or `null` for other rules
+   * (case-insensitive rules are not checked).
+   */
+  _getLiteral(rule) {
+    const matcher = rule.getOriginalMatcher();
+
+    if (rule.isCaseInsensitive() || !LITERAL_PATTERN.test(matcher)) {
+      return null;
+    }
+
+    return matcher.replace(/\\(.)/g, '$1');
+  }
+
+  /**
+   * Whether a rule of a state overrides a rule without start conditions,
+   * included in the state (e.g. `{` in the expression position).
+   */
+  _overrides(earlier, rule) {
+    return earlier.hasStartConditions() && !rule.hasStartConditions();
+  }
+
+  /**
+   * Start conditions used by the rules, and entered from the handlers
+   * should be declared, otherwise the rules are never matched, and an
+   * unknown state is entered. In the handlers only the string literals
+   * passed to `begin`, and `pushState` (`push_state`) are checked.
+   */
+  _checkStartConditions() {
+    const check = (condition, rule, usage) => {
+      if (!this._startConditions.hasOwnProperty(condition)) {
+        throw new Error(
+          `Undeclared start condition "${condition}" ${usage} ` +
+            `the lex rule "${rule.getOriginalMatcher()}".`
+        );
+      }
+    };
+
+    this._rules.forEach(rule => {
+      (rule.getStartConditions() || [])
+        .filter(condition => condition !== '*')
+        .forEach(condition => check(condition, rule, 'of'));
+
+      const handler = rule.getRawHandler() || '';
+      let match;
+
+      while ((match = ENTER_STATE_PATTERN.exec(handler))) {
+        check(match[2], rule, 'entered from');
+      }
+    });
+  }
+
+  /**
+   * Rules with the same pattern in a state: the second one never matches.
+   */
+  _checkDuplicateRules() {
+    for (const state in this._rulesByStartConditions) {
+      const seen = new Map();
+
+      this._rulesByStartConditions[state].forEach(rule => {
+        const matcher =
+          rule.getOriginalMatcher() + (rule.isCaseInsensitive() ? '/i' : '');
+        const earlier = seen.get(matcher);
+
+        if (earlier && !this._overrides(earlier, rule)) {
+          throw new Error(
+            `Duplicate lex rule "${rule.getOriginalMatcher()}" ` +
+              `in the state "${state}".`
+          );
+        }
+
+        if (!earlier) {
+          seen.set(matcher, rule);
+        }
+      });
+    }
+  }
+
   /**
    * Creates rules to index map.
    */
@@ -180,6 +317,19 @@ export default class LexGrammar {
     });
   }
 
+  /**
+   * The `ignore` patterns become the first rules (which are tried before
+   * any other rule), with an empty handler, i.e. skipping the matched text.
+   */
+  _prependIgnoreRules(ignore, rules) {
+    if (!ignore) {
+      return rules;
+    }
+
+    const ignoreRules = [].concat(ignore).map(matcher => [matcher, '']);
+    return ignoreRules.concat(rules);
+  }
+
   /**
    * Builds a map from a start condition to a list of
    * lex rules which should be executed once a lexer
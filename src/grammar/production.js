@@ -8,6 +8,17 @@ import {EPSILON} from '../special-symbols';
 
 import colors from 'colors';
 
+/**
+ * Tag of a target-guarded action block: `{rust: ...}`.
+ */
+const TARGET_TAG_RE = /^\{\s*([a-zA-Z]\w*)\s*:(?!:)/;
+
+/**
+ * Comments, strings, chars, and braces of an action (the quotes of Rust
+ * lifetimes, `&'t str`, are not strings).
+ */
+const ACTION_PIECE_RE = /\/\*[\s\S]*?\*\/|\/\/.*|"(\\.|[^"\\])*"|'(\\.|[^'\\])'|[{}]/g;
+
 /**
  * A production in BNF grammar.
  */
@@ -18,7 +29,17 @@ export default class Production {
    * LHS -> RHS or a short alternative
    *      | RHS if the LHS is the same.
    */
-  constructor(LHS, RHS, number, semanticAction, isShort, grammar, precedence) {
+  constructor(
+    LHS,
+    RHS,
+    number,
+    semanticAction,
+    isShort,
+    grammar,
+    precedence,
+    precedenceTag,
+    errorMessage
+  ) {
     this._rawLHS = LHS;
     this._rawRHS = RHS;
     this._number = number;
@@ -32,9 +53,21 @@ export default class Production {
     }
 
     this._orginialSemanticAction = semanticAction;
+
+    // The target-guarded actions run the `js` block, other targets select
+    // their blocks with `Grammar#selectTargetActions`.
+    this._targetActions = parseTargetActions(semanticAction);
+
+    if (this._targetActions) {
+      semanticAction =
+        this._targetActions.js || this._createDefaultSemanticAction();
+    }
+
     this._rawSemanticAction = this._rewriteNamedArg(semanticAction);
     this._semanticAction = this._buildSemanticAction(this._rawSemanticAction);
     this._precedence = precedence || this._calculatePrecedence();
+    this._precedenceTag = precedenceTag || null;
+    this._errorMessage = errorMessage || null;
   }
 
   /**
@@ -178,6 +211,20 @@ export default class Production {
     return this._precedence;
   }
 
+  /**
+   * Returns the `%prec` tag of this production, if any.
+   */
+  getPrecedenceTag() {
+    return this._precedenceTag;
+  }
+
+  /**
+   * Returns the `%msg` message of this error production, if any.
+   */
+  getErrorMessage() {
+    return this._errorMessage;
+  }
+
   /**
    * Returns original semantic action.
    */
@@ -185,6 +232,15 @@ export default class Production {
     return this._orginialSemanticAction;
   }
 
+  /**
+   * Returns the actions of target languages, if the semantic action
+   * consists of target-guarded blocks: `{rust: ...} {js: ...}` (written
+   * as `{{rust: ...} {js: ...}}` in a grammar). Otherwise returns null.
+   */
+  getTargetActions() {
+    return this._targetActions;
+  }
+
   /**
    * Returns semantic action string.
    */
@@ -333,3 +389,50 @@ export default class Production {
     return 0;
   }
 }
+
+/**
+ * Splits an action into the target-guarded blocks, returns null if it
+ * has any code outside of them.
+ */
+function parseTargetActions(action) {
+  if (!action || !TARGET_TAG_RE.test(action.trim())) {
+    return null;
+  }
+
+  const actions = {};
+  let rest = action.trim();
+
+  while (rest) {
+    const tag = TARGET_TAG_RE.exec(rest);
+    const end = tag && findBlockEnd(rest);
+
+    if (end == null) {
+      return null;
+    }
+
+    actions[tag[1]] = rest.slice(tag[0].length, end).trim();
+    rest = rest.slice(end + 1).trim();
+  }
+
+  return actions;
+}
+
+/**
+ * Position of the `}` closing the block at the start of the code.
+ */
+function findBlockEnd(code) {
+  ACTION_PIECE_RE.lastIndex = 0;
+
+  let depth = 0;
+  let piece;
+
+  while ((piece = ACTION_PIECE_RE.exec(code))) {
+    if (piece[0] === '{') {
+      depth++;
+    } else if (piece[0] === '}' && --depth === 0) {
+      return piece.index;
+    }
+  }
+
+  return null;
+}
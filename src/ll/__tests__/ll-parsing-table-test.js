@@ -0,0 +1,113 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import Grammar from '../../grammar/grammar';
+import {MODES as GRAMMAR_MODE} from '../../grammar/grammar-mode';
+import LLParsingTable from '../ll-parsing-table';
+
+function llGrammar(grammarString) {
+  return Grammar.fromString(grammarString, {mode: GRAMMAR_MODE.LL1});
+}
+
+describe('ll-parsing-table', () => {
+  it('ll1-grammar', () => {
+    const grammar = llGrammar(`
+      %%
+
+      E
+        : T E'
+        ;
+
+      E'
+        : "+" T E'
+        | /* empty */
+        ;
+
+      T
+        : "id"
+        | "(" E ")"
+        ;
+    `);
+
+    const table = new LLParsingTable({grammar});
+
+    expect(table.getLeftRecursionCycles()).toEqual([]);
+    expect(table.get()).toEqual({
+      E: {'"id"': '1', '"("': '1'},
+      "E'": {'"+"': '2', '")"': '3', $: '3'},
+      T: {'"id"': '4', '"("': '5'},
+    });
+  });
+
+  it('direct left recursion', () => {
+    const grammar = llGrammar(`
+      %%
+
+      E
+        : E "+" T
+        | T
+        ;
+
+      T
+        : "id"
+        ;
+    `);
+
+    expect(() => new LLParsingTable({grammar})).toThrow(
+      'Left recursion is not supported in LL(1) mode, found cycles:\n' +
+        '- E -> E\n\n' +
+        'Rewrite the rules using right recursion, or use an LR mode.'
+    );
+  });
+
+  it('indirect left recursion', () => {
+    const grammar = llGrammar(`
+      %%
+
+      S
+        : A "a"
+        | "b"
+        ;
+
+      A
+        : B "c"
+        ;
+
+      B
+        : S "d"
+        | "e"
+        ;
+
+      C
+        : C "f"
+        | "g"
+        ;
+    `);
+
+    expect(() => new LLParsingTable({grammar})).toThrow(
+      'found cycles:\n- S -> A -> B -> S\n- C -> C'
+    );
+  });
+
+  it('hidden left recursion', () => {
+    const grammar = llGrammar(`
+      %%
+
+      S
+        : Opt S "a"
+        | "b"
+        ;
+
+      Opt
+        : "c"
+        | /* empty */
+        ;
+    `);
+
+    expect(() => new LLParsingTable({grammar})).toThrow(
+      'found cycles:\n- S -> S'
+    );
+  });
+});
@@ -189,7 +189,10 @@ export default class LLParser {
   }
 
   _unexpectedEndOfInput() {
-    this._parseError(`Unexpected end of input.`);
+    this._parseError(
+      `Unexpected end of input.`,
+      this._tokenizer.getEndOfInputLoc()
+    );
   }
 
   _unexpectedToken(token) {
@@ -204,7 +207,13 @@ export default class LLParser {
     );
   }
 
-  _parseError(message) {
-    throw new SyntaxError(message);
+  _parseError(message, loc = null) {
+    const error = new SyntaxError(message);
+
+    if (loc) {
+      error.loc = loc;
+    }
+
+    throw error;
   }
 }
@@ -9,6 +9,7 @@ import TablePrinter from '../table-printer';
 import {EOF} from '../special-symbols';
 import colors from 'colors';
 import debug from '../debug';
+import timing from '../timing';
 
 /**
  * LL parsing table.
@@ -66,13 +67,18 @@ export default class LLParsingTable {
     this._setsGenerator = new SetsGenerator({grammar});
 
     debug.time('Building LL parsing table');
+    timing.start('Parsing table');
 
     this._tableTokens = grammar
       .getTerminals()
       .concat(grammar.getTokens(), GrammarSymbol.get(EOF));
 
+    // A left-recursive grammar can't be parsed top-down.
+    this._validateLeftRecursion();
+
     this._table = this._build();
 
+    timing.end('Parsing table');
     debug.timeEnd('Building LL parsing table');
   }
 
@@ -203,6 +209,136 @@ export default class LLParsingTable {
     return table;
   }
 
+  /**
+   * Returns left recursion cycles of the grammar, each cycle is a list
+   * of non-terminals, which starts and ends with the same non-terminal:
+   *
+   *   E -> E "+" T        [['E', 'E']]
+   *
+   *   A -> B "a"          [['A', 'B', 'A']]
+   *   B -> A "b"
+   *
+   * A non-terminal derives another one at the left, if it's the first
+   * symbol of the RHS, or follows only the symbols deriving ε (hidden
+   * left recursion).
+   */
+  getLeftRecursionCycles() {
+    const nullable = this._getNullableSymbols();
+    const leftSymbols = {};
+
+    for (const production of this._grammar.getProductions()) {
+      const lhsSymbol = production.getLHS().getSymbol();
+
+      if (!leftSymbols[lhsSymbol]) {
+        leftSymbols[lhsSymbol] = [];
+      }
+
+      if (production.isEpsilon()) {
+        continue;
+      }
+
+      for (const symbol of production.getRHSSymbols()) {
+        if (!this._grammar.isNonTerminal(symbol)) {
+          break;
+        }
+
+        if (!leftSymbols[lhsSymbol].includes(symbol)) {
+          leftSymbols[lhsSymbol].push(symbol);
+        }
+
+        if (!nullable[symbol]) {
+          break;
+        }
+      }
+    }
+
+    const cycles = [];
+    const inCycle = {};
+
+    // Finds a path from the symbol back to the start one (depth-first).
+    const findPath = (start, symbol, path, visited) => {
+      for (const next of leftSymbols[symbol] || []) {
+        if (next === start) {
+          return path.concat(next);
+        }
+        if (!visited[next]) {
+          visited[next] = true;
+          const found = findPath(start, next, path.concat(next), visited);
+          if (found) {
+            return found;
+          }
+        }
+      }
+      return null;
+    };
+
+    for (const symbol in leftSymbols) {
+      if (inCycle[symbol]) {
+        continue;
+      }
+
+      const cycle = findPath(symbol, symbol, [symbol], {});
+
+      if (cycle) {
+        cycle.forEach(symbolInCycle => (inCycle[symbolInCycle] = true));
+        cycles.push(cycle);
+      }
+    }
+
+    return cycles;
+  }
+
+  /**
+   * Throws if the grammar has left recursion, listing the cycles.
+   */
+  _validateLeftRecursion() {
+    const cycles = this.getLeftRecursionCycles();
+
+    if (cycles.length === 0) {
+      return;
+    }
+
+    const messages = cycles.map(cycle => cycle.join(' -> '));
+
+    throw new Error(
+      `Left recursion is not supported in ${this._grammar.getMode()} mode, ` +
+        `found cycles:\n- ${messages.join('\n- ')}\n\n` +
+        `Rewrite the rules using right recursion, or use an LR mode.`
+    );
+  }
+
+  /**
+   * Non-terminals which derive ε.
+   */
+  _getNullableSymbols() {
+    const nullable = {};
+    const productions = this._grammar.getProductions();
+
+    let changed = true;
+
+    while (changed) {
+      changed = false;
+
+      for (const production of productions) {
+        const lhsSymbol = production.getLHS().getSymbol();
+
+        if (nullable[lhsSymbol]) {
+          continue;
+        }
+
+        if (
+          production.isEpsilon() ||
+          production.getRHSSymbols().every(symbol => nullable[symbol])
+        ) {
+          nullable[lhsSymbol] = true;
+          changed = true;
+        }
+      }
+    }
+
+    return nullable;
+  }
+
   entryHasConflict(entry) {
     return entry.includes('/');
   }
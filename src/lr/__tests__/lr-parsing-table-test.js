@@ -146,4 +146,109 @@ describe('lr-parsing-table', () => {
       expectedTable
     );
   });
+
+  it('prefer-shift directive', () => {
+    const productions = `
+      %%
+
+      Stmt
+        : 'if' 'e' 'then' Stmt %prec IFX
+        | 'if' 'e' 'then' Stmt 'else' Stmt
+        | 'x'
+        ;
+    `;
+
+    const shiftElse = {
+      conflict: 'r1/s7',
+      resolved: 'shift (%prefer-shift directive)',
+    };
+
+    // Without a directive the dangling "else" is a conflict.
+    const table = new LRParsingTable({
+      grammar: Grammar.fromString(productions, {mode: GRAMMAR_MODE.LALR1}),
+    });
+    expect(table.get()['6']["'else'"]).toBe('r1/s7');
+    expect(table.getConflictsData()['6']["'else'"].resolved).toBe(false);
+
+    // A directive may reference a lookahead token, a `%prec` tag, or a rule.
+    ["%prefer-shift 'else'", '%prefer-shift IFX', '%prefer-shift Stmt'].forEach(
+      directive => {
+        const table = new LRParsingTable({
+          grammar: Grammar.fromString(directive + productions, {
+            mode: GRAMMAR_MODE.LALR1,
+          }),
+        });
+        expect(table.get()['6']["'else'"]).toBe('s7');
+        expect(table.getConflictsData()).toEqual({'6': {"'else'": shiftElse}});
+      }
+    );
+
+    const reduceTable = new LRParsingTable({
+      grammar: Grammar.fromString("%prefer-reduce 'else'" + productions, {
+        mode: GRAMMAR_MODE.LALR1,
+      }),
+    });
+    expect(reduceTable.get()['6']["'else'"]).toBe('r1');
+  });
+
+  it('expect directive', () => {
+    const productions = `
+      %%
+
+      Stmt
+        : 'if' 'e' 'then' Stmt
+        | 'if' 'e' 'then' Stmt 'else' Stmt
+        | 'x'
+        ;
+    `;
+
+    const tableOf = string =>
+      new LRParsingTable({
+        grammar: Grammar.fromString(string, {mode: GRAMMAR_MODE.LALR1}),
+      });
+
+    // The expected conflict is resolved as shift.
+    const table = tableOf('%expect 1' + productions);
+    expect(table.get()['6']["'else'"]).toBe('s7');
+    expect(table.getConflictsData()['6']["'else'"].resolved).toBe(
+      'no precedence, shift by default'
+    );
+
+    expect(() => tableOf('%expect 0' + productions)).toThrow(
+      'Expected 0 shift-reduce conflicts (%expect 0), found 1.'
+    );
+    expect(() => tableOf('%expect 2' + productions)).toThrow(
+      'Expected 2 shift-reduce conflicts (%expect 2), found 1.'
+    );
+
+    // Conflicts resolved by a directive are not counted.
+    expect(() =>
+      tableOf("%expect 0\n%prefer-shift 'else'" + productions)
+    ).not.toThrow();
+  });
+
+  it('prefer-reduce directive in reduce-reduce conflict', () => {
+    const grammar = Grammar.fromString(
+      `
+      %prefer-reduce Expr
+
+      %%
+
+      Stmt
+        : Type ';'
+        | Expr ';'
+        ;
+
+      Type : ID ;
+      Expr : ID ;
+    `,
+      {mode: GRAMMAR_MODE.LALR1}
+    );
+
+    const table = new LRParsingTable({grammar});
+    const conflict = table.getConflictsData()['4']["';'"];
+
+    expect(table.get()['4']["';'"]).toBe('r4');
+    expect(conflict.resolved).toBe('reduce (%prefer-reduce directive) 4');
+  });
 });
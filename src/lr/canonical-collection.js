@@ -10,6 +10,7 @@ import SetsGenerator from '../sets-generator';
 import {EOF} from '../special-symbols';
 
 import debug from '../debug';
+import timing from '../timing';
 
 /**
  * Canonical collection of LR items.
@@ -38,6 +39,7 @@ export default class CanonicalCollection {
     this._states = new Set();
 
     debug.time('Building canonical collection');
+    timing.start('LR states');
 
     // Root item for the augmented production, "closure" and "goto"
     // operations applied on this item build the entire collection.
@@ -72,6 +74,8 @@ export default class CanonicalCollection {
       debug.timeEnd('Compressing CLR to LALR');
       debug.log(`Number of states after compression: ${this._states.size}`);
     }
+
+    timing.end('LR states');
   }
 
   /**
@@ -56,10 +56,7 @@ export default class LRParserGeneratorDefault extends BaseParserGenerator {
     return this.getGrammar()
       .getProductions()
       .map(production => {
-        let LHS = production
-          .getLHS()
-          .getSymbol()
-          .replace(/'/g, "\\'");
+        let LHS = production.getLHS().getSymbol();
         let RHSLength = production.isEpsilon() ? 0 : production.getRHS().length;
         let semanticAction = this.buildSemanticAction(production);
 
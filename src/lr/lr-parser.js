@@ -18,10 +18,13 @@ import path from 'path';
 const EntryType = LRParsingTable.EntryType;
 
 export default class LRParser {
-  constructor({grammar, parserModule, resolveConflicts}) {
+  constructor({grammar, parserModule, resolveConflicts, parseTree = false}) {
     this._grammar = grammar;
     this._parserModule = parserModule;
 
+    // Whether the parse tree is built (the `tree` of the result).
+    this._parseTree = parseTree;
+
     this._canonicalCollection = new CanonicalCollection({
       grammar: this._grammar,
     });
@@ -173,6 +176,10 @@ export default class LRParser {
             result.value = parsed.semanticValue;
           }
 
+          if (this._parseTree) {
+            result.tree = parsed.node;
+          }
+
           if (this._yyparse.onParseEnd) {
             this._yyparse.onParseEnd(result.value);
           }
@@ -186,7 +193,10 @@ export default class LRParser {
   }
 
   _unexpectedEndOfInput() {
-    this._parseError(`Unexpected end of input.`);
+    this._parseError(
+      `Unexpected end of input.`,
+      this._tokenizer.getEndOfInputLoc()
+    );
   }
 
   _unexpectedToken(token) {
@@ -201,8 +211,14 @@ export default class LRParser {
     );
   }
 
-  _parseError(message) {
-    throw new SyntaxError(message);
+  _parseError(message, loc = null) {
+    const error = new SyntaxError(message);
+
+    if (loc) {
+      error.loc = loc;
+    }
+
+    throw error;
   }
 
   _conflictError(conflictType, state, column) {
@@ -230,14 +246,17 @@ export default class LRParser {
       };
     }
 
-    this._stack.push(
-      {
-        symbol: token.type,
-        semanticValue: token.value,
-        loc,
-      },
-      Number(entry.slice(1))
-    );
+    const stackEntry = {
+      symbol: token.type,
+      semanticValue: token.value,
+      loc,
+    };
+
+    if (this._parseTree) {
+      stackEntry.node = {symbol: token.type, value: token.value};
+    }
+
+    this._stack.push(stackEntry, Number(entry.slice(1)));
   }
 
   _reduce(entry, token) {
@@ -249,6 +268,8 @@ export default class LRParser {
     const locationArgs =
       hasSemanticAction && this._grammar.shouldCaptureLocations() ? [] : null;
 
+    const children = this._parseTree ? [] : null;
+
     // Pop 2x symbols from the stack (RHS + state number for each),
     // unless it's an ε-production for which nothing to pop.
     if (!production.isEpsilon()) {
@@ -259,6 +280,10 @@ export default class LRParser {
         // Pop production symbol.
         let stackEntry = this._stack.pop();
 
+        if (children) {
+          children.unshift(stackEntry.node);
+        }
+
         if (hasSemanticAction) {
           semanticValueArgs.unshift(stackEntry.semanticValue);
 
@@ -274,6 +299,10 @@ export default class LRParser {
 
     let reduceStackEntry = {symbol: symbolToReduceWith};
 
+    if (children) {
+      reduceStackEntry.node = {symbol: symbolToReduceWith, children};
+    }
+
     if (hasSemanticAction) {
       CodeUnit.setBindings({
         yytext: token ? token.value : '',
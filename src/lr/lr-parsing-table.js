@@ -9,6 +9,7 @@ import TablePrinter from '../table-printer';
 import {EOF} from '../special-symbols';
 import colors from 'colors';
 import debug from '../debug';
+import timing from '../timing';
 
 /**
  * The LR parsing table is built by traversing the graph of the
@@ -115,7 +116,11 @@ export default class LRParsingTable {
     // Stores conflicts data.
     this._conflictsData = {};
 
+    // Shift-reduce conflicts without precedence (checked by `%expect N`).
+    this._defaultConflictsCount = 0;
+
     debug.time('Building LR parsing table');
+    timing.start('Parsing table');
 
     this._action = grammar
       .getTerminals()
@@ -124,6 +129,8 @@ export default class LRParsingTable {
     this._goto = grammar.getNonTerminals();
     this._table = {};
     this._build();
+    this._checkExpectedConflicts();
+    timing.end('Parsing table');
     debug.timeEnd('Building LR parsing table');
   }
 
@@ -131,6 +138,13 @@ export default class LRParsingTable {
     return this._table;
   }
 
+  /**
+   * Returns the canonical collection of LR items the table is built from.
+   */
+  getCanonicalCollection() {
+    return this._canonicalCollection;
+  }
+
   /**
    * Returns conflicts data.
    */
@@ -307,9 +321,28 @@ export default class LRParsingTable {
 
     const [reducePart, shiftPart] = this.splitSRParts(entry);
 
-    // Default resolution is to shift if no precedence is specified.
+    // Explicit `%prefer-shift`, or `%prefer-reduce` directive.
+    const preference = this._getPreference(
+      this._grammar.getProduction(reducePart.slice(1)),
+      symbol
+    );
+
+    if (preference) {
+      row[symbol] = preference === 'shift' ? shiftPart : reducePart;
+      this._getStateConflictData(state)[symbol].resolved =
+        `${preference} (%prefer-${preference} directive)`;
+      return;
+    }
+
+    // Default resolution is to shift if no precedence is specified,
+    // also if the conflicts are expected with `%expect N`.
     if (!operators.hasOwnProperty(symbol)) {
-      if (this._shouldResolveConflicts) {
+      this._defaultConflictsCount++;
+
+      if (
+        this._shouldResolveConflicts ||
+        this._grammar.getExpectedConflicts() !== null
+      ) {
         row[symbol] = shiftPart;
         this._getStateConflictData(state)[symbol].resolved =
           'no precedence, shift by default';
@@ -379,16 +412,48 @@ export default class LRParsingTable {
     }
   }
 
-  _resolveRRConflict(state, row, symbol) {
-    if (!this._shouldResolveConflicts) {
+  /**
+   * With `%expect N` the number of the shift-reduce conflicts resolved
+   * by default should be N.
+   */
+  _checkExpectedConflicts() {
+    const expected = this._grammar.getExpectedConflicts();
+
+    if (expected === null || expected === this._defaultConflictsCount) {
       return;
     }
 
+    throw new Error(
+      `Expected ${expected} shift-reduce conflict` +
+      `${expected === 1 ? '' : 's'} (%expect ${expected}), ` +
+      `found ${this._defaultConflictsCount}.`
+    );
+  }
+
+  _resolveRRConflict(state, row, symbol) {
     const entry = row[symbol];
     const [r1, r2] = entry.split('/');
 
     const symbolConflictData = this._getStateConflictData(state)[symbol];
 
+    // A `%prefer-reduce` directive may reference one of the
+    // productions by its rule, or by its `%prec` tag.
+    const preferred = [r1, r2].filter(reducePart => {
+      const production = this._grammar.getProduction(reducePart.slice(1));
+      return this._getPreference(production) === 'reduce';
+    });
+
+    if (preferred.length === 1) {
+      row[symbol] = preferred[0];
+      symbolConflictData.resolved =
+        'reduce (%prefer-reduce directive) ' + row[symbol].slice(1);
+      return;
+    }
+
+    if (!this._shouldResolveConflicts) {
+      return;
+    }
+
     // R/R conflicts are resolved by choosing a production that
     // goes first in the grammar (i.e. its number is smaller).
     row[symbol] = Number(r1.slice(1)) < Number(r2.slice(1)) ? r1 : r2;
@@ -397,6 +462,29 @@ export default class LRParsingTable {
       'first in order production is chosen ' + row[symbol].slice(1);
   }
 
+  /**
+   * Returns a preferred action (`shift` or `reduce`) for a conflict,
+   * looking up the production's `%prec` tag, its rule, and then the
+   * lookahead symbol.
+   */
+  _getPreference(production, symbol) {
+    const preferences = this._grammar.getPreferences();
+
+    const candidates = [
+      production.getPrecedenceTag(),
+      production.getLHS().getSymbol(),
+      symbol,
+    ];
+
+    for (const candidate of candidates) {
+      if (candidate && preferences.hasOwnProperty(candidate)) {
+        return preferences[candidate];
+      }
+    }
+
+    return null;
+  }
+
   splitSRParts(entry) {
     let srConflict = entry.split('/');
 
@@ -0,0 +1,57 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+/**
+ * Number of spaces per a level of the tree.
+ */
+const INDENT_WIDTH = 2;
+
+/**
+ * Parse tree of an input (the `parseTree` option of the LR parser): a node
+ * of a non-terminal has the children of its production, a node of a token
+ * has its value:
+ *
+ *   {symbol: 'E', children: [{symbol: 'NUMBER', value: '2'}]}
+ */
+const ParseTree = {
+  /**
+   * Renders the tree, a node per line, indented by its depth. The `indent`
+   * is the level of the root, e.g. to print the tree in a nested list:
+   *
+   *   E
+   *     E
+   *       NUMBER "2"
+   *     +
+   *     ...
+   *
+   * A token is shown with its value, unless it's the same as the token
+   * type (e.g. `+`).
+   */
+  pretty(node, indent = 0) {
+    const lines = [];
+
+    const visit = (node, level) => {
+      const padding = ' '.repeat(level * INDENT_WIDTH);
+
+      if (!node.children) {
+        lines.push(
+          node.value === node.symbol
+            ? `${padding}${node.symbol}`
+            : `${padding}${node.symbol} ${JSON.stringify(node.value)}`
+        );
+        return;
+      }
+
+      lines.push(`${padding}${node.symbol}`);
+      node.children.forEach(child => visit(child, level + 1));
+    };
+
+    visit(node, indent);
+
+    return lines.join('\n');
+  },
+};
+
+export default ParseTree;
@@ -286,7 +286,10 @@ const CppParserGeneratorTrait = {
     const lexRules = this._grammar.getLexGrammar().getRules().map(lexRule => {
       let handler = lexRule.getRawHandler();
 
-      if (!handler.includes('return')) {
+      // An empty handler (e.g. of the `%ignore` rules) skips the token.
+      if (!handler.trim()) {
+        handler = 'return %empty;';
+      } else if (!handler.includes('return')) {
         handler = `return ${handler}`;
       }
 
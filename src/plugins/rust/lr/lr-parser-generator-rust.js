@@ -5,6 +5,7 @@
 
 const LRParserGeneratorDefault = require(ROOT + 'lr/lr-parser-generator-default').default;
 const RustParserGeneratorTrait = require('../rust-parser-generator-trait');
+const rewriteIterativeLists = require('../rust-iterative-lists').default;
 
 import fs from 'fs';
 
@@ -29,11 +30,21 @@ export default class LRParserGeneratorRust extends LRParserGeneratorDefault {
     outputFile,
     options = {},
   }) {
+    // Only the `{rust: ...}` blocks of the target-guarded actions.
+    grammar.selectTargetActions('rust');
+
+    // Right-recursive lists are rewritten before the table is built.
+    if (options.iterativeLists) {
+      rewriteIterativeLists(grammar);
+    }
+
     super({grammar, outputFile, options})
       .setTemplate(RUST_LR_PARSER_TEMPLATE);
 
     this._lexHandlers = [];
+    this._lexRulePatterns = [];
     this._productionHandlers = [];
+    this._actionHelpers = [];
 
     /**
      * Stores all used types of the arguments, and return values.
@@ -56,10 +67,36 @@ export default class LRParserGeneratorRust extends LRParserGeneratorDefault {
     Object.assign(this, RustParserGeneratorTrait);
   }
 
+  /**
+   * Generates parser module, with the `emitCrate` option the manifest
+   * of its crate, with the `emitRepl` option a REPL binary, and with
+   * the `emitBench` option a benchmark.
+   */
+  generate() {
+    const result = super.generate();
+
+    if (this._outputFile && this.getOptions().emitCrate) {
+      this.writeCrateManifest();
+    }
+
+    if (this._outputFile && this.getOptions().emitRepl) {
+      this.writeRepl();
+    }
+
+    if (this._outputFile && this.getOptions().emitBench != null) {
+      this.writeBench();
+    }
+
+    return result;
+  }
+
   /**
    * Generates parser code.
    */
   generateParserData() {
+    // The tokens of the classes are added to the tokens map.
+    this.generateTokenClasses();
+
     // Lexical grammar.
     this.generateTokenizer();
 
@@ -68,14 +105,27 @@ export default class LRParserGeneratorRust extends LRParserGeneratorDefault {
 
     // Tables.
     this.generateTokensTable();
+    this.generateEncodedSymbolType();
+    this.generateDocs();
     this.generateParseTable();
 
     this.generateLexHandlers();
     this.generateProductionHandlers();
     this.generateStackValueEnum();
+    this.generateParseWith();
+    this.generateParseStream();
+    this.generateParserTrait();
 
     // The module include which should include at least
     // result type: type TResult = <...>;
     this.generateModuleInclude();
+
+    this.generateGrammarHash();
+    this.generateStableIds();
+    this.generateStableApi();
+    this.generateNodeAttributes();
+    this.generateProcMacroSpans();
+    this.generateWasmEntry();
+    this.generateSmokeTest();
   }
 };
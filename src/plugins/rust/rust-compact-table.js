@@ -0,0 +1,74 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+/**
+ * Packs the rows of a parsing table into a displacement table: the rows
+ * (maps from an encoded symbol to an entry) are interleaved in one array
+ * of entries, each row at its base offset, so the entry of a state and a
+ * symbol is at `base[state] + symbol`, if the slot belongs to the state:
+ *
+ *   check[base[state] + symbol] === state
+ *
+ * The rows are placed from the largest one, each at the first offset
+ * where its slots are free. Most of the slots are filled, since the rows
+ * of LR tables are sparse, and mostly disjoint.
+ *
+ * Returns `{base, check, entries}`, the free slots have the `-1` check,
+ * and the `null` entry.
+ */
+export default function packTable(rows) {
+  const symbolsOf = rows.map(row =>
+    Object.keys(row)
+      .map(Number)
+      .sort((a, b) => a - b)
+  );
+
+  const order = rows
+    .map((_, state) => state)
+    .sort(
+      (a, b) => symbolsOf[b].length - symbolsOf[a].length || a - b
+    );
+
+  const base = rows.map(() => 0);
+  const check = [];
+  const entries = [];
+
+  // All slots before it are taken.
+  let firstFree = 0;
+
+  order.forEach(state => {
+    const symbols = symbolsOf[state];
+
+    if (symbols.length === 0) {
+      return;
+    }
+
+    let offset = Math.max(0, firstFree - symbols[0]);
+
+    while (symbols.some(symbol => check[offset + symbol] !== undefined)) {
+      offset++;
+    }
+
+    base[state] = offset;
+
+    symbols.forEach(symbol => {
+      check[offset + symbol] = state;
+      entries[offset + symbol] = rows[state][symbol];
+    });
+
+    while (check[firstFree] !== undefined) {
+      firstFree++;
+    }
+  });
+
+  for (let i = 0; i < check.length; i++) {
+    if (check[i] === undefined) {
+      check[i] = -1;
+      entries[i] = null;
+    }
+  }
+
+  return {base, check, entries};
+}
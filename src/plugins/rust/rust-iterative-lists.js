@@ -0,0 +1,158 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import Production from '../../grammar/production';
+
+/**
+ * Result type in a Rust action: `|$1: Token| -> i32;`.
+ */
+const RESULT_TYPE_RE = /\|[^|]*\|\s*->\s*([\w&][\w<>, '&]*);/;
+
+/**
+ * Rewrites right-recursive lists without semantic actions into the
+ * left-recursive ones, which are parsed iteratively (the LR stack doesn't
+ * grow with the list), and builds a flat `Vec` of the items:
+ *
+ *   Items : Item Items | Item        ->  Items : Items Item | Item
+ *   Items : Item ',' Items | Item    ->  Items : Items ',' Item | Item
+ *   Items : Item Items | ε           ->  Items : Items Item | ε
+ *
+ * The value of the list is `Vec<T>`, where `T` is the type of the item:
+ * `Token` for a token (or its `%token <T>` type), or the result type of
+ * the item's productions.
+ */
+export default function rewriteIterativeLists(grammar) {
+  const productions = grammar.getProductions();
+
+  const lists = grammar
+    .getNonTerminals()
+    .map(symbol => getListShape(grammar, symbol.getSymbol()))
+    .filter(Boolean);
+
+  lists.forEach(({recursive, base, item, separator}) => {
+    const LHS = recursive.getLHS().getSymbol();
+    const RHS = [LHS, separator, item.symbol].filter(Boolean).join(' ');
+
+    const itemArg = separator ? '$3' : '$2';
+    const listType = `Vec<${item.type}>`;
+
+    productions[productions.indexOf(recursive)] = new Production(
+      LHS,
+      RHS,
+      recursive.getNumber(),
+      `|$1: ${listType}, ${itemArg}: ${item.type}| -> ${listType}; ` +
+        `$1.push(${itemArg}); $$ = $1`,
+      /* isShort */ recursive.toString() !== recursive.toFullString(),
+      grammar,
+      recursive.getPrecedence(),
+      recursive.getPrecedenceTag(),
+      recursive.getErrorMessage()
+    );
+
+    productions[productions.indexOf(base)] = new Production(
+      LHS,
+      base.isEpsilon() ? '' : item.symbol,
+      base.getNumber(),
+      base.isEpsilon()
+        ? `|| -> ${listType}; $$ = Vec::new()`
+        : `|$1: ${item.type}| -> ${listType}; $$ = vec![$1]`,
+      /* isShort */ base.toString() !== base.toFullString(),
+      grammar,
+      base.getPrecedence(),
+      base.getPrecedenceTag(),
+      base.getErrorMessage()
+    );
+  });
+}
+
+/**
+ * Matches the productions of a non-terminal with a right-recursive list
+ * without actions: `L : X L | X`, `L : X sep L | X`, or `L : X L | ε`.
+ */
+function getListShape(grammar, LHS) {
+  const productions = grammar
+    .getProductions()
+    .filter(production => production.getLHS().isSymbol(LHS));
+
+  if (productions.length !== 2) {
+    return null;
+  }
+
+  const [recursive, base] =
+    productions[0].getRHSSymbols().length > 1
+      ? productions
+      : [productions[1], productions[0]];
+
+  const RHS = recursive.getRHSSymbols();
+  const item = RHS[0];
+
+  if (
+    (RHS.length !== 2 && RHS.length !== 3) ||
+    RHS[RHS.length - 1] !== LHS ||
+    item === LHS ||
+    recursive.getOriginalSemanticAction() != null
+  ) {
+    return null;
+  }
+
+  const separator = RHS.length === 3 ? RHS[1] : null;
+
+  if (separator && !grammar.isTokenSymbol(separator)) {
+    return null;
+  }
+
+  // The base has no action, or the default `$$ = $1`.
+  const baseAction = base.getOriginalSemanticAction();
+
+  if (base.isEpsilon()) {
+    if (separator || baseAction != null) {
+      return null;
+    }
+  } else if (
+    base.getRHSSymbols().length !== 1 ||
+    base.getRHSSymbols()[0] !== item ||
+    !/^\s*\$\$\s*=\s*\$1\s*;?\s*$/.test(baseAction || '')
+  ) {
+    return null;
+  }
+
+  return {
+    recursive,
+    base,
+    separator,
+    item: {symbol: item, type: getItemType(grammar, LHS, item)},
+  };
+}
+
+/**
+ * Type of a list item: `Token` (or the value type of a typed token),
+ * or the result type of its productions.
+ */
+function getItemType(grammar, LHS, item) {
+  if (grammar.isTokenSymbol(item)) {
+    return grammar.getTokenTypes()[item] || 'Token';
+  }
+
+  const productions = grammar.getProductionsForSymbol(item);
+
+  for (const production of productions) {
+    const resultType = RESULT_TYPE_RE.exec(
+      production.getOriginalSemanticAction() || ''
+    );
+
+    if (resultType) {
+      return resultType[1].trim();
+    }
+  }
+
+  if (productions.some(production => production.derivesPropagatingToken())) {
+    return 'Token';
+  }
+
+  throw new Error(
+    `Unknown type of the "${item}" items of the "${LHS}" list, ` +
+      `specify the result type in an action of "${item}".`
+  );
+}
@@ -0,0 +1,205 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+/**
+ * A keyword rule: a word with the word boundary, `if\b` (the leading `\b`
+ * of `\bif\b` always matches at the start of the tokenized string).
+ */
+const KEYWORD_PATTERN = /^\^?(?:\\b)?([A-Za-z_]\w*)\\b$/;
+
+/**
+ * The handler of a keyword rule only returns its token.
+ */
+const KEYWORD_HANDLER = /^\s*return\s+(["'])((?:(?!\1).)+)\1\s*;?\s*$/;
+
+/**
+ * Characters after a keyword to check that the identifier rule matches
+ * the same text as the keyword rule: the keyword without the word chars
+ * after it, and a longer word with them.
+ */
+const NON_WORD_PROBES = [' ', '\n', '\t', ...'()[]{}<>.,;:+-*/%=!?&|^~@#$"\'`\\'];
+const WORD_PROBES = ['a', 'z', 'A', 'Z', '0', '9', '_', 'é'];
+
+/**
+ * Finds the keyword rules, which can be matched by the identifier rule,
+ * and a lookup of the matched text in its handler:
+ *
+ *   if\b      return "IF";
+ *   else\b    return "ELSE";
+ *   \w+       return "ID";
+ *
+ * So the keywords are not matched one by one, the identifier rule matches
+ * `match yytext { "if" => return "IF", "else" => return "ELSE", _ => {} }`.
+ *
+ * The keyword rules should be right before the identifier rule in all its
+ * start conditions, and the identifier should match the keyword in the
+ * same text as the keyword rule (and a longer word if a word char follows).
+ *
+ * Returns `{rulesByConditions, keywords, keywordRules}`: the rules of the
+ * start conditions without the keyword rules, the keywords of the identifier
+ * rules, a map from a rule to the list of `[word, token]`, and the keyword
+ * rules of the identifier rules (in the order of the keywords).
+ */
+export default function buildKeywordTable(lexGrammar) {
+  const rulesByConditions = lexGrammar.getRulesByStartConditions();
+  const keywords = new Map();
+  const keywordRules = new Map();
+
+  const candidates = new Map();
+
+  for (const condition in rulesByConditions) {
+    const rules = rulesByConditions[condition];
+
+    rules.forEach((rule, index) => {
+      if (getKeyword(rule)) {
+        return;
+      }
+
+      const block = [];
+
+      for (let i = index - 1; i >= 0 && getKeyword(rules[i]); i--) {
+        block.unshift(rules[i]);
+      }
+
+      if (!candidates.has(rule)) {
+        candidates.set(rule, []);
+      }
+
+      candidates.get(rule).push(new Set(block));
+    });
+  }
+
+  const prunedRules = new Set();
+
+  candidates.forEach((blocks, identifier) => {
+    const matcher = getIdentifierMatcher(identifier);
+
+    if (!matcher) {
+      return;
+    }
+
+    // Keywords right before the identifier in all its conditions.
+    const table = [...blocks[0]].filter(
+      rule =>
+        blocks.every(block => block.has(rule)) &&
+        matchesKeyword(matcher, getKeyword(rule).word)
+    );
+
+    const words = new Set();
+    const entries = [];
+    const entryRules = [];
+
+    table.forEach(rule => {
+      const {word, token} = getKeyword(rule);
+
+      if (!words.has(word)) {
+        words.add(word);
+        entries.push([word, token]);
+        entryRules.push(rule);
+      }
+
+      prunedRules.add(rule);
+    });
+
+    if (entries.length > 0) {
+      keywords.set(identifier, entries);
+      keywordRules.set(identifier, entryRules);
+    }
+  });
+
+  const result = {};
+
+  for (const condition in rulesByConditions) {
+    result[condition] = rulesByConditions[condition].filter(
+      rule => !prunedRules.has(rule)
+    );
+  }
+
+  return {rulesByConditions: result, keywords, keywordRules};
+}
+
+/**
+ * The word, and the token of a keyword rule, or `null` for other rules.
+ */
+function getKeyword(rule) {
+  if (rule.isCaseInsensitive()) {
+    return null;
+  }
+
+  const pattern = KEYWORD_PATTERN.exec(rule.getRawMatcher());
+  const handler = KEYWORD_HANDLER.exec(rule.getRawHandler() || '');
+
+  if (!pattern || !handler) {
+    return null;
+  }
+
+  return {word: pattern[1], token: handler[2]};
+}
+
+/**
+ * A JS regex of the identifier rule, with the Unicode word chars as in
+ * the Rust regexes, or `null` if it's not a JS regex.
+ */
+function getIdentifierMatcher(rule) {
+  if (rule.isCaseInsensitive()) {
+    return null;
+  }
+
+  try {
+    return new RegExp(`^(?:${unicodeWords(rule.getRawMatcher())})`, 'u');
+  } catch (e) {
+    return null;
+  }
+}
+
+/**
+ * Replaces `\w` with the Unicode word chars: a class outside of the
+ * classes, and its chars inside a class.
+ */
+function unicodeWords(pattern) {
+  const WORD = '\\p{L}\\p{M}\\p{N}_';
+
+  let result = '';
+  let inClass = false;
+
+  for (let i = 0; i < pattern.length; i++) {
+    const char = pattern[i];
+
+    if (char === '\\') {
+      const escaped = pattern.slice(i, i + 2);
+      result += escaped !== '\\w' ? escaped : inClass ? WORD : `[${WORD}]`;
+      i++;
+    } else {
+      if (char === '[') {
+        inClass = true;
+      } else if (char === ']') {
+        inClass = false;
+      }
+      result += char;
+    }
+  }
+
+  return result;
+}
+
+/**
+ * Whether the identifier matches the keyword, where the keyword rule
+ * matches, and a longer text (not the keyword) where it doesn't.
+ */
+function matchesKeyword(matcher, word) {
+  const matched = string => {
+    const match = matcher.exec(string);
+    return match ? match[0] : null;
+  };
+
+  return (
+    matched(word) === word &&
+    NON_WORD_PROBES.every(probe => matched(word + probe) === word) &&
+    WORD_PROBES.every(probe => {
+      const text = matched(word + probe);
+      return text !== null && text.length > word.length;
+    })
+  );
+}
@@ -3,10 +3,16 @@
  * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
  */
 
+import crypto from 'crypto';
 import fs from 'fs';
+import path from 'path';
 
 import colors from 'colors';
 
+import {EOF} from '../../special-symbols';
+import packTable from './rust-compact-table';
+import buildKeywordTable from './rust-keyword-table';
+
 /**
  * Rust tokenizer template.
  */
@@ -16,14 +22,236 @@ const RUST_TOKENIZER_TEMPLATE = fs.readFileSync(
 );
 
 /**
- * Default error handler for rust parser when encountered an error. 
+ * Rust WebAssembly entry point template (the `wasm` option).
+ */
+const RUST_WASM_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/wasm.template.rs`,
+  'utf-8'
+);
+
+/**
+ * Rust template of the smoke test (the `emitTest` option).
+ */
+const RUST_SMOKE_TEST_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/smoke-test.template.rs`,
+  'utf-8'
+);
+
+/**
+ * Rust template of the REPL binary (the `emitRepl` option).
+ */
+const RUST_REPL_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/repl.template.rs`,
+  'utf-8'
+);
+
+/**
+ * Rust template of the benchmark (the `emitBench` option).
+ */
+const RUST_BENCH_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/bench.template.rs`,
+  'utf-8'
+);
+
+/**
+ * Rust template of proc-macro2 spans (the `procMacroSpans` option).
+ */
+const RUST_PROC_MACRO_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/proc-macro.template.rs`,
+  'utf-8'
+);
+
+/**
+ * Attributes of the node types of the parse results, without
+ * the `nodeAttributes` option.
+ */
+const DEFAULT_NODE_ATTRIBUTES = '#[derive(Debug, Clone)]';
+
+/**
+ * Node types (the placeholders of their attributes), and the derives which
+ * the generated code needs, added to the ones of the `nodeAttributes`
+ * (e.g. `Debug` of `SyntaxError`, and of `ParseEvent`).
+ */
+const NODE_TYPES = {
+  TOKEN_ATTRIBUTES: ['Debug', 'Clone', 'Copy', 'Default'],
+  OWNED_TOKEN_ATTRIBUTES: ['Debug', 'Clone', 'PartialEq'],
+  LOC_ATTRIBUTES: ['Debug', 'Clone', 'Copy', 'Default', 'PartialEq'],
+};
+
+/**
+ * Rust attributes, `#[...]`, the brackets of the arguments are nested once.
+ */
+const RUST_ATTRIBUTES = /#\[(?:[^\[\]]|\[[^\[\]]*\])*\]/g;
+
+/**
+ * Regex crates for lex rules (the `regexBackend` option):
+ * the `onig` (oniguruma), or the pure Rust `regex`.
+ */
+const REGEX_BACKENDS = ['onig', 'regex'];
+
+/**
+ * Methods of the parser trait (the `parserTrait` option): the params, the
+ * result (`R` is the result type of the parser), and the args passed to
+ * the method of the `Parser`.
+ */
+const PARSER_TRAIT_METHODS = {
+  parse: ["string: &'t str", 'Result<R, SyntaxError>', 'string'],
+  parse_all: ["string: &'t str", '(Option<R>, Vec<SyntaxError>)', 'string'],
+  parse_file: [
+    "path: &std::path::Path, contents: &'t mut String",
+    'Result<R, SyntaxError>',
+    'path, contents',
+  ],
+  parse_utf16: [
+    "string: &[u16], buffer: &'t mut String",
+    'Result<R, SyntaxError>',
+    'string, buffer',
+  ],
+};
+
+/**
+ * Keywords of Rust, the names of the `TokenKind` variants are raw
+ * identifiers (`r#type`), except the ones which can't be raw.
+ */
+const RUST_KEYWORDS = new Set(
+  (
+    'as async await box break const continue dyn else enum extern false fn ' +
+    'for if impl in let loop match mod move mut pub ref return static ' +
+    'struct trait true try type unsafe use where while abstract become ' +
+    'do final gen macro override priv typeof unsized virtual yield'
+  ).split(' ')
+);
+
+const NON_RAW_KEYWORDS = new Set(['self', 'Self', 'super', 'crate', '_']);
+
+/**
+ * Mechanisms of the lazily initialized statics (the `statics` option):
+ * the `lazy_static` crate, the `Lazy` of `once_cell`, or the `LazyLock`
+ * of std (Rust 1.80), without extra crates.
+ */
+const STATICS = {
+  lazy_static: {
+    declare: 'lazy_static! { $($(#[$attr])* static ref $name: $ty = $init;)* }',
+    force: 'lazy_static::initialize',
+  },
+  once_cell: {
+    declare:
+      '$($(#[$attr])* static $name: ::once_cell::sync::Lazy<$ty> = ' +
+      '::once_cell::sync::Lazy::new(|| $init);)*',
+    force: '::once_cell::sync::Lazy::force',
+  },
+  std: {
+    declare:
+      '$($(#[$attr])* static $name: ::std::sync::LazyLock<$ty> = ' +
+      '::std::sync::LazyLock::new(|| $init);)*',
+    force: '::std::sync::LazyLock::force',
+  },
+};
+
+/**
+ * Units of the offsets, and columns of locations (the `offsetEncoding`
+ * option): bytes of the UTF-8 string, or UTF-16 code units (as in JS).
+ */
+const OFFSET_ENCODINGS = ['utf8', 'utf16'];
+
+/**
+ * Oniguruma syntaxes for lex rules (the `regexSyntax` option),
+ * the names of the `onig::Syntax` constructors.
+ */
+const REGEX_SYNTAXES = [
+  'default',
+  'ruby',
+  'oniguruma',
+  'perl',
+  'perl_ng',
+  'java',
+  'emacs',
+  'grep',
+  'gnu_regex',
+  'posix_basic',
+  'posix_extended',
+  'asis',
+];
+
+/**
+ * Regex syntaxes in which `(...)` is a group, and `|` is an alternation,
+ * the lex rules can be combined with the `lexAlternation` option.
+ */
+const ALTERNATION_REGEX_SYNTAXES = [
+  'default',
+  'ruby',
+  'oniguruma',
+  'perl',
+  'perl_ng',
+  'java',
+  'gnu_regex',
+  'posix_extended',
+];
+
+/**
+ * Unsigned integer types of the static tables, from the smallest one,
+ * and their maximum values.
  */
-const DEFAULT_ERROR_HANDLER = `
-  if token.value == EOF && !self.tokenizer.has_more_tokens() {
-    panic!("Unexpected end of input.");
+const UNSIGNED_MAX = {
+  u8: 0xff,
+  u16: 0xffff,
+  u32: 0xffffffff,
+};
+
+/**
+ * Whether a lex rule matcher can be a part of a combined alternation:
+ * it has no own capturing groups (which would shift the group numbers
+ * of the next rules), back references, or subexpression calls.
+ */
+function isCombinableMatcher(matcher) {
+  let classDepth = 0;
+
+  for (let i = 0; i < matcher.length; i++) {
+    const char = matcher[i];
+
+    if (char === '\\') {
+      if (/[1-9kg]/.test(matcher[i + 1] || '')) {
+        return false;
+      }
+      i++;
+    } else if (char === '[') {
+      classDepth++;
+    } else if (char === ']' && classDepth > 0) {
+      classDepth--;
+    } else if (char === '(' && classDepth === 0) {
+      const group = matcher.slice(i + 1, i + 4);
+      // Plain, and named groups: `(`, `(?<name>`, `(?P<name>`, `(?'name'`.
+      if (group[0] !== '?' || /^\?(<[^=!]|P<|')/.test(group)) {
+        return false;
+      }
+    }
   }
-  self.tokenizer.panic_unexpected_token(token.value, token.start_line, token.start_column);
-`;
+
+  return true;
+}
+
+/**
+ * A global var as a whole word, e.g. `yytext`, but not `set_yytext`. The
+ * preceding dots are captured: a var after one dot is a field, and after
+ * two it's the end of a range (`&yytext[1..yytext.len()]`).
+ */
+function globalVar(name) {
+  return new RegExp(`(\\.{0,2})\\b${name}\\b`, 'g');
+}
+
+/**
+ * Handler code returning the token of a matched keyword (the `keywords`
+ * are the list of `[word, token]`), other text is handled further. The
+ * match is counted for the keyword rule (the `keywordRules` indices) in
+ * the lex coverage.
+ */
+function keywordLookup(keywords, identifierRule, keywordRules) {
+  const arms = keywords.map(
+    ([word, token], i) =>
+      `"${word}" => { self.hit_keyword_rule(${identifierRule}, ${keywordRules[i]}); return "${token}" }`
+  );
+  return `match yytext {\n${arms.join('\n')}\n_ => {}\n}`;
+}
 
 /**
  * Make replacer for String#replace method which replace match with replaceText only if first capture group is not '.'
@@ -45,214 +273,1400 @@ function ifNotStartsWithDotReplacer(replaceText) {
 const RustParserGeneratorTrait = {
 
   /**
-   * Generates parsing table in Rust vector format.
+   * Generates parsing table in Rust format: the rows are packed into
+   * static arrays of a displacement table (see `rust-compact-table`).
    */
   generateParseTable() {
+    const {base, check, entries} = packTable(
+      this._buildTable(this.generateParseTableData())
+    );
+
+    // The free slots are owned by no state (the maximum of the type).
+    const checkType = this._unsignedType(base.length);
+    const noState = UNSIGNED_MAX[checkType];
+
+    this.writeData(
+      'TABLE_BASE',
+      `[${this._unsignedType(Math.max(0, ...base))}; ${base.length}] = ` +
+      this._toRustArray(base)
+    );
+
+    this.writeData(
+      'TABLE_CHECK',
+      `[${checkType}; ${check.length}] = ` +
+      this._toRustArray(check.map(state => (state === -1 ? noState : state)))
+    );
+
+    // The entries of the free slots are never used.
     this.writeData(
-      'TABLE',
-      this._buildTable(this.generateParseTableData()),
+      'TABLE_ENTRIES',
+      `[TE; ${entries.length}] = ` +
+      this._toRustArray(entries.map(entry => entry || 'TE::Transit(0)'), 8)
     );
+
+    // The lookups of the static arrays can be evaluated at compile time.
+    this.writeData('CONST_FN', this.getOptions().constTables ? 'const ' : '');
+
+    this.generateStateSymbols();
   },
 
   /**
-   * Converts JS object into Rust HashMap.
-   *
-   * In Rust we represent a table as a vector, where index is a state number,
-   * and a value is a hashmap of LR entries (shift/reduce/etc).
-   *
-   * Example:
+   * The smallest unsigned integer type, which fits the value: `u8`,
+   * `u16`, or `u32`.
+   */
+  _unsignedType(value) {
+    return Object.keys(UNSIGNED_MAX).find(type => value <= UNSIGNED_MAX[type]);
+  },
+
+  /**
+   * With the `tokenClasses` option (e.g. `FN:FUNCTION,DEF;ID:NAME`) the
+   * tokens of a class are read by the parser as its terminal: the tokens
+   * are returned by the lex rules, and not used in the grammar. They are
+   * numbered after the EOF token, and mapped to their terminals before
+   * the parsing table lookups, so the kinds of the tokens are kept.
+   */
+  generateTokenClasses() {
+    const classes = this._getTokenClasses();
+    const eofName = this._grammar.getEofName() || EOF;
+
+    let index = Number(this._tokens[EOF]);
+    const terminals = [];
+
+    Object.keys(classes).forEach(terminal => {
+      if (!this._tokens.hasOwnProperty(terminal) || terminal === EOF) {
+        throw new Error(
+          `Unknown token ${colors.bold(terminal)} of a token class, ` +
+          `should be a token of the grammar.`
+        );
+      }
+
+      classes[terminal].forEach(token => {
+        if (this._tokens.hasOwnProperty(token) || token === eofName) {
+          throw new Error(
+            `Token ${colors.bold(token)} of the ${terminal} class is ` +
+            `already a token of the grammar, or of another class.`
+          );
+        }
+
+        this._tokens[token] = '' + ++index;
+        terminals.push(this._tokens[terminal]);
+      });
+    });
+
+    this.writeData('TOKEN_CLASSES', `&[${terminals.join(', ')}]`);
+  },
+
+  /**
+   * Token classes of the `tokenClasses` option: the tokens by the
+   * terminal of their class.
+   */
+  _getTokenClasses() {
+    const option = this.getOptions().tokenClasses || {};
+
+    if (typeof option !== 'string') {
+      return option;
+    }
+
+    const classes = {};
+
+    option
+      .split(';')
+      .map(tokenClass => tokenClass.trim())
+      .filter(Boolean)
+      .forEach(tokenClass => {
+        const [terminal, tokens = ''] = tokenClass.split(':');
+
+        classes[terminal.trim()] = tokens
+          .split(',')
+          .map(token => token.trim())
+          .filter(Boolean);
+      });
+
+    return classes;
+  },
+
+  /**
+   * Type of the encoded symbols in the static tables (`EncodedSymbol`):
+   * the smallest one, which fits the numbers of the tokens (they follow
+   * the numbers of the non-terminals), and the lengths of the productions.
+   */
+  generateEncodedSymbolType() {
+    const numbers = Object.keys(this._tokens).map(token =>
+      Number(this._tokens[token])
+    );
+
+    const lengths = this._grammar
+      .getProductions()
+      .map(production =>
+        production.isEpsilon() ? 0 : production.getRHS().length
+      );
+
+    this.writeData(
+      'ENCODED_SYMBOL_TYPE',
+      this._unsignedType(Math.max(0, ...numbers, ...lengths))
+    );
+  },
+
+  /**
+   * Formats a Rust array literal, a number of items per line.
+   */
+  _toRustArray(items, itemsPerLine = 20) {
+    const lines = [];
+
+    for (let i = 0; i < items.length; i += itemsPerLine) {
+      lines.push(items.slice(i, i + itemsPerLine).join(', '));
+    }
+
+    return `[\n    ${lines.join(',\n    ')}\n]`;
+  },
+
+  /**
+   * Symbols of the states: the symbol of a state is the one shifted
+   * (a token), or reduced (a non-terminal) to enter the state, the
+   * start state has no symbol.
+   */
+  generateStateSymbols() {
+    const table = this._table.get();
+    const symbols = Object.keys(table).map(() => '');
+
+    for (const state in table) {
+      for (const symbol in table[state]) {
+        String(table[state][symbol]).split('/').forEach(entry => {
+          if (entry[0] === 's') {
+            symbols[entry.slice(1)] = symbol;
+          } else if (/^\d+$/.test(entry)) {
+            symbols[entry] = symbol;
+          }
+        });
+      }
+    }
+
+    const names = symbols.map(symbol =>
+      this._toRustString(this._grammar.getTokenDisplayName(symbol))
+    );
+
+    this.writeData('STATE_SYMBOLS', `&[${names.join(', ')}]`);
+  },
+
+  /**
+   * Converts the entries of the table rows into the Rust `TE` enum,
+   * a row is a map from an encoded symbol to an entry:
    *
-   * vec![
-   *     hashmap! { 1 => TE::Shift(4), 3 => TE::Reduce(1), ... },
-   * ]
+   * {1: 'TE::Shift(4)', 3: 'TE::Reduce(1)', ...}
    */
   _buildTable(table) {
-    const entries = Object.keys(table).map(state => {
+    const lookahead = this._getLookahead();
+
+    const rows = Object.keys(table).map(state => {
       const row = table[state];
 
       // Transform to Rust enum format: "s3" => TE::Shift(3), etc
       Object.keys(row).forEach(key => {
-        const entry = row[key];
-        if (entry[0] === 's') {
-          row[key] = `TE::Shift(${entry.slice(1)})`;
-        } else if (entry[0] === 'r') {
-          row[key] = `TE::Reduce(${entry.slice(1)})`;
-        } else if (entry === 'acc') {
-          row[key] = `TE::Accept`;
-        } else {
-          row[key] = `TE::Transit(${entry})`;
+        const entry = String(row[key]);
+
+        if (!entry.includes('/')) {
+          row[key] = this._toRustTableEntry(entry);
+          return;
+        }
+
+        // An unresolved conflict, e.g. "r1/s7", is resolved at runtime
+        // by the following tokens.
+        if (lookahead < 2) {
+          throw new Error(
+            `Unresolved conflict ${colors.bold(entry)} in state ${state}. ` +
+            `Resolve it in the grammar, or use more lookahead tokens ` +
+            `(e.g. ${colors.bold('--lookahead 2')}).`
+          );
         }
+
+        // Shift goes first, then reduces in order of productions: the
+        // first action which consumes the lookahead tokens is chosen.
+        const actions = entry.split('/').sort((a, b) =>
+          a[0] !== b[0]
+            ? (a[0] === 's' ? -1 : 1)
+            : Number(a.slice(1)) - Number(b.slice(1))
+        );
+
+        row[key] =
+          `TE::Conflict(&[` +
+          actions.map(action => this._toRustTableEntry(action)).join(', ') +
+          `])`;
       });
 
-      return this._toRustHashMap(table[state], 'number')
+      return row;
     });
 
-    return `vec![\n    ${entries.join(',\n    ')}\n]`;
+    this.writeData('LOOKAHEAD', lookahead);
+
+    return rows;
+  },
+
+  /**
+   * Converts a table entry to the `TE` enum: "s3" => TE::Shift(3), etc.
+   */
+  _toRustTableEntry(entry) {
+    if (entry[0] === 's') {
+      return `TE::Shift(${entry.slice(1)})`;
+    } else if (entry[0] === 'r') {
+      return `TE::Reduce(${entry.slice(1)})`;
+    } else if (entry === 'acc') {
+      return `TE::Accept`;
+    }
+    return `TE::Transit(${entry})`;
+  },
+
+  /**
+   * Number of lookahead tokens for conflicts (the `lookahead` option).
+   */
+  _getLookahead() {
+    const option = this.getOptions().lookahead;
+    const lookahead = option == null ? 1 : Number(option);
+
+    if (!Number.isInteger(lookahead) || lookahead < 1) {
+      throw new Error(
+        `Invalid lookahead ${colors.bold(option)}, ` +
+        `should be a positive number of tokens.`
+      );
+    }
+
+    return lookahead;
   },
 
   /**
    * Generates tokens table in Rust hashmap format.
    */
   generateTokensTable() {
+    const eofName = this._getEofName();
+
+    // The end of input token is named with `%eof NAME` in the tokens map.
+    const tokens = {};
+    Object.keys(this._tokens).forEach(token => {
+      tokens[token === EOF ? eofName : token] = this._tokens[token];
+    });
+
     this.writeData(
       'TOKENS',
-      this._toRustHashMap(this._tokens, 'string', 'number'),
+      this.getOptions().constTables
+        ? this._toRustSortedArray(tokens, value => value)
+        : this._toRustHashMap(tokens, 'string', 'number'),
+    );
+
+    // The EOF token is created without the lookup in the tokens map.
+    this.writeData('EOF_KIND', this._tokens[EOF]);
+    this.writeData('EOF', this._toRustString(eofName));
+
+    // Without a name it's "end of input" in the error messages.
+    this.writeData(
+      'EOF_DISPLAY_NAME',
+      this._toRustString(
+        this._grammar.getEofName() ? eofName : 'end of input'
+      ),
+    );
+
+    // Display names of the tokens in the error messages, by the number.
+    const displayNames = Object.keys(this._tokens)
+      .sort((a, b) => this._tokens[a] - this._tokens[b])
+      .map(token =>
+        `(${this._tokens[token]}, ` +
+        `${this._toRustString(this._grammar.getTokenDisplayName(token))})`
+      );
+
+    this.writeData(
+      'TOKEN_DISPLAY_NAMES',
+      `&[${displayNames.join(', ')}]`,
+    );
+
+    // The `error` token of the error recovery, if the grammar uses it.
+    this.writeData(
+      'ERROR_KIND',
+      this._tokens.hasOwnProperty('error') ? this._tokens.error : -1,
+    );
+
+    // Synchronization tokens of the error recovery (`%sync`).
+    const syncKinds = this._grammar
+      .getSyncTokens()
+      .map(token => this._tokens[token]);
+
+    this.writeData('SYNC_KINDS', `&[${syncKinds.join(', ')}]`);
+
+    // Messages of the `error` productions (`%msg`), by the number.
+    const errorMessages = this._grammar
+      .getProductions()
+      .filter(production => production.getErrorMessage())
+      .map(production =>
+        `(${production.getNumber()}, ` +
+        `${this._toRustString(production.getErrorMessage())})`
+      );
+
+    this.writeData('ERROR_MESSAGES', `&[${errorMessages.join(', ')}]`);
+  },
+
+  /**
+   * Docs of the grammar (`//!` comments) on the `Parser`, and the public
+   * `TokenKind` enum of the named tokens with their docs (`///` comments
+   * before `%token`). The docs of the rules are on their handlers.
+   */
+  generateDocs() {
+    const doc = this._grammar.getDoc();
+
+    this.writeData(
+      'GRAMMAR_DOC',
+      doc ? `${this._toDocComment(doc)}\n///\n` : ''
     );
+
+    const tokenDocs = this._grammar.getTokenDocs();
+
+    const variants = Object.keys(this._tokens)
+      .filter(
+        token =>
+          token !== EOF &&
+          /^[a-zA-Z_][a-zA-Z0-9_]*$/.test(token) &&
+          !NON_RAW_KEYWORDS.has(token)
+      )
+      .sort((a, b) => this._tokens[a] - this._tokens[b])
+      .map(token => {
+        const name = RUST_KEYWORDS.has(token) ? `r#${token}` : token;
+        const variant = `    ${name} = ${this._tokens[token]},`;
+
+        return tokenDocs.hasOwnProperty(token)
+          ? `${this._toDocComment(tokenDocs[token], '    ')}\n${variant}`
+          : variant;
+      });
+
+    // A `#[repr(i32)]` enum can't be empty.
+    if (variants.length === 0) {
+      this.writeData('TOKEN_KIND', '');
+      return;
+    }
+
+    this.writeData(
+      'TOKEN_KIND',
+      `/**\n` +
+      ` * Kinds of the named tokens, the \`kind\` of their tokens:\n` +
+      ` * \`token.kind == TokenKind::NUMBER as i32\`.\n` +
+      ` */\n` +
+      `#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]{{{NON_EXHAUSTIVE}}}\n` +
+      `#[repr(i32)]\n` +
+      `#[allow(non_camel_case_types, clippy::upper_case_acronyms)]\n` +
+      `pub enum TokenKind {\n${variants.join('\n')}\n}`
+    );
+  },
+
+  /**
+   * Lines of a doc as a `///` doc comment.
+   */
+  _toDocComment(doc, indent = '') {
+    return doc
+      .split('\n')
+      .map(line => `${indent}///${line ? ' ' + line : ''}`)
+      .join('\n');
+  },
+
+  /**
+   * Name of the end of input token: set with `%eof NAME`, or `$`.
+   * It can't be the name of another token.
+   */
+  _getEofName() {
+    const eofName = this._grammar.getEofName();
+
+    if (!eofName) {
+      return EOF;
+    }
+
+    if (eofName !== EOF && this._tokens.hasOwnProperty(eofName)) {
+      throw new Error(
+        `The end of input name ${colors.bold(eofName)} is the name ` +
+        `of another token.`
+      );
+    }
+
+    return eofName;
   },
 
   /**
    * Production handlers are implemented as methods on the parser class.
    */
   buildSemanticAction(production) {
-    let originalAction = this.getSemanticActionCode(production);
+    // Total number of args.
+    const totalArgsCount = production.isEpsilon()
+      ? 0
+      : production.getRHS().length;
+
+    let originalAction = this._scopeLocations(
+      this.getSemanticActionCode(production),
+      totalArgsCount,
+      production
+    );
 
     let {action, types} = this._extractDataTypes(originalAction);
 
+    // Tokens with value types (`%token <f64> NUMBER`) are converted
+    // from their text, and the default `$$ = $1` propagates the value.
+    const tokenTypes = this._getRHSTokenTypes(production);
+
+    if (
+      tokenTypes._1 &&
+      totalArgsCount === 1 &&
+      !types.hasOwnProperty('__') &&
+      /^\s*\$\$\s*=\s*\$1\s*;?\s*$/.test(
+        production.getOriginalSemanticAction() || ''
+      )
+    ) {
+      types.__ = tokenTypes._1;
+    }
+
+    Object.keys(tokenTypes).forEach(arg => {
+      if (!this._allTypes.hasOwnProperty(tokenTypes[arg])) {
+        this._allTypes[tokenTypes[arg]] = this._allTypesIndex++;
+      }
+    });
+
+    // An action without `$$` is an expression, which is the result of the
+    // production, e.g. a call of a function from the actions module:
+    // `actions::binary($1, $2, $3)`.
+    if (action.trim() && !/\b__\b/.test(action)) {
+      action = `__ = {\n${action.trim()}\n}`;
+    }
+
     action = this._actionFromHandler(action, '.tokenizer');
 
+    const tokenArgs = this._getRHSTokenArgs(production);
+
+    // A typed action is a method with the types of the args, and the
+    // result, which the handler calls with the values from the stack.
+    if (types.hasOwnProperty('__')) {
+      const args = Object.keys(types)
+        .filter(arg => arg !== '__')
+        .sort((a, b) => a.slice(1) - b.slice(1));
+
+      const params = args.map(
+        arg =>
+          `mut ${arg}: ` +
+          this._getArgType(arg, types[arg], tokenTypes[arg], tokenArgs)
+      );
+
+      this._actionHelpers.push(
+        `/// ${production.toString()}\n` +
+        `fn _action${this._productionHandlers.length}` +
+        `(${['&mut self'].concat(params).join(', ')}) -> ` +
+        `${this._withTokenLifetime(types.__)} {\n${action}\n__\n}`
+      );
+
+      action =
+        `SV::_${this._allTypes[types.__]}(` +
+        `self._action${this._productionHandlers.length}(${args.join(', ')}))`;
+    }
+
     action = this._generateArgsPrologue(
       action,
       types,
-      // Total number of args.
-      production.isEpsilon() ? 0 : production.getRHS().length
+      totalArgsCount,
+      tokenTypes,
+      tokenArgs
+    );
+
+    // Append return value of an untyped action.
+    if (!types.hasOwnProperty('__')) {
+      action = action + `\n__`;
+    }
+
+    // Save the action, they are injected later.
+    this._productionHandlers.push({
+      args: '&mut self',
+      action,
+      doc: this._grammar.getRuleDocs()[production.getLHS().getSymbol()],
+    });
+    return null;
+  },
+
+  /**
+   * Type of an arg of a typed action, as it's popped from the stack
+   * in the prologue of the handler (see `_generateArgsPrologue`).
+   */
+  _getArgType(arg, typeInfo, tokenType, tokenArgs) {
+    if (tokenType && (!typeInfo || typeInfo === tokenType)) {
+      return tokenType;
+    }
+
+    if (typeInfo === 'OwnedToken' && tokenArgs.includes(arg)) {
+      return 'OwnedToken';
+    }
+
+    return typeInfo ? this._withTokenLifetime(typeInfo) : "SV<'t>";
+  },
+
+  /**
+   * Locations are always tracked by the parser, and `@$` is set before
+   * a handler is called, no prologue is needed.
+   */
+  createLocationPrologue() {
+    return '';
+  },
+
+  /**
+   * Locations in semantic actions: `@$` (`__loc`) is the location of the
+   * reduced production, and `@1`, `@2`, ... (`_1loc`, ...) are the
+   * locations of the RHS symbols, which are on the stack until the
+   * handler returns.
+   */
+  _scopeLocations(action, totalArgsCount, production) {
+    if (!action) {
+      return action;
+    }
+
+    return action
+      .replace(/\b__loc\b/g, 'self.reduced_loc')
+      .replace(/\b_(\d+)loc\b/g, (_, index) => {
+        if (index < 1 || index > totalArgsCount) {
+          throw new Error(
+            `Location ${colors.bold('@' + index)} is out of the RHS of ` +
+            `the production ${colors.bold(production.toString())}.`
+          );
+        }
+
+        const depth = totalArgsCount - index + 1;
+        return `self.locations_stack[self.locations_stack.len() - ${depth}]`;
+      });
+  },
+
+  /**
+   * Value types of the typed tokens of the RHS by args: `{_1: 'f64'}`.
+   */
+  _getRHSTokenTypes(production) {
+    const tokenTypes = this._grammar.getTokenTypes();
+    const rhsTokenTypes = {};
+
+    if (production.isEpsilon()) {
+      return rhsTokenTypes;
+    }
+
+    production.getRHS().forEach((symbol, index) => {
+      const tokenType = tokenTypes[symbol.getSymbol()];
+
+      if (tokenType && this._grammar.isTokenSymbol(symbol)) {
+        rhsTokenTypes[`_${index + 1}`] = tokenType;
+      }
+    });
+
+    return rhsTokenTypes;
+  },
+
+  /**
+   * Args of the tokens of the RHS: `['_1', '_3']`.
+   */
+  _getRHSTokenArgs(production) {
+    if (production.isEpsilon()) {
+      return [];
+    }
+
+    return production
+      .getRHS()
+      .map((symbol, index) =>
+        this._grammar.isTokenSymbol(symbol) ? `_${index + 1}` : null
+      )
+      .filter(Boolean);
+  },
+
+  /**
+   * Builds SV (stack value) enum from all the used types in handlers.
+   */
+  generateStackValueEnum() {
+    const svEnum = Object.keys(this._allTypes).map(
+      (typeName, idx) => `_${idx}(${this._withTokenLifetime(typeName)})`
+    );
+    this.writeData('SV_ENUM', svEnum.join(',\n    '));
+  },
+
+  /**
+   * Bare `Token` in a type (e.g. `Vec<Token>`) borrows from
+   * the parsing string, and is `Token<'t>` in the generated code.
+   */
+  _withTokenLifetime(typeName) {
+    return typeName.replace(/\bToken\b(?!\s*<)/g, "Token<'t>");
+  },
+
+  /**
+   * Strips lifetimes from a type to compare it with handled types:
+   * `Vec<Token<'t>>` -> `Vec<Token>`.
+   */
+  _withoutLifetimes(typeName) {
+    return typeName
+      .replace(/<\s*'\w+\s*>/g, '')
+      .replace(/'\w+\s*,\s*/g, '')
+      .replace(/\s+/g, '');
+  },
+
+  /**
+   * Generates prologue for fetching arguments from the parsing stack.
+   */
+  _generateArgsPrologue(
+    action,
+    types,
+    totalArgsCount,
+    tokenTypes = {},
+    tokenArgs = []
+  ) {
+    const argsPrologue = [];
+
+    for (let i = totalArgsCount; i > 0; i--) {
+      const arg = '_' + i;
+
+      if (!types.hasOwnProperty(arg)) {
+        // Just pop if arg is not used in the handler.
+        argsPrologue.push(`self.values_stack.pop();`);
+      } else {
+        const typeInfo = types[arg];
+        const tokenType = tokenTypes[arg];
+
+        if (tokenType && (!typeInfo || typeInfo === tokenType)) {
+          // A typed token is parsed from its text (`FromStr`), an error
+          // is returned after the handler (see `parse_token_value`).
+          argsPrologue.push(
+            `let ${arg} = pop!(self.values_stack, _0);\n` +
+            `let mut ${arg} = match self.parse_token_value::<${tokenType}>` +
+            `(${arg}) { Some(value) => value, None => return SV::Undefined };`
+          );
+        } else if (typeInfo === 'OwnedToken' && tokenArgs.includes(arg)) {
+          // A token with an owned value, which can outlive the string.
+          argsPrologue.push(
+            `let mut ${arg} = pop!(self.values_stack, _0).to_owned_token();`
+          );
+        } else if (typeInfo) {
+          argsPrologue.push(
+            `let mut ${arg} = pop!(self.values_stack, ` +
+            `_${this._allTypes[typeInfo]});`
+          );
+        } else {
+          argsPrologue.push(
+            `let mut ${arg} = self.values_stack.pop().unwrap();`
+          );
+        }
+      }
+    }
+
+    return (
+      '// Semantic values prologue.\n' +
+      argsPrologue.join('\n') + '\n\n' +
+      action
+    );
+  },
+
+  /**
+   * Extracts types of the used arguments, and return types.
+   * If a type is defined, a popped stack value is casted,
+   * otherwise, it's just popped (if doesn't participate in an operation
+   * and just propagated).
+   */
+  _extractDataTypes(action) {
+    const types = {};
+
+    if (!action) {
+      return {action: '', types};
+    }
+
+    const typesRe = /\s*\|([^|]*)\|\s*->\s*([\w&][\w<>, '&]*);/g;
+    const typesData = typesRe.exec(action);
+
+    if (typesData) {
+      // '$1:i32, $3:i32'
+      if (typesData[1].trim().length > 0) {
+        const argTypes = typesData[1].trim().split(/\s*[,;]\s*/);
+        argTypes.forEach(argData => {
+          const data = argData.split(/\s*:\s*/);
+          // types[$1] = 'i32';
+          types[data[0]] = data[1];
+
+          // Save the type to all types data to generate enum.
+          if (!this._allTypes.hasOwnProperty(data[1])) {
+            this._allTypes[data[1]] = this._allTypesIndex++;
+          }
+        });
+      }
+
+      // Result type.
+      if (typesData[2]) {
+        types.__ = typesData[2];
+
+        if (!this._allTypes.hasOwnProperty(typesData[2])) {
+          this._allTypes[typesData[2]] = this._allTypesIndex++;
+        }
+      }
+    }
+
+    // Strip the types info.
+    action = action.replace(typesRe, '');
+
+    // Extract other args, which do not use types.
+    const argsRe = /_\d+/g;
+
+    const usedArgs = action.match(argsRe);
+    if (usedArgs) {
+      usedArgs.forEach(usedArg => {
+        if (!types.hasOwnProperty(usedArg)) {
+          // Arg is used, but without a type.
+          types[usedArg] = null;
+        }
+      });
+    }
+
+    return {types, action};
+  },
+
+  /**
+   * Default format in the [ ] array notation.
+   */
+  generateProductionsData() {
+    return this.generateRawProductionsData()
+      .map(data => {
+        // Remove the semantic action handler, since in Rust
+        // we use a different structure to hold it.
+        delete data[2];
+        return `[${data.join(', ')}]`;
+      });
+  },
+
+  /**
+   * Generates built-in tokenizer instance.
+   */
+  generateBuiltInTokenizer() {
+    this.writeData('TOKENIZER', RUST_TOKENIZER_TEMPLATE);
+    this.generateSharedData();
+    this.generateNormalizedTokens();
+    this.writeData('TAB_WIDTH', this.getOptions().tabWidth || 1);
+    this.writeData(
+      'APPEND_EOF_NEWLINE',
+      this.getOptions().appendEofNewline ? 'true' : 'false'
+    );
+
+    // Lines and columns are tracked unless disabled with `--no-locations`.
+    this.writeData(
+      'TRACK_LOCATIONS',
+      this.getOptions().locations === false ? 'false' : 'true'
+    );
+
+    this.generateOffsetEncoding();
+    this.generateGraphemeColumns();
+
+    // The context of the parse, `()` without the `contextType` option.
+    while (this._resultData.includes('{{{CONTEXT_TYPE}}}')) {
+      this.writeData('CONTEXT_TYPE', this.getOptions().contextType || '()');
+    }
+
+    const regexBackend = this.getOptions().regexBackend || 'onig';
+
+    if (!REGEX_BACKENDS.includes(regexBackend)) {
+      throw new Error(
+        `Unknown regex backend ${colors.bold(regexBackend)}, ` +
+        `supported: ${REGEX_BACKENDS.join(', ')}.`
+      );
+    }
+
+    this.writeData(
+      'REGEX_BACKEND',
+      fs.readFileSync(
+        `${__dirname}/templates/${regexBackend}.template.rs`,
+        'utf-8'
+      )
+    );
+
+    // The `regex` crate has one syntax.
+    if (regexBackend === 'regex') {
+      if (this.getOptions().regexSyntax) {
+        throw new Error(
+          `The regex syntax is supported only by the ` +
+          `${colors.bold('onig')} regex backend.`
+        );
+      }
+      return;
+    }
+
+    const regexSyntax = this.getOptions().regexSyntax || 'default';
+
+    if (!REGEX_SYNTAXES.includes(regexSyntax)) {
+      throw new Error(
+        `Unknown regex syntax ${colors.bold(regexSyntax)}, ` +
+        `supported: ${REGEX_SYNTAXES.join(', ')}.`
+      );
+    }
+
+    // The syntax of the rules, and of the combined rules.
+    while (this._resultData.includes('{{{REGEX_SYNTAX}}}')) {
+      this.writeData('REGEX_SYNTAX', regexSyntax);
+    }
+
+    if (
+      this.getOptions().lexAlternation &&
+      !ALTERNATION_REGEX_SYNTAXES.includes(regexSyntax)
+    ) {
+      throw new Error(
+        `The lex alternation is not supported with the ` +
+        `${colors.bold(regexSyntax)} regex syntax, ` +
+        `supported: ${ALTERNATION_REGEX_SYNTAXES.join(', ')}.`
+      );
+    }
+  },
+
+  /**
+   * With the `normalizeTokens` option (a list of token types, e.g.
+   * `ID,NAME`) the values of the tokens are normalized to the NFC form
+   * for the `unicode-normalization` feature of the crate.
+   */
+  generateNormalizedTokens() {
+    const tokens = this._getNormalizedTokens();
+
+    tokens.forEach(token => {
+      if (!this._tokens.hasOwnProperty(token) || token === EOF) {
+        throw new Error(
+          `Unknown token ${colors.bold(token)} to normalize, ` +
+          `should be a token of the grammar.`
+        );
+      }
+    });
+
+    this.writeData(
+      'NORMALIZED_TOKENS',
+      `&[${tokens.map(token => JSON.stringify(token)).join(', ')}]`
+    );
+
+    // The crate has the feature only with the option.
+    const feature = tokens.length > 0
+      ? 'feature = "unicode-normalization"'
+      : 'any()';
+
+    while (this._resultData.includes('{{{NORMALIZATION_FEATURE}}}')) {
+      this.writeData('NORMALIZATION_FEATURE', feature);
+    }
+  },
+
+  /**
+   * Token types of the `normalizeTokens` option.
+   */
+  _getNormalizedTokens() {
+    const option = this.getOptions().normalizeTokens || [];
+
+    return (Array.isArray(option) ? option : option.split(','))
+      .map(token => token.trim())
+      .filter(Boolean);
+  },
+
+  /**
+   * With the `stableApi` option the public enums are `#[non_exhaustive]`,
+   * so adding a variant is not a breaking change for other crates.
+   */
+  generateStableApi() {
+    const attribute = this.getOptions().stableApi ? '\n#[non_exhaustive]' : '';
+
+    // The attribute is used for all public enums.
+    while (this._resultData.includes('{{{NON_EXHAUSTIVE}}}')) {
+      this.writeData('NON_EXHAUSTIVE', attribute);
+    }
+  },
+
+  /**
+   * Attributes of the node types of the parse results (`Token`,
+   * `OwnedToken`, and `Loc`): the `nodeAttributes` option is a list of
+   * attributes, e.g. `#[derive(Debug, Clone, PartialEq, Hash)]`, so the
+   * results can be compared, hashed, etc. The derives the generated code
+   * needs (e.g. `Copy` of tokens) are added to the ones of the option.
+   */
+  generateNodeAttributes() {
+    const option = this.getOptions().nodeAttributes || DEFAULT_NODE_ATTRIBUTES;
+
+    const attributes = option.match(RUST_ATTRIBUTES) || [];
+
+    if (!attributes.length || option.replace(RUST_ATTRIBUTES, '').trim()) {
+      throw new Error(
+        `Invalid node attributes ${colors.bold(option)}, ` +
+        `expected e.g. ${DEFAULT_NODE_ATTRIBUTES}.`
+      );
+    }
+
+    // The derives of the option go to one `derive`, the other attributes
+    // follow it.
+    const derives = [];
+    const others = [];
+
+    attributes.forEach(attribute => {
+      const derive = /^#\[\s*derive\s*\(([\s\S]*)\)\s*\]$/.exec(attribute);
+
+      if (!derive) {
+        others.push(attribute);
+        return;
+      }
+
+      derive[1]
+        .split(',')
+        .map(name => name.trim())
+        .filter(name => name && !derives.includes(name))
+        .forEach(name => derives.push(name));
+    });
+
+    Object.keys(NODE_TYPES).forEach(placeholder => {
+      const typeDerives = derives.concat(
+        NODE_TYPES[placeholder].filter(name => !derives.includes(name))
+      );
+
+      this.writeData(
+        placeholder,
+        [`#[derive(${typeDerives.join(', ')})]`].concat(others).join('\n')
+      );
+    });
+  },
+
+  /**
+   * With the `wasm` option a `wasm-bindgen` entry point is generated
+   * for the `wasm` feature of the crate.
+   */
+  generateWasmEntry() {
+    this.writeData(
+      'WASM_ENTRY',
+      this.getOptions().wasm ? RUST_WASM_TEMPLATE : ''
+    );
+  },
+
+  /**
+   * With the `contextType` option the `Parser::parse_with` method parses
+   * with a context of the caller, available in the semantic actions, and
+   * lex handlers (the context of the tokenizer, see `Tokenizer::context`).
+   */
+  generateParseWith() {
+    const {contextType} = this.getOptions();
+
+    if (!contextType) {
+      this.writeData('PARSE_WITH', '');
+      return;
+    }
+
+    this.writeData(
+      'PARSE_WITH',
+      `
+    /**
+     * Parses a string with a context of the caller, e.g. an interner, or
+     * a sink of diagnostics, which is \`self.context()\` in the semantic
+     * actions, and the lex handlers during the parse:
+     *
+     *   Stmt : Expr ';' { self.context().unused_values += 1; ... }
+     *
+     *   let mut diagnostics = Diagnostics::default();
+     *   let result = parser.parse_with(source, &mut diagnostics);
+     *
+     * The context is swapped with the one of the parser for the parse,
+     * and back after it (the context of the parser is the default value
+     * of the type, used in other parses).
+     */
+    pub fn parse_with<S>(
+        &mut self,
+        string: &'t S,
+        context: &mut ${contextType},
+    ) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        std::mem::swap(&mut self.tokenizer.context, context);
+        let result = self.parse(string);
+        std::mem::swap(&mut self.tokenizer.context, context);
+
+        result
+    }
+
+    /**
+     * Context of the parse in the semantic actions (see \`parse_with\`).
+     */
+    pub fn context(&mut self) -> &mut ${contextType} {
+        self.tokenizer.context()
+    }
+`
+    );
+  },
+
+  /**
+   * With the `asyncStream` option the `Parser::parse_stream` method parses
+   * the tokens of an async stream for the `futures` feature of the crate.
+   */
+  generateParseStream() {
+    if (!this.getOptions().asyncStream) {
+      this.writeData('PARSE_STREAM', '');
+      return;
+    }
+
+    this.writeData(
+      'PARSE_STREAM',
+      `
+    /**
+     * Parses the tokens of an async stream, e.g. tokenized from the data
+     * of a socket, awaiting the next token when the parser needs one:
+     *
+     *   let result = parser.parse_stream(tokens).await;
+     *
+     * The end of the stream is the end of input (the EOF token is added at
+     * the end of the last token, unless the stream ends with it). The
+     * tokens are not filtered, and the parser hooks are not called. The
+     * tokens of a conflict (see \`LOOKAHEAD\`) are awaited before its step,
+     * the error recovery is not used.
+     */
+    #[cfg(feature = "futures")]
+    pub async fn parse_stream<S>(&mut self, tokens: S) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError>
+    where
+        S: futures::Stream<Item = Token<'t>>,
+    {
+        use futures::StreamExt;
+
+        futures::pin_mut!(tokens);
+
+        let mut events = self.parse_events("");
+
+        // The tokens are read from the stream, nothing is left to tokenize.
+        events.parser.tokenizer.consume_end();
+
+        let mut last = Token::default();
+        let mut ended = false;
+
+        loop {
+            // The current token, and the tokens after it, which the step
+            // may read ahead.
+            while !ended && events.lookahead.len() < LOOKAHEAD {
+                let token = match tokens.next().await {
+                    Some(token) => token,
+                    None => Token {
+                        kind: EOF_KIND,
+                        value: "",
+                        start_offset: last.end_offset,
+                        start_line: last.end_line,
+                        start_column: last.end_column,
+                        ..last
+                    },
+                };
+
+                ended = token.is_eof();
+                last = token;
+                events.lookahead.push_back(Ok(token));
+            }
+
+            match events.next() {
+                Some(event) => event?,
+                None => break,
+            };
+        }
+
+        // The parsed value is left on the stack on accept.
+        let parsed = self.values_stack.pop().unwrap();
+
+        Ok(get_result!(parsed, {{{RESULT_TYPE}}}))
+    }
+`
+    );
+  },
+
+  /**
+   * With the `parserTrait` option a public trait of the parse methods,
+   * implemented by the `Parser`, so the code using the parser can depend
+   * on the trait, e.g. to be tested with a mock. The option is the name
+   * of the trait, and its methods (`parse` by default) after a colon:
+   * `ParseDsl:parse,parse_file`.
+   */
+  generateParserTrait() {
+    const {parserTrait} = this.getOptions();
+
+    if (!parserTrait) {
+      this.writeData('PARSER_TRAIT', '');
+      return;
+    }
+
+    const [name, methodsList = 'parse'] = parserTrait.split(':');
+
+    if (!/^[a-zA-Z_]\w*$/.test(name) || RUST_KEYWORDS.has(name)) {
+      throw new Error(`Invalid parser trait name ${colors.bold(name)}.`);
+    }
+
+    const methods = methodsList
+      .split(',')
+      .map(method => method.trim())
+      .filter(Boolean);
+
+    methods.forEach(method => {
+      if (!PARSER_TRAIT_METHODS.hasOwnProperty(method)) {
+        throw new Error(
+          `Unknown parser trait method ${colors.bold(method)}, ` +
+          `supported: ${Object.keys(PARSER_TRAIT_METHODS).join(', ')}.`
+        );
+      }
+    });
+
+    const signatures = methods.map(method => {
+      const [params, result] = PARSER_TRAIT_METHODS[method];
+      return (
+        `fn ${method}(&mut self, ${params}) -> ` +
+        result.replace(/\bR\b/, '{{{PARSE_RESULT_TYPE}}}')
+      );
+    });
+
+    const declarations = methods.map(
+      (method, index) =>
+        `    /**\n     * See \`Parser::${method}\`.\n     */\n` +
+        `    ${signatures[index]};`
+    );
+
+    const implementations = methods.map(
+      (method, index) =>
+        `    ${signatures[index]} {\n` +
+        `        Parser::${method}(self, ${PARSER_TRAIT_METHODS[method][2]})\n` +
+        `    }`
+    );
+
+    this.writeData(
+      'PARSER_TRAIT',
+      `// ------------------------------------------------------------------\n` +
+      `// Parser trait.\n\n` +
+      `/**\n` +
+      ` * Parse methods of the \`Parser\`, so the code using the parser can\n` +
+      ` * depend on the trait, and be tested with another implementation.\n` +
+      ` */\n` +
+      `pub trait ${name}<'t> {\n${declarations.join('\n\n')}\n}\n\n` +
+      `impl<'t> ${name}<'t> for Parser<'t> {\n` +
+      `${implementations.join('\n\n')}\n}\n`
+    );
+  },
+
+  /**
+   * Shared data of the lex rules, and the tokens: the maps built lazily
+   * (with the mechanism of the `statics` option, `lazy_static` by
+   * default), or with the `constTables` option the static arrays with
+   * `const fn` accessors, without the lazy statics.
+   */
+  generateSharedData() {
+    const {constTables, statics = 'lazy_static'} = this.getOptions();
+    const data = constTables ? 'const' : 'lazy';
+
+    if (!STATICS.hasOwnProperty(statics)) {
+      throw new Error(
+        `Unknown statics ${colors.bold(statics)}, ` +
+        `supported: ${Object.keys(STATICS).join(', ')}.`
+      );
+    }
+
+    this.writeData(
+      'SHARED_DATA',
+      fs.readFileSync(`${__dirname}/templates/${data}-data.template.rs`, 'utf-8')
     );
 
-    // Append return value.
-    const returnValue = types.hasOwnProperty('__')
-      ? `SV::_${this._allTypes[types.__]}(__)`
-      : `__`;
+    if (!constTables) {
+      this.writeData('SHARED_STATICS', STATICS[statics].declare);
 
-    action = action + `\n${returnValue}`;
+      while (this._resultData.includes('{{{FORCE_STATIC}}}')) {
+        this.writeData('FORCE_STATIC', STATICS[statics].force);
+      }
+    }
 
-    // Save the action, they are injected later.
-    this._productionHandlers.push({args: '&mut self', action});
-    return null;
+    this.writeData(
+      'EXTERN_CRATES',
+      constTables || statics !== 'lazy_static'
+        ? ''
+        : '\n#[macro_use]\nextern crate lazy_static;'
+    );
   },
 
   /**
-   * Builds SV (stack value) enum from all the used types in handlers.
+   * Units of the offsets, and columns of locations: the UTF-16 code
+   * units with the `utf16` offset encoding, otherwise the bytes.
    */
-  generateStackValueEnum() {
-    const svEnum = Object.keys(this._allTypes).map(
-      (typeName, idx) => `_${idx}(${typeName})`
+  generateOffsetEncoding() {
+    const {offsetEncoding = 'utf8', procMacroSpans} = this.getOptions();
+
+    if (!OFFSET_ENCODINGS.includes(offsetEncoding)) {
+      throw new Error(
+        `Unknown offset encoding ${colors.bold(offsetEncoding)}, ` +
+        `supported: ${OFFSET_ENCODINGS.join(', ')}.`
+      );
+    }
+
+    // The spans of a string literal are byte ranges.
+    if (offsetEncoding === 'utf16' && procMacroSpans) {
+      throw new Error(
+        `The ${colors.bold('utf16')} offset encoding can't be used ` +
+        `with the proc-macro2 spans.`
+      );
+    }
+
+    this.writeData(
+      'UTF16_OFFSETS',
+      offsetEncoding === 'utf16' ? 'true' : 'false'
     );
-    this.writeData('SV_ENUM', svEnum.join(',\n    '));
   },
 
   /**
-   * Generates prologue for fetching arguments from the parsing stack.
+   * The sha256 hash of the grammar source (its data if the grammar is not
+   * loaded from a string), to check that the parser is up to date.
    */
-  _generateArgsPrologue(action, types, totalArgsCount) {
-    const argsPrologue = [];
+  generateGrammarHash() {
+    const lexRules = this._grammar.getLexGrammar().getRules();
+
+    const source =
+      this._grammar.getSource() ||
+      JSON.stringify({
+        lex: lexRules.map(rule => rule.toData()),
+        bnf: this._grammar.getProductions().map(production => [
+          production.toString(),
+          production.getRawSemanticAction(),
+        ]),
+      });
 
-    for (let i = totalArgsCount; i > 0; i--) {
-      const arg = '_' + i;
+    const hash = crypto
+      .createHash('sha256')
+      .update(source)
+      .digest('hex');
 
-      if (!types.hasOwnProperty(arg)) {
-        // Just pop if arg is not used in the handler.
-        argsPrologue.push(`self.values_stack.pop();`);
-      } else {
-        const typeInfo = types[arg];
+    // The hash is in the header comment, and the `GRAMMAR_HASH` constant.
+    while (this._resultData.includes('{{{GRAMMAR_HASH}}}')) {
+      this.writeData('GRAMMAR_HASH', hash);
+    }
+  },
 
-        if (typeInfo) {
-          argsPrologue.push(
-            `let mut ${arg} = pop!(self.values_stack, ` +
-            `_${this._allTypes[typeInfo]});`
-          );
-        } else {
-          argsPrologue.push(
-            `let mut ${arg} = self.values_stack.pop().unwrap();`
-          );
-        }
-      }
+  /**
+   * With the `stableIds` option the productions, and the states get IDs
+   * derived from their contents (a production by its symbols, a state by
+   * its kernel items) instead of their numbers, so the IDs are kept when
+   * unrelated rules are added to the grammar, or removed from it.
+   */
+  generateStableIds() {
+    if (!this.getOptions().stableIds) {
+      this.writeData('PRODUCTION_IDS', '&[]');
+      this.writeData('STATE_IDS', '&[]');
+      return;
     }
 
-    return (
-      '// Semantic values prologue.\n' +
-      argsPrologue.join('\n') + '\n\n' +
-      action
+    const productionKeys = this._grammar
+      .getProductions()
+      .map(production => production.toFullString());
+
+    // The lookaheads distinguish the CLR(1) states with the same items.
+    const withLookaheads = this._grammar.getMode().isCLR1();
+
+    const states = this._table.getCanonicalCollection().getStates();
+
+    const stateKeys = Array.from(states)
+      .sort((a, b) => a.getNumber() - b.getNumber())
+      .map(state =>
+        state
+          .getKernelItems()
+          .map(item => {
+            const RHS = item
+              .getProduction()
+              .getRHS()
+              .map(symbol => symbol.getSymbol());
+            RHS.splice(item.getDotPosition(), 0, '•');
+
+            const lookaheads = withLookaheads
+              ? ` ${Object.keys(item.getLookaheadSet()).sort().join(' ')}`
+              : '';
+
+            return (
+              `${item.getProduction().getLHS().getSymbol()} -> ` +
+              `${RHS.join(' ')}${lookaheads}`
+            );
+          })
+          .sort()
+          .join('\n')
+      );
+
+    this.writeData(
+      'PRODUCTION_IDS',
+      '&' + this._toRustArray(this._toStableIds(productionKeys), 8)
+    );
+
+    this.writeData(
+      'STATE_IDS',
+      '&' + this._toRustArray(this._toStableIds(stateKeys), 8)
     );
   },
 
   /**
-   * Extracts types of the used arguments, and return types.
-   * If a type is defined, a popped stack value is casted,
-   * otherwise, it's just popped (if doesn't participate in an operation
-   * and just propagated).
+   * The first 32 bits of the sha256 hash of each key, a repeated key,
+   * or a collision is hashed again with `#` appended.
    */
-  _extractDataTypes(action) {
-    const types = {};
-
-    if (!action) {
-      return {action: '', types};
-    }
-
-    const typesRe = /\s*\|([^|]*)\|\s*->\s*([\w&][\w<>, '&]*);/g;
-    const typesData = typesRe.exec(action);
+  _toStableIds(keys) {
+    const ids = new Set();
+
+    return keys.map(key => {
+      let id;
+
+      do {
+        id = crypto
+          .createHash('sha256')
+          .update(key)
+          .digest('hex')
+          .slice(0, 8);
+        key += '#';
+      } while (ids.has(id));
+
+      ids.add(id);
+      return `0x${id}`;
+    });
+  },
 
-    if (typesData) {
-      // '$1:i32, $3:i32'
-      if (typesData[1].trim().length > 0) {
-        const argTypes = typesData[1].trim().split(/\s*[,;]\s*/);
-        argTypes.forEach(argData => {
-          const data = argData.split(/\s*:\s*/);
-          // types[$1] = 'i32';
-          types[data[0]] = data[1];
+  /**
+   * With the `graphemeColumns` option the columns are counted in grapheme
+   * clusters (as editors show them) for the `unicode-segmentation` feature
+   * of the crate, otherwise it's not compiled (`any()`).
+   */
+  generateGraphemeColumns() {
+    const {graphemeColumns} = this.getOptions();
 
-          // Save the type to all types data to generate enum.
-          if (!this._allTypes.hasOwnProperty(data[1])) {
-            this._allTypes[data[1]] = this._allTypesIndex++;
-          }
-        });
-      }
+    this.writeData('GRAPHEME_COLUMNS', graphemeColumns ? 'true' : 'false');
 
-      // Result type.
-      if (typesData[2]) {
-        types.__ = typesData[2];
+    const feature = graphemeColumns
+      ? 'feature = "unicode-segmentation"'
+      : 'any()';
 
-        if (!this._allTypes.hasOwnProperty(typesData[2])) {
-          this._allTypes[typesData[2]] = this._allTypesIndex++;
-        }
-      }
+    while (this._resultData.includes('{{{SEGMENTATION_FEATURE}}}')) {
+      this.writeData('SEGMENTATION_FEATURE', feature);
     }
+  },
 
-    // Strip the types info.
-    action = action.replace(typesRe, '');
-
-    // Extract other args, which do not use types.
-    const argsRe = /_\d+/g;
+  /**
+   * With the `emitTest` option (a sample input) a test module is generated,
+   * which parses the sample, and fails on a syntax error.
+   */
+  generateSmokeTest() {
+    const {emitTest} = this.getOptions();
 
-    const usedArgs = action.match(argsRe);
-    if (usedArgs) {
-      usedArgs.forEach(usedArg => {
-        if (!types.hasOwnProperty(usedArg)) {
-          // Arg is used, but without a type.
-          types[usedArg] = null;
-        }
-      });
+    if (emitTest == null) {
+      this.writeData('SMOKE_TEST', '');
+      return;
     }
 
-    return {types, action};
+    this.writeData('SMOKE_TEST', RUST_SMOKE_TEST_TEMPLATE);
+    this.writeData('TEST_INPUT', this._toRustString(String(emitTest)));
   },
 
   /**
-   * Default format in the [ ] array notation.
+   * Rust string literal of a string: the JSON escapes, except the ones
+   * which Rust doesn't have (`\b`, `\f`, and `\uXXXX`).
    */
-  generateProductionsData() {
-    return this.generateRawProductionsData()
-      .map(data => {
-        // Remove the semantic action handler, since in Rust
-        // we use a different structure to hold it.
-        delete data[2];
-        return `[${data.join(', ')}]`;
-      });
+  _toRustString(string) {
+    return JSON.stringify(string).replace(
+      /\\(\\|b|f|u([0-9a-f]{4}))/g,
+      (escape, sequence, code) => {
+        switch (sequence) {
+          case 'b':
+            return '\\u{8}';
+          case 'f':
+            return '\\u{c}';
+          default:
+            return code ? `\\u{${code}}` : escape;
+        }
+      }
+    );
   },
 
   /**
-   * Generates built-in tokenizer instance.
+   * With the `procMacroSpans` option locations are converted to spans of
+   * a string literal for the `proc-macro2` feature of the crate.
    */
-  generateBuiltInTokenizer() {
-    this.writeData('TOKENIZER', RUST_TOKENIZER_TEMPLATE);
+  generateProcMacroSpans() {
+    this.writeData(
+      'PROC_MACRO_SPANS',
+      this.getOptions().procMacroSpans ? RUST_PROC_MACRO_TEMPLATE : ''
+    );
   },
 
   /**
@@ -278,9 +1692,28 @@ const RustParserGeneratorTrait = {
    */
   generateLexRules() {
     const lexRulesArray = [];
+    const {keywords, keywordRules} = this._getKeywordTable();
+    const rules = this._grammar.getLexGrammar().getRules();
+
+    const lexRules = rules.map((rule, i) => {
+      let rawHandler = rule.getRawHandler();
+
+      // The identifier rule looks up the keywords in the matched text.
+      if (keywords.has(rule)) {
+        rawHandler =
+          keywordLookup(
+            keywords.get(rule),
+            i,
+            keywordRules.get(rule).map(keywordRule => rules.indexOf(keywordRule))
+          ) +
+          '\n' +
+          rawHandler;
+      }
 
-    const lexRules = this._grammar.getLexGrammar().getRules().map((rule, i) => {
-      let action = this._actionFromHandler(rule.getRawHandler());
+      // An empty handler (e.g. of the `%ignore` rules) skips the token.
+      let action = rawHandler.trim()
+        ? this._actionFromHandler(rawHandler)
+        : '""';
 
       this._lexHandlers.push({args: '&mut self', action});
 
@@ -304,6 +1737,9 @@ const RustParserGeneratorTrait = {
       // (actually you cannot have such escape sequence as '\/', whihc causes rust's regex parser to panic!)
       matcher = matcher.replace("\\/", "/");
 
+      // Saved for the combined alternation of the rules of a state.
+      this._lexRulePatterns.push(`${flags}${matcher}`);
+
       // well, maybe so many # is enough
       // this is for handling character '"' correctly
       // reference: rust's raw string literals https://rahul-thakoor.github.io/rust-raw-string-literals
@@ -318,14 +1754,14 @@ const RustParserGeneratorTrait = {
 
     this.writeData(
       'LEX_RULES',
-      `[&'static str; ${lexRules.length}] = ` +
+      `[&str; ${lexRules.length}] = ` +
       `[\n    ${lexRules.join(',\n    ')}\n]`
     );
   },
 
   generateLexRulesByStartConditions() {
     const lexGrammar = this._grammar.getLexGrammar();
-    const lexRulesByConditions = lexGrammar.getRulesByStartConditions();
+    const lexRulesByConditions = this._getKeywordTable().rulesByConditions;
     const result = [];
 
     for (const condition in lexRulesByConditions) {
@@ -336,7 +1772,65 @@ const RustParserGeneratorTrait = {
 
     this.writeData(
       'LEX_RULES_BY_START_CONDITIONS',
-      `${this._toRustHashMap(result, 'string')}`,
+      this.getOptions().constTables
+        ? this._toRustSortedArray(result, rules => `&[${rules.join(', ')}]`)
+        : this._toRustHashMap(result, 'string'),
+    );
+
+    this.generateCombinedLexRules(result);
+  },
+
+  /**
+   * Keyword rules matched by the identifier rule (the `keywordTable`
+   * option, enabled by default): the rules of the start conditions
+   * without them, and the keywords of the identifier rules.
+   */
+  _getKeywordTable() {
+    if (!this._keywordTable) {
+      const lexGrammar = this._grammar.getLexGrammar();
+
+      this._keywordTable =
+        this.getOptions().keywordTable === false
+          ? {
+              rulesByConditions: lexGrammar.getRulesByStartConditions(),
+              keywords: new Map(),
+              keywordRules: new Map(),
+            }
+          : buildKeywordTable(lexGrammar);
+    }
+    return this._keywordTable;
+  },
+
+  /**
+   * With the `lexAlternation` option the rules of a start condition
+   * are combined into one alternation, `(rule_1)|(rule_2)|...`, where the
+   * number of the matched group is the position of the matched rule.
+   * A condition is combined only if all its rules are combinable.
+   */
+  generateCombinedLexRules(rulesByConditions) {
+    const combinedRules = [];
+
+    if (this.getOptions().lexAlternation) {
+      for (const condition in rulesByConditions) {
+        const patterns = rulesByConditions[condition].map(
+          index => this._lexRulePatterns[index]
+        );
+
+        if (patterns.length === 0 || !patterns.every(isCombinableMatcher)) {
+          continue;
+        }
+
+        const alternation = patterns.map(pattern => `(${pattern})`).join('|');
+        combinedRules.push(
+          `("${condition}", r##########"${alternation}"##########)`
+        );
+      }
+    }
+
+    this.writeData(
+      'COMBINED_LEX_RULES',
+      `[(&str, &str); ${combinedRules.length}] = ` +
+      `[\n    ${combinedRules.join(',\n    ')}\n]`
     );
   },
 
@@ -346,10 +1840,10 @@ const RustParserGeneratorTrait = {
    */
   _scopeVars(code, context = '') {
     return code
-      .replace(/(.?)yytext/g, ifNotStartsWithDotReplacer(`self${context}.yytext`))
-      .replace(/(.?)yyleng/g, ifNotStartsWithDotReplacer(`self${context}.yyleng`))
+      .replace(globalVar('yytext'), ifNotStartsWithDotReplacer(`self${context}.yytext`))
+      .replace(globalVar('yyleng'), ifNotStartsWithDotReplacer(`self${context}.yyleng`))
       .replace(/__\s*=/g, `let __ =`)
-      .replace(/(.?)yyloc/g, ifNotStartsWithDotReplacer('Loc::from_tokens_range'));
+      .replace(globalVar('yyloc'), ifNotStartsWithDotReplacer('Loc::from_tokens_range'));
   },
 
   /**
@@ -380,6 +1874,18 @@ const RustParserGeneratorTrait = {
     }
   },
 
+  /**
+   * Converts JS object to a Rust array of `(key, value)` pairs sorted by
+   * the keys (in the byte order of UTF-8, as the `const fn` lookups).
+   */
+  _toRustSortedArray(object, toRustValue) {
+    const entries = Object.keys(object)
+      .sort((a, b) => Buffer.compare(Buffer.from(a), Buffer.from(b)))
+      .map(key => `(${this._toRustString(key)}, ${toRustValue(object[key])})`);
+
+    return `&[${entries.join(', ')}]`;
+  },
+
   /**
    * Converts JS object to Rust HashMap representation.
    */
@@ -415,7 +1921,7 @@ const RustParserGeneratorTrait = {
     const handlers = this._generateHandlers(
       this._productionHandlers,
       '_handler',
-      'SV'
+      "SV<'t>"
     );
 
     this.writeData('PRODUCTION_HANDLERS_COUNT', handlers.length);
@@ -430,20 +1936,51 @@ const RustParserGeneratorTrait = {
       `[\n    ${handlersArray.join(',\n    ')}\n],`
     );
 
-    this.writeData('PRODUCTION_HANDLERS', handlers.join('\n\n'));
+    // Typed actions, called from the handlers.
+    this.writeData(
+      'PRODUCTION_HANDLERS',
+      handlers.concat(this._actionHelpers).join('\n\n')
+    );
   },
 
   /**
    * Productions array in Rust format.
    */
   generateProductions() {
-    const productionsData = this.generateProductionsData();
+    // The augmented production is never reduced, and has no LHS symbol.
+    const productionsData = this.generateProductionsData().map(data =>
+      data.replace(/^\[-1,/, '[EncodedSymbol::MAX,')
+    );
     const productionsCount = productionsData.length;
     this.writeData(
       'PRODUCTIONS',
-      `[[i32; 2]; ${productionsCount}] = ` +
+      `[[EncodedSymbol; 2]; ${productionsCount}] = ` +
       `[\n    ${productionsData.join(',\n    ')}\n]`
     );
+
+    this.generateProductionSymbols();
+  },
+
+  /**
+   * Names of the LHS, and RHS symbols of the productions,
+   * the `PRODUCTIONS` static available at runtime.
+   */
+  generateProductionSymbols() {
+    const productions = this.getGrammar()
+      .getProductions()
+      .map(production => {
+        const LHS = JSON.stringify(production.getLHS().getSymbol());
+        const RHS = production.isEpsilon()
+          ? []
+          : production.getRHSSymbols().map(symbol => JSON.stringify(symbol));
+
+        return `(${LHS}, &[${RHS.join(', ')}])`;
+      });
+
+    this.writeData(
+      'PRODUCTION_SYMBOLS',
+      `&[\n    ${productions.join(',\n    ')}\n]`
+    );
   },
 
   /**
@@ -452,7 +1989,9 @@ const RustParserGeneratorTrait = {
   generateModuleInclude() {
     const moduleInclude = this._grammar.getModuleInclude();
 
-    const resultTypeData = /type\s+TResult\s*=\s*([^;]+);/.exec(moduleInclude);
+    const resultTypeData = /type\s+TResult\s*(<\s*'\w+\s*>)?\s*=\s*([^;]+);/.exec(
+      moduleInclude
+    );
 
     if (!resultTypeData) {
       throw new Error(
@@ -461,44 +2000,290 @@ const RustParserGeneratorTrait = {
       );
     }
 
-    // Result type.
-    const resultType = resultTypeData[1];
+    // Result type, it's `TResult<'t>` if it borrows from the parsing string.
+    const resultType = Object.keys(this._allTypes).find(
+      typeName =>
+        this._withoutLifetimes(typeName) ===
+        this._withoutLifetimes(resultTypeData[2])
+    );
 
-    if (!this._allTypes.hasOwnProperty(resultType)) {
+    if (!resultType) {
       throw new Error(
-        `Result type ${colors.bold(resultType)} is not found in ` +
+        `Result type ${colors.bold(resultTypeData[2])} is not found in ` +
         `handled types. Make sure your productions return it.\n`
       );
     }
 
-    this.writeData('RESULT_TYPE', `_${this._allTypes[resultType]}`);
+    this.generateAstType(moduleInclude);
+
+    // The result type of the parser, and of the grammar `parse` methods.
+    while (this._resultData.includes('{{{PARSE_RESULT_TYPE}}}')) {
+      this.writeData(
+        'PARSE_RESULT_TYPE',
+        resultTypeData[1] ? "TResult<'t>" : 'TResult'
+      );
+    }
+
+    // `Grammar::parse` names the string lifetime only if the result
+    // borrows from the string, otherwise it's elided.
+    this.writeData('GRAMMAR_PARSE_GENERICS', resultTypeData[1] ? "<'t, S>" : '<S>');
+    this.writeData('GRAMMAR_PARSE_STRING', resultTypeData[1] ? "&'t S" : '&S');
+
+    while (this._resultData.includes('{{{RESULT_TYPE}}}')) {
+      this.writeData('RESULT_TYPE', `_${this._allTypes[resultType]}`);
+    }
 
     // Parser hooks.
     const onParseBegin = moduleInclude.indexOf('fn on_parse_begin') !== -1
-      ? 'on_parse_begin(self, &string);'
+      ? 'on_parse_begin(self, string);'
       : '';
 
     const onParseEnd = moduleInclude.indexOf('fn on_parse_end') !== -1
       ? 'on_parse_end(self, &result);'
       : '';
 
+    // The error hook is called before the error is returned from `parse`.
     const onParseError = moduleInclude.indexOf('fn on_parse_error') !== -1
-      ? 'on_parse_error(self, &token);'
-      : DEFAULT_ERROR_HANDLER;
+      ? 'on_parse_error(self, token);'
+      : '';
 
     this.writeData('ON_PARSE_BEGIN_CALL', onParseBegin);
     this.writeData('ON_PARSE_END_CALL', onParseEnd);
     this.writeData('ON_PARSE_ERROR_CALL', onParseError);
 
     this.writeData('MODULE_INCLUDE', moduleInclude);
+
+    this.generateActionsModule();
+  },
+
+  /**
+   * Public alias of the result type, `Ast<'t>` with the lifetime of the
+   * parsing string whether the result borrows from it or not, so the
+   * callers don't depend on it. It's not generated if the module include
+   * declares an `Ast` itself.
+   */
+  generateAstType(moduleInclude) {
+    if (/\b(?:type|struct|enum|union|trait)\s+Ast\b/.test(moduleInclude)) {
+      this.writeData('AST_TYPE', '');
+      return;
+    }
+
+    this.writeData(
+      'AST_TYPE',
+      `/**\n` +
+      ` * Parsed value of a string (the result type of the grammar),\n` +
+      ` * which can't outlive the string:\n` +
+      ` *\n` +
+      ` *   fn parse_source(source: &str) -> Result<Ast<'_>, SyntaxError> {\n` +
+      ` *       Parser::new().parse(source)\n` +
+      ` *   }\n` +
+      ` */\n` +
+      `pub type Ast<'t> = {{{PARSE_RESULT_TYPE}}};\n`
+    );
+  },
+
+  /**
+   * With the `actionsModule` option the module with functions called
+   * from semantic actions is declared, e.g. `mod actions;` for the
+   * `actions.rs` file next to the generated parser.
+   */
+  generateActionsModule() {
+    const actionsModule = this.getOptions().actionsModule;
+
+    if (actionsModule && !/^[A-Za-z_]\w*$/.test(actionsModule)) {
+      throw new Error(
+        `Invalid actions module name ${colors.bold(actionsModule)}, ` +
+        `should be a Rust identifier.`
+      );
+    }
+
+    this.writeData(
+      'ACTIONS_MODULE',
+      actionsModule ? `mod ${actionsModule};` : ''
+    );
+  },
+
+  /**
+   * With the `emitCrate` option the parser is generated into `src/lib.rs`
+   * of a crate, and its `Cargo.toml` is written next to `src`, with the
+   * dependencies for the used options. An existing manifest is kept.
+   */
+  writeCrateManifest() {
+    const crateDir = path.dirname(path.dirname(this._outputFile));
+    const manifestFile = path.join(crateDir, 'Cargo.toml');
+
+    if (fs.existsSync(manifestFile)) {
+      return;
+    }
+
+    const {
+      regexBackend,
+      wasm,
+      asyncStream,
+      procMacroSpans,
+      graphemeColumns,
+      constTables,
+      statics = 'lazy_static',
+      emitBench,
+    } = this.getOptions();
+    const normalizeTokens = this._getNormalizedTokens().length > 0;
+
+    const dependencies = [
+      regexBackend === 'regex' ? `regex = "1"` : `onig = "4"`,
+    ];
+
+    // The const tables have no lazily initialized data, and the std
+    // statics need no crate.
+    if (!constTables && statics === 'lazy_static') {
+      dependencies.push(`lazy_static = "1"`);
+    } else if (!constTables && statics === 'once_cell') {
+      dependencies.push(`once_cell = "1"`);
+    }
+
+    const features = [];
+
+    // The entry point is compiled only for the `wasm` feature.
+    if (wasm) {
+      dependencies.push(
+        `wasm-bindgen = { version = "0.2", optional = true }`
+      );
+      features.push(`wasm = ["wasm-bindgen"]`);
+    }
+
+    // The async parse is compiled only for the `futures` feature.
+    if (asyncStream) {
+      dependencies.push(`futures = { version = "0.3", optional = true }`);
+    }
+
+    // Spans, the normalization, and the grapheme columns are enabled
+    // by default, the features can be turned off.
+    const defaultFeatures = [];
+
+    if (procMacroSpans) {
+      dependencies.push(`proc-macro2 = { version = "1", optional = true }`);
+      defaultFeatures.push(`"proc-macro2"`);
+    }
+
+    if (normalizeTokens) {
+      dependencies.push(
+        `unicode-normalization = { version = "0.1", optional = true }`
+      );
+      defaultFeatures.push(`"unicode-normalization"`);
+    }
+
+    if (graphemeColumns) {
+      dependencies.push(
+        `unicode-segmentation = { version = "1", optional = true }`
+      );
+      defaultFeatures.push(`"unicode-segmentation"`);
+    }
+
+    if (defaultFeatures.length > 0) {
+      features.push(`default = [${defaultFeatures.join(', ')}]`);
+    }
+
+    const sections = [
+      `[package]\n` +
+      `name = "${path.basename(path.resolve(crateDir))}"\n` +
+      `version = "0.1.0"\n` +
+      `edition = "2018"\n`,
+    ];
+
+    // A wasm module is built with `wasm-pack` as a dynamic library.
+    if (wasm) {
+      sections.push(`[lib]\ncrate-type = ["cdylib", "rlib"]\n`);
+    }
+
+    sections.push(`[dependencies]\n${dependencies.join('\n')}\n`);
+
+    if (features.length > 0) {
+      sections.push(`[features]\n${features.join('\n')}\n`);
+    }
+
+    // The benchmark runs with the harness of `criterion`.
+    if (emitBench != null) {
+      sections.push(`[dev-dependencies]\ncriterion = "0.5"\n`);
+      sections.push(`[[bench]]\nname = "parse"\nharness = false\n`);
+    }
+
+    fs.writeFileSync(manifestFile, sections.join('\n'), 'utf-8');
+  },
+
+  /**
+   * With the `emitRepl` option a REPL binary is written to `src/main.rs`
+   * of the crate (the parser is its crate root, so the option is used
+   * with the `emitCrate` one). An existing `main.rs` is kept.
+   */
+  writeRepl() {
+    if (!this.getOptions().emitCrate) {
+      throw new Error(
+        `The REPL is generated into a crate, use it with the ` +
+        `${colors.bold('--emit-crate')} option.`
+      );
+    }
+
+    const replFile = path.join(path.dirname(this._outputFile), 'main.rs');
+
+    if (fs.existsSync(replFile)) {
+      return;
+    }
+
+    // Name of the library crate of the parser.
+    const crateDir = path.dirname(path.dirname(this._outputFile));
+    const crateName = path.basename(path.resolve(crateDir)).replace(/-/g, '_');
+
+    fs.writeFileSync(
+      replFile,
+      RUST_REPL_TEMPLATE.replace('{{{CRATE_NAME}}}', crateName),
+      'utf-8'
+    );
+  },
+
+  /**
+   * With the `emitBench` option (a file of a sample input) a `criterion`
+   * benchmark is written to `benches/parse.rs` of the crate, with the
+   * sample copied next to it. An existing benchmark is kept.
+   */
+  writeBench() {
+    const {emitBench, emitCrate} = this.getOptions();
+
+    if (!emitCrate) {
+      throw new Error(
+        `The benchmark is generated into a crate, use it with the ` +
+        `${colors.bold('--emit-crate')} option.`
+      );
+    }
+
+    const crateDir = path.dirname(path.dirname(this._outputFile));
+    const benchesDir = path.join(crateDir, 'benches');
+    const benchFile = path.join(benchesDir, 'parse.rs');
+
+    if (fs.existsSync(benchFile)) {
+      return;
+    }
+
+    const sampleFile = path.basename(emitBench);
+    const crateName = path.basename(path.resolve(crateDir)).replace(/-/g, '_');
+
+    fs.mkdirSync(benchesDir, {recursive: true});
+    fs.copyFileSync(emitBench, path.join(benchesDir, sampleFile));
+
+    fs.writeFileSync(
+      benchFile,
+      RUST_BENCH_TEMPLATE
+        .replace('{{{CRATE_NAME}}}', crateName)
+        .replace('{{{SAMPLE_FILE}}}', () => this._toRustString(sampleFile)),
+      'utf-8'
+    );
   },
 
   /**
    * Generates Rust function declarations for handlers.
    */
   _generateHandlers(handlers, name, returnType) {
-    return handlers.map(({args, action}, index) => {
-      return `fn ${name}${index}` +
+    return handlers.map(({args, action, doc}, index) => {
+      return (doc ? `${this._toDocComment(doc)}\n` : '') +
+        `fn ${name}${index}` +
         `(${args}) -> ${returnType} {\n${action}\n}`
     });
   },
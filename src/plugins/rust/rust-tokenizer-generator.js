@@ -0,0 +1,104 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+const BaseParserGenerator = require(ROOT + 'base-parser-generator').default;
+const RustParserGeneratorTrait = require('./rust-parser-generator-trait');
+
+import fs from 'fs';
+
+import {EOF} from '../../special-symbols';
+
+/**
+ * Rust template of a standalone tokenizer.
+ */
+const RUST_STANDALONE_TOKENIZER_TEMPLATE = fs.readFileSync(
+  `${__dirname}/templates/standalone-tokenizer.template.rs`,
+  'utf-8',
+);
+
+/**
+ * Generates a standalone Rust tokenizer (the `--tokenizer-only` option):
+ * the lex rules, and the public `Tokenizer`, without the parser, and its
+ * tables.
+ */
+export default class RustTokenizerGenerator extends BaseParserGenerator {
+
+  /**
+   * Instance constructor.
+   */
+  constructor({
+    grammar,
+    outputFile,
+    options = {},
+  }) {
+    super({grammar, outputFile, options})
+      .setTemplate(RUST_STANDALONE_TOKENIZER_TEMPLATE);
+
+    this._lexHandlers = [];
+    this._lexRulePatterns = [];
+
+    // Trait provides methods for lex handlers.
+    Object.assign(this, RustParserGeneratorTrait);
+  }
+
+  /**
+   * Tokens of the grammar, and the tokens returned from the lex rule
+   * handlers (a lexical grammar without BNF has only these).
+   */
+  _encodeSymbols() {
+    super._encodeSymbols();
+
+    const tokens = Object.keys(this._tokens).filter(token => token !== EOF);
+    const returnRe = /\breturn\s+(["'])((?:(?!\1).)+)\1/g;
+
+    this._grammar
+      .getLexGrammar()
+      .getRules()
+      .forEach(rule => {
+        let match;
+        while ((match = returnRe.exec(rule.getRawHandler()))) {
+          if (!tokens.includes(match[2])) {
+            tokens.push(match[2]);
+          }
+        }
+      });
+
+    this._tokens = {};
+    tokens.forEach((token, index) => (this._tokens[token] = '' + index));
+    this._tokens[EOF] = '' + tokens.length;
+  }
+
+  /**
+   * Generates tokenizer module, and with the `emitCrate` option
+   * the manifest of its crate.
+   */
+  generate() {
+    const result = super.generate();
+
+    if (this._outputFile && this.getOptions().emitCrate) {
+      this.writeCrateManifest();
+    }
+
+    return result;
+  }
+
+  /**
+   * Generates tokenizer code.
+   */
+  generateParserData() {
+    this.generateTokenizer();
+    this.generateTokensTable();
+    this.generateLexHandlers();
+
+    // No result type is required without a parser.
+    this.writeData('MODULE_INCLUDE', this._grammar.getModuleInclude());
+
+    this.generateStableApi();
+    this.generateNodeAttributes();
+    this.generateProcMacroSpans();
+
+    return this._resultData;
+  }
+};
@@ -0,0 +1,51 @@
+use {{{CRATE_NAME}}}::{Parser, Tokenizer};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/**
+ * Sample input of the grammar (the `emitBench` option).
+ */
+const SAMPLE: &str = include_str!({{{SAMPLE_FILE}}});
+
+/**
+ * Benchmark of the parser (the `emitBench` option): parses the sample,
+ * and tokenizes it only, to measure the tokenizer apart from the parser.
+ * The throughput is reported in the bytes of the sample.
+ *
+ *   cargo bench
+ */
+fn parse(c: &mut Criterion) {
+    if let Err(error) = Parser::new().parse(SAMPLE) {
+        panic!("{}", error);
+    }
+
+    let mut group = c.benchmark_group("sample");
+    group.throughput(Throughput::Bytes(SAMPLE.len() as u64));
+
+    let mut parser = Parser::new();
+
+    group.bench_function("parse", |b| {
+        b.iter(|| black_box(parser.parse(black_box(SAMPLE)).is_ok()))
+    });
+
+    let mut tokenizer = Tokenizer::new();
+
+    group.bench_function("tokenize", |b| {
+        b.iter(|| {
+            tokenizer.init_string(black_box(SAMPLE));
+
+            while let Ok(token) = tokenizer.get_next_token() {
+                if token.is_eof() {
+                    break;
+                }
+                black_box(token);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
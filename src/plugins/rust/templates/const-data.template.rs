@@ -0,0 +1,119 @@
+// ------------------------------------------------------------------
+// Shared data of the lex rules, and the tokens, as static arrays with
+// `const fn` accessors (the `constTables` option). Only the regexes are
+// compiled at runtime.
+
+/**
+ * Lexical rules grouped by lexer state (by start condition), sorted
+ * by the state.
+ */
+static LEX_RULES_BY_START_CONDITIONS: &[(&str, &[i32])] = {{{LEX_RULES_BY_START_CONDITIONS}}};
+
+/**
+ * String names of the token types, and their encoded numbers (the first
+ * token number starts after all numbers for non-terminal), sorted by
+ * the name.
+ */
+static TOKENS_MAP: &[(&str, i32)] = {{{TOKENS}}};
+
+/**
+ * Regexes of the lex rules, and the combined regexes of the states (in
+ * the order of `COMBINED_LEX_RULES`), compiled on the first use.
+ */
+static REGEX_RULES: std::sync::OnceLock<Vec<Regex>> = std::sync::OnceLock::new();
+static COMBINED_REGEX_RULES: std::sync::OnceLock<Vec<Regex>> = std::sync::OnceLock::new();
+
+/**
+ * Position of a name in the entries sorted by the names, found with
+ * the binary search. The names are compared byte by byte, since the
+ * `str` comparisons are not `const`.
+ */
+const fn find_name<T>(entries: &[(&str, T)], name: &str) -> Option<usize> {
+    let name = name.as_bytes();
+    let mut low = 0;
+    let mut high = entries.len();
+
+    while low < high {
+        let middle = (low + high) / 2;
+        let entry = entries[middle].0.as_bytes();
+
+        let mut i = 0;
+        while i < entry.len() && i < name.len() && entry[i] == name[i] {
+            i += 1;
+        }
+
+        if i == entry.len() && i == name.len() {
+            return Some(middle);
+        }
+
+        let entry_is_less = if i < entry.len() && i < name.len() {
+            entry[i] < name[i]
+        } else {
+            i == entry.len()
+        };
+
+        if entry_is_less {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    None
+}
+
+/**
+ * Encoded number of a token type (the `kind` of its tokens), e.g. of
+ * a synthetic token, also in `const` contexts:
+ *
+ *   const NUMBER: Option<i32> = token_kind("NUMBER");
+ */
+pub const fn token_kind(token_type: &str) -> Option<i32> {
+    match find_name(TOKENS_MAP, token_type) {
+        Some(index) => Some(TOKENS_MAP[index].1),
+        None => None,
+    }
+}
+
+/**
+ * Numbers of the lex rules of a state (start condition).
+ */
+const fn lex_rules_of_state(state: &str) -> &'static [i32] {
+    match find_name(LEX_RULES_BY_START_CONDITIONS, state) {
+        Some(index) => LEX_RULES_BY_START_CONDITIONS[index].1,
+        None => panic!("Unknown start condition"),
+    }
+}
+
+fn regex_rules() -> &'static [Regex] {
+    REGEX_RULES.get_or_init(|| LEX_RULES.iter().map(|rule| lex_rule_regex(rule)).collect())
+}
+
+fn combined_regex_rules() -> &'static [Regex] {
+    COMBINED_REGEX_RULES.get_or_init(|| COMBINED_LEX_RULES.iter().map(|(_, rule)| combined_lex_rule_regex(rule)).collect())
+}
+
+/**
+ * Regex of a lex rule by its number.
+ */
+fn regex_rule(rule: usize) -> &'static Regex {
+    &regex_rules()[rule]
+}
+
+/**
+ * Combined regex of the rules of a state, if they are combined.
+ */
+fn combined_regex_rule(state: &str) -> Option<&'static Regex> {
+    COMBINED_LEX_RULES
+        .iter()
+        .position(|(rule_state, _)| *rule_state == state)
+        .map(|index| &combined_regex_rules()[index])
+}
+
+/**
+ * Compiles the regexes, if they are not yet.
+ */
+fn initialize_shared_data() {
+    regex_rules();
+    combined_regex_rules();
+}
@@ -0,0 +1,95 @@
+// ------------------------------------------------------------------
+// Shared data of the lex rules, and the tokens, initialized lazily.
+
+/**
+ * A macro for map literals.
+ *
+ * hashmap!{ 1 => "one", 2 => "two" };
+ */
+macro_rules! hashmap(
+    { $($key:expr => $value:expr),+ } => {
+        {
+            let mut m = ::std::collections::HashMap::new();
+            $(
+                m.insert($key, $value);
+            )+
+            m
+        }
+     };
+);
+
+/**
+ * Declares the lazily initialized statics, with the mechanism chosen
+ * with the `statics` generator option.
+ */
+macro_rules! shared_statics {
+    ($($(#[$attr:meta])* static ref $name:ident: $ty:ty = $init:expr;)*) => {
+        {{{SHARED_STATICS}}}
+    };
+}
+
+shared_statics! {
+    /**
+     * Lexical rules grouped by lexer state (by start condition).
+     */
+    static ref LEX_RULES_BY_START_CONDITIONS: HashMap<&'static str, Vec<i32>> = {{{LEX_RULES_BY_START_CONDITIONS}}};
+
+    /**
+     * Maps a string name of a token type to its encoded number (the first
+     * token number starts after all numbers for non-terminal).
+     */
+    static ref TOKENS_MAP: HashMap<&'static str, i32> = {{{TOKENS}}};
+
+    /**
+     * Pre-parse the regex instead of parsing it every time when calling `get_next_token`.
+     * The regex crate is chosen with the `regexBackend` generator option.
+     */
+    static ref REGEX_RULES: Vec<Regex> = LEX_RULES.iter().map(|rule| lex_rule_regex(rule)).collect();
+
+    /**
+     * Combined regexes by start conditions: the first matched rule of a
+     * state is found with one match instead of trying the rules one by one.
+     */
+    static ref COMBINED_REGEX_RULES: HashMap<&'static str, Regex> = COMBINED_LEX_RULES.iter().map(|(state, rule)| (*state, combined_lex_rule_regex(rule))).collect();
+}
+
+/**
+ * Encoded number of a token type (the `kind` of its tokens), e.g. of
+ * a synthetic token:
+ *
+ *   token.kind == token_kind("NUMBER").unwrap()
+ */
+pub fn token_kind(token_type: &str) -> Option<i32> {
+    TOKENS_MAP.get(token_type).cloned()
+}
+
+/**
+ * Numbers of the lex rules of a state (start condition).
+ */
+fn lex_rules_of_state(state: &str) -> &'static [i32] {
+    &LEX_RULES_BY_START_CONDITIONS[state]
+}
+
+/**
+ * Regex of a lex rule by its number.
+ */
+fn regex_rule(rule: usize) -> &'static Regex {
+    &REGEX_RULES[rule]
+}
+
+/**
+ * Combined regex of the rules of a state, if they are combined.
+ */
+fn combined_regex_rule(state: &str) -> Option<&'static Regex> {
+    COMBINED_REGEX_RULES.get(state)
+}
+
+/**
+ * Compiles the regexes, and builds the maps, if they are not yet.
+ */
+fn initialize_shared_data() {
+    {{{FORCE_STATIC}}}(&REGEX_RULES);
+    {{{FORCE_STATIC}}}(&COMBINED_REGEX_RULES);
+    {{{FORCE_STATIC}}}(&LEX_RULES_BY_START_CONDITIONS);
+    {{{FORCE_STATIC}}}(&TOKENS_MAP);
+}
@@ -1,19 +1,22 @@
+// Generated by Syntax from the grammar with the sha256 hash:
+// {{{GRAMMAR_HASH}}}
+
 #![allow(dead_code)]
 #![allow(unused_mut)]
 #![allow(unreachable_code)]
+#![allow(clippy::just_underscores_and_digits)]
+#![allow(clippy::let_and_return)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::wrong_self_convention)]
+{{{EXTERN_CRATES}}}
 
-extern crate onig;
-
-#[macro_use]
-extern crate lazy_static;
-
-use onig::{Regex, Syntax, RegexOptions};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 
 /**
  * Stack value.
  */
-enum SV {
+enum SV<'t> {
     Undefined,
     {{{SV_ENUM}}}
 }
@@ -24,26 +27,67 @@ enum SV {
 static LEX_RULES: {{{LEX_RULES}}};
 
 /**
- * EOF value.
+ * Lex rules of a start condition combined into one alternation
+ * (the `lexAlternation` option).
  */
-static EOF: &'static str = "$";
+static COMBINED_LEX_RULES: {{{COMBINED_LEX_RULES}}};
 
 /**
- * A macro for map literals.
- *
- * hashmap!{ 1 => "one", 2 => "two" };
- */
-macro_rules! hashmap(
-    { $($key:expr => $value:expr),+ } => {
-        {
-            let mut m = ::std::collections::HashMap::new();
-            $(
-                m.insert($key, $value);
-            )+
-            m
-        }
-     };
-);
+ * EOF value (the `%eof` name of the grammar, `$` by default).
+ */
+static EOF: &str = {{{EOF}}};
+
+/**
+ * The end of input in error messages: the `%eof` name of the grammar,
+ * or "end of input".
+ */
+static EOF_DISPLAY_NAME: &str = {{{EOF_DISPLAY_NAME}}};
+
+/**
+ * Type of the encoded symbols in the static tables: the smallest unsigned
+ * type, which fits the numbers of the grammar (the `kind` of a token is
+ * still an `i32`).
+ */
+type EncodedSymbol = {{{ENCODED_SYMBOL_TYPE}}};
+
+/**
+ * Encoded number of the EOF token (the same as in `TOKENS_MAP`).
+ */
+const EOF_KIND: i32 = {{{EOF_KIND}}};
+
+/**
+ * Encoded number of the `error` token, which is shifted on a syntax error
+ * in the error recovery (see `Parser::parse_all`), or -1 if the grammar
+ * has no `error` productions.
+ */
+const ERROR_KIND: i32 = {{{ERROR_KIND}}};
+
+/**
+ * Encoded numbers of the synchronization tokens of the error recovery
+ * (`%sync ';' '}'`): if no state shifts the `error` token, the input is
+ * skipped up to one of them (see `Parser::parse_all`).
+ */
+const SYNC_KINDS: &[EncodedSymbol] = {{{SYNC_KINDS}}};
+
+/**
+ * Messages of the `error` productions by their numbers (`%msg "..."`):
+ * when such a production is reduced, the message replaces the one
+ * of the syntax error it recovered from.
+ */
+const ERROR_MESSAGES: &[(usize, &str)] = {{{ERROR_MESSAGES}}};
+
+/**
+ * Number of tokens shifted after a recovered syntax error, before a next
+ * error is reported (as in yacc), so one error is not reported again
+ * while the input is skipped.
+ */
+const RECOVERY_SHIFTS: usize = 3;
+
+/**
+ * Number of lookahead tokens for a conflict (the `lookahead` option):
+ * an action of the conflict is chosen by the following tokens.
+ */
+const LOOKAHEAD: usize = {{{LOOKAHEAD}}};
 
 /**
  * Unwraps a SV for the result. The result type is known from the grammar.
@@ -63,8 +107,67 @@ macro_rules! pop {
  * Productions data.
  *
  * 0 - encoded non-terminal, 1 - length of RHS to pop from the stack
+ * (the augmented production has no non-terminal: `EncodedSymbol::MAX`)
+ */
+static ENCODED_PRODUCTIONS : {{{PRODUCTIONS}}};
+
+/**
+ * The sha256 hash of the grammar source the parser is generated from,
+ * to check that the parser is up to date with the grammar.
+ */
+pub const GRAMMAR_HASH: &str = "{{{GRAMMAR_HASH}}}";
+
+/**
+ * Productions of the grammar: the LHS name, and the RHS symbol names
+ * (empty for an epsilon production), indexed by the production number,
+ * e.g. of the `ParseEvent::Reduce`. The production 0 is the augmented
+ * `$accept -> <start symbol>`.
+ */
+pub static PRODUCTIONS: &[(&str, &[&str])] = {{{PRODUCTION_SYMBOLS}}};
+
+/**
+ * Stable IDs of the productions, indexed by the production number (empty
+ * without the `stableIds` option): derived from the symbols of a
+ * production, an ID is kept when unrelated rules are added to the
+ * grammar, while the production numbers shift.
+ */
+pub static PRODUCTION_IDS: &[u32] = {{{PRODUCTION_IDS}}};
+
+/**
+ * Stable IDs of the states, indexed by the state number (empty without
+ * the `stableIds` option): derived from the kernel items of a state.
+ */
+pub static STATE_IDS: &[u32] = {{{STATE_IDS}}};
+
+/**
+ * The production number of a stable ID from `PRODUCTION_IDS`.
+ */
+pub fn production_number(id: u32) -> Option<usize> {
+    PRODUCTION_IDS.iter().position(|production_id| *production_id == id)
+}
+
+/**
+ * The state number of a stable ID from `STATE_IDS`.
+ */
+pub fn state_number(id: u32) -> Option<usize> {
+    STATE_IDS.iter().position(|state_id| *state_id == id)
+}
+
+{{{TOKEN_KIND}}}
+
+/**
+ * Symbol of each state: the grammar symbol (a token, or a non-terminal)
+ * shifted, or reduced to enter the state. The start state 0 has none.
+ * A token with an alias (`%token SEMI ";"`) is shown by its alias.
+ */
+static STATE_SYMBOLS: &[&str] = {{{STATE_SYMBOLS}}};
+
+/**
+ * Display names of the tokens in the error messages by their encoded
+ * numbers, in the order of the grammar: the alias of a token declared
+ * with `%token SEMI ";"`, or its name.
  */
-static PRODUCTIONS : {{{PRODUCTIONS}}};
+static TOKEN_DISPLAY_NAMES: &[(EncodedSymbol, &str)] = {{{TOKEN_DISPLAY_NAMES}}};
 
 /**
  * Table entry.
@@ -80,27 +183,100 @@ enum TE {
 
     // Simple state transition.
     Transit(usize),
+
+    // Unresolved conflict: the first action, which consumes the
+    // lookahead tokens, is chosen.
+    Conflict(&'static [TE]),
 }
 
-lazy_static! {
-    /**
-     * Lexical rules grouped by lexer state (by start condition).
-     */
-    static ref LEX_RULES_BY_START_CONDITIONS: HashMap<&'static str, Vec<i32>> = {{{LEX_RULES_BY_START_CONDITIONS}}};
+/**
+ * Parsing table, packed into static arrays (a displacement table): the
+ * rows of the states are interleaved, the entry of a state, and an
+ * encoded symbol is at `TABLE_BASE[state] + symbol` in `TABLE_ENTRIES`,
+ * if the slot belongs to the state in `TABLE_CHECK`.
+ */
+static TABLE_BASE: {{{TABLE_BASE}}};
 
-    /**
-     * Maps a string name of a token type to its encoded number (the first
-     * token number starts after all numbers for non-terminal).
-     */
-    static ref TOKENS_MAP: HashMap<&'static str, i32> = {{{TOKENS}}};
+static TABLE_CHECK: {{{TABLE_CHECK}}};
 
-    /**
-     * Parsing table.
-     *
-     * Vector index is the state number, value is a map
-     * from an encoded symbol to table entry (TE).
-     */
-    static ref TABLE: Vec<HashMap<i32, TE>>= {{{TABLE}}};
+static TABLE_ENTRIES: {{{TABLE_ENTRIES}}};
+
+/**
+ * Terminals of the tokens of the classes (the `tokenClasses` option),
+ * which are numbered after the EOF token: the kind `EOF_KIND + 1 + i`
+ * is parsed as the terminal `TOKEN_CLASSES[i]`.
+ */
+static TOKEN_CLASSES: &[EncodedSymbol] = {{{TOKEN_CLASSES}}};
+
+/**
+ * Entry of the parsing table for a state, and an encoded symbol
+ * (`None` for an error). It's a `const fn` with the `constTables`
+ * option.
+ */
+{{{CONST_FN}}}fn table_entry(state: usize, symbol: i32) -> Option<&'static TE> {
+    // A token of a class is looked up by the terminal of its class.
+    let symbol = if symbol > EOF_KIND {
+        TOKEN_CLASSES[(symbol - EOF_KIND - 1) as usize] as i32
+    } else {
+        symbol
+    };
+
+    let index = TABLE_BASE[state] as usize + symbol as usize;
+
+    if index < TABLE_CHECK.len() && TABLE_CHECK[index] as usize == state {
+        Some(&TABLE_ENTRIES[index])
+    } else {
+        None
+    }
+}
+
+/**
+ * Whether the parser in the states can consume the lookahead tokens (their
+ * kinds) starting with the action for the first token. Only the states
+ * are simulated, the semantic actions are not called.
+ */
+fn can_consume(states: &[usize], action: &TE, kinds: &[i32]) -> bool {
+    let mut states = states.to_vec();
+    let mut action = action;
+    let mut consumed = 0;
+
+    loop {
+        match *action {
+            TE::Shift(next_state) => {
+                states.push(next_state);
+                consumed += 1;
+
+                if consumed == kinds.len() {
+                    return true;
+                }
+            },
+
+            TE::Reduce(production_number) => {
+                let production = ENCODED_PRODUCTIONS[production_number];
+                states.truncate(states.len() - production[1] as usize);
+
+                match table_entry(*states.last().unwrap(), production[0] as i32) {
+                    Some(TE::Transit(next_state)) => states.push(*next_state),
+                    _ => return false,
+                }
+            },
+
+            TE::Accept => return true,
+
+            TE::Conflict(actions) => {
+                return actions
+                    .iter()
+                    .any(|action| can_consume(&states, action, &kinds[consumed..]));
+            },
+
+            TE::Transit(_) => unreachable!(),
+        }
+
+        action = match table_entry(*states.last().unwrap(), kinds[consumed]) {
+            Some(action) => action,
+            None => return false,
+        };
+    }
 }
 
 // ------------------------------------
@@ -110,6 +286,10 @@ lazy_static! {
 //
 // type TResult = <...>;
 //
+// The result type can also borrow from the parsing string:
+//
+// type TResult<'t> = <...>;
+//
 // Can also include parsing hooks:
 //
 //   fn on_parse_begin(parser: &mut Parser, string: &str) {
@@ -120,39 +300,275 @@ lazy_static! {
 //     ...
 //   }
 //
+//   fn on_parse_error(parser: &mut Parser, token: &Token) {
+//     ...
+//   }
+//
 
 {{{MODULE_INCLUDE}}}
 
+{{{ACTIONS_MODULE}}}
+
 // ---  end of Module include ---------
 
+{{{AST_TYPE}}}
+
 {{{TOKENIZER}}}
 
+// ------------------------------------------------------------------
+// Grammar.
+
+/**
+ * Compiled grammar: the parsing table, the lex rules, and their regexes
+ * are immutable, and shared by all parsers. The grammar is `Send + Sync`,
+ * and cheap to copy, so one grammar can be used from many threads, each
+ * parsing with an own parser (which holds the mutable parsing state):
+ *
+ *   let grammar = Grammar::new();
+ *
+ *   thread::spawn(move || grammar.parse("2 + 2"));
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Grammar;
+
+impl Grammar {
+    /**
+     * Returns the grammar, compiling the shared data (the regexes of the
+     * lex rules, and the maps) if it's not compiled yet, so it's not
+     * compiled lazily by the first parse of one of the threads. The
+     * parsing table is static data.
+     */
+    pub fn new() -> Grammar {
+        initialize_shared_data();
+
+        Grammar
+    }
+
+    /**
+     * Creates a new parser (a parsing session) for the grammar.
+     */
+    pub fn parser<'t>(&self) -> Parser<'t> {
+        Parser::new()
+    }
+
+    /**
+     * Parses a string with a new parser.
+     */
+    pub fn parse{{{GRAMMAR_PARSE_GENERICS}}}(&self, string: {{{GRAMMAR_PARSE_STRING}}}) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        self.parser().parse(string)
+    }
+}
+
 // ------------------------------------------------------------------
 // Parser.
 
 /**
- * Parser.
+ * Parsing event, produced on each step of the LR parsing loop.
+ */
+#[derive(Debug, Clone, Copy)]{{{NON_EXHAUSTIVE}}}
+pub enum ParseEvent<'t> {
+    /**
+     * A token is shifted onto the stack.
+     */
+    Shift(Token<'t>),
+
+    /**
+     * Stack is reduced by a production number (index in `PRODUCTIONS`),
+     * the span is the location of the reduced input.
+     */
+    Reduce { production: usize, span: Loc },
+
+    /**
+     * The input is accepted.
+     */
+    Accept,
+}
+
+/**
+ * Value of a grammar symbol in the runtime actions (see `set_action`):
+ * a shifted token, or a value built by an action.
+ */{{{NON_EXHAUSTIVE}}}
+pub enum ActionValue<'t> {
+    Token(Token<'t>),
+    Node(Box<dyn std::any::Any>),
+}
+
+impl<'t> ActionValue<'t> {
+    /**
+     * Value built by an action.
+     */
+    pub fn node<T: 'static>(value: T) -> ActionValue<'t> {
+        ActionValue::Node(Box::new(value))
+    }
+
+    /**
+     * The shifted token, panics for a value built by an action.
+     */
+    pub fn token(self) -> Token<'t> {
+        match self {
+            ActionValue::Token(token) => token,
+            ActionValue::Node(_) => panic!("Expected a token, found a value of an action."),
+        }
+    }
+
+    /**
+     * Takes the value built by an action, panics for a token, or a value
+     * of another type.
+     */
+    pub fn take<T: 'static>(self) -> T {
+        match self {
+            ActionValue::Node(value) => match value.downcast::<T>() {
+                Ok(value) => *value,
+                Err(_) => panic!("Unexpected type of a value of an action."),
+            },
+            ActionValue::Token(_) => panic!("Expected a value of an action, found a token."),
+        }
+    }
+}
+
+/**
+ * Whether a token kind is one of the `%sync` tokens.
+ */
+fn is_sync_kind(kind: i32) -> bool {
+    SYNC_KINDS.iter().any(|&sync_kind| sync_kind as i32 == kind)
+}
+
+/**
+ * Runtime semantic action of a production (see `set_action`): receives
+ * the values of the RHS symbols, and returns the value of the LHS.
+ */
+pub type RuntimeAction<'t> = Box<dyn Fn(Vec<ActionValue<'t>>) -> ActionValue<'t>>;
+
+/**
+ * Post-lex pass of the tokens (see `Parser::set_token_filter`): rewrites
+ * the tokens of the tokenizer before the parser reads them, e.g. converts
+ * a contextual keyword to an identifier, merges adjacent strings, or
+ * drops redundant new lines.
+ */
+pub trait TokenFilter<'t> {
+    /**
+     * Filters the next token of the tokenizer: pushes the tokens to read
+     * instead of it to `tokens`, none to drop it, another one to replace
+     * it, or several to inject tokens. A token can be held back, and pushed
+     * with the next one. The EOF token is filtered as well, and should be
+     * pushed last (the tokenizer returns it again, if it's dropped).
+     */
+    fn filter(&mut self, token: Token<'t>, tokens: &mut VecDeque<Token<'t>>);
+}
+
+/**
+ * Allocates an empty stack for the expected depth, shrinking a larger
+ * one (see `Parser::set_capacity_hint`).
+ */
+fn fit_capacity<T>(stack: &mut Vec<T>, capacity: usize) {
+    stack.shrink_to(capacity);
+    stack.reserve_exact(capacity);
+}
+
+{{{GRAMMAR_DOC}}}/**
+ * Parser: the parsing state of one input at a time (the stacks, and
+ * the tokenizer). Parsers of a `Grammar` share its data.
  */
 pub struct Parser<'t> {
     /**
      * Parsing stack: semantic values.
      */
-    values_stack: Vec<SV>,
+    values_stack: Vec<SV<'t>>,
+
+    /**
+     * Parsing stack: locations of the semantic values.
+     */
+    locations_stack: Vec<Loc>,
+
+    /**
+     * Location of the production being reduced (`@$` in semantic actions).
+     */
+    reduced_loc: Loc,
 
     /**
      * Parsing stack: state numbers.
      */
     states_stack: Vec<usize>,
 
+    /**
+     * Expected depth of the parsing stack (see `set_capacity_hint`).
+     */
+    capacity_hint: usize,
+
+    /**
+     * Maximum length of the input in bytes (see `set_max_input_len`).
+     */
+    max_input_len: Option<usize>,
+
+    /**
+     * Maximum number of the parsing steps (see `set_max_steps`).
+     */
+    max_steps: Option<usize>,
+
     /**
      * Tokenizer instance.
      */
     tokenizer: Tokenizer<'t>,
 
+    /**
+     * Synthetic tokens returned before the tokens of the next parsed
+     * string (see `prime_tokens`).
+     */
+    primed_tokens: Vec<Token<'t>>,
+
+    /**
+     * Offset, line, and column of the range of the string parsed by the
+     * next parse (see `parse_at`).
+     */
+    range_start: Option<(usize, i32, i32)>,
+
+    /**
+     * Error of a nested parse in a semantic action (see `parse_nested`),
+     * or of a value of a typed token (see `parse_token_value`), returned
+     * after the action.
+     */
+    nested_error: Option<SyntaxError>,
+
+    /**
+     * Syntax errors recovered with the `error` token (see `parse_all`),
+     * `None` if the error recovery is off.
+     */
+    recovered_errors: Option<Vec<SyntaxError>>,
+
+    /**
+     * Runtime semantic actions by the production number (see `set_action`).
+     */
+    actions: Vec<Option<RuntimeAction<'t>>>,
+
+    /**
+     * Parsing stack: values of the runtime actions, used instead of the
+     * semantic values in `parse_with_actions`, `None` in other parses.
+     */
+    action_values: Option<Vec<ActionValue<'t>>>,
+
+    /**
+     * Filter of the tokens of the tokenizer (see `set_token_filter`).
+     */
+    token_filter: Option<Box<dyn TokenFilter<'t> + 't>>,
+
+    /**
+     * Tokens pushed by the filter, which are not read yet.
+     */
+    filtered_tokens: VecDeque<Token<'t>>,
+
     /**
      * Semantic action handlers.
      */
-    handlers: [fn(&mut Parser<'t>) -> SV; {{{PRODUCTION_HANDLERS_COUNT}}}],
+    handlers: [fn(&mut Parser<'t>) -> SV<'t>; {{{PRODUCTION_HANDLERS_COUNT}}}],
+}
+
+impl<'t> Default for Parser<'t> {
+    fn default() -> Self {
+        Parser::new()
+    }
 }
 
 impl<'t> Parser<'t> {
@@ -163,117 +579,1127 @@ impl<'t> Parser<'t> {
         Parser {
             // Stacks.
             values_stack: Vec::new(),
+            locations_stack: Vec::new(),
+            reduced_loc: Loc::default(),
             states_stack: Vec::new(),
+            capacity_hint: 0,
+
+            max_input_len: None,
+            max_steps: None,
 
             tokenizer: Tokenizer::new(),
+            primed_tokens: Vec::new(),
+            range_start: None,
+
+            nested_error: None,
+            recovered_errors: None,
+
+            actions: Vec::new(),
+            action_values: None,
+
+            token_filter: None,
+            filtered_tokens: VecDeque::new(),
 
             handlers: {{{PRODUCTION_HANDLERS_ARRAY}}}
         }
     }
 
     /**
-     * Parses a string.
+     * Resets the parser for a string of another lifetime, e.g. a line read
+     * in a loop, which is dropped before the next one:
+     *
+     *   let mut parser = Parser::new();
+     *
+     *   for line in io::stdin().lock().lines() {
+     *       let line = line?;
+     *       let mut session = parser.reset();
+     *       println!("{:?}", session.parse(&line));
+     *       parser = session.reset();
+     *   }
+     *
+     * The settings of the tokenizer (see `Tokenizer::reset`), and the limits
+     * are kept, the runtime actions, the token filter, and the primed tokens
+     * are dropped.
      */
-    pub fn parse(&mut self, string: &'t str) -> TResult {
-        {{{ON_PARSE_BEGIN_CALL}}}
-
-        // Initialize the tokenizer and the string.
-        self.tokenizer.init_string(string);
-
-        // Initialize the stacks.
-        self.values_stack.clear();
-
-        // Initial 0 state.
-        self.states_stack.clear();
-        self.states_stack.push(0);
-
-        let mut token = self.tokenizer.get_next_token();
-        let mut shifted_token = token;
-
-        loop {
-            let state = *self.states_stack.last().unwrap();
-            let column = token.kind;
-
-            if !TABLE[state].contains_key(&column) {
-                self.unexpected_token(&token);
-                break;
-            }
-
-            let entry = &TABLE[state][&column];
+    pub fn reset<'s>(self) -> Parser<'s> {
+        Parser {
+            tokenizer: self.tokenizer.reset(),
+            max_input_len: self.max_input_len,
+            max_steps: self.max_steps,
+            ..Parser::new()
+        }
+    }
 
-            match entry {
+    /**
+     * Parses a string (`&str`, `&String`, etc). The tokens, and the
+     * result (if its type borrows) can't outlive the string.
+     */
+    pub fn parse<S>(&mut self, string: &'t S) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let string = string.as_ref();
 
-                // Shift a token, go to state.
-                &TE::Shift(next_state) => {
-                    // Push token.
-                    self.values_stack.push(SV::_0(token));
+        {{{ON_PARSE_BEGIN_CALL}}}
 
-                    // Push next state number: "s5" -> 5
-                    self.states_stack.push(next_state as usize);
+        for event in self.parse_events(string) {
+            event?;
+        }
 
-                    shifted_token = token;
-                    token = self.tokenizer.get_next_token();
-                },
+        // The parsed value is left on the stack on accept.
+        let parsed = self.values_stack.pop().unwrap();
 
-                // Reduce by production.
-                &TE::Reduce(production_number) => {
-                    let production = PRODUCTIONS[production_number];
+        let result = get_result!(parsed, {{{RESULT_TYPE}}});
+        {{{ON_PARSE_END_CALL}}}
+        Ok(result)
+    }
 
-                    self.tokenizer.yytext = shifted_token.value;
-                    self.tokenizer.yyleng = shifted_token.value.len();
+    /**
+     * Reads a file into the `contents` buffer of the caller, and parses it.
+     * The file name is shown in the error messages ("Unexpected token: ...
+     * in foo.calc at 3:5"), a file which can't be read is an error as well.
+     * The result (if its type borrows) can't outlive the buffer:
+     *
+     *   let mut contents = String::new();
+     *   let result = parser.parse_file("foo.calc", &mut contents)?;
+     */
+    pub fn parse_file<P>(&mut self, path: P, contents: &'t mut String) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
 
-                    let mut rhs_length = production[1];
-                    while rhs_length > 0 {
-                        self.states_stack.pop();
-                        rhs_length = rhs_length - 1;
-                    }
+        *contents = std::fs::read_to_string(path).map_err(|error| SyntaxError {
+            message: format!("Can't read {}: {}.", path.display(), error),
+            loc: Loc {
+                start_offset: 0,
+                end_offset: 0,
+                start_line: 0,
+                end_line: 0,
+                start_column: 0,
+                end_column: 0,
+            },
+            expected: Vec::new(),
+            limit: None,
+            kind: SyntaxErrorKind::Other,
+        })?;
 
-                    // Call the handler, push result onto the stack.
-                    let result_value = self.handlers[production_number](self);
+        // The buffer is borrowed by the parser for `'t`.
+        let contents: &'t String = contents;
 
-                    let previous_state = *self.states_stack.last().unwrap();
-                    let symbol_to_reduce_with = production[0];
+        self.tokenizer.file_name = Some(path.display().to_string());
+        let result = self.parse(contents);
+        self.tokenizer.file_name = None;
 
-                    // Then push LHS onto the stack.
-                    self.values_stack.push(result_value);
+        result
+    }
 
-                    let next_state = match &TABLE[previous_state][&symbol_to_reduce_with] {
-                        &TE::Transit(next_state) => next_state,
-                        _ => unreachable!(),
-                    };
+    /**
+     * Parses a UTF-16 string, e.g. of JS, the offsets, and columns of the
+     * locations are in UTF-16 code units (see `set_utf16_offsets`), so they
+     * match the ones of the original string. An unpaired surrogate is
+     * replaced with U+FFFD, which takes one unit as well. The string is
+     * converted into the `buffer` of the caller, as in `parse_file`, its
+     * capacity is reused:
+     *
+     *   let mut buffer = String::new();
+     *   let result = parser.parse_utf16(&string, &mut buffer)?;
+     */
+    pub fn parse_utf16(&mut self, string: &[u16], buffer: &'t mut String) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError> {
+        buffer.clear();
+        buffer.extend(
+            std::char::decode_utf16(string.iter().cloned())
+                .map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER))
+        );
 
-                    self.states_stack.push(next_state);
-                },
+        // The buffer is borrowed by the parser for `'t`.
+        let string: &'t String = buffer;
 
-                // Accept the string.
-                &TE::Accept => {
-                    // Pop state number.
-                    self.states_stack.pop();
+        let utf16_offsets = self.tokenizer.utf16_offsets;
+        self.tokenizer.set_utf16_offsets(true);
+        let result = self.parse(string);
+        self.tokenizer.utf16_offsets = utf16_offsets;
 
-                    // Pop the parsed value.
-                    let parsed = self.values_stack.pop().unwrap();
+        result
+    }
 
-                    if self.states_stack.len() != 1 ||
-                        self.states_stack.pop().unwrap() != 0 ||
-                        self.tokenizer.has_more_tokens() {
-                        self.unexpected_token(&token);
-                    }
+    /**
+     * Parses a nested string from a semantic action, e.g. an interpolated
+     * expression of a string literal, with the same grammar:
+     *
+     *   let value = self.parse_nested(expression);
+     *
+     * The string is parsed by a new parser, so the state of this parse
+     * is not changed. It has the settings of this parser (the tab width,
+     * the locations tracking, etc., but not the error formatter, and the
+     * fallbacks), the locations are in the nested string. A syntax error
+     * is returned to the action, and is also the error of this parse
+     * after the action.
+     */
+    pub fn parse_nested(&mut self, string: &'t str) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError> {
+        let mut parser = Parser::new();
+        parser.tokenizer = self.tokenizer.with_settings();
 
-                    let result = get_result!(parsed, {{{RESULT_TYPE}}});
-                    {{{ON_PARSE_END_CALL}}}
-                    return result;
-                },
+        let result = parser.parse(string);
 
-                _ => unreachable!(),
-            }
+        if let Err(ref error) = result {
+            self.nested_error = Some(error.clone());
         }
 
-        unreachable!();
+        result
     }
 
-    fn unexpected_token(&self, token: &Token) {
+    /**
+     * Parses a range of a string, e.g. a region of another language
+     * embedded into it, from a semantic action of its parser (the parsers
+     * of several grammars are composed this way):
+     *
+     *   let range = token.start_offset as usize + 2..token.end_offset as usize - 2;
+     *   let value = expr::Parser::new().parse_at(self.source(), range, line, column);
+     *
+     * The range starts at a line, and column of the string, so the
+     * locations of the tokens, and the syntax errors are in the whole
+     * string (see `Tokenizer::init_string_at`), the string after the range
+     * is not read.
+     */
+    pub fn parse_at(
+        &mut self,
+        string: &'t str,
+        range: std::ops::Range<usize>,
+        line: i32,
+        column: i32,
+    ) -> Result<{{{PARSE_RESULT_TYPE}}}, SyntaxError> {
+        self.range_start = Some((range.start, line, column));
+        self.parse(&string[..range.end])
+    }
+
+    /**
+     * The parsed string, e.g. to parse its range with the parser of another
+     * grammar in a semantic action (see `parse_at`).
+     */
+    pub fn source(&self) -> &'t str {
+        self.tokenizer.string
+    }
+
+    /**
+     * Parses a string with the error recovery, and returns all the syntax
+     * errors, e.g. to report them at once. On a syntax error the parser
+     * shifts the `error` token of the grammar:
+     *
+     *   Stmt : error ';' { || -> Stmt; $$ = Stmt::Invalid }
+     *
+     * skipping the input up to a token, which can follow it. Without
+     * an `error` production for the error, the input is skipped up to
+     * a `%sync` token of the grammar, and the parse continues at it.
+     * The result is built with the values of the `error` productions.
+     * It's `None` if the parse can't recover: no `error` production
+     * applies, and there is no `%sync` token to resume at before the end
+     * of input, or the error is a lexical one (or of an exceeded limit).
+     * The last error is the one the parse failed with. The message of an
+     * error is replaced by the `%msg "..."` of the `error` production
+     * which recovers from it.
+     */
+    pub fn parse_all<S>(&mut self, string: &'t S) -> (Option<{{{PARSE_RESULT_TYPE}}}>, Vec<SyntaxError>)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        self.recovered_errors = Some(Vec::new());
+        let result = self.parse(string);
+        let mut errors = self.recovered_errors.take().unwrap_or_default();
+
+        match result {
+            Ok(result) => (Some(result), errors),
+            Err(error) => {
+                errors.push(error);
+                (None, errors)
+            },
+        }
+    }
+{{{PARSE_WITH}}}{{{PARSE_STREAM}}}
+    /**
+     * Sets the expected depth of the parsing stack for the next parses,
+     * e.g. estimated from the size of the input. The stacks are allocated
+     * for it at the start of a parse, so a deep parse doesn't reallocate
+     * them while they grow, and the stacks grown by a previous parse are
+     * shrunk to it, so a small parse after a large one stays lean. With
+     * 0 (the default) the stacks are kept as they are.
+     */
+    pub fn set_capacity_hint(&mut self, depth: usize) {
+        self.capacity_hint = depth;
+    }
+
+    /**
+     * Sets the maximum length of the input in bytes for the next parses,
+     * `None` (the default) for no limit. A longer input is rejected before
+     * it's tokenized, with the `ResourceLimit::InputLength` error.
+     */
+    pub fn set_max_input_len(&mut self, max_input_len: Option<usize>) {
+        self.max_input_len = max_input_len;
+    }
+
+    /**
+     * Sets the maximum number of the parsing steps (the shifts, and the
+     * reduces) of the next parses, `None` (the default) for no limit.
+     * A parse is stopped at the step exceeding it, with the
+     * `ResourceLimit::Steps` error, e.g. to bound the time of a parse
+     * of a pathological input.
+     */
+    pub fn set_max_steps(&mut self, max_steps: Option<usize>) {
+        self.max_steps = max_steps;
+    }
+
+    /**
+     * Sets whether an empty match of a lex rule in the middle of the string
+     * is a token (see `Tokenizer::set_empty_tokens`).
+     */
+    pub fn set_empty_tokens(&mut self, empty_tokens: bool) {
+        self.tokenizer.set_empty_tokens(empty_tokens);
+    }
+
+    /**
+     * Sets whether the matches of each lex rule are counted over the
+     * next parses (see `Tokenizer::set_lex_coverage`).
+     */
+    pub fn set_lex_coverage(&mut self, lex_coverage: bool) {
+        self.tokenizer.set_lex_coverage(lex_coverage);
+    }
+
+    /**
+     * The regex, and the number of matches of each lex rule over the
+     * parses since the lex coverage is set (see `Tokenizer::lex_coverage`).
+     */
+    pub fn lex_coverage(&self) -> Vec<(&'static str, usize)> {
+        self.tokenizer.lex_coverage()
+    }
+
+    /**
+     * Line, and column of an offset of the parsed string, as the tokens
+     * report them, e.g. for a diagnostic of an AST node after the parse
+     * (see `Tokenizer::offset_to_line_col`).
+     */
+    pub fn offset_to_line_col(&self, offset: i32) -> Option<(i32, i32)> {
+        self.tokenizer.offset_to_line_col(offset)
+    }
+
+    /**
+     * Primes the token stream of the next parse with synthetic tokens,
+     * which are consumed before the tokens of the string, e.g. a start
+     * token of an implicit program wrapper, not matched by a lex rule:
+     *
+     *   Program : BEGIN Statements { ... }
+     *
+     *   parser.prime_tokens(vec![Token {
+     *       kind: token_kind("BEGIN").unwrap(),
+     *       ..Token::default()
+     *   }]);
+     *   parser.parse(source)
+     *
+     * The tokens are empty at the start of the string (offset 0, and
+     * the first line), whatever their locations are. They are used by
+     * one parse only, and are not passed to the nested parses.
+     */
+    pub fn prime_tokens(&mut self, tokens: Vec<Token<'t>>) {
+        self.primed_tokens = tokens;
+    }
+
+    /**
+     * Sets a filter of the tokens (see `TokenFilter`), which rewrites the
+     * tokens of the tokenizer before the parser reads them:
+     *
+     *   struct Keywords { async_functions: bool }
+     *
+     *   impl<'t> TokenFilter<'t> for Keywords {
+     *       fn filter(&mut self, token: Token<'t>, tokens: &mut VecDeque<Token<'t>>) {
+     *           ...
+     *       }
+     *   }
+     *
+     *   parser.set_token_filter(Keywords { async_functions: false });
+     *
+     * The filter is used by the next parses (not by the nested ones), the
+     * primed tokens are not filtered.
+     */
+    pub fn set_token_filter<F>(&mut self, filter: F)
+    where
+        F: TokenFilter<'t> + 't,
+    {
+        self.token_filter = Some(Box::new(filter));
+    }
+
+    /**
+     * Sets a runtime semantic action of a production (its number in
+     * `PRODUCTIONS`), which is used by `parse_with_actions` instead of
+     * the action of the grammar. So the same parser can build different
+     * values, e.g. evaluate an expression, or build its tree:
+     *
+     *   // Expr -> Expr + Expr
+     *   parser.set_action(1, |values| {
+     *       let mut values = values.into_iter();
+     *       let left: i32 = values.next().unwrap().take();
+     *       let right: i32 = values.nth(1).unwrap().take();
+     *       ActionValue::node(left + right)
+     *   });
+     */
+    pub fn set_action<F>(&mut self, production: usize, action: F)
+    where
+        F: Fn(Vec<ActionValue<'t>>) -> ActionValue<'t> + 'static,
+    {
+        if self.actions.len() < PRODUCTIONS.len() {
+            self.actions.resize_with(PRODUCTIONS.len(), || None);
+        }
+
+        self.actions[production] = Some(Box::new(action));
+    }
+
+    /**
+     * Parses a string with the runtime actions (see `set_action`), and
+     * returns the value of the start symbol, which should be of the type
+     * `T`. The actions of the grammar, and the parser hooks are not called.
+     * A production without a runtime action has the value of its first
+     * symbol (`$$ = $1`), or `()` if it's an epsilon production.
+     */
+    pub fn parse_with_actions<T, S>(&mut self, string: &'t S) -> Result<T, SyntaxError>
+    where
+        T: 'static,
+        S: AsRef<str> + ?Sized,
+    {
+        self.action_values = Some(Vec::with_capacity(self.capacity_hint));
+        let result = self.parse_events(string).try_for_each(|event| event.map(|_| ()));
+        let mut values = self.action_values.take().unwrap_or_default();
+
+        result?;
+        Ok(values.pop().unwrap().take())
+    }
+
+    /**
+     * Parses a string step by step: returns an iterator of parsing events
+     * (shifts, reduces, and the final accept). A syntax error is the last
+     * item of the iterator. The parser hooks are not called in this mode.
+     */
+    pub fn parse_events<'p, S>(&'p mut self, string: &'t S) -> ParseEvents<'p, 't>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        // Initialize the tokenizer and the string (or its range).
+        match self.range_start.take() {
+            Some((offset, line, column)) => {
+                self.tokenizer.init_string_at(string.as_ref(), offset, "INITIAL", line, column);
+            }
+            None => {
+                self.tokenizer.init_string(string.as_ref());
+            }
+        }
+
+        // Initialize the stacks.
+        self.values_stack.clear();
+        self.locations_stack.clear();
+        self.nested_error = None;
+        self.filtered_tokens.clear();
+
+        self.states_stack.clear();
+
+        if self.capacity_hint > 0 {
+            fit_capacity(&mut self.values_stack, self.capacity_hint);
+            fit_capacity(&mut self.locations_stack, self.capacity_hint);
+            fit_capacity(&mut self.states_stack, self.capacity_hint);
+        }
+
+        // Initial 0 state.
+        self.states_stack.push(0);
+
+        // A too long input fails at the first step, nothing is tokenized.
+        let len = string.as_ref().len();
+
+        if let Some(max_input_len) = self.max_input_len.filter(|max| len > *max) {
+            self.primed_tokens.clear();
+
+            let error = self.resource_limit_error(
+                ResourceLimit::InputLength,
+                format!("Input of {} bytes exceeds the limit of {} bytes", len, max_input_len),
+                Loc::default(),
+            );
+
+            return ParseEvents::new(self, VecDeque::from(vec![Err(error)]));
+        }
+
+        // The primed tokens are read first, as the tokens read ahead.
+        let line = if self.tokenizer.track_locations { 1 } else { 0 };
+        let lookahead = self.primed_tokens
+            .drain(..)
+            .map(|token| Ok(Token {
+                start_offset: 0,
+                end_offset: 0,
+                start_line: line,
+                end_line: line,
+                start_column: 0,
+                end_column: 0,
+                ..token
+            }))
+            .collect();
+
+        ParseEvents::new(self, lookahead)
+    }
+
+    /**
+     * States on the parsing stack, from the start state 0 at the bottom.
+     * The stack is kept after a syntax error (until the next parse), so
+     * it shows the context of the error.
+     */
+    pub fn state_stack(&self) -> &[usize] {
+        &self.states_stack
+    }
+
+    /**
+     * Names of the symbols on the parsing stack: the shifted tokens, and
+     * the non-terminals of the reduced productions (one per state above
+     * the start state). E.g. `["Expr", "+", "(", "Expr", "*"]` at the
+     * error of `2 + (3 * )`.
+     */
+    pub fn symbol_stack(&self) -> Vec<&'static str> {
+        self.states_stack
+            .iter()
+            .skip(1)
+            .map(|state| STATE_SYMBOLS[*state])
+            .collect()
+    }
+
+    /**
+     * Reads the next token of the tokenizer, passed through the token
+     * filter, if it's set (see `set_token_filter`).
+     */
+    fn next_token(&mut self) -> Result<Token<'t>, SyntaxError> {
+        let filter = match self.token_filter {
+            Some(ref mut filter) => filter,
+            None => return self.tokenizer.get_next_token(),
+        };
+
+        loop {
+            if let Some(token) = self.filtered_tokens.pop_front() {
+                return Ok(token);
+            }
+
+            let token = self.tokenizer.get_next_token()?;
+            filter.filter(token, &mut self.filtered_tokens);
+        }
+    }
+
+    /**
+     * Pushes a shifted token onto the values stack.
+     */
+    fn push_token(&mut self, token: Token<'t>) {
+        match self.action_values {
+            Some(ref mut values) => values.push(ActionValue::Token(token)),
+            None => self.values_stack.push(SV::_0(token)),
+        }
+    }
+
+    /**
+     * Reduces the values of the runtime actions by a production, the values
+     * of the RHS are passed to its action.
+     */
+    fn run_action(&mut self, production: usize, rhs_length: usize) {
+        let values = self.action_values.as_mut().unwrap();
+        let rhs = values.split_off(values.len() - rhs_length);
+
+        let value = match self.actions.get(production) {
+            Some(Some(action)) => action(rhs),
+            _ => rhs.into_iter().next().unwrap_or_else(|| ActionValue::node(())),
+        };
+
+        self.action_values.as_mut().unwrap().push(value);
+    }
+
+    /**
+     * Pops the states (with their values, and locations) to the height
+     * of the stack, e.g. to a state of the error recovery.
+     */
+    fn pop_states(&mut self, height: usize) {
+        while self.states_stack.len() > height {
+            self.states_stack.pop();
+            self.locations_stack.pop();
+
+            match self.action_values {
+                Some(ref mut values) => drop(values.pop()),
+                None => drop(self.values_stack.pop()),
+            }
+        }
+    }
+
+    /**
+     * Creates the error for an unexpected token.
+     */
+    fn unexpected_token(&mut self, token: &Token<'t>) -> SyntaxError {
         {{{ON_PARSE_ERROR_CALL}}}
+
+        let expected = self.expected_tokens();
+
+        if token.is_eof() {
+            return SyntaxError {
+                message: format!(
+                    "Unexpected {}{}{}.",
+                    EOF_DISPLAY_NAME,
+                    self.tokenizer.in_file(),
+                    expected_message(&expected)
+                ),
+                loc: Loc::from_token(token),
+                expected,
+                limit: None,
+                kind: SyntaxErrorKind::UnexpectedEof,
+            };
+        }
+
+        self.tokenizer.unexpected_token_error(token.value, Loc::from_token(token), expected)
+    }
+
+    /**
+     * Creates the error of an exceeded limit (see `ResourceLimit`).
+     */
+    fn resource_limit_error(&self, limit: ResourceLimit, message: String, loc: Loc) -> SyntaxError {
+        SyntaxError {
+            message: format!("{}{}.", message, self.tokenizer.in_file()),
+            loc,
+            expected: Vec::new(),
+            limit: Some(limit),
+            kind: SyntaxErrorKind::Other,
+        }
+    }
+
+    /**
+     * Value of a typed token (`%token <type>`) parsed from its text. An
+     * unparsable text, e.g. a number which doesn't fit the type, is an
+     * error at the token, returned after the handler.
+     */
+    fn parse_token_value<T>(&mut self, token: Token<'t>) -> Option<T>
+        where T: std::str::FromStr, T::Err: std::fmt::Display {
+        let error = match token.value.parse::<T>() {
+            Ok(value) => return Some(value),
+            Err(error) => error,
+        };
+
+        let loc = Loc::from_token(&token);
+
+        let position = if self.tokenizer.track_locations {
+            format!("{}:{}", loc.start_line, loc.start_column)
+        } else {
+            format!("offset {}", loc.start_offset)
+        };
+
+        self.nested_error = Some(SyntaxError {
+            message: format!(
+                "Invalid value of token \"{}\"{} at {}: {}.",
+                token.value,
+                self.tokenizer.in_file(),
+                position,
+                error
+            ),
+            loc,
+            expected: Vec::new(),
+            limit: None,
+            kind: SyntaxErrorKind::Other,
+        });
+
+        None
+    }
+
+    /**
+     * Display names of the tokens expected in the current state: the
+     * terminals of its row in the parsing table, in the order of the
+     * grammar (without the tokens of the classes).
+     */
+    fn expected_tokens(&self) -> Vec<&'static str> {
+        let state = match self.states_stack.last() {
+            Some(state) => *state,
+            None => return Vec::new(),
+        };
+
+        TOKEN_DISPLAY_NAMES
+            .iter()
+            .filter(|(kind, _)| *kind as i32 != ERROR_KIND && *kind as i32 <= EOF_KIND && table_entry(state, *kind as i32).is_some())
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /**
+     * Chooses an action of a conflict: the first one, with which the parser
+     * can consume the current token, and the next `LOOKAHEAD - 1` tokens
+     * (read ahead into the lookahead buffer). The first action is chosen
+     * if none of them can, and it fails with a syntax error.
+     */
+    fn choose_action(
+        &mut self,
+        actions: &'static [TE],
+        token: &Token<'t>,
+        lookahead: &mut VecDeque<Result<Token<'t>, SyntaxError>>,
+    ) -> &'static TE {
+        // Nothing is read after the end of input, or an error.
+        while lookahead.len() + 1 < LOOKAHEAD {
+            let at_end = match lookahead.back() {
+                Some(Ok(next)) => next.is_eof(),
+                Some(Err(_)) => true,
+                None => token.is_eof(),
+            };
+
+            if at_end {
+                break;
+            }
+
+            lookahead.push_back(self.next_token());
+        }
+
+        let mut kinds = vec![token.kind];
+        for next in lookahead.iter() {
+            match next {
+                Ok(next) => kinds.push(next.kind),
+                Err(_) => break,
+            }
+        }
+
+        actions
+            .iter()
+            .find(|action| can_consume(&self.states_stack, action, &kinds))
+            .unwrap_or(&actions[0])
     }
 
     {{{PRODUCTION_HANDLERS}}}
 }
+
+/**
+ * Iterator of parsing events, each `next` call runs one step
+ * of the LR parsing loop.
+ */
+pub struct ParseEvents<'p, 't> {
+    parser: &'p mut Parser<'t>,
+
+    /**
+     * Current lookahead token, read on the next step after a shift.
+     */
+    token: Option<Token<'t>>,
+
+    /**
+     * Tokens read ahead of the current one to choose an action
+     * of a conflict (see `LOOKAHEAD`), and the primed tokens.
+     */
+    lookahead: VecDeque<Result<Token<'t>, SyntaxError>>,
+
+    /**
+     * Last shifted token, its value is `yytext` in reduce handlers.
+     */
+    shifted_token: Option<Token<'t>>,
+
+    /**
+     * Tokens to shift after a recovered syntax error, before a next error
+     * is reported (see `RECOVERY_SHIFTS`).
+     */
+    recovering: usize,
+
+    /**
+     * Index of the recorded error, which the `error` token shifted last
+     * recovers from, its message is replaced by the `%msg` of the `error`
+     * production (see `ERROR_MESSAGES`).
+     */
+    recovered_error: Option<usize>,
+
+    /**
+     * Whether the parse continues at a `%sync` token, and nothing is
+     * shifted since (see `synchronize`).
+     */
+    resynchronized: bool,
+
+    /**
+     * Number of the shifts, and reduces (see `Parser::set_max_steps`).
+     */
+    steps: usize,
+
+    /**
+     * Whether the input is accepted, or a parse error happened.
+     */
+    finished: bool,
+}
+
+impl<'p, 't> ParseEvents<'p, 't> {
+    /**
+     * Events of a parse from the start state, the tokens of the lookahead
+     * (or an error) are read first.
+     */
+    fn new(parser: &'p mut Parser<'t>, lookahead: VecDeque<Result<Token<'t>, SyntaxError>>) -> ParseEvents<'p, 't> {
+        ParseEvents {
+            parser,
+            token: None,
+            lookahead,
+            shifted_token: None,
+            recovering: 0,
+            recovered_error: None,
+            resynchronized: false,
+            steps: 0,
+            finished: false,
+        }
+    }
+
+    /**
+     * The parser, e.g. its stacks after an event.
+     */
+    pub fn parser(&self) -> &Parser<'t> {
+        self.parser
+    }
+
+    /**
+     * Recovers from a syntax error at the token: the error is recorded
+     * (unless it follows a recent one), the states are popped to one,
+     * which shifts the `error` token, and it's shifted. The tokens which
+     * can't follow it are skipped. If no state shifts the `error` token,
+     * the parse is synchronized at a `%sync` token. Fails with the error
+     * if neither applies.
+     */
+    fn recover(&mut self, token: Token<'t>) -> Option<Result<ParseEvent<'t>, SyntaxError>> {
+        let parser = &mut *self.parser;
+
+        // Nothing is shifted since the `error` token: skip the input.
+        if self.recovering == RECOVERY_SHIFTS && !self.resynchronized {
+            let mut token = token;
+
+            while table_entry(*parser.states_stack.last().unwrap(), token.kind).is_none() {
+                if token.is_eof() {
+                    self.finished = true;
+                    return Some(Err(parser.unexpected_token(&token)));
+                }
+
+                token = match self.lookahead.pop_front().unwrap_or_else(|| parser.next_token()) {
+                    Ok(token) => token,
+                    Err(error) => {
+                        self.finished = true;
+                        return Some(Err(error));
+                    },
+                };
+            }
+
+            self.token = Some(token);
+            return self.next();
+        }
+
+        let error = parser.unexpected_token(&token);
+
+        // The nearest state on the stack, which shifts the `error` token.
+        let error_shift = if ERROR_KIND >= 0 {
+            parser.states_stack.iter().enumerate().rev().find_map(|(depth, &state)| {
+                let next_state = match table_entry(state, ERROR_KIND) {
+                    Some(TE::Shift(next_state)) => Some(*next_state),
+                    Some(TE::Conflict(actions)) => actions.iter().find_map(|action| match action {
+                        TE::Shift(next_state) => Some(*next_state),
+                        _ => None,
+                    }),
+                    _ => None,
+                };
+
+                next_state.map(|next_state| (depth, next_state))
+            })
+        } else {
+            None
+        };
+
+        let next_state = match error_shift {
+            Some((depth, next_state)) => {
+                parser.pop_states(depth + 1);
+                next_state
+            },
+            None if !SYNC_KINDS.is_empty() => return self.synchronize(token, error),
+            None => {
+                self.finished = true;
+                return Some(Err(error));
+            },
+        };
+
+        self.record_error(error);
+        let parser = &mut *self.parser;
+
+        // The `error` token is empty, at the start of the unexpected one.
+        let error_token = Token {
+            kind: ERROR_KIND,
+            value: "",
+            end_offset: token.start_offset,
+            end_line: token.start_line,
+            end_column: token.start_column,
+            ..token
+        };
+
+        parser.push_token(error_token);
+        parser.locations_stack.push(Loc::from_token(&error_token));
+        parser.states_stack.push(next_state);
+
+        self.shifted_token = Some(error_token);
+        self.recovering = RECOVERY_SHIFTS;
+        self.resynchronized = false;
+
+        Some(Ok(ParseEvent::Shift(error_token)))
+    }
+
+    /**
+     * Recovers from a syntax error at the token with the `%sync` tokens:
+     * the input is skipped up to a sync token, and the states are popped
+     * to one, which has an action for it, the parse continues at it. If
+     * no state has, the sync token is skipped too, and the parse continues
+     * at the next token the same way, or the input is skipped up to the
+     * next sync token. A token unexpected again (nothing is shifted since
+     * the parse continued at it) is skipped. Fails with the error at the
+     * end of input.
+     */
+    fn synchronize(&mut self, token: Token<'t>, error: SyntaxError) -> Option<Result<ParseEvent<'t>, SyntaxError>> {
+        let mut token = token;
+        let mut skip = self.resynchronized;
+        let mut after_sync = false;
+
+        loop {
+            let parser = &mut *self.parser;
+
+            if !skip && (after_sync || is_sync_kind(token.kind)) {
+                let depth = parser.states_stack
+                    .iter()
+                    .rposition(|&state| table_entry(state, token.kind).is_some());
+
+                if let Some(depth) = depth {
+                    parser.pop_states(depth + 1);
+                    self.record_error(error);
+                    self.recovered_error = None;
+
+                    self.token = Some(token);
+                    self.recovering = RECOVERY_SHIFTS;
+                    self.resynchronized = true;
+
+                    return self.next();
+                }
+            }
+
+            if token.is_eof() {
+                self.finished = true;
+                return Some(Err(error));
+            }
+
+            after_sync = is_sync_kind(token.kind);
+            skip = false;
+
+            token = match self.lookahead.pop_front().unwrap_or_else(|| parser.next_token()) {
+                Ok(token) => token,
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                },
+            };
+        }
+    }
+
+    /**
+     * Records a recovered syntax error, unless it follows a recent one.
+     */
+    fn record_error(&mut self, error: SyntaxError) {
+        if self.recovering == 0 {
+            if let Some(ref mut errors) = self.parser.recovered_errors {
+                errors.push(error);
+                self.recovered_error = Some(errors.len() - 1);
+            }
+        }
+    }
+
+    /**
+     * Replaces the message of the recovered error with the `%msg`
+     * of the reduced `error` production, if it has one.
+     */
+    fn apply_error_message(&mut self, production_number: usize) {
+        let message = match ERROR_MESSAGES.iter().find(|(number, _)| *number == production_number) {
+            Some((_, message)) => message,
+            None => return,
+        };
+
+        if let (Some(index), Some(errors)) = (self.recovered_error.take(), self.parser.recovered_errors.as_mut()) {
+            errors[index].message = message.to_string();
+        }
+    }
+}
+
+impl<'p, 't> Iterator for ParseEvents<'p, 't> {
+    type Item = Result<ParseEvent<'t>, SyntaxError>;
+
+    fn next(&mut self) -> Option<Result<ParseEvent<'t>, SyntaxError>> {
+        if self.finished {
+            return None;
+        }
+
+        let parser = &mut *self.parser;
+
+        let token = match self.token {
+            Some(token) => token,
+            None => match self.lookahead.pop_front().unwrap_or_else(|| parser.next_token()) {
+                Ok(token) => {
+                    self.token = Some(token);
+                    token
+                },
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                },
+            },
+        };
+
+        let state = *parser.states_stack.last().unwrap();
+        let column = token.kind;
+
+        let mut action = match table_entry(state, column) {
+            Some(action) => action,
+            None if parser.recovered_errors.is_some() && (ERROR_KIND >= 0 || !SYNC_KINDS.is_empty()) => {
+                // The `error` token is the lookahead of a reduction, e.g.
+                // of the `Expr` in `Stmt : Expr error`: it's reduced before
+                // the recovery, unless the input is skipped after the error.
+                let skipping = self.recovering == RECOVERY_SHIFTS && !self.resynchronized;
+
+                let error_action = if ERROR_KIND >= 0 && !skipping {
+                    table_entry(state, ERROR_KIND)
+                } else {
+                    None
+                };
+
+                match error_action {
+                    Some(action @ TE::Reduce(_)) => action,
+                    _ => return self.recover(token),
+                }
+            },
+            None => {
+                self.finished = true;
+                return Some(Err(parser.unexpected_token(&token)));
+            },
+        };
+
+        if let TE::Conflict(actions) = *action {
+            action = parser.choose_action(actions, &token, &mut self.lookahead);
+        }
+
+        if let (TE::Shift(_) | TE::Reduce(_), Some(max_steps)) = (action, parser.max_steps) {
+            if self.steps == max_steps {
+                self.finished = true;
+
+                return Some(Err(parser.resource_limit_error(
+                    ResourceLimit::Steps,
+                    format!("Parse exceeds the limit of {} steps", max_steps),
+                    Loc::from_token(&token),
+                )));
+            }
+
+            self.steps += 1;
+        }
+
+        match *action {
+
+            // Shift a token, go to state.
+            TE::Shift(next_state) => {
+                // Push token.
+                parser.push_token(token);
+                parser.locations_stack.push(Loc::from_token(&token));
+
+                // Push next state number: "s5" -> 5
+                parser.states_stack.push(next_state);
+
+                self.shifted_token = Some(token);
+                self.token = None;
+                self.recovering = self.recovering.saturating_sub(1);
+                self.resynchronized = false;
+
+                Some(Ok(ParseEvent::Shift(token)))
+            },
+
+            // Reduce by production.
+            TE::Reduce(production_number) => {
+                let production = ENCODED_PRODUCTIONS[production_number];
+
+                if let Some(shifted_token) = self.shifted_token {
+                    parser.tokenizer.yytext = shifted_token.value;
+                    parser.tokenizer.yyleng = shifted_token.value.len();
+                }
+
+                let rhs_length = production[1] as usize;
+
+                let states_count = parser.states_stack.len();
+                parser.states_stack.truncate(states_count - rhs_length);
+
+                // Location of the reduced RHS. An epsilon production has
+                // an empty span at the end of the previous symbol, and
+                // empty symbols at the ends of the RHS (e.g. the empty
+                // start of a list) are not a part of the span.
+                let locations_count = parser.locations_stack.len();
+                let span = if rhs_length > 0 {
+                    let rhs = &parser.locations_stack[locations_count - rhs_length..];
+                    let first = rhs.iter().find(|loc| !loc.is_empty()).unwrap_or(&rhs[0]);
+                    let last = rhs.iter().rev().find(|loc| !loc.is_empty()).unwrap_or(&rhs[rhs_length - 1]);
+                    Loc::from_tokens_range(first, last)
+                } else {
+                    match parser.locations_stack.last() {
+                        Some(previous) => Loc::empty_at_end(previous),
+                        None => Loc::empty_at_start(&Loc::from_token(&token)),
+                    }
+                };
+
+                // Call the handler, push result onto the stack. The locations
+                // of the RHS (`@1`, `@2`, ...) are popped after the handler.
+                parser.reduced_loc = span;
+
+                if parser.action_values.is_some() {
+                    parser.run_action(production_number, rhs_length);
+                } else {
+                    let result_value = parser.handlers[production_number](parser);
+                    parser.values_stack.push(result_value);
+                }
+
+                parser.locations_stack.truncate(locations_count - rhs_length);
+
+                if let Some(error) = parser.nested_error.take() {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+
+                let previous_state = *parser.states_stack.last().unwrap();
+                let symbol_to_reduce_with = production[0] as i32;
+
+                // Then push LHS onto the stack.
+                parser.locations_stack.push(span);
+
+                let next_state = match table_entry(previous_state, symbol_to_reduce_with) {
+                    Some(TE::Transit(next_state)) => *next_state,
+                    _ => unreachable!(),
+                };
+
+                parser.states_stack.push(next_state);
+
+                if self.recovered_error.is_some() {
+                    self.apply_error_message(production_number);
+                }
+
+                Some(Ok(ParseEvent::Reduce {
+                    production: production_number,
+                    span,
+                }))
+            },
+
+            // Accept the string.
+            TE::Accept => {
+                self.finished = true;
+
+                // Pop state number.
+                parser.states_stack.pop();
+
+                if parser.states_stack.len() != 1 ||
+                    parser.states_stack.pop().unwrap() != 0 ||
+                    parser.tokenizer.has_more_tokens() {
+                    return Some(Err(parser.unexpected_token(&token)));
+                }
+
+                Some(Ok(ParseEvent::Accept))
+            },
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+{{{PARSER_TRAIT}}}
+
+{{{PROC_MACRO_SPANS}}}
+
+{{{WASM_ENTRY}}}
+
+{{{SMOKE_TEST}}}
@@ -0,0 +1,49 @@
+// ------------------------------------------------------------------
+// Regex backend: oniguruma (the `onig` crate).
+
+extern crate onig;
+
+use onig::{Regex, Region, Syntax, RegexOptions, SearchOptions};
+
+/**
+ * Compiles a lex rule. The regex syntax is chosen with the `regexSyntax`
+ * generator option.
+ */
+fn lex_rule_regex(rule: &str) -> Regex {
+    Regex::with_options(rule, RegexOptions::REGEX_OPTION_SINGLELINE, Syntax::{{{REGEX_SYNTAX}}}()).unwrap()
+}
+
+/**
+ * Compiles the combined lex rules of a state. Empty matches are skipped
+ * (the same as in the middle of the string for the rules one by one).
+ */
+fn combined_lex_rule_regex(rule: &str) -> Regex {
+    Regex::with_options(rule, RegexOptions::REGEX_OPTION_SINGLELINE | RegexOptions::REGEX_OPTION_FIND_NOT_EMPTY, Syntax::{{{REGEX_SYNTAX}}}()).unwrap()
+}
+
+/**
+ * Matches a lex rule at the beginning of the string.
+ */
+fn match_lex_rule<'s>(re: &Regex, string: &'s str) -> Option<&'s str> {
+    re.captures(string).map(|caps| caps.at(0).unwrap())
+}
+
+/**
+ * Matches the combined lex rules at the beginning of the string, returns
+ * the position of the matched rule (the number of the participating
+ * group), and the matched text.
+ */
+fn match_combined_lex_rules<'s>(re: &Regex, string: &'s str) -> Option<(usize, &'s str)> {
+    let mut region = Region::new();
+    re.match_with_options(string, 0, SearchOptions::SEARCH_OPTION_NONE, Some(&mut region))?;
+
+    let group = (1..region.len()).find(|group| region.pos(*group).is_some())?;
+    Some((group - 1, &string[..region.pos(group).unwrap().1]))
+}
+
+/**
+ * Searches the regex in the string, returns the end of the first match.
+ */
+fn find_regex_end(re: &Regex, string: &str) -> Option<usize> {
+    re.find(string).map(|(_, end)| end)
+}
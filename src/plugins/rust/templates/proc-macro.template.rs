@@ -0,0 +1,54 @@
+// ------------------------------------------------------------------
+// Spans in a procedural macro input.
+
+/**
+ * With the `proc-macro2` feature (the optional `proc-macro2` dependency)
+ * locations are converted to the spans of a string literal of a macro
+ * input, parsed with this parser. E.g. a syntax error is reported as a
+ * compile error at the unexpected token:
+ *
+ *   let literal = parse_macro_input!(input as LitStr);
+ *
+ *   if let Err(error) = Parser::new().parse(&literal.value()) {
+ *       return syn::Error::new(error.span_in(&literal.token()), &error.message)
+ *           .to_compile_error()
+ *           .into();
+ *   }
+ *
+ * The byte offsets are shifted by the prefix of the literal (`"`, `r#"`,
+ * etc.), so they match its source if the string has no escapes. When the
+ * compiler doesn't support subspans of literals (the stable Rust), the
+ * span of the whole literal is returned.
+ */
+#[cfg(feature = "proc-macro2")]
+impl Loc {
+    pub fn span_in(&self, literal: &proc_macro2::Literal) -> proc_macro2::Span {
+        let source = literal.to_string();
+        let base = source.find('"').map_or(0, |quote| quote + 1);
+
+        let start = base + self.start_offset.max(0) as usize;
+        let end = base + self.end_offset.max(self.start_offset).max(0) as usize;
+
+        literal.subspan(start..end).unwrap_or_else(|| literal.span())
+    }
+}
+
+#[cfg(feature = "proc-macro2")]
+impl<'t> Token<'t> {
+    /**
+     * Span of the token in a string literal (see `Loc::span_in`).
+     */
+    pub fn span_in(&self, literal: &proc_macro2::Literal) -> proc_macro2::Span {
+        Loc::from_token(self).span_in(literal)
+    }
+}
+
+#[cfg(feature = "proc-macro2")]
+impl SyntaxError {
+    /**
+     * Span of the unexpected token in a string literal (see `Loc::span_in`).
+     */
+    pub fn span_in(&self, literal: &proc_macro2::Literal) -> proc_macro2::Span {
+        self.loc.span_in(literal)
+    }
+}
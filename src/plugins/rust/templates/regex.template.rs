@@ -0,0 +1,50 @@
+// ------------------------------------------------------------------
+// Regex backend: the pure Rust `regex` crate (builds for any target,
+// including `wasm32-unknown-unknown`). The lex rules should use its
+// syntax: e.g. look-around assertions, and back references are not
+// supported.
+
+extern crate regex;
+
+use regex::Regex;
+
+/**
+ * Compiles a lex rule.
+ */
+fn lex_rule_regex(rule: &str) -> Regex {
+    Regex::new(rule).unwrap()
+}
+
+/**
+ * Compiles the combined lex rules of a state. An empty match is skipped
+ * by the tokenizer, which tries the next rules one by one.
+ */
+fn combined_lex_rule_regex(rule: &str) -> Regex {
+    Regex::new(rule).unwrap()
+}
+
+/**
+ * Matches a lex rule at the beginning of the string.
+ */
+fn match_lex_rule<'s>(re: &Regex, string: &'s str) -> Option<&'s str> {
+    re.find(string).map(|found| found.as_str())
+}
+
+/**
+ * Matches the combined lex rules at the beginning of the string, returns
+ * the position of the matched rule (the number of the participating
+ * group), and the matched text.
+ */
+fn match_combined_lex_rules<'s>(re: &Regex, string: &'s str) -> Option<(usize, &'s str)> {
+    let caps = re.captures(string)?;
+
+    let group = (1..caps.len()).find(|group| caps.get(*group).is_some())?;
+    Some((group - 1, caps.get(group).unwrap().as_str()))
+}
+
+/**
+ * Searches the regex in the string, returns the end of the first match.
+ */
+fn find_regex_end(re: &Regex, string: &str) -> Option<usize> {
+    re.find(string).map(|found| found.end())
+}
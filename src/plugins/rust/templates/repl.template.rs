@@ -0,0 +1,128 @@
+use {{{CRATE_NAME}}}::Parser;
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/**
+ * REPL of the parser (the `emitRepl` option): parses each line of the
+ * standard input, and prints the result (the result type should implement
+ * `Debug`), or the syntax error. The same parser is reused for all lines,
+ * reset for each next one.
+ *
+ * With `--lex-coverage <dir>` the files of the corpus directory are parsed
+ * instead, and the matches of each lex rule are reported.
+ */
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.len() == 2 && args[0] == "--lex-coverage" {
+        return lex_coverage(Path::new(&args[1]));
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    let mut parser = Parser::new();
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(error)) => {
+                eprintln!("{}", error);
+                break;
+            }
+            None => break,
+        };
+
+        let mut session = parser.reset();
+
+        if !line.trim().is_empty() {
+            match session.parse(&line) {
+                Ok(result) => println!("{:?}", result),
+                Err(error) => println!("{}", error),
+            }
+        }
+
+        parser = session.reset();
+    }
+
+    println!();
+}
+
+/**
+ * Parses all files of the corpus directory (and its subdirectories) with
+ * the lex coverage, and prints the number of matches of each lex rule,
+ * and the rules which are never matched. Syntax errors of the files are
+ * printed, the tokens before the error are counted.
+ */
+fn lex_coverage(dir: &Path) {
+    let mut files = Vec::new();
+
+    if let Err(error) = corpus_files(dir, &mut files) {
+        eprintln!("{}: {}", dir.display(), error);
+        process::exit(1);
+    }
+
+    files.sort();
+
+    let mut sources = Vec::new();
+
+    for file in &files {
+        match fs::read_to_string(file) {
+            Ok(source) => sources.push(source),
+            Err(error) => {
+                eprintln!("{}: {}", file.display(), error);
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut parser = Parser::new();
+    parser.set_lex_coverage(true);
+
+    for (file, source) in files.iter().zip(&sources) {
+        if let Err(error) = parser.parse(source) {
+            println!("{}: {}", file.display(), error);
+        }
+    }
+
+    let coverage = parser.lex_coverage();
+
+    for (rule, (regex, hits)) in coverage.iter().enumerate() {
+        println!("{:>5} {:>8}  {}", rule, hits, regex);
+    }
+
+    let unmatched: Vec<_> = coverage.iter().filter(|(_, hits)| *hits == 0).collect();
+
+    println!(
+        "\n{} files, {} of {} lex rules are never matched.",
+        files.len(),
+        unmatched.len(),
+        coverage.len()
+    );
+
+    for (regex, _) in unmatched {
+        println!("  {}", regex);
+    }
+}
+
+/**
+ * Collects the files of a directory, and of its subdirectories.
+ */
+fn corpus_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            corpus_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
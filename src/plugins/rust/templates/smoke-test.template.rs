@@ -0,0 +1,18 @@
+// ------------------------------------------------------------------
+// Smoke test.
+
+/**
+ * Parses a sample input of the grammar (the `emitTest` option), a seed
+ * of the tests of the parser.
+ */
+#[cfg(test)]
+mod smoke_test {
+    use super::*;
+
+    #[test]
+    fn parses_sample_input() {
+        if let Err(error) = Parser::new().parse({{{TEST_INPUT}}}) {
+            panic!("{}", error);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+#![allow(unused_mut)]
+#![allow(unreachable_code)]
+#![allow(clippy::let_and_return)]
+#![allow(clippy::wrong_self_convention)]
+{{{EXTERN_CRATES}}}
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/**
+ * Lex rules.
+ */
+static LEX_RULES: {{{LEX_RULES}}};
+
+/**
+ * Lex rules of a start condition combined into one alternation
+ * (the `lexAlternation` option).
+ */
+static COMBINED_LEX_RULES: {{{COMBINED_LEX_RULES}}};
+
+/**
+ * EOF value (the `%eof` name of the grammar, `$` by default).
+ */
+static EOF: &str = {{{EOF}}};
+
+/**
+ * The end of input in error messages: the `%eof` name of the grammar,
+ * or "end of input".
+ */
+static EOF_DISPLAY_NAME: &str = {{{EOF_DISPLAY_NAME}}};
+
+/**
+ * Encoded number of the EOF token (the same as in `TOKENS_MAP`).
+ */
+const EOF_KIND: i32 = {{{EOF_KIND}}};
+
+// ------------------------------------
+// Module include prologue.
+//
+// Can include helper functions, and types used in the lex rule
+// handlers. The standalone tokenizer has no parser, so no result
+// type, or parser hooks are needed.
+//
+
+{{{MODULE_INCLUDE}}}
+
+// ---  end of Module include ---------
+
+{{{TOKENIZER}}}
+
+{{{PROC_MACRO_SPANS}}}
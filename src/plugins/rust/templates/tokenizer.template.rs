@@ -7,315 +7,2089 @@
 // ------------------------------------------------------------------
 // Token.
 
-#[derive(Debug, Clone, Copy)]
-struct Token {
-    kind: i32,
-    value: &'static str,
-
-    start_offset: i32,
-    end_offset: i32,
-    start_line: i32,
-    end_line: i32,
-    start_column: i32,
-    end_column: i32,
+{{{TOKEN_ATTRIBUTES}}}
+pub struct Token<'t> {
+    pub kind: i32,
+
+    // A slice of the parsing string (or a string of the string
+    // buffer, see `StringBuffer`), so a token can't outlive them.
+    pub value: &'t str,
+
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub start_line: i32,
+    pub end_line: i32,
+    pub start_column: i32,
+    pub end_column: i32,
+}
+
+impl<'t> Token<'t> {
+    /**
+     * Whether it's the end of input token. The check is by the token
+     * kind, since a regular token value can be the same as the `EOF`.
+     */
+    pub fn is_eof(&self) -> bool {
+        self.kind == EOF_KIND
+    }
+
+    /**
+     * Copy of the token with an owned value.
+     */
+    pub fn to_owned_token(&self) -> OwnedToken {
+        OwnedToken {
+            kind: self.kind,
+            value: self.value.to_string(),
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            start_line: self.start_line,
+            end_line: self.end_line,
+            start_column: self.start_column,
+            end_column: self.end_column,
+        }
+    }
+}
+
+/**
+ * Token with an owned value, which doesn't borrow the parsing string,
+ * so it can outlive it, e.g. kept in diagnostics after the parsing.
+ * In semantic actions it's the `OwnedToken` type of a token argument:
+ *
+ *   |$1: OwnedToken| -> OwnedToken; $$ = $1
+ */
+{{{OWNED_TOKEN_ATTRIBUTES}}}
+pub struct OwnedToken {
+    pub kind: i32,
+    pub value: String,
+
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub start_line: i32,
+    pub end_line: i32,
+    pub start_column: i32,
+    pub end_column: i32,
+}
+
+impl<'t> From<Token<'t>> for OwnedToken {
+    fn from(token: Token<'t>) -> OwnedToken {
+        token.to_owned_token()
+    }
+}
+
+// ------------------------------------------------------------------
+// String buffer.
+
+/**
+ * Strings made in lex handlers, which are not a part of the parsing
+ * string, e.g. an unescaped string literal (see `Tokenizer::set_yytext`).
+ * The buffer is owned by the caller, and is borrowed by the tokenizer
+ * (see `Tokenizer::set_string_buffer`) for the lifetime of the parsing
+ * string, so the tokens can't outlive it as well. It's cleared by the
+ * caller, once the tokens, and the results are dropped, e.g. after each
+ * parse of a reused parser (see `Parser::reset`):
+ *
+ *   let mut strings = StringBuffer::new();
+ *
+ *   for line in lines {
+ *       let mut session = parser.reset();
+ *       session.tokenizer.set_string_buffer(&strings);
+ *       println!("{:?}", session.parse(&line));
+ *       parser = session.reset();
+ *       strings.clear();
+ *   }
+ */
+#[derive(Debug, Default)]
+pub struct StringBuffer {
+    strings: std::cell::RefCell<Vec<String>>,
+}
+
+impl StringBuffer {
+    pub fn new() -> StringBuffer {
+        StringBuffer::default()
+    }
+
+    /**
+     * Moves a string into the buffer, and returns it for
+     * the lifetime of the buffer borrow.
+     */
+    pub fn push(&self, s: String) -> &str {
+        let mut strings = self.strings.borrow_mut();
+        strings.push(s);
+
+        let string: *const str = strings.last().unwrap().as_str();
+
+        // The text of a string is not moved when the vector grows, and
+        // it's dropped only by `clear` (which needs `&mut self`), or with
+        // the buffer, so it lives while the buffer is borrowed.
+        unsafe { &*string }
+    }
+
+    /**
+     * Number of the strings in the buffer.
+     */
+    pub fn len(&self) -> usize {
+        self.strings.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * Drops the strings, it can't be borrowed by a tokenizer.
+     */
+    pub fn clear(&mut self) {
+        self.strings.get_mut().clear();
+    }
+}
+
+// ------------------------------------------------------------------
+// Location.
+
+/**
+ * Location of a token, or of a range of tokens.
+ */
+{{{LOC_ATTRIBUTES}}}
+pub struct Loc {
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub start_line: i32,
+    pub end_line: i32,
+    pub start_column: i32,
+    pub end_column: i32,
+}
+
+impl Loc {
+    /**
+     * Location of a token.
+     */
+    pub fn from_token(token: &Token) -> Loc {
+        Loc {
+            start_offset: token.start_offset,
+            end_offset: token.end_offset,
+            start_line: token.start_line,
+            end_line: token.end_line,
+            start_column: token.start_column,
+            end_column: token.end_column,
+        }
+    }
+
+    /**
+     * Location from the start of the `start` location,
+     * to the end of the `end` location.
+     */
+    pub fn from_tokens_range(start: &Loc, end: &Loc) -> Loc {
+        Loc {
+            start_offset: start.start_offset,
+            end_offset: end.end_offset,
+            start_line: start.start_line,
+            end_line: end.end_line,
+            start_column: start.start_column,
+            end_column: end.end_column,
+        }
+    }
+
+    /**
+     * Location enclosing both locations (in any order), e.g. of a node
+     * built from the children in a semantic action:
+     *
+     *   Loc::merge(&left.loc, &right.loc)
+     *
+     * An empty location (e.g. of an empty production) is not merged,
+     * the other one is returned.
+     */
+    pub fn merge(a: &Loc, b: &Loc) -> Loc {
+        if a.is_empty() && !b.is_empty() {
+            return *b;
+        }
+
+        if b.is_empty() {
+            return *a;
+        }
+
+        let start = if a.start_offset <= b.start_offset { a } else { b };
+        let end = if a.end_offset >= b.end_offset { a } else { b };
+
+        Loc::from_tokens_range(start, end)
+    }
+
+    /**
+     * Empty location at the start of the location.
+     */
+    pub fn empty_at_start(loc: &Loc) -> Loc {
+        Loc {
+            end_offset: loc.start_offset,
+            end_line: loc.start_line,
+            end_column: loc.start_column,
+            ..*loc
+        }
+    }
+
+    /**
+     * Empty location at the end of the location.
+     */
+    pub fn empty_at_end(loc: &Loc) -> Loc {
+        Loc {
+            start_offset: loc.end_offset,
+            start_line: loc.end_line,
+            start_column: loc.end_column,
+            ..*loc
+        }
+    }
+
+    /**
+     * Whether the location is empty (e.g. of an empty production).
+     */
+    pub fn is_empty(&self) -> bool {
+        self.start_offset == self.end_offset
+    }
+
+    /**
+     * Byte range of the location in the source string (a range of
+     * UTF-16 code units with the UTF-16 offsets, see `set_utf16_offsets`).
+     */
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.start_offset as usize..self.end_offset as usize
+    }
+
+    /**
+     * Source text of the location, e.g. of a node with `@$` stored
+     * in a semantic action:
+     *
+     *   node.loc.text(source) // "2 * 2"
+     *
+     * The offsets should be in bytes (not the UTF-16 offsets).
+     */
+    pub fn text<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.span()]
+    }
+}
+
+// ------------------------------------------------------------------
+// Syntax error.
+
+/**
+ * Syntax error: an unexpected token, or the unexpected end of input.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    /**
+     * Error message (see `Tokenizer::set_error_formatter`).
+     */
+    pub message: String,
+
+    /**
+     * Location of the unexpected token.
+     */
+    pub loc: Loc,
+
+    /**
+     * Names of the tokens expected by the parser instead of the unexpected
+     * one (in the order of the grammar), empty for the tokenizer errors.
+     */
+    pub expected: Vec<&'static str>,
+
+    /**
+     * Limit of the parser exceeded by the parse (see `ResourceLimit`),
+     * `None` for the syntax errors.
+     */
+    pub limit: Option<ResourceLimit>,
+
+    /**
+     * What the error is at: the end of input, or an unexpected token (see
+     * `SyntaxErrorKind`), e.g. to guide the user differently.
+     */
+    pub kind: SyntaxErrorKind,
+}
+
+/**
+ * Kind of a syntax error: the input ended before the parse is complete
+ * ("input ended, expected `}`"), or it has a stray token. The tokens the
+ * parser expected instead are the `expected` of the error in both cases.
+ */
+#[derive(Debug, Clone, PartialEq)]{{{NON_EXHAUSTIVE}}}
+pub enum SyntaxErrorKind {
+    /**
+     * The end of input (the EOF token) is unexpected.
+     */
+    UnexpectedEof,
+
+    /**
+     * A token is unexpected (by its text), or a text no lex rule matches.
+     */
+    UnexpectedToken { token: String },
+
+    /**
+     * Other errors: of a lex handler (see `Tokenizer::set_error`), of an
+     * exceeded limit, of a text of a typed token which can't be parsed,
+     * or a file which can't be read.
+     */
+    Other,
+}
+
+/**
+ * Limits of the parser against a pathological input, e.g. of a service
+ * parsing untrusted input: a parse exceeding a limit fails with an error
+ * of its kind (see `Parser::set_max_input_len`, and `set_max_steps`).
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]{{{NON_EXHAUSTIVE}}}
+pub enum ResourceLimit {
+    /**
+     * Length of the input in bytes.
+     */
+    InputLength,
+
+    /**
+     * Number of the parsing steps (shifts, and reduces).
+     */
+    Steps,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/**
+ * Default tab width for column numbers: 1 counts a tab as one column
+ * (the same as byte offsets), otherwise tabs are expanded to the next
+ * tab stop, as shown in editors.
+ */
+const DEFAULT_TAB_WIDTH: i32 = {{{TAB_WIDTH}}};
+
+/**
+ * Whether the input is tokenized as if it ends with a new line
+ * (see `set_append_eof_newline`).
+ */
+const DEFAULT_APPEND_EOF_NEWLINE: bool = {{{APPEND_EOF_NEWLINE}}};
+
+/**
+ * Whether lines and columns of tokens are tracked (see
+ * `set_track_locations`), otherwise only the offsets are.
+ */
+const DEFAULT_TRACK_LOCATIONS: bool = {{{TRACK_LOCATIONS}}};
+
+/**
+ * Whether offsets, and columns are in UTF-16 code units (see
+ * `set_utf16_offsets`), otherwise in bytes.
+ */
+const DEFAULT_UTF16_OFFSETS: bool = {{{UTF16_OFFSETS}}};
+
+/**
+ * Whether columns are in grapheme clusters (see `set_grapheme_columns`).
+ */
+const DEFAULT_GRAPHEME_COLUMNS: bool = {{{GRAPHEME_COLUMNS}}};
+
+/**
+ * Number of UTF-16 code units of a UTF-8 text: a character of 4 bytes
+ * is a surrogate pair, the continuation bytes are not counted.
+ */
+fn utf16_len(text: &[u8]) -> i32 {
+    text.iter()
+        .fold(0, |len, byte| match *byte {
+            0x80..=0xbf => len,
+            0xf0..=0xff => len + 2,
+            _ => len + 1,
+        })
+}
+
+/**
+ * Token types, values of which are normalized to the NFC form with
+ * the `unicode-normalization` feature (see `normalize_yytext`).
+ */
+static NORMALIZED_TOKENS: &[&str] = {{{NORMALIZED_TOKENS}}};
+
+/**
+ * Synthetic tokens of the indentation tracking (see `indentation`).
+ */
+static NEWLINE: &str = "NEWLINE";
+static INDENT: &str = "INDENT";
+static DEDENT: &str = "DEDENT";
+
+/**
+ * How spaces and tabs are mixed in the indentation.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]{{{NON_EXHAUSTIVE}}}
+pub enum IndentPolicy {
+    /**
+     * A tab moves to the next tab stop (see `set_tab_width`).
+     */
+    TabStops,
+
+    /**
+     * Spaces and tabs in the indentation of one line is an error.
+     */
+    NoMixing,
+}
+
+/**
+ * Formats the "Unexpected token" error message from the token, its
+ * `line:column` location, and the source line with the ^ marker.
+ */
+pub type ErrorFormatter = Box<dyn Fn(&str, i32, i32, &str) -> String>;
+
+/**
+ * Default message, followed by the tokens expected by the parser.
+ */
+fn default_error_formatter(token: &str, line: i32, column: i32, line_data: &str, in_file: &str, expected: &[&str]) -> String {
+    format!(
+        "{} Unexpected token: \"{}\"{} at {}:{}{}.",
+        line_data,
+        token,
+        in_file,
+        line,
+        column,
+        expected_message(expected)
+    )
+}
+
+/**
+ * The expected tokens in error messages: "; expected one of: NUMBER, (",
+ * the `EOF` token is the end of input. Empty if there are no expected tokens.
+ */
+fn expected_message(expected: &[&str]) -> String {
+    if expected.is_empty() {
+        return String::new();
+    }
+
+    let names: Vec<&str> = expected
+        .iter()
+        .map(|name| if *name == EOF { EOF_DISPLAY_NAME } else { *name })
+        .collect();
+
+    format!("; expected one of: {}", names.join(", "))
 }
 
-fn str_as_static<'t>(s: &'t str) -> &'static str {
-    unsafe {
-        std::mem::transmute::<&'t str, &'static str>(s)
-    }
-}
+/**
+ * Fallback of a state for a text not matched by its lex rules (see
+ * `set_fallback`): receives the unmatched character, and returns the
+ * token type for it, or "" to skip it.
+ */
+pub type FallbackHandler = Box<dyn Fn(&str) -> &'static str>;
+
+/**
+ * Handler of the end of input (see `set_eof_handler`): queues the tokens
+ * returned before the EOF, e.g. to close the open blocks of a layout.
+ */
+pub type EofHandler = Box<dyn Fn(&mut Tokenizer)>;
+
+// ------------------------------------------------------------------
+// Tokenizer.
+
+{{{REGEX_BACKEND}}}
+
+{{{SHARED_DATA}}}
+
+/**
+ * Tokenizer of a string. Besides the parser, it can be used standalone:
+ *
+ *   let mut tokenizer = Tokenizer::new();
+ *   tokenizer.init_string("2 + 2");
+ *
+ *   for token in tokenizer {
+ *       println!("{:?}", token?);
+ *   }
+ */
+pub struct Tokenizer<'t> {
+    /**
+     * Tokenizing string.
+     */
+    string: &'t str,
+
+    /**
+     * Cursor for current symbol. It's in `0..=len` of the string while
+     * the input is not consumed (at `len` only the end of input is left,
+     * which is matched by the empty matches, e.g. of `<<EOF>>` rules), and
+     * `len + 1` once the end of input is consumed (see `consume_end`).
+     * So the cursor never moves back, and the EOF token is returned after
+     * all the tokens of the string.
+     */
+    cursor: i32,
+
+    /**
+     * States.
+     */
+    states: Vec<&'static str>,
+
+    /**
+     * Runtime data of the states (see `push_state_with`), the stack
+     * is parallel to the states.
+     */
+    states_data: Vec<Option<String>>,
+
+    /**
+     * Line-based location tracking.
+     */
+    current_line: i32,
+    current_column: i32,
+    current_line_begin_offset: i32,
+
+    /**
+     * Byte offsets of the begins of the lines in the tokenized part of the
+     * string (see `offset_to_line_col`), recorded with the locations.
+     */
+    line_starts: Vec<i32>,
+
+    /**
+     * Line, and column of the first line start: the begin of the string,
+     * or of the tokenized range (see `init_string_at`).
+     */
+    first_line: i32,
+    first_line_column: i32,
+
+    /**
+     * Tab width for column numbers.
+     */
+    tab_width: i32,
+
+    /**
+     * Whether the input which doesn't end with a new line
+     * is tokenized as if it does.
+     */
+    append_eof_newline: bool,
+
+    /**
+     * Whether the new line at the end of the input (see
+     * `append_eof_newline`) is not matched yet.
+     */
+    eof_newline: bool,
+
+    /**
+     * Whether lines and columns are tracked.
+     */
+    track_locations: bool,
+
+    /**
+     * Whether offsets, and columns are in UTF-16 code units. And the
+     * last converted offset: the byte offset, and its UTF-16 offset.
+     */
+    utf16_offsets: bool,
+    utf16_position: std::cell::Cell<(i32, i32)>,
+
+    /**
+     * Whether columns are in grapheme clusters.
+     */
+    grapheme_columns: bool,
+
+    /**
+     * Indentation tracking: the stack of indentation widths
+     * of the open blocks, and the policy for tabs.
+     */
+    indent_stack: Vec<i32>,
+    indent_policy: IndentPolicy,
+
+    /**
+     * Whether `indentation` is used, the open blocks are closed
+     * with DEDENT tokens at the end of input.
+     */
+    tracks_indentation: bool,
+
+    /**
+     * Tokens to return before matching the string: the token type,
+     * the value, and the location. Queued by lex handlers (see
+     * `push_token`), or synthetic ones (e.g. INDENT). And the type
+     * of the last returned token.
+     */
+    tokens_queue: VecDeque<(&'static str, &'t str, Loc)>,
+    last_token_type: &'static str,
+
+    /**
+     * Formatter of the "Unexpected token" error message, the
+     * default one if it's not set.
+     */
+    error_formatter: Option<ErrorFormatter>,
+
+    /**
+     * Fallbacks for the unmatched text by states.
+     */
+    fallbacks: HashMap<&'static str, FallbackHandler>,
+
+    /**
+     * Handler of the end of input, and whether it's called for the
+     * current string (it's called once).
+     */
+    eof_handler: Option<EofHandler>,
+    eof_handled: bool,
+
+    /**
+     * Error reported by a lex handler (see `set_error`).
+     */
+    error: Option<SyntaxError>,
+
+    /**
+     * Name of the parsed file in error messages (see `Parser::parse_file`).
+     */
+    file_name: Option<String>,
+
+    /**
+     * Whether the combined regexes are used for the states which
+     * have them, otherwise the rules are always tried one by one.
+     */
+    combined_rules: bool,
+
+    /**
+     * Whether an empty match in the middle of the string is a token (see
+     * `set_empty_tokens`), and the rules which matched at the offset of the
+     * cursor (an empty match, or the end of a text), none of them matches
+     * empty there again.
+     */
+    empty_tokens: bool,
+    empty_matches: Vec<usize>,
+    empty_matches_offset: i32,
+
+    /**
+     * Number of matches of each lex rule (see `set_lex_coverage`).
+     */
+    lex_rule_hits: Option<Vec<usize>>,
+
+    /**
+     * Context of the parse (the `contextType` option, see `context`).
+     */
+    context: {{{CONTEXT_TYPE}}},
+
+    /**
+     * Location data of a matched token.
+     */
+    token_start_offset: i32,
+    token_end_offset: i32,
+    token_start_line: i32,
+    token_end_line: i32,
+    token_start_column: i32,
+    token_end_column: i32,
+
+    /**
+     * Matched text, and its length.
+     */
+    yytext: &'t str,
+    yyleng: usize,
+
+    /*
+     * Buffer for manually generated tokens in lex handlers (see `StringBuffer`).
+     * For regular unmodified tokens yytext just points to slice in "string",
+     * so no extra memory allocated here.
+     */
+    yybuffer: Option<&'t StringBuffer>,
+
+    handlers: [fn(&mut Tokenizer<'t>) -> &'static str; {{{LEX_RULE_HANDLERS_COUNT}}}],
+}
+
+impl<'t> Tokenizer<'t> {
+
+    /**
+     * Creates a new Tokenizer instance.
+     *
+     * The same instance can be then reused in parser
+     * by calling `init_string`.
+     */
+    pub fn new() -> Tokenizer<'t> {
+        Tokenizer {
+            string: "",
+            cursor: 0,
+
+            states: Vec::new(),
+            states_data: Vec::new(),
+
+            current_line: 1,
+            current_column: 0,
+            current_line_begin_offset: 0,
+            line_starts: vec![0],
+            first_line: 1,
+            first_line_column: 0,
+
+            tab_width: DEFAULT_TAB_WIDTH,
+            append_eof_newline: DEFAULT_APPEND_EOF_NEWLINE,
+            eof_newline: false,
+            track_locations: DEFAULT_TRACK_LOCATIONS,
+
+            utf16_offsets: DEFAULT_UTF16_OFFSETS,
+            utf16_position: std::cell::Cell::new((0, 0)),
+            grapheme_columns: DEFAULT_GRAPHEME_COLUMNS,
+
+            indent_stack: Vec::new(),
+            indent_policy: IndentPolicy::TabStops,
+            tracks_indentation: false,
+
+            tokens_queue: VecDeque::new(),
+            last_token_type: "",
+
+            error_formatter: None,
+
+            fallbacks: HashMap::new(),
+
+            eof_handler: None,
+            eof_handled: false,
+
+            error: None,
+
+            file_name: None,
+
+            combined_rules: true,
+
+            empty_tokens: false,
+            empty_matches: Vec::new(),
+            empty_matches_offset: 0,
+
+            lex_rule_hits: None,
+
+            context: Default::default(),
+
+            token_start_offset: 0,
+            token_end_offset: 0,
+            token_start_line: 0,
+            token_end_line: 0,
+            token_start_column: 0,
+            token_end_column: 0,
+
+            yytext: "",
+            yyleng: 0,
+
+            yybuffer: None,
+
+            handlers: {{{LEX_RULE_HANDLERS_ARRAY}}}
+        }
+    }
+
+    /**
+     * A new tokenizer with the settings of this one, e.g. for a nested
+     * parse. The error formatter, the fallbacks, and the string buffer
+     * are not copied.
+     */
+    fn with_settings<'s>(&self) -> Tokenizer<'s> {
+        let mut tokenizer = Tokenizer::new();
+
+        tokenizer.tab_width = self.tab_width;
+        tokenizer.append_eof_newline = self.append_eof_newline;
+        tokenizer.track_locations = self.track_locations;
+        tokenizer.utf16_offsets = self.utf16_offsets;
+        tokenizer.grapheme_columns = self.grapheme_columns;
+        tokenizer.indent_policy = self.indent_policy;
+        tokenizer.combined_rules = self.combined_rules;
+        tokenizer.empty_tokens = self.empty_tokens;
+        tokenizer.file_name = self.file_name.clone();
+
+        tokenizer
+    }
+
+    /**
+     * Resets the tokenizer for a string of another lifetime, e.g. a line
+     * read in a loop, which is dropped before the next one. The settings,
+     * the error formatter, the fallbacks, the EOF handler, the counts of
+     * the lex coverage, and the context are kept, the string buffer
+     * (borrowed for the previous strings, see `StringBuffer`) is not.
+     */
+    pub fn reset<'s>(self) -> Tokenizer<'s> {
+        let mut tokenizer = self.with_settings();
+
+        tokenizer.error_formatter = self.error_formatter;
+        tokenizer.fallbacks = self.fallbacks;
+        tokenizer.eof_handler = self.eof_handler;
+        tokenizer.lex_rule_hits = self.lex_rule_hits;
+        tokenizer.context = self.context;
+
+        tokenizer
+    }
+
+    /**
+     * Initializes a parsing string.
+     */
+    pub fn init_string(&mut self, string: &'t str) -> &mut Tokenizer<'t> {
+        self.string = string;
+        self.eof_newline = self.append_eof_newline && !string.is_empty() && !string.ends_with('\n');
+
+        // Initialize states.
+        self.states.clear();
+        self.states.push("INITIAL");
+        self.states_data.clear();
+        self.states_data.push(None);
+
+        self.cursor = 0;
+        self.current_line = 1;
+        self.current_column = 0;
+        self.current_line_begin_offset = 0;
+        self.utf16_position.set((0, 0));
+
+        self.line_starts.clear();
+        self.line_starts.push(0);
+        self.first_line = 1;
+        self.first_line_column = 0;
+
+        self.indent_stack.clear();
+        self.indent_stack.push(0);
+        self.tracks_indentation = false;
+
+        self.tokens_queue.clear();
+        self.last_token_type = "";
+
+        self.eof_handled = false;
+        self.error = None;
+
+        self.empty_matches.clear();
+
+        self.token_start_offset = 0;
+        self.token_end_offset = 0;
+        self.token_start_line = 0;
+        self.token_end_line = 0;
+        self.token_start_column = 0;
+        self.token_end_column = 0;
+
+        // No text of the previous input (the string buffer is
+        // cleared by its owner, see `StringBuffer`).
+        self.yytext = "";
+        self.yyleng = 0;
+
+        self
+    }
+
+    /**
+     * Initializes a parsing string to tokenize from a byte offset, e.g. the
+     * visible range of an editor, in a lexer state (start condition), at
+     * a line, and column. The state, and the location of the offset are
+     * not known without tokenizing the text before it, so they are given
+     * by the caller, which can keep them for the lines it tokenized:
+     *
+     *   tokenizer.init_string_at(source, line_offset, "comment", 42, 0);
+     *
+     *   while let Ok(token) = tokenizer.get_next_token() {
+     *       if token.is_eof() || token.start_offset >= range_end {
+     *           break;
+     *       }
+     *       ...
+     *   }
+     *
+     * The offsets of the tokens are in the whole string. A state other than
+     * `INITIAL` is pushed on it, so the range can pop it.
+     *
+     * Panics if the offset isn't at a char boundary of the string.
+     */
+    pub fn init_string_at(
+        &mut self,
+        string: &'t str,
+        offset: usize,
+        state: &'static str,
+        line: i32,
+        column: i32,
+    ) -> &mut Tokenizer<'t> {
+        assert!(
+            string.is_char_boundary(offset),
+            "offset {} is not at a char boundary of the string", offset
+        );
+
+        self.init_string(string);
+
+        if state != "INITIAL" {
+            self.push_state(state);
+        }
+
+        self.cursor = offset as i32;
+        self.current_line = line;
+        self.current_column = column;
+        self.current_line_begin_offset = offset as i32;
+
+        self.line_starts[0] = offset as i32;
+        self.first_line = line;
+        self.first_line_column = column;
+
+        self
+    }
+
+    /**
+     * Sets tab width for column numbers (the width does not
+     * affect offsets). Applied to the tokens matched after it.
+     */
+    pub fn set_tab_width(&mut self, tab_width: i32) -> &mut Tokenizer<'t> {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /**
+     * Sets whether the input is tokenized as if it ends with a new line,
+     * e.g. for grammars where a new line terminates a statement, so the
+     * last line doesn't need it. The end of a non-empty input which
+     * doesn't end with a new line is matched as `\n` (the input is not
+     * copied), applied to the strings initialized after it.
+     */
+    pub fn set_append_eof_newline(&mut self, append_eof_newline: bool) -> &mut Tokenizer<'t> {
+        self.append_eof_newline = append_eof_newline;
+        self
+    }
+
+    /**
+     * Sets whether lines and columns of tokens are tracked. Without it
+     * the new lines of the matched text are not counted, and columns are
+     * not calculated, which is faster when only the parsed value is needed:
+     * the line and column fields of locations are 0, and error messages
+     * show the byte offset (the error formatter is not used). The offsets
+     * are always tracked. It's set before the parsing, since the lines
+     * are counted from the start of the string.
+     */
+    pub fn set_track_locations(&mut self, track_locations: bool) -> &mut Tokenizer<'t> {
+        self.track_locations = track_locations;
+        self
+    }
+
+    /**
+     * Sets whether offsets, and columns of locations are in UTF-16 code
+     * units, as the strings, and ranges of JS (e.g. of an editor), instead
+     * of bytes. A character outside of the BMP takes two units. The string
+     * is still tokenized as UTF-8, only the reported locations are
+     * converted. It's set before the parsing.
+     */
+    pub fn set_utf16_offsets(&mut self, utf16_offsets: bool) -> &mut Tokenizer<'t> {
+        self.utf16_offsets = utf16_offsets;
+        self
+    }
+
+    /**
+     * Sets whether columns of locations are in grapheme clusters, as the
+     * cursor of an editor moves, e.g. a family emoji of several characters
+     * joined with ZWJ is one column. The offsets are not changed. With the
+     * `unicode-segmentation` feature the extended grapheme clusters are
+     * counted, otherwise the characters (Unicode scalar values).
+     */
+    pub fn set_grapheme_columns(&mut self, grapheme_columns: bool) -> &mut Tokenizer<'t> {
+        self.grapheme_columns = grapheme_columns;
+        self
+    }
+
+    /**
+     * Sets whether a lex rule which matches an empty string in the middle
+     * of the string produces a token, e.g. an optional sign, for the parser
+     * to decide on it (`number : SIGN DIGITS`):
+     *
+     *   [+-]?    return "SIGN";
+     *
+     * Otherwise an empty match is skipped, and the next rules are tried.
+     * A rule matches empty once at an offset: the cursor doesn't advance,
+     * so the next token is matched by the following rules (also if the
+     * handler skips the empty match). Neither it matches empty right after
+     * its own text, so `-1` has one sign. At the end of the string an empty
+     * match is not a token, the end of input is handled by the EOF handler
+     * (see `set_eof_handler`).
+     */
+    pub fn set_empty_tokens(&mut self, empty_tokens: bool) -> &mut Tokenizer<'t> {
+        self.empty_tokens = empty_tokens;
+        self
+    }
+
+    /**
+     * Sets whether the matches of each lex rule are counted, e.g. to find
+     * the rules a test corpus never exercises (see `lex_coverage`). The
+     * counts start from zero, and are kept on `reset`, so they accumulate
+     * over several strings. A keyword matched by the identifier rule (the
+     * `keywordTable` option) is counted for the keyword rule.
+     */
+    pub fn set_lex_coverage(&mut self, lex_coverage: bool) -> &mut Tokenizer<'t> {
+        self.lex_rule_hits = if lex_coverage {
+            Some(vec![0; LEX_RULES.len()])
+        } else {
+            None
+        };
+        self
+    }
+
+    /**
+     * The regex, and the number of matches of each lex rule, in the order
+     * of the rules of the grammar. Empty if the lex coverage isn't set.
+     */
+    pub fn lex_coverage(&self) -> Vec<(&'static str, usize)> {
+        match &self.lex_rule_hits {
+            Some(hits) => LEX_RULES.iter().copied().zip(hits.iter().copied()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /**
+     * Counts a match of the rule for the lex coverage.
+     */
+    fn hit_lex_rule(&mut self, rule: usize) {
+        if let Some(hits) = &mut self.lex_rule_hits {
+            hits[rule] += 1;
+        }
+    }
+
+    /**
+     * Moves the match of the identifier rule to the keyword rule
+     * found in the keyword table, for the lex coverage.
+     */
+    fn hit_keyword_rule(&mut self, identifier: usize, keyword: usize) {
+        if let Some(hits) = &mut self.lex_rule_hits {
+            hits[identifier] -= 1;
+            hits[keyword] += 1;
+        }
+    }
+
+    /**
+     * Sets formatter of the "Unexpected token" error message, e.g.
+     * to prepend a file name:
+     *
+     *   tokenizer.set_error_formatter(|token, line, column, _| {
+     *       format!("main.calc:{}:{}: unexpected \"{}\"", line, column, token)
+     *   });
+     */
+    pub fn set_error_formatter<F>(&mut self, formatter: F) -> &mut Tokenizer<'t>
+        where F: Fn(&str, i32, i32, &str) -> String + 'static {
+        self.error_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /**
+     * Sets the fallback of a state, a catch-all rule with the lowest
+     * priority: a character not matched by the lex rules of the state is
+     * passed to it instead of the "Unexpected token" error, e.g. to return
+     * an error token, so the parsing continues:
+     *
+     *   tokenizer.set_fallback("INITIAL", |_| "ERROR");
+     *
+     * The returned token type should be used in the grammar,
+     * "" skips the character.
+     */
+    pub fn set_fallback<F>(&mut self, state: &'static str, fallback: F) -> &mut Tokenizer<'t>
+        where F: Fn(&str) -> &'static str + 'static {
+        self.fallbacks.insert(state, Box::new(fallback));
+        self
+    }
+
+    /**
+     * Sets the handler of the end of input, called once per string
+     * before the EOF token is returned: the tokens it queues (see
+     * `push_synthetic_token`) are returned before the EOF, e.g. to close
+     * the blocks of a custom layout, kept as the data of the states:
+     *
+     *   tokenizer.set_eof_handler(|tokenizer| {
+     *       while tokenizer.get_state_data().is_some() {
+     *           tokenizer.pop_state();
+     *           tokenizer.push_synthetic_token("DEDENT");
+     *       }
+     *   });
+     *
+     * The blocks of the `indentation` tracking are closed before it.
+     */
+    pub fn set_eof_handler<F>(&mut self, handler: F) -> &mut Tokenizer<'t>
+        where F: Fn(&mut Tokenizer) + 'static {
+        self.eof_handler = Some(Box::new(handler));
+        self
+    }
+
+    /**
+     * Sets how spaces and tabs are mixed in the indentation.
+     */
+    pub fn set_indent_policy(&mut self, policy: IndentPolicy) -> &mut Tokenizer<'t> {
+        self.indent_policy = policy;
+        self
+    }
+
+    /**
+     * Indentation tracking for layout-sensitive grammars, called from
+     * a lex rule which matches new lines with the following indentation:
+     *
+     *   (\n[ \t]*)+    return self.indentation();
+     *
+     * Returns NEWLINE, followed (in the next tokens) by an INDENT if
+     * the line is indented deeper than the current block, or by DEDENTs
+     * for each closed block. New lines before the first token, and
+     * at the end of input are skipped. At the end of input a NEWLINE
+     * (if needed) and DEDENTs for all open blocks are returned.
+     */
+    pub fn indentation(&mut self) -> &'static str {
+        self.tracks_indentation = true;
+
+        if self.last_token_type.is_empty() {
+            return "";
+        }
+
+        if self.is_eof() {
+            return NEWLINE;
+        }
+
+        let yytext = self.yytext;
+        let indent = &yytext[yytext.rfind('\n').map_or(0, |i| i + 1)..];
+
+        if self.indent_policy == IndentPolicy::NoMixing &&
+            indent.contains(' ') && indent.contains('\t') {
+            self.set_error(format!(
+                "Mixed spaces and tabs in indentation{} at {}.",
+                self.in_file(),
+                self.end_position()
+            ));
+            return "";
+        }
+
+        let width = self.text_width(0, indent.as_bytes());
+        let current = *self.indent_stack.last().unwrap();
+
+        if width > current {
+            self.indent_stack.push(width);
+            self.push_synthetic_token(INDENT);
+        }
+
+        while width < *self.indent_stack.last().unwrap() {
+            self.indent_stack.pop();
+            self.push_synthetic_token(DEDENT);
+        }
+
+        if width != *self.indent_stack.last().unwrap() {
+            self.set_error(format!(
+                "Inconsistent dedent{} at {}.",
+                self.in_file(),
+                self.end_position()
+            ));
+            return "";
+        }
+
+        NEWLINE
+    }
+
+    /**
+     * Reports an error from a lex handler: the `get_next_token` returns
+     * it instead of the matched token. The location is the matched text.
+     */
+    pub fn set_error(&mut self, message: String) {
+        let start_offset = self.reported_offset(self.token_start_offset);
+        let end_offset = self.reported_offset(self.token_end_offset);
+
+        self.error = Some(SyntaxError {
+            message,
+            loc: Loc {
+                start_offset,
+                end_offset,
+                start_line: self.token_start_line,
+                end_line: self.token_end_line,
+                start_column: self.token_start_column,
+                end_column: self.token_end_column,
+            },
+            expected: Vec::new(),
+            limit: None,
+            kind: SyntaxErrorKind::Other,
+        });
+    }
+
+    /**
+     * Replace yytext with given string (kept in the string buffer, see `string_ref`)
+     */
+    pub fn set_yytext(&mut self, s: String) {
+        self.yytext = self.string_ref(s);
+    }
+
+    /**
+     * With the `unicode-normalization` feature the value of a token of
+     * the `NORMALIZED_TOKENS` types is normalized to the NFC form, so the
+     * equivalent identifiers (e.g. "caf\u{e9}", and "cafe\u{301}") are equal.
+     * A normalized value is kept in the string buffer (see `set_string_buffer`).
+     * Without the `normalizeTokens` option it's not compiled (`any()`).
+     */
+    #[cfg({{{NORMALIZATION_FEATURE}}})]
+    fn normalize_yytext(&mut self, token_type: &str) {
+        use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+        if NORMALIZED_TOKENS.contains(&token_type) && !is_nfc(self.yytext) {
+            let normalized = self.yytext.nfc().collect();
+            self.set_yytext(normalized);
+        }
+    }
+
+    #[cfg(not({{{NORMALIZATION_FEATURE}}}))]
+    fn normalize_yytext(&mut self, _token_type: &str) {}
+
+    /**
+     * Move ownership of given string to the string buffer (see `set_string_buffer`)
+     * and returns reference to it as &str.
+     * Use this method for overriding yytext with new strings wich are not part of text being parsed.
+     * Without the string buffer it's an error of the matched text (see `set_error`),
+     * and the returned string is empty.
+     */
+    pub fn string_ref(&mut self, s: String) -> &'t str {
+        match self.yybuffer {
+            Some(buffer) => buffer.push(s),
+            None => {
+                self.set_error(format!(
+                    "No string buffer for the text of a token{} at {}.",
+                    self.in_file(),
+                    self.end_position()
+                ));
+                ""
+            }
+        }
+    }
+
+    /**
+     * Sets the buffer of the strings made in lex handlers (see `StringBuffer`),
+     * which is borrowed for the lifetime of the parsing string.
+     */
+    pub fn set_string_buffer(&mut self, buffer: &'t StringBuffer) -> &mut Tokenizer<'t> {
+        self.yybuffer = Some(buffer);
+        self
+    }
+
+    /**
+     * Consumes the input up to, and including the first match of the
+     * pattern (or up to the end of input, if there is no match), and
+     * returns the consumed text. Used in lex handlers for constructs
+     * which are easier to scan imperatively, e.g. a heredoc body with
+     * the terminator known only at runtime.
+     *
+     * The location of the current token is extended to the end of the
+     * consumed text, `yytext` is not changed.
+     */
+    pub fn read_until(&mut self, pattern: &Regex) -> &'t str {
+        let string = self.string;
+        let str_slice = &string[(self.cursor as usize).min(string.len())..];
+
+        let end = find_regex_end(pattern, str_slice).unwrap_or(str_slice.len());
+        let consumed = &str_slice[..end];
+
+        // The token still starts at the text matched by the lex rule.
+        let start_offset = self.token_start_offset;
+        let start_line = self.token_start_line;
+        let start_column = self.token_start_column;
+
+        self.capture_location(consumed);
+        self.cursor += consumed.len() as i32;
+
+        self.token_start_offset = start_offset;
+        self.token_start_line = start_line;
+        self.token_start_column = start_column;
+
+        consumed
+    }
+
+    /**
+     * The input from the cursor to the end of the string. In a lex
+     * handler it starts right after the matched text, so the handler
+     * can peek at the following input to choose the token type.
+     */
+    pub fn remaining(&self) -> &'t str {
+        &self.string[self.cursor_offset()..]
+    }
+
+    /**
+     * The input from the start of the string to the cursor (in a lex
+     * handler, including the matched text).
+     */
+    pub fn consumed(&self) -> &'t str {
+        &self.string[..self.cursor_offset()]
+    }
+
+    /**
+     * Whether the matched text (in a lex handler) starts a line, e.g. for
+     * a comment which starts only in the first column, otherwise the same
+     * text is another token:
+     *
+     *   ";"     %{
+     *               if self.at_line_start() {
+     *                   self.read_until(&NEW_LINE);
+     *                   return "";
+     *               }
+     *               return "SEMICOLON";
+     *           %}
+     *
+     * It doesn't depend on the location tracking.
+     */
+    pub fn at_line_start(&self) -> bool {
+        let start = self.token_start_offset as usize;
+        start == 0 || self.string.as_bytes()[start - 1] == b'\n'
+    }
+
+    /**
+     * Whether only spaces, and tabs are before the matched text on its
+     * line (see `at_line_start`), e.g. a `#` comment is the first text
+     * of a line, which may be indented.
+     */
+    pub fn after_indentation(&self) -> bool {
+        self.string.as_bytes()[..self.token_start_offset as usize]
+            .iter()
+            .rev()
+            .take_while(|byte| **byte != b'\n')
+            .all(|byte| *byte == b' ' || *byte == b'\t')
+    }
+
+    /**
+     * Context of the parse in lex handlers, of the type of the `contextType`
+     * option (`()` without it), e.g. an interner, or a sink of diagnostics
+     * of the caller (see `Parser::parse_with`):
+     *
+     *   \w+    self.context().intern(yytext); return "ID";
+     */
+    pub fn context(&mut self) -> &mut {{{CONTEXT_TYPE}}} {
+        &mut self.context
+    }
+
+    /**
+     * Kind of the last token returned by the tokenizer, `None` at the start
+     * of the string (the skipped text isn't a token). In a lex handler it's
+     * the token before the matched text, e.g. a `/` after an operand is
+     * the division, otherwise it starts a regex:
+     *
+     *   "/"     %{
+     *               if self.last_token_kind() == token_kind("ID") {
+     *                   return "DIVIDE";
+     *               }
+     *               self.push_state("regex");
+     *               return "REGEX_START";
+     *           %}
+     */
+    pub fn last_token_kind(&self) -> Option<i32> {
+        if self.last_token_type.is_empty() {
+            return None;
+        }
+        token_kind(self.last_token_type)
+    }
+
+    /**
+     * Cursor as an offset in the string (the cursor is one past the end
+     * once the EOF token is returned).
+     */
+    fn cursor_offset(&self) -> usize {
+        (self.cursor as usize).min(self.string.len())
+    }
+
+    /**
+     * Returns next token.
+     *
+     * Once the whole string is consumed, returns the EOF token
+     * (see `eof_token`) on this and all subsequent calls. A syntax
+     * error is returned for a string not matched by the lex rules,
+     * unless the state has a fallback (see `set_fallback`).
+     */
+    pub fn get_next_token(&mut self) -> Result<Token<'t>, SyntaxError> {
+        if !self.tokens_queue.is_empty() {
+            return self.queued_token();
+        }
+
+        if !self.has_more_tokens() {
+            return self.eof_token();
+        }
+
+        // The new line at the end of the input (see `set_append_eof_newline`)
+        // is matched on its own, it's not a part of the string.
+        let string = self.string;
+        let str_slice = if self.eof_newline && self.is_end_of_string() {
+            "\n"
+        } else {
+            &string[self.cursor as usize..]
+        };
+        let at_end = str_slice.is_empty();
+
+        let state = self.get_current_state();
+        let lex_rules_for_state = lex_rules_of_state(state);
+
+        // The end of input is matched by the rules one by one,
+        // the combined regex doesn't match empty strings.
+        let combined_rules = if self.combined_rules && !at_end {
+            combined_regex_rule(state)
+        } else {
+            None
+        };
+
+        let mut next_rule = 0;
+
+        while let Some((position, matched)) =
+            self._match_rule(str_slice, lex_rules_for_state, next_rule, combined_rules)
+        {
+            let i = lex_rules_for_state[position] as usize;
+            next_rule = position + 1;
+
+            // An empty match at the end of the string (e.g. of `<<EOF>>`
+            // rules) consumes the end of input. In the middle of the string
+            // it's a token only with the empty tokens, once for a rule at
+            // the offset (and not after its own text), since it doesn't
+            // advance.
+            if matched.is_empty() {
+                if at_end && !self.empty_tokens {
+                    self.consume_end();
+                } else if at_end || !self.accept_empty_match(i) {
+                    continue;
+                }
+            } else if self.empty_tokens {
+                // The rule doesn't match empty right after its text.
+                self.empty_matches.clear();
+                self.empty_matches.push(i);
+                self.empty_matches_offset = self.cursor;
+            }
+
+            self.hit_lex_rule(i);
+
+            self.yytext = matched;
+            self.yyleng = matched.len();
+
+            let token_type = self.handlers[i](self);
+
+            if let Some(error) = self.error.take() {
+                return Err(error);
+            }
+
+            // "" - no token (skip)
+            if token_type.is_empty() {
+                return self.get_next_token();
+            }
+
+            return self.matched_token(token_type);
+        }
+
+        if at_end {
+            return self.eof_token();
+        }
+
+        // The fallback of the state matches one character.
+        if self.fallbacks.contains_key(state) {
+            let matched = &str_slice[..str_slice.chars().next().unwrap().len_utf8()];
+
+            self.consume_match(matched);
+
+            self.yytext = matched;
+            self.yyleng = matched.len();
+
+            let token_type = (self.fallbacks[state])(matched);
+
+            if token_type.is_empty() {
+                return self.get_next_token();
+            }
+
+            return self.matched_token(token_type);
+        }
 
-// NOTE: LEX_RULES_BY_START_CONDITIONS, and TOKENS_MAP
-// are defined in the lazy_static! block in lr.templates.rs
+        // The unexpected character (a multibyte one is not split).
+        let unexpected_char = str_slice.chars().next().unwrap();
+        let unexpected = &str_slice[..unexpected_char.len_utf8()];
 
-// ------------------------------------------------------------------
-// Tokenizer.
+        let width = if self.utf16_offsets {
+            unexpected_char.len_utf16()
+        } else {
+            unexpected.len()
+        } as i32;
 
-lazy_static! {
-    /** 
-     * Pre-parse the regex instead of parsing it every time when calling `get_next_token`.
-     */
-    static ref REGEX_RULES: Vec<Regex> = LEX_RULES.iter().map(|rule| Regex::with_options(rule, RegexOptions::REGEX_OPTION_SINGLELINE, Syntax::default()).unwrap()).collect();
-}
+        let column_width = if self.grapheme_columns { 1 } else { width };
+
+        let start_offset = self.reported_offset(self.cursor);
+
+        Err(self.unexpected_token_error(
+            unexpected,
+            Loc {
+                start_offset,
+                end_offset: start_offset + width,
+                start_line: self.current_line,
+                end_line: self.current_line,
+                start_column: self.current_column,
+                end_column: self.current_column + column_width,
+            },
+            Vec::new(),
+        ))
+    }
 
-struct Tokenizer<'t> {
     /**
-     * Tokenizing string.
+     * Whether an empty match of a rule in the middle of the string is a
+     * token: with the empty tokens, if the rule hasn't matched at this
+     * offset yet.
      */
-    string: &'t str,
+    fn accept_empty_match(&mut self, rule: usize) -> bool {
+        if !self.empty_tokens {
+            return false;
+        }
+
+        if self.empty_matches_offset != self.cursor {
+            self.empty_matches.clear();
+            self.empty_matches_offset = self.cursor;
+        }
+
+        if self.empty_matches.contains(&rule) {
+            return false;
+        }
+
+        self.empty_matches.push(rule);
+        true
+    }
 
     /**
-     * Cursor for current symbol.
+     * Returns the EOF token: an empty token at the end of the string,
+     * with the `EOF` value. The end of input is consumed (see `cursor`).
      */
-    cursor: i32,
+    fn eof_token(&mut self) -> Result<Token<'t>, SyntaxError> {
+        let end = self.string.len() as i32;
+        self.consume_end();
+
+        self.token_start_offset = end;
+        self.token_end_offset = end;
+
+        if self.track_locations {
+            self.token_start_line = self.current_line;
+            self.token_end_line = self.current_line;
+            self.token_start_column = self.column_at(end);
+            self.token_end_column = self.token_start_column;
+        }
+
+        // Close the open blocks of the indentation tracking.
+        if self.tracks_indentation {
+            self.tracks_indentation = false;
+
+            if !self.last_token_type.is_empty() &&
+                self.last_token_type != NEWLINE &&
+                self.last_token_type != DEDENT {
+                self.push_synthetic_token(NEWLINE);
+            }
+
+            while self.indent_stack.len() > 1 {
+                self.indent_stack.pop();
+                self.push_synthetic_token(DEDENT);
+            }
+        }
+
+        // The handler is called after the indentation blocks are closed.
+        if !self.eof_handled {
+            self.eof_handled = true;
+
+            if let Some(handler) = self.eof_handler.take() {
+                handler(self);
+                self.eof_handler = Some(handler);
+            }
+        }
+
+        if !self.tokens_queue.is_empty() {
+            return self.queued_token();
+        }
+
+        self.yytext = EOF;
+        self.yyleng = 0;
+
+        self.to_token(EOF)
+    }
 
     /**
-     * States.
+     * Queues a token of a part of the text matched by the current lex
+     * rule, `range` is the byte range in the matched text (an empty range
+     * for an implicit token, e.g. a separator). The queued tokens are
+     * returned by the next `get_next_token` calls, before matching the
+     * string again. E.g. `>>` split into two `>` for nested generics:
+     *
+     *   ">>"    %{
+     *               self.push_token("GT", 0..1);
+     *               self.push_token("GT", 1..2);
+     *               return "";
+     *           %}
+     *
+     * The token returned by the handler itself (if any) goes first.
      */
-    states: Vec<&'static str>,
+    pub fn push_token(&mut self, token_type: &'static str, range: std::ops::Range<usize>) {
+        let string = self.string;
+        let start = self.token_start_offset + range.start as i32;
+        let end = self.token_start_offset + range.end as i32;
+
+        let (start_line, start_column) = self.position_in_token(start);
+        let (end_line, end_column) = self.position_in_token(end);
+
+        self.tokens_queue.push_back((
+            token_type,
+            &string[start as usize..end as usize],
+            Loc {
+                start_offset: start,
+                end_offset: end,
+                start_line,
+                end_line,
+                start_column,
+                end_column,
+            },
+        ));
+    }
 
     /**
-     * Line-based location tracking.
+     * Queues a synthetic token, an empty token at the end
+     * of the current one (at the end of input in the EOF handler).
      */
-    current_line: i32,
-    current_column: i32,
-    current_line_begin_offset: i32,
+    pub fn push_synthetic_token(&mut self, token_type: &'static str) {
+        let loc = Loc::empty_at_end(&Loc {
+            start_offset: self.token_start_offset,
+            end_offset: self.token_end_offset,
+            start_line: self.token_start_line,
+            end_line: self.token_end_line,
+            start_column: self.token_start_column,
+            end_column: self.token_end_column,
+        });
+
+        self.tokens_queue.push_back((token_type, "", loc));
+    }
 
     /**
-     * Location data of a matched token.
+     * Line and column of an offset in the current token.
      */
-    token_start_offset: i32,
-    token_end_offset: i32,
-    token_start_line: i32,
-    token_end_line: i32,
-    token_start_column: i32,
-    token_end_column: i32,
+    fn position_in_token(&self, offset: i32) -> (i32, i32) {
+        if !self.track_locations {
+            return (0, 0);
+        }
+
+        let string = self.string;
+        let prefix = &string[self.token_start_offset as usize..offset as usize];
+
+        let line = self.token_start_line + prefix.matches('\n').count() as i32;
+        let line_begin = string[..offset as usize].rfind('\n').map_or(0, |i| i + 1);
+
+        (line, self.column_in_line(line_begin.max(self.line_starts[0] as usize) as i32, offset))
+    }
 
     /**
-     * Matched text, and its length.
+     * Returns the next token from the queue.
      */
-    yytext: &'static str,
-    yyleng: usize,
+    fn queued_token(&mut self) -> Result<Token<'t>, SyntaxError> {
+        let (token_type, value, loc) = self.tokens_queue.pop_front().unwrap();
 
-    /*
-     * Buffer for manually generated tokens in lex handlers.
-     * We do need this buffer because for regular unmodified tokens yytext just points to slice in "string",
-     * so no extra memory allocated here.
-     * But for generated tokens we need some place in memory to keep them up while Tokenizer is alive.
-     */
-    yybuffer: Vec<String>,
+        self.token_start_offset = loc.start_offset;
+        self.token_end_offset = loc.end_offset;
+        self.token_start_line = loc.start_line;
+        self.token_end_line = loc.end_line;
+        self.token_start_column = loc.start_column;
+        self.token_end_column = loc.end_column;
 
-    handlers: [fn(&mut Tokenizer<'t>) -> &'static str; {{{LEX_RULE_HANDLERS_COUNT}}}],
-}
+        self.yytext = value;
+        self.yyleng = value.len();
 
-impl<'t> Tokenizer<'t> {
+        self.matched_token(token_type)
+    }
 
     /**
-     * Creates a new Tokenizer instance.
+     * Creates default "Unexpected token" error, showing the actual
+     * line from the source, pointing with the ^ marker to the bad token.
+     * In addition, shows `line:column` location. The message is built
+     * by the error formatter (see `set_error_formatter`), the default
+     * one is followed by the tokens expected by the parser.
      *
-     * The same instance can be then reused in parser
-     * by calling `init_string`.
+     * Without the location tracking only the offset is shown.
      */
-    pub fn new() -> Tokenizer<'t> {
-        let mut tokenizer = Tokenizer {
-            string: "",
-            cursor: 0,
+    fn unexpected_token_error(&self, string: &str, loc: Loc, expected: Vec<&'static str>) -> SyntaxError {
+        if !self.track_locations {
+            return SyntaxError {
+                message: format!(
+                    "Unexpected token: \"{}\"{} at offset {}{}.",
+                    string,
+                    self.in_file(),
+                    loc.start_offset,
+                    expected_message(&expected)
+                ),
+                loc: Loc {
+                    start_line: 0,
+                    end_line: 0,
+                    start_column: 0,
+                    end_column: 0,
+                    ..loc
+                },
+                expected,
+                limit: None,
+                kind: SyntaxErrorKind::UnexpectedToken { token: string.to_string() },
+            };
+        }
 
-            states: Vec::new(),
+        let line = loc.start_line;
+        let column = loc.start_column;
 
-            current_line: 1,
-            current_column: 0,
-            current_line_begin_offset: 0,
+        let mut line_source = self.line_source(&loc).to_string();
 
-            token_start_offset: 0,
-            token_end_offset: 0,
-            token_start_line: 0,
-            token_end_line: 0,
-            token_start_column: 0,
-            token_end_column: 0,
+        // Expand tabs, so the marker is aligned with the column.
+        if self.tab_width > 1 {
+            let mut expanded = String::new();
+            for c in line_source.chars() {
+                if c == '\t' {
+                    let tab_stop = (expanded.len() as i32 / self.tab_width + 1) * self.tab_width;
+                    expanded.push_str(&" ".repeat(tab_stop as usize - expanded.len()));
+                } else {
+                    expanded.push(c);
+                }
+            }
+            line_source = expanded;
+        }
 
-            yytext: "",
-            yyleng: 0,
+        let pad = " ".repeat(column as usize);
 
-            yybuffer: Vec::new(),
+        let line_data = format!("\n\n{}\n{}^\n", line_source, pad);
 
-            handlers: {{{LEX_RULE_HANDLERS_ARRAY}}}
+        let message = match self.error_formatter {
+            Some(ref formatter) => formatter(string, line, column, &line_data),
+            None => default_error_formatter(string, line, column, &line_data, &self.in_file(), &expected),
         };
 
-        tokenizer
+        SyntaxError {
+            message,
+            loc,
+            expected,
+            limit: None,
+            kind: SyntaxErrorKind::UnexpectedToken { token: string.to_string() },
+        }
     }
 
     /**
-     * Initializes a parsing string.
+     * Source line of a location, without the new line. Only the line is
+     * scanned: from its begin offset (known for the current line) to the
+     * next new line, so it's cheap for long lines, and large inputs.
      */
-    pub fn init_string(&mut self, string: &'t str) -> &mut Tokenizer<'t> {
-        self.string = string;
+    fn line_source(&self, loc: &Loc) -> &'t str {
+        let string = self.string;
+        let offset = (self.byte_offset(loc.start_offset).max(0) as usize).min(string.len());
 
-        // Initialize states.
-        self.states.clear();
-        self.states.push("INITIAL");
+        // The begin of a range (see `init_string_at`) is not a line begin.
+        let begin = if loc.start_line == self.current_line &&
+            self.current_line_begin_offset as usize <= offset &&
+            (self.current_line_begin_offset != self.line_starts[0] || self.first_line_column == 0) {
+            self.current_line_begin_offset as usize
+        } else {
+            string[..offset].rfind('\n').map_or(0, |i| i + 1)
+        };
 
-        self.cursor = 0;
-        self.current_line = 1;
-        self.current_column = 0;
-        self.current_line_begin_offset = 0;
+        let end = string[offset..].find('\n').map_or(string.len(), |i| offset + i);
 
-        self.token_start_offset = 0;
-        self.token_end_offset = 0;
-        self.token_start_line = 0;
-        self.token_end_line = 0;
-        self.token_start_column = 0;
-        self.token_end_column = 0;
+        &string[begin..end]
+    }
 
-        self
+    fn capture_location(&mut self, matched: &str) {
+        // Absolute offsets.
+        self.token_start_offset = self.cursor;
+
+        if !self.track_locations {
+            self.token_end_offset = self.cursor + matched.len() as i32;
+            return;
+        }
+
+        // Line-based locations, start. The start column is calculated
+        // against the line begin offset *before* the matched text is
+        // scanned for new lines (set by the previous token).
+        self.token_start_line = self.current_line;
+        self.token_start_column = self.column_at(self.token_start_offset);
+
+        // Extract `\n` in the matched token: each one starts a new line, and
+        // the last one is where the line of the token's end begins.
+        if let Some(last_nl) = matched.rfind('\n') {
+            self.current_line += matched.matches('\n').count() as i32;
+            self.current_line_begin_offset = self.token_start_offset + last_nl as i32 + 1;
+
+            for (nl, _) in matched.match_indices('\n') {
+                self.line_starts.push(self.token_start_offset + nl as i32 + 1);
+            }
+        }
+
+        self.token_end_offset = self.cursor + matched.len() as i32;
+
+        // Line-based locations, end.
+        self.token_end_line = self.current_line;
+        self.token_end_column = self.column_at(self.token_end_offset);
+        self.current_column = self.token_end_column;
     }
 
     /**
-     * Replace yytext with given string
+     * The parsed file in error messages: " in foo.calc", or empty
+     * if a string is parsed.
      */
-    pub fn set_yytext(&mut self, s: String) {
-        self.yytext = self.string_ref(s);
+    fn in_file(&self) -> String {
+        match self.file_name {
+            Some(ref file_name) => format!(" in {}", file_name),
+            None => String::new(),
+        }
     }
 
     /**
-     * Move ownership of given string to tokenizer and returns reference to it as &str.
-     * Use this method for overriding yytext with new strings wich are not part of text being parsed.
+     * Position of the end of the matched text in error messages:
+     * `line:column`, or the offset without the location tracking.
      */
-    pub fn string_ref(&mut self, s: String) -> &'static str {
-        self.yybuffer.push(s);
-        str_as_static(self.yybuffer.last().unwrap().as_str())
+    fn end_position(&self) -> String {
+        if self.track_locations {
+            format!("{}:{}", self.token_end_line, self.token_end_column)
+        } else {
+            format!("offset {}", self.reported_offset(self.token_end_offset))
+        }
     }
 
     /**
-     * Returns next token.
+     * Offset in the units of locations: the byte offset, or the number
+     * of UTF-16 code units before it. The tokens are converted mostly in
+     * order, so the string is scanned from the last converted offset.
      */
-    pub fn get_next_token(&mut self) -> Token {
-        if !self.has_more_tokens() {
-            self.yytext = EOF;
-            return self.to_token(EOF)
+    fn reported_offset(&self, offset: i32) -> i32 {
+        if !self.utf16_offsets {
+            return offset;
         }
 
-        let str_slice = &self.string[self.cursor as usize..];
+        let (mut byte_offset, mut utf16_offset) = self.utf16_position.get();
 
-        let lex_rules_for_state = LEX_RULES_BY_START_CONDITIONS
-            .get(self.get_current_state())
-            .unwrap();
+        if offset < byte_offset {
+            byte_offset = 0;
+            utf16_offset = 0;
+        }
 
-        for i in lex_rules_for_state {
-            let i = *i as usize;
-            
-            if let Some(matched) = self._match(str_slice, &REGEX_RULES[i]) {
+        utf16_offset += utf16_len(&self.string.as_bytes()[byte_offset as usize..offset as usize]);
+        self.utf16_position.set((offset, utf16_offset));
 
-                // Manual handling of EOF token (the end of string). Return it
-                // as `EOF` symbol.
-                if matched.len() == 0 {
-                    self.cursor = self.cursor + 1;
-                }
-                
-                // lifetime of parsed string is greater than lifetime of tokens or tokenizer
-                // so it's safe to extend lifetime of matched text
-                self.yytext = str_as_static(matched);
-                self.yyleng = matched.len();
+        utf16_offset
+    }
 
-                let token_type = self.handlers[i](self);
+    /**
+     * Byte offset of an offset in the units of locations.
+     */
+    fn byte_offset(&self, offset: i32) -> i32 {
+        if !self.utf16_offsets {
+            return offset;
+        }
 
-                // "" - no token (skip)
-                if token_type.len() == 0 {
-                    return self.get_next_token();
-                }
+        let mut utf16_offset = 0;
 
-                return self.to_token(token_type)
+        for (byte_offset, c) in self.string.char_indices() {
+            if utf16_offset >= offset {
+                return byte_offset as i32;
             }
+            utf16_offset += c.len_utf16() as i32;
         }
 
-        if self.is_eof() {
-            self.cursor = self.cursor + 1;
-            self.yytext = EOF;
-            return self.to_token(EOF);
+        self.string.len() as i32
+    }
+
+    /**
+     * Line, and column of an offset of the string (in the units of the
+     * locations, e.g. of the `Loc` of an AST node) as the tokens report
+     * them, for a diagnostic which has only the offset:
+     *
+     *   let (line, column) = parser.offset_to_line_col(node.loc.start_offset).unwrap();
+     *
+     * The begins of the lines are recorded while tokenizing, so for the
+     * tokenized part of the string it's a binary search, the rest of the
+     * string (e.g. after a syntax error) is scanned. `None` if the
+     * locations aren't tracked, or the offset isn't in the string.
+     */
+    pub fn offset_to_line_col(&self, offset: i32) -> Option<(i32, i32)> {
+        if !self.track_locations || offset < 0 {
+            return None;
         }
 
-        self.panic_unexpected_token(
-            &str_slice[0..1],
-            self.current_line,
-            self.current_column
-        );
+        let offset = self.byte_offset(offset);
+
+        if offset as usize > self.string.len() ||
+            !self.string.is_char_boundary(offset as usize) ||
+            offset < self.line_starts[0] {
+            return None;
+        }
+
+        let index = self.line_starts.partition_point(|line_start| *line_start <= offset) - 1;
+
+        let mut line = index as i32 + self.first_line;
+        let mut line_begin = self.line_starts[index];
 
-        unreachable!()
+        // The part after the tokenized text.
+        let tokenized = self.cursor.min(self.string.len() as i32);
+
+        if offset > tokenized {
+            let rest = &self.string[tokenized as usize..offset as usize];
+
+            if let Some(last_nl) = rest.rfind('\n') {
+                line += rest.matches('\n').count() as i32;
+                line_begin = tokenized + last_nl as i32 + 1;
+            }
+        }
+
+        Some((line, self.column_in_line(line_begin, offset)))
     }
 
     /**
-     * Throws default "Unexpected token" exception, showing the actual
-     * line from the source, pointing with the ^ marker to the bad token.
-     * In addition, shows `line:column` location.
+     * Column of an offset on the current line: a tab moves
+     * to the next tab stop, other bytes take one column (or
+     * the UTF-16 code units of the characters, or the grapheme
+     * clusters).
+     */
+    fn column_at(&self, offset: i32) -> i32 {
+        self.column_in_line(self.current_line_begin_offset, offset)
+    }
+
+    /**
+     * Column of an offset on the line which begins at `line_begin` (the
+     * first line starts at the column given to `init_string_at`).
      */
-    fn panic_unexpected_token(&self, string: &str, line: i32, column: i32) {
-        let line_source = self.string
-            .split('\n')
-            .collect::<Vec<&str>>()
-            [(line - 1) as usize];
+    fn column_in_line(&self, line_begin: i32, offset: i32) -> i32 {
+        let column = if line_begin == self.line_starts[0] { self.first_line_column } else { 0 };
 
-        let pad = ::std::iter::repeat(" ")
-            .take(column as usize)
-            .collect::<String>();
+        if self.tab_width == 1 && !self.utf16_offsets && !self.grapheme_columns {
+            return column + offset - line_begin;
+        }
 
-        let line_data = format!("\n\n{}\n{}^\n", line_source, pad);
+        self.text_width(column, &self.string.as_bytes()[line_begin as usize..offset as usize])
+    }
 
-        panic!(
-            "{} Unexpected token: \"{}\" at {}:{}.",
-            line_data,
-            string,
-            line,
-            column
-        );
+    /**
+     * Column after a text which starts at a column, with tabs expanded
+     * to the tab stops.
+     */
+    fn text_width(&self, column: i32, text: &[u8]) -> i32 {
+        if self.grapheme_columns {
+            // The text is between the char boundaries.
+            return self.grapheme_width(column, std::str::from_utf8(text).unwrap());
+        }
+
+        text.iter()
+            .fold(column, |column, byte| match *byte {
+                b'\t' => (column / self.tab_width + 1) * self.tab_width,
+                0x80..=0xbf if self.utf16_offsets => column,
+                0xf0..=0xff if self.utf16_offsets => column + 2,
+                _ => column + 1,
+            })
     }
 
-    fn capture_location<'s>(&mut self, matched: &'s str) {
-        let nl_re = Regex::new(r"\n").unwrap();
+    /**
+     * Column after a text in grapheme clusters, a cluster of a combining mark,
+     * an emoji modifier, or a ZWJ sequence is one column. The line begins
+     * with a new cluster, so the clusters are counted from the line begin.
+     */
+    #[cfg({{{SEGMENTATION_FEATURE}}})]
+    fn grapheme_width(&self, column: i32, text: &str) -> i32 {
+        use unicode_segmentation::UnicodeSegmentation;
 
-        // Absolute offsets.
-        self.token_start_offset = self.cursor;
+        text.graphemes(true)
+            .fold(column, |column, grapheme| match grapheme {
+                "\t" => (column / self.tab_width + 1) * self.tab_width,
+                _ => column + 1,
+            })
+    }
 
-        // Line-based locations, start.
-        self.token_start_line = self.current_line;
-        self.token_start_column = self.token_start_offset - self.current_line_begin_offset;
+    #[cfg(not({{{SEGMENTATION_FEATURE}}}))]
+    fn grapheme_width(&self, column: i32, text: &str) -> i32 {
+        text.chars()
+            .fold(column, |column, c| match c {
+                '\t' => (column / self.tab_width + 1) * self.tab_width,
+                _ => column + 1,
+            })
+    }
+
+    /**
+     * Matches the rules of a state starting from the `from` position, and
+     * returns the position of the first matched rule, and the matched text.
+     *
+     * The combined regex finds the rule with one match: the number of the
+     * participating group is the position of the rule. It's used only for
+     * the first match, after a skipped empty match the next rules are
+     * tried one by one.
+     */
+    fn _match_rule<'s>(
+        &mut self,
+        str_slice: &'s str,
+        rules: &[i32],
+        from: usize,
+        combined: Option<&Regex>,
+    ) -> Option<(usize, &'s str)> {
+        if let (0, Some(re)) = (from, combined) {
+            let (position, matched) = match_combined_lex_rules(re, str_slice)?;
+
+            self.consume_match(matched);
 
-        // Extract `\n` in the matched token.
-        for cap in nl_re.captures_iter(matched) {
-            self.current_line = self.current_line + 1;
-            self.current_line_begin_offset = self.token_start_offset +
-                cap.pos(0).unwrap().0 as i32 + 1;
+            return Some((position, matched));
         }
 
-        self.token_end_offset = self.cursor + matched.len() as i32;
+        for (position, i) in rules.iter().enumerate().skip(from) {
+            if let Some(matched) = self._match(str_slice, regex_rule(*i as usize)) {
+                return Some((position, matched));
+            }
+        }
 
-        // Line-based locations, end.
-        self.token_end_line = self.current_line;
-        self.token_end_column = self.token_end_offset - self.current_line_begin_offset;
-        self.current_column = self.token_end_column;
+        None
     }
 
     fn _match<'s>(&mut self, str_slice: &'s str, re: &Regex) -> Option<&'s str> {
-        match re.captures(str_slice) {
-            Some(caps) => {
-                let matched = caps.at(0).unwrap();
-                self.capture_location(matched);
-                self.cursor = self.cursor + (matched.len() as i32);
-                Some(matched)
-            },
-            None => None
+        let matched = match_lex_rule(re, str_slice)?;
+        self.consume_match(matched);
+        Some(matched)
+    }
+
+    /**
+     * Captures the location of the matched text, and moves the cursor
+     * past it. The new line at the end of the input (see `eof_newline`)
+     * is an empty token at the end of the string, it doesn't start a line.
+     */
+    fn consume_match(&mut self, matched: &str) {
+        if self.eof_newline && self.is_end_of_string() && !matched.is_empty() {
+            self.eof_newline = false;
+            self.capture_location("");
+            return;
+        }
+
+        self.capture_location(matched);
+        self.cursor += matched.len() as i32;
+    }
+
+    /**
+     * Creates a token of the matched text, its value is normalized (see
+     * `normalize_yytext`), which is an error without the string buffer.
+     */
+    fn matched_token(&mut self, token_type: &'static str) -> Result<Token<'t>, SyntaxError> {
+        self.normalize_yytext(token_type);
+
+        if let Some(error) = self.error.take() {
+            return Err(error);
         }
+
+        self.last_token_type = token_type;
+        self.to_token(token_type)
     }
 
-    fn to_token(&self, token: &str) -> Token {
-        Token {
-            kind: *TOKENS_MAP.get(token).expect(
-                format!("Token {} was reached, but there is no grammar rule for them", token).as_str()
-            ),
+    /**
+     * Creates a token of the current location. A token type returned by
+     * a lex handler, which is not used in the grammar, is an error.
+     */
+    fn to_token(&self, token: &str) -> Result<Token<'t>, SyntaxError> {
+        let start_offset = self.reported_offset(self.token_start_offset);
+        let end_offset = self.reported_offset(self.token_end_offset);
+
+        let mut result = Token {
+            // The EOF kind is known, it's not looked up in the tokens map.
+            kind: EOF_KIND,
             value: self.yytext,
-            start_offset: self.token_start_offset,
-            end_offset: self.token_end_offset,
+            start_offset,
+            end_offset,
             start_line: self.token_start_line,
             end_line: self.token_end_line,
             start_column: self.token_start_column,
             end_column: self.token_end_column,
+        };
+
+        if token != EOF {
+            result.kind = match token_kind(token) {
+                Some(kind) => kind,
+                None => return Err(SyntaxError {
+                    message: format!("Token {} was reached, but there is no grammar rule for it.", token),
+                    loc: Loc::from_token(&result),
+                    expected: Vec::new(),
+                    limit: None,
+                    kind: SyntaxErrorKind::Other,
+                }),
+            };
         }
+
+        Ok(result)
     }
 
     /**
-     * Whether there are still tokens in the stream.
+     * Whether the end of input is not consumed yet (see `cursor`): the
+     * string has more text to match, or the EOF token is not returned
+     * yet. The queued tokens are not counted.
      */
     pub fn has_more_tokens(&self) -> bool {
         self.cursor <= self.string.len() as i32
     }
 
     /**
-     * Whether the cursor is at the EOF.
+     * Whether only the end of input is left (see `cursor`): all the text
+     * of the string is matched, but the EOF token is not returned yet.
+     * It's `false` once the EOF token is returned.
      */
     pub fn is_eof(&self) -> bool {
+        self.is_end_of_string() && !self.eof_newline
+    }
+
+    /**
+     * Whether the cursor is at the end of the string (the new line at the
+     * end of the input, see `eof_newline`, may be not matched yet).
+     */
+    fn is_end_of_string(&self) -> bool {
         self.cursor == self.string.len() as i32
     }
 
+    /**
+     * Consumes the end of input: moves the cursor one past the end of the
+     * string, after which `get_next_token` returns only the queued tokens,
+     * and the EOF token.
+     */
+    fn consume_end(&mut self) {
+        self.cursor = self.string.len() as i32 + 1;
+    }
+
     /**
      * Returns current tokenizing state.
      */
@@ -328,9 +2102,29 @@ impl<'t> Tokenizer<'t> {
      */
     pub fn push_state(&mut self, state: &'static str) -> &mut Tokenizer<'t> {
         self.states.push(state);
+        self.states_data.push(None);
+        self
+    }
+
+    /**
+     * Enters a new state with runtime data, e.g. the tag of a heredoc,
+     * which ends it. The data is available while the state is current,
+     * see `get_state_data`.
+     */
+    pub fn push_state_with<D: Into<String>>(&mut self, state: &'static str, data: D) -> &mut Tokenizer<'t> {
+        self.states.push(state);
+        self.states_data.push(Some(data.into()));
         self
     }
 
+    /**
+     * Runtime data of the current state, if it's entered
+     * with `push_state_with`.
+     */
+    pub fn get_state_data(&self) -> Option<&str> {
+        self.states_data.last().and_then(|data| data.as_deref())
+    }
+
     /**
      * Alias for `push_state`.
      */
@@ -343,7 +2137,8 @@ impl<'t> Tokenizer<'t> {
      * Exits a current state popping it from the states stack.
      */
     pub fn pop_state(&mut self) -> &'static str {
-        self.states.pop().unwrap_or(&"INITIAL")
+        self.states_data.pop();
+        self.states.pop().unwrap_or("INITIAL")
     }
 
     /**
@@ -351,3 +2146,34 @@ impl<'t> Tokenizer<'t> {
      */
     {{{LEX_RULE_HANDLERS}}}
 }
+
+impl<'t> Default for Tokenizer<'t> {
+    fn default() -> Self {
+        Tokenizer::new()
+    }
+}
+
+/**
+ * Iterates the tokens of the string until the EOF token (which is not
+ * yielded). A syntax error is the last item: the rest of the string
+ * is skipped.
+ */
+impl<'t> Iterator for Tokenizer<'t> {
+    type Item = Result<Token<'t>, SyntaxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.has_more_tokens() && self.tokens_queue.is_empty() {
+            return None;
+        }
+
+        match self.get_next_token() {
+            Ok(token) if token.is_eof() => None,
+            Ok(token) => Some(Ok(token)),
+            Err(error) => {
+                self.tokens_queue.clear();
+                self.consume_end();
+                Some(Err(error))
+            },
+        }
+    }
+}
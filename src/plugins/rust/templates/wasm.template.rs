@@ -0,0 +1,21 @@
+// ------------------------------------------------------------------
+// WebAssembly entry point.
+
+/**
+ * Parses a string from JavaScript, when the crate is built with the
+ * `wasm` feature (which enables the `wasm-bindgen` dependency). Returns
+ * the result converted to a JS value (the result type should implement
+ * `Into<JsValue>`), or throws the message of the syntax error:
+ *
+ *   import {parse} from './pkg/calc';
+ *
+ *   parse('2 + 2 * 2'); // 6
+ */
+#[cfg(feature = "wasm")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn parse(string: &str) -> Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue> {
+    match Parser::new().parse(string) {
+        Ok(result) => Ok(result.into()),
+        Err(error) => Err(wasm_bindgen::JsValue::from_str(&error.message)),
+    }
+}
@@ -0,0 +1,107 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+/**
+ * SARIF version, and schema of the reports.
+ */
+const SARIF_VERSION = '2.1.0';
+
+const SARIF_SCHEMA =
+  'https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/' +
+  'Schemata/sarif-schema-2.1.0.json';
+
+/**
+ * Rule id of the parse errors.
+ */
+const SYNTAX_ERROR_RULE = 'syntax-error';
+
+/**
+ * Parse error reports in the SARIF format (Static Analysis Results
+ * Interchange Format), which code review tools show as annotations.
+ */
+const SarifReport = {
+  /**
+   * Creates a report of the parse results: one result for the error
+   * of parsing a string, or no results if it's parsed. The `uri` is
+   * the path of the parsed file.
+   */
+  create({error = null, string = '', uri = null}) {
+    const results = error ? [this.createResult({error, string, uri})] : [];
+
+    return {
+      $schema: SARIF_SCHEMA,
+      version: SARIF_VERSION,
+      runs: [
+        {
+          tool: {
+            driver: {
+              name: 'syntax-cli',
+              informationUri: 'https://github.com/DmitrySoshnikov/syntax',
+              rules: [
+                {
+                  id: SYNTAX_ERROR_RULE,
+                  shortDescription: {text: 'Syntax error'},
+                },
+              ],
+            },
+          },
+          columnKind: 'utf16CodeUnits',
+          results,
+        },
+      ],
+    };
+  },
+
+  /**
+   * Result of a parse error. The region of the error is known from its
+   * location (`error.loc`): the lines, and columns are 1-based in SARIF,
+   * and the byte span is in UTF-8.
+   */
+  createResult({error, string, uri}) {
+    const physicalLocation = {};
+
+    if (uri) {
+      physicalLocation.artifactLocation = {uri};
+    }
+
+    if (error.loc) {
+      physicalLocation.region = this.createRegion(error.loc, string);
+    }
+
+    const result = {
+      ruleId: SYNTAX_ERROR_RULE,
+      ruleIndex: 0,
+      level: 'error',
+      message: {text: error.message.trim()},
+    };
+
+    if (Object.keys(physicalLocation).length > 0) {
+      result.locations = [{physicalLocation}];
+    }
+
+    return result;
+  },
+
+  /**
+   * SARIF region of a location.
+   */
+  createRegion(loc, string) {
+    const byteOffset = Buffer.byteLength(string.slice(0, loc.startOffset));
+
+    return {
+      startLine: loc.startLine,
+      startColumn: loc.startColumn + 1,
+      endLine: loc.endLine,
+      endColumn: loc.endColumn + 1,
+      charOffset: loc.startOffset,
+      charLength: loc.endOffset - loc.startOffset,
+      byteOffset,
+      byteLength:
+        Buffer.byteLength(string.slice(0, loc.endOffset)) - byteOffset,
+    };
+  },
+};
+
+export default SarifReport;
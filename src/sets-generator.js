@@ -8,6 +8,7 @@ import TablePrinter from './table-printer';
 import {EPSILON, EOF} from './special-symbols';
 
 import debug from './debug';
+import timing from './timing';
 
 // Default exclude set on merging.
 const EXCLUDE_EPSILON = {[EPSILON]: true};
@@ -25,6 +26,7 @@ export default class SetsGenerator {
     this._firstSets = {};
     this._followSets = {};
     this._predictSets = {};
+    this._nullableSymbols = {};
   }
 
   /**
@@ -70,6 +72,7 @@ export default class SetsGenerator {
       return this._firstSets[symbol];
     }
 
+    timing.start('First sets');
     let firstSet = (this._firstSets[symbol] = {});
 
     // If it's a terminal, its First set contains just itself.
@@ -79,6 +82,7 @@ export default class SetsGenerator {
       GrammarSymbol.isEOF(symbol)
     ) {
       firstSet[symbol] = true;
+      timing.end('First sets');
       return this._firstSets[symbol];
     }
 
@@ -89,6 +93,7 @@ export default class SetsGenerator {
       this._mergeSets(firstSet, this.firstOfRHS(RHS));
     });
 
+    timing.end('First sets');
     return firstSet;
   }
 
@@ -96,6 +101,7 @@ export default class SetsGenerator {
    * Returns First set of the whole RHS, excluding derived epsilons.
    */
   firstOfRHS(RHS) {
+    timing.start('First sets');
     let firstSet = {};
 
     for (let i = 0; i < RHS.length; i++) {
@@ -127,6 +133,7 @@ export default class SetsGenerator {
       }
     }
 
+    timing.end('First sets');
     return firstSet;
   }
 
@@ -162,6 +169,7 @@ export default class SetsGenerator {
     }
 
     // Else init and calculate.
+    timing.start('Follow sets');
     let followSet = (this._followSets[symbol] = {});
 
     // Start symbol always contain `$` in its follow set.
@@ -216,6 +224,7 @@ export default class SetsGenerator {
       }
     });
 
+    timing.end('Follow sets');
     return followSet;
   }
 
@@ -228,6 +237,7 @@ export default class SetsGenerator {
   getPredictSets() {
     this._predictSets = {};
     debug.time('Building Predict sets');
+    timing.start('Predict sets');
 
     this._grammar.getProductions().forEach(production => {
       let LHS = production.getLHS();
@@ -252,11 +262,32 @@ export default class SetsGenerator {
       }
     });
 
+    timing.end('Predict sets');
     debug.timeEnd('Building Predict sets');
 
     return this._predictSets;
   }
 
+  /**
+   * Nullable flags of the non-terminals: a non-terminal is nullable
+   * if it derives ε, i.e. its First set contains ε.
+   */
+  getNullableSymbols() {
+    this._nullableSymbols = {};
+
+    this._grammar.getProductions().forEach(production => {
+      const symbol = production.getLHS().getSymbol();
+
+      if (!this._nullableSymbols.hasOwnProperty(symbol)) {
+        this._nullableSymbols[symbol] = this.firstOf(symbol).hasOwnProperty(
+          EPSILON
+        );
+      }
+    });
+
+    return this._nullableSymbols;
+  }
+
   /**
    * Outputs a set with the label in readable format.
    */
@@ -277,6 +308,10 @@ export default class SetsGenerator {
         lhsHeader = 'Production';
         rhsHeader = 'Predict set';
         break;
+      case this._nullableSymbols:
+        lhsHeader = 'Symbol';
+        rhsHeader = 'Nullable';
+        break;
       default:
         throw new Error('Unknow set');
     }
@@ -292,7 +327,13 @@ export default class SetsGenerator {
       if (this._grammar.isTokenSymbol(symbol)) {
         continue;
       }
-      printer.push([symbol, Object.keys(set[symbol]).join(', ')]);
+      // Nullable flags are booleans.
+      const value =
+        typeof set[symbol] === 'boolean'
+          ? (set[symbol] ? 'yes' : 'no')
+          : Object.keys(set[symbol]).join(', ');
+
+      printer.push([symbol, value]);
     }
 
     console.info(printer.toString());
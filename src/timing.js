@@ -0,0 +1,120 @@
+/**
+ * The MIT License (MIT)
+ * Copyright (c) 2015-present Dmitry Soshnikov <dmitry.soshnikov@gmail.com>
+ */
+
+import TablePrinter from './table-printer';
+
+/**
+ * Current time in milliseconds (with the fractional part).
+ */
+function now() {
+  const [seconds, nanoseconds] = process.hrtime();
+  return seconds * 1e3 + nanoseconds / 1e6;
+}
+
+/**
+ * Time of the generation phases (the `--timing` option): grammar loading,
+ * First, and Follow sets, LR states, parsing table, and code emission.
+ *
+ * The phases may be nested, e.g. the Follow sets are built while filling
+ * the parsing table, the time of a nested phase is not counted in the
+ * outer one, so the times of all phases add up to the total.
+ */
+const Timing = {
+  _phases: {},
+  _order: [],
+  _stack: [],
+
+  isEnabled() {
+    return !!global.SYNTAX_TIMING;
+  },
+
+  /**
+   * Starts (or continues) measuring a phase.
+   */
+  start(phase) {
+    if (!this.isEnabled()) {
+      return;
+    }
+
+    const time = now();
+    this._pauseCurrent(time);
+
+    if (!this._phases.hasOwnProperty(phase)) {
+      this._phases[phase] = 0;
+      this._order.push(phase);
+    }
+
+    this._stack.push({phase, since: time});
+  },
+
+  /**
+   * Ends the current run of a phase, and resumes the outer one.
+   */
+  end(phase) {
+    if (!this.isEnabled()) {
+      return;
+    }
+
+    const time = now();
+    const current = this._stack.pop();
+
+    if (!current || current.phase !== phase) {
+      throw new Error(`Timing: phase "${phase}" is not started.`);
+    }
+
+    this._phases[phase] += time - current.since;
+
+    if (this._stack.length > 0) {
+      this._stack[this._stack.length - 1].since = time;
+    }
+  },
+
+  /**
+   * Measured phases in the order they started: [{phase, ms}].
+   */
+  getPhases() {
+    return this._order.map(phase => ({phase, ms: this._phases[phase]}));
+  },
+
+  reset() {
+    this._phases = {};
+    this._order = [];
+    this._stack = [];
+  },
+
+  /**
+   * Prints a table of the phases, and their time in milliseconds.
+   */
+  print() {
+    if (!this.isEnabled()) {
+      return;
+    }
+
+    const table = new TablePrinter({head: ['Phase', 'Time (ms)']});
+    let total = 0;
+
+    this.getPhases().forEach(({phase, ms}) => {
+      table.push([phase, ms.toFixed(2)]);
+      total += ms;
+    });
+
+    table.push(['Total', total.toFixed(2)]);
+
+    console.info('\nTiming:\n');
+    console.info(table.toString(), '\n');
+  },
+
+  _pauseCurrent(time) {
+    if (this._stack.length === 0) {
+      return;
+    }
+
+    const current = this._stack[this._stack.length - 1];
+    this._phases[current.phase] += time - current.since;
+    current.since = time;
+  },
+};
+
+export default Timing;
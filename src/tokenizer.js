@@ -242,9 +242,50 @@ export default class Tokenizer {
       lineData = '\n\n' + lineSource + '\n' + pad + '^\n';
     }
 
-    throw new SyntaxError(
+    const error = new SyntaxError(
       `${lineData}Unexpected token: "${symbol}" ` + `at ${line}:${column}.`
     );
+
+    error.loc = this._symbolLoc(symbol, line, column);
+    throw error;
+  }
+
+  /**
+   * Location of the end of input, e.g. of the "Unexpected end
+   * of input" error.
+   */
+  getEndOfInputLoc() {
+    const lines = this._string.split('\n');
+    return this._symbolLoc(
+      '',
+      lines.length,
+      lines[lines.length - 1].length
+    );
+  }
+
+  /**
+   * Location of a symbol at the line, and column (the offsets are
+   * calculated from the lines of the string).
+   */
+  _symbolLoc(symbol, line, column) {
+    const startOffset = this._string
+      .split('\n')
+      .slice(0, line - 1)
+      .reduce((offset, lineSource) => offset + lineSource.length + 1, column);
+
+    const symbolLines = String(symbol).split('\n');
+    const endLine = line + symbolLines.length - 1;
+    const lastLine = symbolLines[symbolLines.length - 1];
+
+    return {
+      startOffset,
+      endOffset: startOffset + String(symbol).length,
+      startLine: line,
+      endLine,
+      startColumn: column,
+      endColumn:
+        symbolLines.length > 1 ? lastLine.length : column + lastLine.length,
+    };
   }
 
   _captureLocation(matched) {